@@ -1,28 +1,92 @@
-use crate::dimension::{Dimension, DimensionConfig, Tab};
-use crate::tmux::Tmux;
+use crate::backend::{MultiplexerBackend, TmuxBackend};
+use crate::command_line::{self, CommandLine};
+use crate::dimension::{
+    ConfirmStyle, Dimension, DimensionConfig, DimensionSort, EscapeBehavior, IconStyle, MatchAlgorithm,
+    OpenDirWith, StartupFocus, Tab,
+};
+use crate::discover;
+use crate::engine::{self, DimensionsEngine, SessionStart, StartupProgress, WaitingForReady};
+use crate::expanded;
+use crate::hooks::{self, Lifecycle};
+use crate::icons::{self, ProjectKind};
+use crate::keymap::Keymap;
+use crate::matcher;
+use crate::paths;
+use crate::profile;
+use crate::readiness::ReadyEvent;
+use crate::search_history;
+use crate::search_index::SearchIndex;
+use crate::snapshot;
+use crate::sync::{self, LiveWindow, SyncItem};
+use crate::template;
+use crate::tmux::{Tmux, TmuxClient};
+use crate::tmux_control::ControlClient;
+use crate::tmux_options;
+use crate::tmux_state::{self, TmuxStateCache};
+use crate::usage::{self, EventKind};
+use crate::tutorial::Tutorial;
+use crate::zoxide;
+use crate::stale;
 use crate::update;
 use anyhow::Result;
-use fuzzy_matcher::FuzzyMatcher;
-use fuzzy_matcher::skim::SkimMatcherV2;
+use ratatui::layout::Rect;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::sync::mpsc;
 use std::sync::mpsc::TryRecvError;
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// A message this recent when the TUI exits is repeated by tmux, since the popup takes it along
+const NOTICE_FRESH: Duration = Duration::from_secs(10);
+
+/// How often to retry a lost (or refused) control-mode connection
+const CONTROL_RECONNECT: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
     Normal,
     CreatingDimension,
     CreatingDimensionDirectory,
+    RepointingDirectory,
     AddingTab,
+    AddingTabDirectory,
     DeletingDimension,
     DeletingTab,
     RenamingDimension,
     RenamingTab,
     Searching,
     JumpingToTab,
+    ConfirmingSharedAttach,
+    CurrentWindowMenu,
+    RenamingCurrentWindow,
+    QuickActions,
+    PickingTask,
+    PickingLink,
+    PickingSuggestion,
+    PickingTemplate,
+    FillingTemplate,
+    SearchActions,
+    Syncing,
+    Command,
 }
 
-#[derive(Debug, Clone)]
+/// A switch on hold because the session is attached elsewhere at another size
+pub struct SharedAttach {
+    pub session: String,
+    pub others: Vec<TmuxClient>,
+    // Our own size, when known
+    pub own_size: Option<(u16, u16)>,
+}
+
+/// Which list has keyboard focus in normal mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pane {
+    Dimensions,
+    Tabs,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum MatchType {
     DimensionOnly,   // Dimension name matched
     TabOnly,         // Tab name matched
@@ -42,56 +106,322 @@ pub struct SearchResult {
     pub match_type: MatchType,
 }
 
+impl SearchResult {
+    /// Whether the result is about a tab rather than the dimension as a whole
+    pub fn is_tab(&self) -> bool {
+        self.match_type != MatchType::DimensionOnly && self.tab_name != "(no tabs)"
+    }
+}
+
+/// What Tab completes in an input prompt
+enum Completion {
+    Directory,
+    // A word on the `:` command line
+    Command,
+    // Host argument of an `ssh` tab command; `head` is the input before it
+    SshHost { head: String, partial: String },
+}
+
+/// Buttons in the quick actions row under the tabs pane, in display order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuickAction {
+    Start,
+    Kill,
+    Hibernate,
+    Rename,
+    EditConfig,
+    OpenDir,
+}
+
+impl QuickAction {
+    pub const ALL: [QuickAction; 6] =
+        [Self::Start, Self::Kill, Self::Hibernate, Self::Rename, Self::EditConfig, Self::OpenDir];
+
+    pub fn label(self, running: bool) -> &'static str {
+        match self {
+            Self::Start if running => "Switch",
+            Self::Start => "Start",
+            Self::Kill => "Kill",
+            Self::Hibernate => "Hibernate",
+            Self::Rename => "Rename",
+            Self::EditConfig => "Edit config",
+            Self::OpenDir => "Open dir",
+        }
+    }
+
+    /// Whether the action can do anything for `dimension` right now
+    pub fn is_enabled(self, dimension: &Dimension, running: bool) -> bool {
+        match self {
+            Self::Kill | Self::Hibernate => running,
+            Self::OpenDir => dimension.base_dir.is_some(),
+            _ => true,
+        }
+    }
+}
+
+/// What `Tab` on a search result offers, in menu order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchAction {
+    Switch,
+    SwitchReadOnly,
+    CopyTarget,
+    Delete,
+}
+
+impl SearchAction {
+    pub const ALL: [SearchAction; 4] = [Self::Switch, Self::SwitchReadOnly, Self::CopyTarget, Self::Delete];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Switch => "Switch",
+            Self::SwitchReadOnly => "Switch read-only",
+            Self::CopyTarget => "Copy target",
+            Self::Delete => "Delete",
+        }
+    }
+}
+
+/// A dimension's differences from its live session, under review before syncing
+pub struct SyncState {
+    pub session: String,
+    pub live: Vec<LiveWindow>,
+    pub items: Vec<SyncItem>,
+    pub selected: usize,
+}
+
+/// Where a list's rows were last drawn, so a click can be mapped back to a row
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListArea {
+    pub area: Rect,
+    // First row shown (the list scrolls to keep the selection visible)
+    pub offset: usize,
+}
+
+impl ListArea {
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        column >= self.area.x
+            && column < self.area.x + self.area.width
+            && row >= self.area.y
+            && row < self.area.y + self.area.height
+    }
+
+    /// Position in the list of the row at a terminal cell, if the cell is on a row
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.contains(column, row)
+            .then(|| self.offset + (row - self.area.y) as usize)
+    }
+}
+
+/// What a row of the dimensions pane stands for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DimensionItem {
+    Dimension(usize),
+    // A tab nested under an expanded dimension: (dimension, window index or configured position)
+    Tab(usize, usize),
+    // The "other tmux sessions" header
+    Header,
+    Orphan(usize),
+}
+
+/// Clickable lists from the last frame
+#[derive(Debug, Clone, Default)]
+pub struct MouseTargets {
+    pub dimensions: Option<ListArea>,
+    // What each row of the dimensions pane stands for
+    pub dimension_items: Vec<DimensionItem>,
+    pub tabs: Option<ListArea>,
+    // Tab to select (window index or configured position) for each tab row
+    pub tab_indexes: Vec<usize>,
+}
+
+/// A deletion that `u` can take back
+#[derive(Debug, Clone)]
+pub enum Undo {
+    // Restored from the config's trash
+    DeletedDimension(String),
+    // A configured tab, where it stood in its dimension's list and, when the session was
+    // running, where its window stood among the session's windows
+    DeletedTab { dimension: String, index: usize, window: Option<usize>, tab: Tab },
+}
+
 pub struct App {
-    pub config: DimensionConfig,
+    // Config and tmux, shared with other frontends through the same API
+    pub engine: DimensionsEngine,
     pub selected_dimension: usize,
     // None means dimension selected.
     // Some(i) means:
     // - if the selected dimension's tmux session exists: tmux window index (#I)
     // - otherwise: configured tab list index
     pub selected_tab: Option<usize>,
+    // Pane receiving navigation keys; Tabs implies a tab is selected
+    pub focus: Pane,
     pub input_mode: InputMode,
     pub input_buffer: String,
     pub search_query: String,
     pub search_results: Vec<SearchResult>,
     pub search_selected_index: usize,
+    // Ctrl+R in search: match the query as a regex whatever `ui.matcher` says
+    pub search_regex: bool,
+    // Why the regex query doesn't compile, shown next to it
+    pub search_error: Option<String>,
+    // Past queries, newest first, and which one `↑`/`↓` is showing
+    pub search_history: Vec<String>,
+    pub search_history_index: Option<usize>,
     pub last_computed_query: String,
     pub pre_search_dimension: usize,
     pub pre_search_tab: Option<usize>,
     pub message: Option<String>,
+    message_at: Option<Instant>, // When `message` was set
     pub update_message: Option<String>,
     pub should_quit: bool,
+    pub dirty: bool, // Something changed since the last frame; the loop only draws when set
     pub should_attach: Option<String>, // Session name to attach to after quitting
     pub should_select_window: Option<usize>, // Window index to select after attaching
     pub should_detach: bool, // Whether to detach from tmux on quit
+    pub should_edit_config: bool, // Open the config file in $EDITOR after quitting
+    pub should_detach_others: bool, // Detach other clients of the target session when switching
+    pub should_attach_read_only: bool,
+    pub shared_attach: Option<SharedAttach>, // Switch waiting for confirmation (ConfirmingSharedAttach)
+    pub delete_requires_name: bool, // DeletingDimension must be confirmed by typing the name
+    pub delete_running: Vec<String>, // Programs the DeletingDimension confirmation warns would be killed
+    pub undo_stack: Vec<Undo>, // Deletions made this run, most recent last
     pub current_session: Option<String>, // Current tmux session when app was opened
     pub current_window: Option<usize>, // Current tmux window index when app was opened
 
     // Directory input completion state
     pub pending_dimension_name: Option<String>, // Cache dimension name between creation steps
-    pub completion_candidates: Vec<String>, // Directory matches for tab completion
-    pub completion_index: usize, // Current selection when cycling through completions
-    pub completion_base: String, // Original input before cycling completions
+    pub pending_tab: Option<(String, Option<String>)>, // Name and command of a tab awaiting its directory
+    pub completion_candidates: Vec<String>, // Directory matches shown in the completion popup
+    pub completion_index: usize, // Highlighted candidate in the completion popup
+    pub completion_base: String, // Input that produced the current candidates
 
     // Tab preview state
     pub preview_content: Option<String>, // Captured pane contents
     pub preview_session: Option<String>, // Session of cached preview
     pub preview_window: Option<usize>, // Window index of cached preview
 
+    // Dimensions whose base_dir or a tab working dir no longer exists
+    pub broken_dimensions: HashSet<String>,
+
+    // Project type detected in each dimension's base directory, for its icon
+    pub project_kinds: HashMap<String, ProjectKind>,
+
+    // Session currently being created (drives the startup progress screen)
+    pub startup: Option<StartupProgress>,
+    // Session waiting on its readiness check before we switch into it
+    pub waiting: Option<WaitingForReady>,
+
+    // Window lists etc. for rendering, refreshed after a short TTL
+    pub tmux_state: TmuxStateCache,
+
+    // Lowercased names/commands/tags for search, built lazily per search session
+    pub search_index: Option<SearchIndex>,
+
+    // Non-default tmux target (custom socket, nested, over SSH), shown in the title
+    pub server_label: Option<String>,
+
+    // Set when running `dimensions tutorial`
+    pub tutorial: Option<Tutorial>,
+
+    // `--pick`: search only, and print the chosen target instead of switching to it
+    pub pick: bool,
+
+    // `--here`: only the prompt for the current window, closing once it's done
+    pub here: bool,
+
+    // Highlighted button in the quick actions row (index into QuickAction::ALL)
+    pub quick_action: usize,
+
+    // Highlighted entry in the task picker of a task tab
+    pub task_selected: usize,
+
+    // Highlighted entry in the link picker of the selected dimension
+    pub link_selected: usize,
+
+    // Projects under `discover.roots` without a dimension, while `D` lists them
+    pub suggestions: Vec<std::path::PathBuf>,
+    pub suggestion_selected: usize,
+
+    // Highlighted entry of the menu `Tab` opens on a search result (index into SearchAction::ALL)
+    pub search_action: usize,
+
+    // Highlighted entry of the `T` template menu, then the template being filled in
+    pub template_selected: usize,
+    pub template_fill: Option<template::Fill>,
+    // Task command to start a task tab with when its session is created (tab name, command)
+    task_override: Option<(String, String)>,
+
+    // Set while a tmux session outside the config is selected (index into `orphan_sessions()`)
+    pub selected_orphan: Option<usize>,
+
+    // Pending reconciliation of the selected dimension with its live windows (`S`)
+    pub sync: Option<SyncState>,
+
+    // Lists drawn in the last frame, for mouse clicks and scrolling
+    pub mouse_targets: MouseTargets,
+
+    // Private dimensions revealed with `v` until the popup closes
+    pub revealed: HashSet<String>,
+    // Dimensions whose tabs are listed under them (`c`), remembered across runs
+    pub expanded: HashSet<String>,
+
+    // Names only: commands, paths and previews are hidden everywhere (`P`)
+    pub presentation: bool,
+
+    // Sessions running when the TUI opened, for `startup.sort = "active"`
+    pub active_at_startup: HashSet<String>,
+
+    // The preview as a third column showing the end of the pane (`p`), instead of under the tabs
+    pub preview_panel: bool,
+
+    // Dimensions as rows and their tabs as columns, instead of the two lists (`m`)
+    pub matrix: bool,
+
+    // Keys of the main view, with the config's `keybindings` applied
+    pub keymap: Keymap,
+
     update_rx: Option<mpsc::Receiver<Option<String>>>,
+    // Monthly mention of stale dimensions, computed in the background
+    tidy_rx: Option<mpsc::Receiver<Option<String>>>,
+    // `tmux -C` connection reporting session/window changes, and when to try again without one
+    tmux_events: Option<ControlClient>,
+    tmux_events_retry_at: Option<Instant>,
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
-        let config = DimensionConfig::load()?;
+    pub fn new(config: DimensionConfig) -> Result<Self> {
+        let mut app = Self::with_backend(config, Rc::new(TmuxBackend))?;
+
+        // Check for updates in the background (best-effort).
+        let (update_tx, update_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let msg = update::check_for_update_message(paths::cache_dir(), env!("CARGO_PKG_VERSION"));
+            let _ = update_tx.send(msg);
+        });
+        app.update_rx = Some(update_rx);
+
+        let (tidy_tx, tidy_rx) = mpsc::channel();
+        let dimensions = app.engine.config.dimensions.clone();
+        let tidy = app.engine.config.tidy.clone();
+        thread::spawn(move || {
+            let _ = tidy_tx.send(stale::tidy_prompt(&dimensions, &tidy));
+        });
+        app.tidy_rx = Some(tidy_rx);
+        Ok(app)
+    }
 
+    /// An app driving `backend` instead of the tmux binary; no update check runs
+    pub fn with_backend(config: DimensionConfig, backend: Rc<dyn MultiplexerBackend>) -> Result<Self> {
         // Detect current tmux session and window if inside tmux
-        let (current_session, current_window) = if Tmux::is_inside_session() {
-            let session = Tmux::get_current_session().ok();
-            let window = Tmux::get_current_window_index().ok();
-            (session, window)
-        } else {
-            (None, None)
+        let (current_session, current_window) = {
+            let _span = profile::span("  current session/window");
+            if backend.is_inside_session() {
+                let session = backend.get_current_session().ok();
+                let window = backend.get_current_window_index().ok();
+                (session, window)
+            } else {
+                (None, None)
+            }
         };
 
         // Start selection on the current tmux session's dimension (useful for popup mode).
@@ -100,54 +430,189 @@ impl App {
             .and_then(|session| config.dimensions.iter().position(|d| d.name == *session))
             .unwrap_or(0);
 
-        // Check for updates in the background (best-effort).
-        let (update_tx, update_rx) = mpsc::channel();
-        thread::spawn(move || {
-            let config_dir = dirs::config_dir()
-                .unwrap_or_else(|| std::path::PathBuf::from("."))
-                .join("dimensions");
-            let msg = update::check_for_update_message(config_dir, env!("CARGO_PKG_VERSION"));
-            let _ = update_tx.send(msg);
-        });
-
-        Ok(Self {
-            config,
+        let detach_others = config.attach.detach_others;
+        let keymap = Keymap::new(&config.keybindings);
+        let presentation = config.ui.presentation;
+        let mut app = Self {
             selected_dimension,
             selected_tab: None, // Start with dimension selected, not a tab
+            focus: Pane::Dimensions,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
             search_query: String::new(),
             search_results: Vec::new(),
             search_selected_index: 0,
+            search_regex: false,
+            search_error: None,
+            search_history: search_history::load(),
+            search_history_index: None,
             last_computed_query: String::new(),
             pre_search_dimension: 0,
             pre_search_tab: None,
             message: None,
+            message_at: None,
             update_message: None,
             should_quit: false,
+            dirty: true,
             should_attach: None,
             should_select_window: None,
+            should_detach_others: detach_others,
+            should_attach_read_only: false,
+            shared_attach: None,
+            delete_requires_name: false,
+            delete_running: vec![],
+            undo_stack: vec![],
             should_detach: false,
+            should_edit_config: false,
             current_session,
             current_window,
             pending_dimension_name: None,
+            pending_tab: None,
             completion_candidates: Vec::new(),
             completion_index: 0,
             completion_base: String::new(),
             preview_content: None,
             preview_session: None,
             preview_window: None,
-            update_rx: Some(update_rx),
-        })
+            broken_dimensions: HashSet::new(),
+            project_kinds: HashMap::new(),
+            startup: None,
+            waiting: None,
+            tmux_state: TmuxStateCache::new(backend.clone(), tmux_state::DEFAULT_TTL),
+            search_index: None,
+            server_label: Tmux::target_label(),
+            tutorial: None,
+            pick: false,
+            here: false,
+            quick_action: 0,
+            task_selected: 0,
+            link_selected: 0,
+            suggestions: vec![],
+            suggestion_selected: 0,
+            search_action: 0,
+            template_selected: 0,
+            template_fill: None,
+            task_override: None,
+            selected_orphan: None,
+            sync: None,
+            mouse_targets: MouseTargets::default(),
+            revealed: HashSet::new(),
+            expanded: expanded::load(),
+            presentation,
+            active_at_startup: HashSet::new(),
+            preview_panel: false,
+            matrix: false,
+            keymap,
+            update_rx: None,
+            tidy_rx: None,
+            tmux_events: None,
+            tmux_events_retry_at: None,
+            engine: DimensionsEngine::with_backend(config, backend),
+        };
+        profile::time("  startup sort/focus", || app.apply_startup_settings());
+        profile::time("  directory health check", || app.refresh_path_health());
+        let option_conflicts = profile::time("  tmux option check", || {
+            tmux_options::conflicts(|option| app.engine.backend.global_option(option))
+        });
+        if !app.broken_dimensions.is_empty() {
+            app.set_message(format!(
+                "⚠ {} dimension(s) point at missing directories — press R on one to re-point it",
+                app.broken_dimensions.len()
+            ));
+        } else if let Some(warning) = tmux_options::summary(&option_conflicts) {
+            app.set_message(warning);
+        }
+        Ok(app)
+    }
+
+    /// Recompute which dimensions reference directories that no longer exist, and
+    /// what kind of project the others hold
+    pub fn refresh_path_health(&mut self) {
+        self.broken_dimensions = self
+            .engine.config
+            .dimensions
+            .iter()
+            .filter(|d| !d.missing_paths().is_empty())
+            .map(|d| d.name.clone())
+            .collect();
+        self.project_kinds = if self.engine.config.ui.icons == IconStyle::None {
+            HashMap::new()
+        } else {
+            self.engine.config
+                .dimensions
+                .iter()
+                .filter_map(|d| Some((d.name.clone(), icons::detect(d.base_dir.as_ref()?)?)))
+                .collect()
+        };
+    }
+
+    /// Apply the configured initial ordering and pane focus
+    fn apply_startup_settings(&mut self) {
+        // One tmux call for the `active` sort, rather than one per comparison
+        if self.engine.config.startup.sort == DimensionSort::Active {
+            self.active_at_startup = self.engine.backend.list_sessions().unwrap_or_default().into_iter().collect();
+        }
+
+        match self.engine.config.startup.focus {
+            StartupFocus::Dimensions => {}
+            StartupFocus::Tabs => self.focus_first_or_current_tab(),
+            StartupFocus::Search => self.start_search(),
+        }
+    }
+
+    /// Select the current tmux window if it belongs to the selected dimension, else its first tab
+    fn focus_first_or_current_tab(&mut self) {
+        let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) else {
+            return;
+        };
+
+        if self.engine.backend.session_exists(&dimension.name) {
+            let is_current = self.current_session.as_deref() == Some(dimension.name.as_str());
+            let tab = match self.current_window {
+                Some(window) if is_current => Some(window),
+                _ => self.engine.backend.list_windows(&dimension.name)
+                    .unwrap_or_default()
+                    .first()
+                    .map(|(idx, _)| *idx),
+            };
+            self.select_tab(tab);
+        } else if !dimension.configured_tabs.is_empty() {
+            self.select_tab(Some(0));
+        }
+    }
+
+    pub fn save_config(&mut self) -> Result<()> {
+        self.engine.save()?;
+        self.note_merge();
+        Ok(())
     }
 
-    pub fn save_config(&self) -> Result<()> {
-        self.config.save()
+    /// After a save through the engine: another popup may have saved meanwhile, so the
+    /// list may have grown or shrunk
+    fn note_merge(&mut self) {
+        if self.engine.take_merged() {
+            self.selected_dimension = self
+                .selected_dimension
+                .min(self.engine.config.dimensions.len().saturating_sub(1));
+            self.set_message("Merged with changes saved from another Dimensions window".to_string());
+        }
     }
 
     pub fn quit(&mut self) {
         self.should_quit = true;
         self.should_detach = true; // Quit means detach from tmux
+        if !self.pick {
+            self.run_detach_hooks();
+        }
+    }
+
+    /// `on_detach` of the dimension this client is leaving, if it was in one
+    fn run_detach_hooks(&self) {
+        let left = self.current_session.as_deref().and_then(|s| self.engine.config.get_dimension(s));
+        if let Some(dimension) = left {
+            let backend = self.engine.backend.as_ref();
+            hooks::run_lifecycle(backend, &self.engine.config.hooks, dimension, Lifecycle::Detach);
+        }
     }
 
     pub fn quit_without_detach(&mut self) {
@@ -161,14 +626,95 @@ impl App {
         // Don't set should_attach - just close and stay where we are
     }
 
+    /// Esc in normal mode: drop the message, then the tab or orphan selection, and only
+    /// close the popup when there's nothing left to clear (or `ui.escape` is "close")
+    pub fn escape(&mut self) {
+        if self.engine.config.ui.escape == EscapeBehavior::Clear {
+            if self.message.is_some() {
+                self.clear_message();
+                return;
+            }
+            if self.selected_orphan.is_some() {
+                self.selected_orphan = None;
+                return;
+            }
+            if self.selected_tab.is_some() {
+                self.select_tab(None);
+                return;
+            }
+        }
+        self.close_popup();
+    }
+
     pub fn set_message(&mut self, msg: String) {
         self.message = Some(msg);
+        self.message_at = Some(Instant::now());
     }
 
     pub fn clear_message(&mut self) {
         self.message = None;
     }
 
+    /// What the status bar was just saying, for `tmux display-message` to repeat after the
+    /// popup closes (e.g. "Created dimension: api" right before switching to it)
+    pub fn exit_notice(&self) -> Option<String> {
+        let fresh = self.message_at.is_some_and(|at| at.elapsed() < NOTICE_FRESH);
+        self.message.clone().filter(|_| fresh)
+    }
+
+    /// Show the stale-dimensions mention once it's ready, unless something else is being said
+    pub fn poll_tidy(&mut self) {
+        let Some(rx) = self.tidy_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(msg) => {
+                self.tidy_rx = None;
+                if let Some(msg) = msg.filter(|_| self.message.is_none()) {
+                    self.set_message(msg);
+                    self.dirty = true;
+                }
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.tidy_rx = None;
+            }
+        }
+    }
+
+    /// Drop cached tmux state when the control-mode connection reports changes made anywhere,
+    /// and keep that connection up: while it is, the cache is kept for `EVENT_DRIVEN_TTL`
+    /// rather than re-read every second. Off with `tmux.control_mode = false`.
+    pub fn poll_tmux_events(&mut self) {
+        if self.engine.config.tmux.control_mode == Some(false) {
+            return;
+        }
+        let Some(client) = self.tmux_events.as_ref() else {
+            if self.tmux_events_retry_at.is_some_and(|at| Instant::now() < at) {
+                return;
+            }
+            self.tmux_events_retry_at = Some(Instant::now() + CONTROL_RECONNECT);
+            self.tmux_events = ControlClient::connect(self.engine.backend.as_ref());
+            if self.tmux_events.is_some() {
+                self.tmux_state.set_ttl(tmux_state::EVENT_DRIVEN_TTL);
+            }
+            return;
+        };
+
+        let drained = client.drain();
+        if drained.changed {
+            self.tmux_state.invalidate_all();
+            self.dirty = true;
+        }
+        if drained.exited {
+            // Back to polling until a reconnect works (there may be no session left to attach to)
+            self.tmux_events = None;
+            self.tmux_state.set_ttl(tmux_state::DEFAULT_TTL);
+            self.tmux_state.invalidate_all();
+            self.dirty = true;
+        }
+    }
+
     pub fn poll_update(&mut self) {
         let Some(rx) = self.update_rx.as_ref() else {
             return;
@@ -177,6 +723,7 @@ impl App {
             Ok(msg) => {
                 self.update_message = msg;
                 self.update_rx = None;
+                self.dirty = true;
             }
             Err(TryRecvError::Empty) => {}
             Err(TryRecvError::Disconnected) => {
@@ -186,31 +733,191 @@ impl App {
     }
 
     // Navigation
+
+    /// Select a tab (or none) and move focus to the matching pane
+    pub fn select_tab(&mut self, tab: Option<usize>) {
+        self.selected_tab = tab;
+        self.focus = if tab.is_some() { Pane::Tabs } else { Pane::Dimensions };
+    }
+
+    /// Tab key in normal mode: move focus to the other pane
+    pub fn cycle_focus(&mut self) {
+        match self.focus {
+            Pane::Dimensions => self.focus_tabs(),
+            Pane::Tabs => self.select_tab(None),
+        }
+    }
+
+    /// Focus the tabs pane, selecting the first tab if none is selected yet
+    pub fn focus_tabs(&mut self) {
+        if self.selected_tab.is_none() {
+            self.next_tab();
+        }
+        if self.selected_tab.is_none() && let Some(dimension) = self.get_current_dimension() {
+            let msg = format!("'{}' has no tabs", dimension.name);
+            self.set_message(msg);
+        }
+    }
+
+    /// Up/down inside the tabs pane: wraps within the tab list instead of leaving the pane
+    pub fn previous_tab_in_pane(&mut self) {
+        let before = self.selected_tab;
+        self.previous_tab();
+        if before.is_some() && self.selected_tab.is_none() {
+            // Stepped off the first tab; wrap to the last one
+            self.previous_tab();
+        }
+    }
+
+    /// Config positions of the dimensions in the order they're listed: `startup.sort`
+    /// applied on top of the config's order, which itself stays as written
+    pub fn dimension_order(&self) -> Vec<usize> {
+        let dimensions = &self.engine.config.dimensions;
+        let mut order: Vec<usize> = (0..dimensions.len()).collect();
+        match self.engine.config.startup.sort {
+            DimensionSort::Config => {}
+            DimensionSort::Name => order.sort_by_key(|&i| dimensions[i].name.to_lowercase()),
+            DimensionSort::Active => order.sort_by_key(|&i| !self.active_at_startup.contains(&dimensions[i].name)),
+        }
+        order
+    }
+
+    /// Where the selected dimension is listed
+    fn selected_dimension_row(&self, order: &[usize]) -> usize {
+        order.iter().position(|&i| i == self.selected_dimension).unwrap_or(0)
+    }
+
     pub fn next_dimension(&mut self) {
-        if !self.config.dimensions.is_empty() {
-            self.selected_dimension = (self.selected_dimension + 1) % self.config.dimensions.len();
-            self.selected_tab = None; // Reset to dimension when switching dimensions
+        // Orphan sessions are listed after the dimensions, so the selection runs through both
+        let orphans = self.orphan_sessions().len();
+        let order = self.dimension_order();
+        let dimensions = order.len();
+        let row = self.selected_dimension_row(&order);
+        match self.selected_orphan {
+            Some(i) if i + 1 < orphans => self.selected_orphan = Some(i + 1),
+            Some(_) => {
+                self.selected_orphan = None;
+                self.selected_dimension = order.first().copied().unwrap_or(0);
+                if dimensions == 0 && orphans > 0 {
+                    self.selected_orphan = Some(0);
+                }
+            }
+            None if row + 1 >= dimensions && orphans > 0 => {
+                self.selected_orphan = Some(0);
+            }
+            None if dimensions > 0 => {
+                self.selected_dimension = order[(row + 1) % dimensions];
+            }
+            None => return,
         }
+        self.select_tab(None); // Reset to dimension when switching dimensions
     }
 
     pub fn previous_dimension(&mut self) {
-        if !self.config.dimensions.is_empty() {
-            if self.selected_dimension == 0 {
-                self.selected_dimension = self.config.dimensions.len() - 1;
+        let orphans = self.orphan_sessions().len();
+        let order = self.dimension_order();
+        let dimensions = order.len();
+        let row = self.selected_dimension_row(&order);
+        match self.selected_orphan {
+            Some(i) if i > 0 => self.selected_orphan = Some(i - 1),
+            Some(_) if dimensions > 0 => {
+                self.selected_orphan = None;
+                self.selected_dimension = order[dimensions - 1];
+            }
+            Some(_) => self.selected_orphan = Some(orphans.saturating_sub(1)),
+            None if row == 0 && orphans > 0 => {
+                self.selected_orphan = Some(orphans - 1);
+            }
+            None if dimensions > 0 => {
+                self.selected_dimension = order[if row == 0 { dimensions - 1 } else { row - 1 }];
+            }
+            None => return,
+        }
+        self.select_tab(None); // Reset to dimension when switching dimensions
+    }
+
+    /// Live tmux sessions that don't belong to any dimension, by name
+    pub fn orphan_sessions(&self) -> Vec<String> {
+        let mut orphans: Vec<String> = self
+            .tmux_state
+            .sessions()
+            .into_iter()
+            .filter(|session| !self.engine.config.name_in_use(session))
+            .collect();
+        orphans.sort();
+        orphans
+    }
+
+    pub fn selected_orphan_session(&self) -> Option<String> {
+        self.orphan_sessions().get(self.selected_orphan?).cloned()
+    }
+
+    /// Attach to the selected orphan session as it is
+    pub fn switch_to_orphan(&mut self) {
+        if let Some(session) = self.selected_orphan_session() {
+            self.should_attach = Some(session);
+            self.should_select_window = None;
+            self.quit_without_detach();
+        }
+    }
+
+    /// Turn the selected orphan session into a dimension, saving its windows as tabs
+    pub fn adopt_orphan(&mut self) -> Result<()> {
+        let Some(session) = self.selected_orphan_session() else {
+            return Ok(());
+        };
+
+        let mut dimension = Dimension::new_with_base_dir(session.clone(), self.engine.backend.session_path(&session));
+        for (_, name, path) in self.engine.backend.list_window_paths(&session)? {
+            dimension.add_tab(Tab::new(name, None, path));
+        }
+        self.engine.config.add_dimension(dimension);
+        self.save_config()?;
+        self.tmux_state.invalidate_all();
+
+        self.selected_orphan = None;
+        if let Some(pos) = self.engine.config.dimensions.iter().position(|d| d.name == session) {
+            self.selected_dimension = pos;
+        }
+        self.select_tab(None);
+        self.set_message(format!("Adopted '{}' as a dimension", session));
+        Ok(())
+    }
+
+    /// Number keys: in the dimensions pane, select the Nth dimension listed (from 1); in the tabs
+    /// pane, select the tab listed with number `n` (its window index in a running session)
+    pub fn jump_to_number(&mut self, n: usize) {
+        if self.focus == Pane::Tabs {
+            let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) else {
+                return;
+            };
+            let exists = if self.engine.backend.session_exists(&dimension.name) {
+                let windows = self.engine.backend.list_windows(&dimension.name).unwrap_or_default();
+                windows.iter().any(|(idx, _)| *idx == n)
             } else {
-                self.selected_dimension -= 1;
+                n < dimension.configured_tabs.len()
+            };
+            if exists {
+                self.select_tab(Some(n));
             }
-            self.selected_tab = None; // Reset to dimension when switching dimensions
+            return;
+        }
+
+        let order = self.dimension_order();
+        if let Some(&index) = n.checked_sub(1).and_then(|row| order.get(row)) {
+            self.selected_orphan = None;
+            self.selected_dimension = index;
+            self.select_tab(None);
         }
     }
 
     pub fn next_tab(&mut self) {
-        if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
-            if Tmux::session_exists(&dimension.name) {
+        if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
+            if self.engine.backend.session_exists(&dimension.name) {
                 // Live tmux windows: track selection by tmux window index for robustness.
-                let windows = Tmux::list_windows(&dimension.name).unwrap_or_default();
+                let windows = self.engine.backend.list_windows(&dimension.name).unwrap_or_default();
                 if windows.is_empty() {
-                    self.selected_tab = None;
+                    self.select_tab(None);
                     return;
                 }
 
@@ -224,33 +931,33 @@ impl App {
                         windows[(pos + 1) % windows.len()].0
                     }
                 };
-                self.selected_tab = Some(next_idx);
+                self.select_tab(Some(next_idx));
             } else {
                 // Configured tabs: track selection by configured tab index.
                 let tab_count = dimension.configured_tabs.len();
                 if tab_count == 0 {
-                    self.selected_tab = None;
+                    self.select_tab(None);
                     return;
                 }
 
-                self.selected_tab = Some(match self.selected_tab {
+                self.select_tab(Some(match self.selected_tab {
                     None => 0, // First right arrow selects first tab
                     Some(i) => (i + 1) % tab_count,
-                });
+                }));
             }
         }
     }
 
     pub fn previous_tab(&mut self) {
-        if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
-            if Tmux::session_exists(&dimension.name) {
-                let windows = Tmux::list_windows(&dimension.name).unwrap_or_default();
+        if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
+            if self.engine.backend.session_exists(&dimension.name) {
+                let windows = self.engine.backend.list_windows(&dimension.name).unwrap_or_default();
                 if windows.is_empty() {
-                    self.selected_tab = None;
+                    self.select_tab(None);
                     return;
                 }
 
-                self.selected_tab = match self.selected_tab {
+                let previous = match self.selected_tab {
                     None => Some(windows[windows.len() - 1].0), // Left arrow selects last tab
                     Some(current_window_idx) => {
                         let pos = windows
@@ -264,67 +971,125 @@ impl App {
                         }
                     }
                 };
+                self.select_tab(previous);
             } else {
                 let tab_count = dimension.configured_tabs.len();
                 if tab_count == 0 {
-                    self.selected_tab = None;
+                    self.select_tab(None);
                     return;
                 }
 
-                self.selected_tab = match self.selected_tab {
+                self.select_tab(match self.selected_tab {
                     None => Some(tab_count - 1), // Left arrow selects last tab
                     Some(0) => None, // Wrap back to dimension
                     Some(i) => Some(i - 1),
-                };
+                });
             }
         }
     }
 
     // Dimension operations
     pub fn create_dimension(&mut self, name: String, base_dir: Option<std::path::PathBuf>) -> Result<()> {
-        // Check if dimension already exists
-        if self.config.get_dimension(&name).is_some() {
-            anyhow::bail!("Dimension '{}' already exists", name);
-        }
-
         // Add to config only - tmux session will be created when switching to it
-        let dimension = Dimension::new_with_base_dir(name.clone(), base_dir);
-        self.config.add_dimension(dimension);
-        self.save_config()?;
+        self.engine.create(&name, base_dir)?;
+        self.note_merge();
+        self.refresh_path_health();
 
         self.set_message(format!("Created dimension: {}", name));
         Ok(())
     }
 
-    pub fn delete_dimension(&mut self, name: &str) -> Result<()> {
-        // Remove from config
-        if self.config.remove_dimension(name).is_none() {
-            anyhow::bail!("Dimension '{}' not found", name);
+    /// `g`: switch to the dimension of the git repo Dimensions was started in (or of that
+    /// directory), creating it first, from the `here.template` template when one is set
+    pub fn switch_to_here(&mut self) -> Result<()> {
+        let dir = std::env::current_dir()?;
+        let template = self.engine.config.here.template.clone();
+        let (name, created) = self.engine.here(&dir, template.as_deref())?;
+        if created {
+            self.note_merge();
+            self.refresh_path_health();
+        }
+        self.select_dimension_named(&name)?;
+        self.switch_to_dimension()
+    }
+
+    /// `D`: list projects under `discover.roots` that have no dimension yet
+    pub fn start_suggestions(&mut self) {
+        if self.engine.config.discover.roots.is_empty() {
+            self.set_message("No project roots yet: set discover.roots in the config (e.g. [\"~/code\"])".to_string());
+            return;
+        }
+        self.suggestions = discover::suggestions(&self.engine.config);
+        if self.suggestions.is_empty() {
+            self.set_message("Every project under discover.roots already has a dimension".to_string());
+            return;
         }
+        self.suggestion_selected = 0;
+        self.input_mode = InputMode::PickingSuggestion;
+        self.clear_message();
+    }
 
-        // Save config first before killing anything
-        self.save_config()?;
+    pub fn next_suggestion(&mut self) {
+        let count = self.suggestions.len();
+        if count > 0 {
+            self.suggestion_selected = (self.suggestion_selected + 1) % count;
+        }
+    }
+
+    pub fn previous_suggestion(&mut self) {
+        let count = self.suggestions.len();
+        if count > 0 {
+            self.suggestion_selected = (self.suggestion_selected + count - 1) % count;
+        }
+    }
+
+    /// Create a dimension for the highlighted project, named after its folder and rooted there.
+    /// The list stays open for the rest until it runs out.
+    pub fn create_suggested_dimension(&mut self) -> Result<()> {
+        if self.suggestion_selected >= self.suggestions.len() {
+            self.cancel_input();
+            return Ok(());
+        }
+        let dir = self.suggestions.remove(self.suggestion_selected);
+        let name = discover::unique_name(&self.engine.config, &dir);
+        self.create_dimension(name, Some(dir))?;
+        if self.suggestions.is_empty() {
+            self.input_mode = InputMode::Normal;
+        } else {
+            self.suggestion_selected = self.suggestion_selected.min(self.suggestions.len() - 1);
+        }
+        Ok(())
+    }
+
+    pub fn delete_dimension(&mut self, name: &str) -> Result<()> {
+        // Remove from config and save before killing anything
+        let removed = self.engine.remove(name)?;
+        self.undo_stack.push(Undo::DeletedDimension(name.to_string()));
+        self.note_merge();
 
         // Adjust selection - handle empty list case
-        if self.config.dimensions.is_empty() {
+        if self.engine.config.dimensions.is_empty() {
             self.selected_dimension = 0;
-        } else if self.selected_dimension >= self.config.dimensions.len() {
-            self.selected_dimension = self.config.dimensions.len() - 1;
+        } else if self.selected_dimension >= self.engine.config.dimensions.len() {
+            self.selected_dimension = self.engine.config.dimensions.len() - 1;
         }
-        self.selected_tab = None;
+        self.select_tab(None);
 
         let inside_target_dimension = self.current_session.as_deref() == Some(name);
 
+        let mut teardown_error = None;
+
         // Kill tmux session if it exists
-        if Tmux::session_exists(name) {
-            if inside_target_dimension && Tmux::is_inside_session() {
+        if self.engine.backend.session_exists(name) {
+            if inside_target_dimension && self.engine.backend.is_inside_session() {
                 // Switch away before killing our own session
                 let (fallback_session, fallback_window) =
                     self.find_or_create_fallback_session(name)?;
                 let target = format!("{}:{}", fallback_session, fallback_window);
-                Tmux::switch_session(&target)?;
+                self.engine.backend.switch_session(&target)?;
             }
-            Tmux::kill_session(name)?;
+            teardown_error = self.engine.kill_session(&removed)?;
+            self.tmux_state.invalidate_all();
 
             if inside_target_dimension {
                 self.quit_without_detach();
@@ -332,148 +1097,259 @@ impl App {
             }
         }
 
-        self.set_message(format!("Deleted dimension: {}", name));
+        match teardown_error {
+            Some(err) => self.set_message(format!("Deleted dimension: {} ({})", name, err)),
+            None => self.set_message(format!("Deleted dimension: {}", name)),
+        }
         Ok(())
     }
 
     pub fn switch_to_dimension(&mut self) -> Result<()> {
-        if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
-            let name = dimension.name.clone();
-            let base_dir = dimension.base_dir.clone();
-            let has_tabs = !dimension.configured_tabs.is_empty();
-            let tabs = dimension.configured_tabs.clone();
-            let session_preexisted = Tmux::session_exists(&name);
-
-            // Ensure tmux session exists
-            if !session_preexisted {
-                // Create session in base_dir if available
-                if let Some(dir) = base_dir.as_ref() {
-                    Tmux::create_session_with_dir(&name, true, dir.to_str().unwrap_or("."))?;
-                } else {
-                    Tmux::create_session(&name, true)?;
-                }
-
-                // If there are configured tabs, create windows for them
-                if has_tabs {
-                    for (i, tab) in tabs.iter().enumerate() {
-                        if i == 0 {
-                            // First window is created with the session, rename it to match first tab
-                            let first_idx = Tmux::get_first_window_index(&name).unwrap_or(0);
-                            Tmux::rename_window(&name, first_idx, &tab.name)?;
-
-                            // Build command for first tab (with working dir if needed)
-                            let full_command = match (&tab.working_dir, &tab.command) {
-                                (Some(dir), Some(cmd)) => {
-                                    // Both working_dir and command: cd then run command
-                                    format!("cd {:?} && {}", dir, cmd)
-                                }
-                                (Some(dir), None) => {
-                                    // Only working_dir: just cd
-                                    format!("cd {:?}", dir)
-                                }
-                                (None, Some(cmd)) => {
-                                    // Only command: just run it
-                                    cmd.clone()
-                                }
-                                (None, None) => String::new(),
-                            };
-
-                            // Send command if we have one
-                            if !full_command.is_empty() {
-                                Tmux::send_keys(&name, first_idx, &full_command)?;
-                            }
-                        } else {
-                            Tmux::new_window(&name, &tab.name, tab.command.as_deref(), tab.working_dir.as_deref())?;
-                        }
-                    }
-                } else {
-                    // No configured tabs: create and save an initial tab
-                    let initial_tab_name = format!("{}-1", name);
-                    let first_idx = Tmux::get_first_window_index(&name).unwrap_or(0);
-                    Tmux::rename_window(&name, first_idx, &initial_tab_name)?;
-
-                    // Save this initial tab to config so it persists across restarts
-                    let initial_tab = Tab::new(initial_tab_name, None, base_dir.clone());
-                    if let Some(dim) = self.config.dimensions.get_mut(self.selected_dimension) {
-                        dim.add_tab(initial_tab);
-                        self.save_config()?;
-                    }
-                }
+        let Some(name) = self.engine.config.dimensions.get(self.selected_dimension).map(|d| d.name.clone()) else {
+            return Ok(());
+        };
+        // A task picked before the session existed becomes its tab's initial command
+        let task_override = self.task_override.take();
+        let (start, warning) = self.engine.start_session(&name, task_override)?;
+        self.tmux_state.invalidate_all();
+        self.note_merge();
+        if let Some(warning) = warning {
+            self.set_message(warning);
+        }
+        match start {
+            SessionStart::Running => self.complete_switch(&name, true),
+            SessionStart::Thawed => {
+                self.set_message(format!("Thawed '{}'", name));
+                self.select_tab(None);
+                self.complete_switch(&name, true);
             }
+            SessionStart::Started => self.complete_switch(&name, false),
+            // Windows are created one per tick so the progress screen can redraw in between
+            SessionStart::Starting(progress) => self.startup = Some(progress),
+        }
+        Ok(())
+    }
 
-            // Determine which window to select
-            let window_index = match self.selected_tab {
-                None => {
-                    // No tab selected, go to first window
-                    Tmux::get_first_window_index(&name).unwrap_or(0)
-                }
-                Some(selected) => {
-                    if session_preexisted {
-                        // Selected is already a tmux window index; validate it still exists.
-                        let windows = Tmux::list_windows(&name).unwrap_or_default();
-                        if windows.iter().any(|(idx, _)| *idx == selected) {
-                            selected
-                        } else {
-                            // Fallback to first window
-                            windows.first().map(|(idx, _)| *idx)
-                                .unwrap_or_else(|| Tmux::get_first_window_index(&name).unwrap_or(0))
-                        }
-                    } else {
-                        // Selected is a configured tab index; map to tmux window index after creation.
-                        let windows = Tmux::list_windows(&name).unwrap_or_default();
-                        windows.get(selected).map(|(idx, _)| *idx)
-                            .unwrap_or_else(|| windows.first().map(|(idx, _)| *idx)
-                                .unwrap_or_else(|| Tmux::get_first_window_index(&name).unwrap_or(0)))
-                    }
-                }
-            };
-
-            // Set the session and window to attach to after exiting TUI
-            self.should_attach = Some(name);
-            self.should_select_window = Some(window_index);
+    /// Create the next configured window of an in-flight session startup.
+    /// Once every tab exists, finishes the switch into the new session.
+    pub fn advance_startup(&mut self) -> Result<()> {
+        let result = self.step_startup();
+        if result.is_err() {
+            self.startup = None;
+        }
+        result
+    }
 
-            // Quit the TUI without detaching (we're switching/attaching to a session)
-            self.quit_without_detach();
+    fn step_startup(&mut self) -> Result<()> {
+        let Some(progress) = self.startup.as_mut() else {
+            return Ok(());
+        };
+        if self.engine.step_startup(progress)? {
+            self.tmux_state.invalidate_all();
+            return Ok(());
         }
+        let name = progress.session.clone();
+        self.startup = None;
 
+        // Hold off switching until the environment reports ready, if configured
+        if let Some(waiting) = self.engine.wait_for_ready(&name) {
+            self.waiting = Some(waiting);
+            return Ok(());
+        }
+
+        self.complete_switch(&name, false);
         Ok(())
     }
 
+    /// Check whether the session we're waiting on became ready (or gave up)
+    pub fn poll_ready(&mut self) {
+        let Some(event) = self.waiting.as_ref().and_then(WaitingForReady::poll) else {
+            return;
+        };
+        let Some(waiting) = self.waiting.take() else {
+            return;
+        };
+        match event {
+            ReadyEvent::Ready => self.complete_switch(&waiting.session, false),
+            ReadyEvent::TimedOut => self.set_message(format!(
+                "'{}' not ready ({}) after {}s — press Enter to switch anyway",
+                waiting.session,
+                waiting.description,
+                waiting.started.elapsed().as_secs()
+            )),
+        }
+    }
+
+    /// Stop waiting and stay in the picker
+    pub fn cancel_wait(&mut self) {
+        if let Some(waiting) = self.waiting.take() {
+            waiting.cancel();
+            self.set_message(format!("Stopped waiting for '{}'", waiting.session));
+        }
+    }
+
+    /// Stop waiting and switch into the session right away
+    pub fn skip_wait(&mut self) {
+        if let Some(waiting) = self.waiting.take() {
+            waiting.cancel();
+            self.complete_switch(&waiting.session, false);
+        }
+    }
+
+    /// Pick the window to land on in `name`, then quit the TUI so main attaches to it
+    fn complete_switch(&mut self, name: &str, session_preexisted: bool) {
+        let window_index = self.engine.landing_window(name, self.selected_tab, session_preexisted);
+        self.engine.enter_session(name, self.current_session.as_deref());
+
+        // Set the session and window to attach to after exiting TUI
+        self.should_attach = Some(name.to_string());
+        self.should_select_window = Some(window_index);
+
+        // Sharing a session with a differently sized client shrinks it to the smaller one; ask first
+        if session_preexisted
+            && !self.should_detach_others
+            && let Some(shared) = self.shared_attach_conflict(name)
+        {
+            self.shared_attach = Some(shared);
+            self.input_mode = InputMode::ConfirmingSharedAttach;
+            return;
+        }
+
+        // Quit the TUI without detaching (we're switching/attaching to a session)
+        self.quit_without_detach();
+    }
+
+    /// Other clients on `session` whose size differs from ours, if any
+    fn shared_attach_conflict(&self, session: &str) -> Option<SharedAttach> {
+        let current = self.engine.backend.current_client();
+        let own_size = current
+            .as_ref()
+            .map(|c| (c.width, c.height))
+            .or_else(|| crossterm::terminal::size().ok());
+
+        let others: Vec<TmuxClient> = self.engine.backend.list_clients(session)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|c| current.as_ref().is_none_or(|cur| cur.tty != c.tty))
+            .collect();
+
+        let mismatched = match own_size {
+            Some(size) => others.iter().any(|c| (c.width, c.height) != size),
+            None => !others.is_empty(),
+        };
+        if !mismatched {
+            return None;
+        }
+        Some(SharedAttach { session: session.to_string(), others, own_size })
+    }
+
+    /// Advance the tutorial once the current step's action has happened.
+    /// Switching is intercepted: the TUI stays open on the sandbox server.
+    pub fn update_tutorial(&mut self) {
+        let Some(step) = self.tutorial.as_ref().map(|t| t.step) else {
+            return;
+        };
+
+        let done = match step {
+            0 => !self.engine.config.dimensions.is_empty(),
+            1 => self.engine.config.dimensions.iter().any(|d| !d.configured_tabs.is_empty()),
+            2 => self.input_mode == InputMode::Searching && !self.search_query.is_empty(),
+            3 => self.should_attach.is_some(),
+            _ => false,
+        };
+        if !done {
+            return;
+        }
+
+        if let Some(session) = self.should_attach.take() {
+            self.should_quit = false;
+            self.should_select_window = None;
+            self.current_session = Some(session.clone());
+            self.set_message(format!(
+                "Switched! Outside the tutorial you would now be in '{}'",
+                session
+            ));
+        }
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            tutorial.step += 1;
+        }
+        self.dirty = true;
+    }
+
+    /// Drop all cached tmux state so the next frame shows fresh windows (F5 / Ctrl+R)
+    pub fn refresh_tmux_state(&mut self) {
+        self.tmux_state.invalidate_all();
+        self.search_index = None;
+        self.last_computed_query.clear();
+        self.refresh_path_health();
+        if self.should_refresh_preview() || self.preview_content.is_some() {
+            self.update_preview();
+        }
+        self.set_message("Refreshed tmux state".to_string());
+    }
+
+    /// Run a switch (Enter, search selection) that detaches other clients of the target (Alt+Enter)
+    pub fn switch_detaching_others(&mut self, switch: fn(&mut Self) -> Result<()>) -> Result<()> {
+        let configured = self.should_detach_others;
+        self.should_detach_others = true;
+        let result = switch(self);
+        if result.is_err() {
+            self.should_detach_others = configured;
+        }
+        result
+    }
+
+    /// Go ahead with a switch held by `ConfirmingSharedAttach`
+    pub fn confirm_shared_attach(&mut self, detach_others: bool) {
+        self.shared_attach = None;
+        self.should_detach_others = detach_others;
+        self.input_mode = InputMode::Normal;
+        self.quit_without_detach();
+    }
+
     pub fn switch_to_last_tab_in_dimension(&mut self) -> Result<()> {
-        if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
+        if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
             let session_name = dimension.name.clone();
-            if Tmux::session_exists(&session_name) {
-                let windows = Tmux::list_windows(&session_name).unwrap_or_default();
-                self.selected_tab = windows.last().map(|(idx, _)| *idx);
+            if self.engine.backend.session_exists(&session_name) {
+                let windows = self.engine.backend.list_windows(&session_name).unwrap_or_default();
+                self.select_tab(windows.last().map(|(idx, _)| *idx));
             } else {
                 let tab_count = dimension.configured_tabs.len();
-                self.selected_tab = if tab_count > 0 { Some(tab_count - 1) } else { None };
+                self.select_tab(if tab_count > 0 { Some(tab_count - 1) } else { None });
             }
         }
         self.switch_to_dimension()
     }
 
     // Tab operations
-    pub fn add_tab_to_current_dimension(&mut self, name: String, command: Option<String>) -> Result<()> {
-        if let Some(dimension) = self.config.dimensions.get_mut(self.selected_dimension) {
-            // Inherit working_dir from dimension's base_dir, or use current_dir as fallback
-            let working_dir = dimension.base_dir.clone()
+    pub fn add_tab_to_current_dimension(
+        &mut self,
+        name: String,
+        command: Option<String>,
+        working_dir: Option<std::path::PathBuf>,
+    ) -> Result<()> {
+        if let Some(dimension) = self.engine.config.dimensions.get_mut(self.selected_dimension) {
+            // Without an explicit directory, inherit the dimension's base_dir, or use current_dir as fallback
+            let working_dir = working_dir
+                .or_else(|| dimension.base_dir.clone())
                 .or_else(|| std::env::current_dir().ok());
 
             let tab = Tab::new(name.clone(), command.clone(), working_dir.clone());
+            let command = dimension.launched_tab(&tab).command;
             dimension.add_tab(tab);
 
             let session_name = dimension.name.clone();
             let new_config_index = dimension.configured_tabs.len() - 1;
 
             // Create window in tmux if session exists
-            if Tmux::session_exists(&session_name) {
-                Tmux::new_window(&session_name, &name, command.as_deref(), working_dir.as_deref())?;
+            if self.engine.backend.session_exists(&session_name) {
+                self.engine.backend.new_window(&session_name, &name, command.as_deref(), working_dir.as_deref())?;
+                self.tmux_state.invalidate_all();
                 // Select the newly created window
-                let windows = Tmux::list_windows(&session_name).unwrap_or_default();
-                self.selected_tab = windows.last().map(|(idx, _)| *idx);
+                let windows = self.engine.backend.list_windows(&session_name).unwrap_or_default();
+                self.select_tab(windows.last().map(|(idx, _)| *idx));
             } else {
-                self.selected_tab = Some(new_config_index);
+                self.select_tab(Some(new_config_index));
             }
 
             self.save_config()?;
@@ -483,10 +1359,84 @@ impl App {
         Ok(())
     }
 
+    /// Remove a configured tab of the selected dimension, keeping it for `undo`
+    fn remove_configured_tab(&mut self, index: usize, window: Option<usize>) -> Option<Tab> {
+        let dimension = self.engine.config.dimensions.get_mut(self.selected_dimension)?;
+        let tab = dimension.remove_tab(index)?;
+        self.undo_stack.push(Undo::DeletedTab { dimension: dimension.name.clone(), index, window, tab: tab.clone() });
+        Some(tab)
+    }
+
+    /// `remove_configured_tab` by name, for the window at `window` in the session's list;
+    /// windows that aren't configured tabs are left alone
+    fn remove_configured_tab_named(&mut self, name: &str, window: usize) {
+        let index = self
+            .get_current_dimension()
+            .and_then(|dimension| dimension.configured_tabs.iter().position(|t| t.name == name));
+        if let Some(index) = index {
+            self.remove_configured_tab(index, Some(window));
+        }
+    }
+
+    /// `u`: take back the most recent dimension or tab deletion of this run. A dimension
+    /// comes back from the trash; a tab goes back in its place, and gets its window again
+    /// when the session is running.
+    pub fn undo(&mut self) -> Result<()> {
+        let Some(undo) = self.undo_stack.pop() else {
+            self.set_message("Nothing to undo".to_string());
+            return Ok(());
+        };
+        self.selected_orphan = None;
+        match undo {
+            Undo::DeletedDimension(name) => {
+                let name = self.engine.restore(Some(&name))?;
+                self.note_merge();
+                self.refresh_path_health();
+                if let Some(pos) = self.engine.config.dimensions.iter().position(|d| d.name == name) {
+                    self.selected_dimension = pos;
+                }
+                self.select_tab(None);
+                self.set_message(format!("Restored dimension: {}", name));
+            }
+            Undo::DeletedTab { dimension, index, window, tab } => {
+                let Some(pos) = self.engine.config.dimensions.iter().position(|d| d.name == dimension) else {
+                    anyhow::bail!("Dimension '{}' no longer exists", dimension);
+                };
+                let config = &mut self.engine.config.dimensions[pos];
+                let launched = config.launched_tab(&tab);
+                let index = index.min(config.configured_tabs.len());
+                config.configured_tabs.insert(index, tab);
+                self.selected_dimension = pos;
+                if self.engine.backend.session_exists(&dimension) {
+                    self.engine.backend.new_window(
+                        &dimension,
+                        &launched.name,
+                        launched.command.as_deref(),
+                        launched.working_dir.as_deref(),
+                    )?;
+                    self.tmux_state.invalidate_all();
+                    // The new window comes last; swap it back down to where the old one stood
+                    let windows = self.engine.backend.list_windows(&dimension).unwrap_or_default();
+                    let last = windows.len().saturating_sub(1);
+                    let position = window.unwrap_or(last).min(last);
+                    for i in (position + 1..=last).rev() {
+                        self.engine.backend.swap_windows(&dimension, windows[i - 1].0, windows[i].0)?;
+                    }
+                    self.select_tab(windows.get(position).map(|(idx, _)| *idx));
+                } else {
+                    self.select_tab(Some(index));
+                }
+                self.save_config()?;
+                self.set_message(format!("Restored tab: {}", launched.name));
+            }
+        }
+        Ok(())
+    }
+
     pub fn remove_tab_from_current_dimension(&mut self) -> Result<()> {
         if let Some(tab_index) = self.selected_tab {
             let session_name = {
-                if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
+                if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
                     dimension.name.clone()
                 } else {
                     return Ok(());
@@ -494,101 +1444,87 @@ impl App {
             };
 
             // Get the actual window index and name from tmux
-            if Tmux::session_exists(&session_name) {
-                let windows = Tmux::list_windows(&session_name)?;
+            if self.engine.backend.session_exists(&session_name) {
+                let windows = self.engine.backend.list_windows(&session_name)?;
                 if let Some((window_idx, window_name)) =
                     windows.iter().find(|(idx, _)| *idx == tab_index)
                 {
                     let window_idx = *window_idx;
                     let window_name = window_name.clone();
+                    let window_position = windows.iter().position(|(idx, _)| *idx == window_idx).unwrap_or(0);
                     let is_last_window = windows.len() == 1;
                     let is_current_session =
                         self.current_session.as_deref() == Some(session_name.as_str());
 
-                    if is_last_window && is_current_session && Tmux::is_inside_session() {
+                    // Killing the last window kills the session, so tear it down first
+                    let teardown_error = if is_last_window {
+                        self.get_current_dimension().and_then(engine::run_teardown)
+                    } else {
+                        None
+                    };
+
+                    if is_last_window && is_current_session && self.engine.backend.is_inside_session() {
                         // About to kill the last window of the session we're in.
                         // Find somewhere safe to land before the session disappears.
                         let (fallback_session, fallback_window) =
                             self.find_or_create_fallback_session(&session_name)?;
 
                         // Update config before killing
-                        if let Some(dimension) =
-                            self.config.dimensions.get_mut(self.selected_dimension)
-                        {
-                            if let Some(config_index) = dimension
-                                .configured_tabs
-                                .iter()
-                                .position(|t| t.name == window_name)
-                            {
-                                dimension.remove_tab(config_index);
-                            }
-                        }
+                        self.remove_configured_tab_named(&window_name, window_position);
                         self.save_config()?;
 
                         // Switch the client to the fallback before the session dies
                         let target = format!("{}:{}", fallback_session, fallback_window);
-                        Tmux::switch_session(&target)?;
+                        self.engine.backend.switch_session(&target)?;
 
                         // Kill the last window (kills the session)
-                        Tmux::kill_window(&session_name, window_idx)?;
+                        self.engine.backend.kill_window(&session_name, window_idx)?;
+                        self.tmux_state.invalidate_all();
 
-                        self.selected_tab = None;
+                        self.select_tab(None);
                         self.quit_without_detach();
                         return Ok(());
                     }
 
                     // Kill the tmux window
-                    Tmux::kill_window(&session_name, window_idx)?;
+                    self.engine.backend.kill_window(&session_name, window_idx)?;
+                    self.tmux_state.invalidate_all();
 
                     // Remove from config if it exists there
-                    if let Some(dimension) = self.config.dimensions.get_mut(self.selected_dimension) {
-                        if let Some(config_index) = dimension
-                            .configured_tabs
-                            .iter()
-                            .position(|t| t.name == window_name)
-                        {
-                            dimension.remove_tab(config_index);
-                        }
-                    }
+                    self.remove_configured_tab_named(&window_name, window_position);
                     self.save_config()?;
-                    self.set_message(format!("Removed tab: {}", window_name));
+                    match teardown_error {
+                        Some(err) => self.set_message(format!("Removed tab: {} ({})", window_name, err)),
+                        None => self.set_message(format!("Removed tab: {}", window_name)),
+                    }
 
                     // If we just killed the active window in the current session, tmux will
                     // switch the client to another window. Keep our selection in sync.
-                    if self.current_session.as_ref() == Some(&session_name) && Tmux::is_inside_session() {
-                        if let Ok(current_idx) = Tmux::get_current_window_index() {
+                    if self.current_session.as_ref() == Some(&session_name) && self.engine.backend.is_inside_session() {
+                        if let Ok(current_idx) = self.engine.backend.get_current_window_index() {
                             self.current_window = Some(current_idx);
-                            self.selected_tab = Some(current_idx);
+                            self.select_tab(Some(current_idx));
                             return Ok(());
                         }
                     }
 
                     // Otherwise, adjust selection based on remaining windows (track by tmux window index).
-                    let remaining = Tmux::list_windows(&session_name).unwrap_or_default();
-                    self.selected_tab = remaining.first().map(|(idx, _)| *idx);
+                    let remaining = self.engine.backend.list_windows(&session_name).unwrap_or_default();
+                    self.select_tab(remaining.first().map(|(idx, _)| *idx));
                 }
             } else {
                 // Session doesn't exist, just remove from config
-                let (removed_name, new_tab_count) = {
-                    if let Some(dimension) = self.config.dimensions.get_mut(self.selected_dimension) {
-                        if let Some(tab) = dimension.remove_tab(tab_index) {
-                            (Some(tab.name), dimension.configured_tabs.len())
-                        } else {
-                            (None, dimension.configured_tabs.len())
-                        }
-                    } else {
-                        (None, 0)
-                    }
-                };
+                let removed_name = self.remove_configured_tab(tab_index, None).map(|tab| tab.name);
+                let new_tab_count = self.get_current_dimension().map_or(0, |d| d.configured_tabs.len());
 
                 if let Some(name) = removed_name {
                     self.save_config()?;
                     self.set_message(format!("Removed tab: {}", name));
 
                     if tab_index >= new_tab_count && new_tab_count > 0 {
-                        self.selected_tab = Some(new_tab_count - 1);
+                        self.select_tab(Some(new_tab_count - 1));
                     } else if new_tab_count == 0 {
-                        self.selected_tab = None;
+                        self.select_tab(None);
                     }
                 }
             }
@@ -604,50 +1540,919 @@ impl App {
         self.clear_message();
     }
 
-    pub fn start_add_tab(&mut self) {
-        self.input_mode = InputMode::AddingTab;
-        self.input_buffer.clear();
-        self.clear_message();
+    pub fn start_add_tab(&mut self) {
+        self.input_mode = InputMode::AddingTab;
+        self.input_buffer.clear();
+        self.clear_message();
+    }
+
+    pub fn start_rename_dimension(&mut self) {
+        if let Some(dim) = self.engine.config.dimensions.get(self.selected_dimension) {
+            self.input_buffer = dim.name.clone();
+            self.input_mode = InputMode::RenamingDimension;
+            self.clear_message();
+        }
+    }
+
+    pub fn start_rename_tab(&mut self) {
+        if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
+            if let Some(tab_index) = self.selected_tab {
+                let current_name = if self.engine.backend.session_exists(&dimension.name) {
+                    self.engine.backend.list_windows(&dimension.name)
+                        .ok()
+                        .and_then(|windows| {
+                            windows.iter()
+                                .find(|(idx, _)| *idx == tab_index)
+                                .map(|(_, name)| name.clone())
+                        })
+                        .unwrap_or_default()
+                } else {
+                    dimension.configured_tabs
+                        .get(tab_index)
+                        .map(|t| t.name.clone())
+                        .unwrap_or_default()
+                };
+                self.input_buffer = current_name;
+                self.input_mode = InputMode::RenamingTab;
+                self.clear_message();
+            }
+        }
+    }
+
+    /// The tmux window Dimensions was opened from: (session, window index, window name)
+    fn current_window_target(&self) -> Result<(String, usize, String)> {
+        let (Some(session), Some(index)) = (self.current_session.clone(), self.current_window) else {
+            anyhow::bail!("Not inside a tmux window");
+        };
+        let name = self.engine.backend.list_windows(&session)?
+            .into_iter()
+            .find(|(idx, _)| *idx == index)
+            .map(|(_, name)| name)
+            .ok_or_else(|| anyhow::anyhow!("Current window {}:{} no longer exists", session, index))?;
+        Ok((session, index, name))
+    }
+
+    /// Open the menu of actions on the current tmux window (`w`)
+    pub fn start_current_window_menu(&mut self) {
+        match self.current_window_target() {
+            Ok(_) => {
+                self.input_mode = InputMode::CurrentWindowMenu;
+                self.clear_message();
+            }
+            Err(e) => self.set_message(e.to_string()),
+        }
+    }
+
+    /// `--here`: open straight into renaming the current window (Tab for the rest of the menu)
+    pub fn start_here(&mut self) {
+        self.here = true;
+        self.start_rename_current_window();
+    }
+
+    /// ↑/↓ in the window menu: choose the dimension to adopt or move into
+    pub fn cycle_window_target(&mut self, forward: bool) {
+        let count = self.engine.config.dimensions.len();
+        if count == 0 {
+            return;
+        }
+        let current = if self.selected_orphan.is_some() { 0 } else { self.selected_dimension };
+        self.selected_orphan = None;
+        self.selected_dimension = if forward { (current + 1) % count } else { (current + count - 1) % count };
+        self.select_tab(None);
+    }
+
+    /// Save the current window as a configured tab of the selected dimension,
+    /// moving it into that dimension's session when it is running elsewhere
+    pub fn adopt_current_window(&mut self) -> Result<()> {
+        let (session, index, name) = self.current_window_target()?;
+        let Some(target) = self.get_current_dimension().map(|d| d.name.clone()) else {
+            anyhow::bail!("No dimension selected");
+        };
+
+        let working_dir = self.engine.backend.pane_current_path(&session, index);
+        if let Some(dimension) = self.engine.config.dimensions.get_mut(self.selected_dimension) {
+            if dimension.configured_tabs.iter().any(|t| t.name == name) {
+                anyhow::bail!("'{}' already has a tab named '{}'", target, name);
+            }
+            dimension.add_tab(Tab::new(name.clone(), None, working_dir));
+        }
+
+        if target != session && self.engine.backend.session_exists(&target) {
+            self.engine.backend.move_window(&session, index, &target)?;
+            self.tmux_state.invalidate_all();
+            self.current_window = self.engine.backend.get_current_window_index().ok();
+        }
+        self.save_config()?;
+        self.input_mode = InputMode::Normal;
+        self.set_message(format!("Adopted window '{}' into '{}'", name, target));
+        Ok(())
+    }
+
+    /// Move the current window into the selected dimension's session, carrying its
+    /// configured tab along if it has one
+    pub fn move_current_window(&mut self) -> Result<()> {
+        let (session, index, name) = self.current_window_target()?;
+        let Some(target) = self.get_current_dimension().map(|d| d.name.clone()) else {
+            anyhow::bail!("No dimension selected");
+        };
+        if target == session {
+            anyhow::bail!("Window '{}' is already in '{}'", name, target);
+        }
+        if !self.engine.backend.session_exists(&target) {
+            anyhow::bail!("Start '{}' first (Enter), then move the window into it", target);
+        }
+
+        self.engine.backend.move_window(&session, index, &target)?;
+        self.tmux_state.invalidate_all();
+        self.current_window = self.engine.backend.get_current_window_index().ok();
+
+        let tab = self
+            .engine.config
+            .dimensions
+            .iter_mut()
+            .find(|d| d.name == session)
+            .and_then(|d| {
+                let pos = d.configured_tabs.iter().position(|t| t.name == name)?;
+                d.remove_tab(pos)
+            });
+        if let Some(tab) = tab {
+            if let Some(dimension) = self.engine.config.dimensions.iter_mut().find(|d| d.name == target) {
+                dimension.add_tab(tab);
+            }
+            self.save_config()?;
+        }
+
+        self.input_mode = InputMode::Normal;
+        self.set_message(format!("Moved window '{}' to '{}'", name, target));
+        Ok(())
+    }
+
+    pub fn start_rename_current_window(&mut self) {
+        match self.current_window_target() {
+            Ok((_, _, name)) => {
+                self.input_buffer = name;
+                self.input_mode = InputMode::RenamingCurrentWindow;
+                self.clear_message();
+            }
+            Err(e) => {
+                self.input_mode = InputMode::Normal;
+                self.set_message(e.to_string());
+            }
+        }
+    }
+
+    /// Rename the current tmux window and its configured tab, if any
+    pub fn rename_current_window(&mut self, new_name: String) -> Result<()> {
+        let (session, index, old_name) = self.current_window_target()?;
+        if new_name.is_empty() || new_name == old_name {
+            return Ok(());
+        }
+
+        self.engine.backend.rename_window(&session, index, &new_name)?;
+        self.tmux_state.invalidate_all();
+
+        let tab = self
+            .engine.config
+            .dimensions
+            .iter_mut()
+            .find(|d| d.name == session)
+            .and_then(|d| d.configured_tabs.iter_mut().find(|t| t.name == old_name));
+        if let Some(tab) = tab {
+            tab.name = new_name;
+            self.save_config()?;
+        }
+        Ok(())
+    }
+
+    /// Open the quick actions menu for the selected dimension
+    pub fn start_quick_actions(&mut self) {
+        if self.get_current_dimension().is_some() {
+            self.quick_action = 0;
+            self.input_mode = InputMode::QuickActions;
+            self.clear_message();
+        }
+    }
+
+    pub fn next_quick_action(&mut self) {
+        self.quick_action = (self.quick_action + 1) % QuickAction::ALL.len();
+    }
+
+    pub fn previous_quick_action(&mut self) {
+        self.quick_action = (self.quick_action + QuickAction::ALL.len() - 1) % QuickAction::ALL.len();
+    }
+
+    pub fn run_quick_action(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        match QuickAction::ALL[self.quick_action] {
+            QuickAction::Start => self.switch_to_dimension(),
+            QuickAction::Kill => self.kill_dimension_session().map(|_| ()),
+            QuickAction::Hibernate => self.hibernate_dimension_session(),
+            QuickAction::Rename => {
+                self.start_rename_dimension();
+                Ok(())
+            }
+            QuickAction::EditConfig => self.edit_config(),
+            QuickAction::OpenDir => self.open_dimension_dir(),
+        }
+    }
+
+    /// Move the selected dimension up (-1) or down (+1) in the list and the config (`K`/`J`)
+    pub fn move_dimension(&mut self, delta: isize) -> Result<()> {
+        // The list wouldn't show the new config order
+        if self.engine.config.startup.sort != DimensionSort::Config {
+            self.set_message("Reordering is off while startup.sort sorts the list".to_string());
+            return Ok(());
+        }
+        let from = self.selected_dimension;
+        let Some(to) = from.checked_add_signed(delta).filter(|&to| to < self.engine.config.dimensions.len()) else {
+            return Ok(());
+        };
+        self.engine.move_dimension(from, to)?;
+        self.selected_dimension = to;
+        self.note_merge();
+        Ok(())
+    }
+
+    /// Move the selected tab left (-1) or right (+1) (`H`/`L`). In a running session the window
+    /// swaps places with its neighbour, and their saved tabs swap in the config as well.
+    pub fn move_tab(&mut self, delta: isize) -> Result<()> {
+        let (Some(selected), Some(dimension)) = (self.selected_tab, self.get_current_dimension()) else {
+            return Ok(());
+        };
+        let name = dimension.name.clone();
+
+        if !self.engine.backend.session_exists(&name) {
+            let Some(to) = selected.checked_add_signed(delta).filter(|&to| to < dimension.configured_tabs.len()) else {
+                return Ok(());
+            };
+            if let Some(dimension) = self.engine.config.dimensions.get_mut(self.selected_dimension) {
+                dimension.configured_tabs.swap(selected, to);
+            }
+            self.select_tab(Some(to));
+            return self.save_config();
+        }
+
+        let windows = self.engine.backend.list_windows(&name)?;
+        let Some(pos) = windows.iter().position(|(idx, _)| *idx == selected) else {
+            return Ok(());
+        };
+        let Some(to) = pos.checked_add_signed(delta).filter(|&to| to < windows.len()) else {
+            return Ok(());
+        };
+        let other = windows[to].0;
+        self.engine.backend.swap_windows(&name, selected, other)?;
+        self.tmux_state.invalidate_all();
+        self.select_tab(Some(other));
+
+        // Windows made by hand have no saved tab; only two saved tabs trade places
+        let (first, second) = (&windows[pos].1, &windows[to].1);
+        if let Some(dimension) = self.engine.config.dimensions.get_mut(self.selected_dimension) {
+            let tabs = &mut dimension.configured_tabs;
+            if let (Some(a), Some(b)) = (tabs.iter().position(|t| &t.name == first), tabs.iter().position(|t| &t.name == second)) {
+                tabs.swap(a, b);
+                return self.save_config();
+            }
+        }
+        Ok(())
+    }
+
+    /// Kill the selected dimension's tmux session but keep it in the config.
+    /// Returns whether a session was killed.
+    pub fn kill_dimension_session(&mut self) -> Result<bool> {
+        let Some(dimension) = self.get_current_dimension().cloned() else {
+            return Ok(false);
+        };
+        let name = dimension.name.clone();
+        if !self.engine.backend.session_exists(&name) {
+            anyhow::bail!("'{}' is not running", name);
+        }
+
+        let inside_target_dimension = self.current_session.as_deref() == Some(name.as_str());
+        if inside_target_dimension && self.engine.backend.is_inside_session() {
+            // Switch away before killing our own session
+            let (fallback_session, fallback_window) = self.find_or_create_fallback_session(&name)?;
+            self.engine.backend.switch_session(&format!("{}:{}", fallback_session, fallback_window))?;
+        }
+        let teardown_error = self.engine.kill_session(&dimension)?;
+        self.tmux_state.invalidate_all();
+        self.select_tab(None);
+
+        if inside_target_dimension {
+            self.quit_without_detach();
+            return Ok(true);
+        }
+        match teardown_error {
+            Some(err) => self.set_message(format!("Killed session: {} ({})", name, err)),
+            None => self.set_message(format!("Killed session: {}", name)),
+        }
+        Ok(true)
+    }
+
+    /// Snapshot the selected dimension's session, then kill it; switching to it later thaws it
+    pub fn hibernate_dimension_session(&mut self) -> Result<()> {
+        let Some(name) = self.get_current_dimension().map(|d| d.name.clone()) else {
+            return Ok(());
+        };
+        let snapshot = snapshot::capture(self.engine.backend.as_ref(), &name)?;
+        if !self.kill_dimension_session()? {
+            return Ok(());
+        }
+        let snapshot = snapshot::hibernate(snapshot)?;
+        let panes: usize = snapshot.windows.iter().map(|w| w.panes.len()).sum();
+        self.set_message(format!(
+            "Hibernated '{}' ({} windows, {} panes); switch to it to thaw",
+            name,
+            snapshot.windows.len(),
+            panes
+        ));
+        Ok(())
+    }
+
+    /// Quit and open the config file in $EDITOR (main launches it once the terminal is restored)
+    pub fn edit_config(&mut self) -> Result<()> {
+        if std::env::var_os("EDITOR").is_none() {
+            anyhow::bail!("Set $EDITOR to edit the config from here");
+        }
+        self.should_edit_config = true;
+        self.quit_without_detach();
+        Ok(())
+    }
+
+    /// Show the selected dimension's base directory, as configured in `open_dir`
+    pub fn open_dimension_dir(&mut self) -> Result<()> {
+        let Some(dimension) = self.get_current_dimension() else {
+            return Ok(());
+        };
+        let Some(dir) = dimension.base_dir.clone() else {
+            anyhow::bail!("'{}' has no base directory", dimension.name);
+        };
+        let name = dimension.name.clone();
+
+        match self.engine.config.open_dir.with {
+            OpenDirWith::System => {
+                self.engine.backend.spawn_detached(system_opener(dir.as_os_str()))?;
+                self.set_message(format!("Opened {}", dir.display()));
+                Ok(())
+            }
+            OpenDirWith::Window => self.open_dir_in_window(&name, &dir),
+        }
+    }
+
+    /// Ask which of the selected dimension's links to open
+    pub fn start_link_picker(&mut self) -> Result<()> {
+        let Some(dimension) = self.get_current_dimension() else {
+            return Ok(());
+        };
+        if dimension.links.is_empty() {
+            anyhow::bail!("'{}' has no links; add some under `links` in its config", dimension.name);
+        }
+        self.link_selected = 0;
+        self.input_mode = InputMode::PickingLink;
+        self.clear_message();
+        Ok(())
+    }
+
+    pub fn next_link(&mut self) {
+        let count = self.get_current_dimension().map(|d| d.links.len()).unwrap_or(0);
+        if count > 0 {
+            self.link_selected = (self.link_selected + 1) % count;
+        }
+    }
+
+    pub fn previous_link(&mut self) {
+        let count = self.get_current_dimension().map(|d| d.links.len()).unwrap_or(0);
+        if count > 0 {
+            self.link_selected = (self.link_selected + count - 1) % count;
+        }
+    }
+
+    /// Open the highlighted link in the system browser
+    pub fn open_selected_link(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        let Some(link) = self.get_current_dimension().and_then(|d| d.links.get(self.link_selected)).cloned() else {
+            return Ok(());
+        };
+        self.engine.backend.spawn_detached(system_opener(link.url.as_ref()))?;
+        self.set_message(format!("Opened {} ({})", link.name, link.url));
+        Ok(())
+    }
+
+    /// Run the configured file manager in a new window, in the dimension's session when it
+    /// is running (the current session otherwise), then switch to that window
+    fn open_dir_in_window(&mut self, name: &str, dir: &std::path::Path) -> Result<()> {
+        let command = self
+            .engine.config
+            .open_dir
+            .command
+            .clone()
+            .or_else(|| std::env::var("FILE_MANAGER").ok())
+            .filter(|c| !c.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Set open_dir.command or $FILE_MANAGER to open directories in a window"))?;
+
+        let session = if self.engine.backend.session_exists(name) {
+            name.to_string()
+        } else if let Some(current) = self.current_session.clone() {
+            current
+        } else {
+            anyhow::bail!("'{}' is not running; start it to open a file manager window", name);
+        };
+
+        // Window named after the program, e.g. "yazi"
+        let window_name = command
+            .split_whitespace()
+            .next()
+            .and_then(|bin| bin.rsplit('/').next())
+            .unwrap_or("files")
+            .to_string();
+        self.engine.backend.new_window(&session, &window_name, Some(&command), Some(dir))?;
+        self.tmux_state.invalidate_all();
+
+        let window = self.engine.backend.list_windows(&session)?
+            .into_iter()
+            .rev()
+            .find(|(_, n)| *n == window_name)
+            .map(|(idx, _)| idx);
+        self.should_attach = Some(session);
+        self.should_select_window = window;
+        self.quit_without_detach();
+        Ok(())
+    }
+
+    /// The configured tab behind the selection (matched by name for live sessions)
+    pub fn selected_configured_tab(&self) -> Option<&Tab> {
+        let dimension = self.get_current_dimension()?;
+        let selected = self.selected_tab?;
+        match self.tmux_state.windows(&dimension.name) {
+            Some(windows) => {
+                let (_, name) = windows.into_iter().find(|(idx, _)| *idx == selected)?;
+                dimension.configured_tabs.iter().find(|t| t.name == name)
+            }
+            None => dimension.configured_tabs.get(selected),
+        }
+    }
+
+    /// Whether `dimension`'s details are masked (private and not revealed)
+    pub fn is_concealed(&self, dimension: &str) -> bool {
+        !self.revealed.contains(dimension)
+            && self.engine.config.get_dimension(dimension).is_some_and(|d| d.private)
+    }
+
+    /// `v`: show or hide the selected private dimension's tabs and preview
+    pub fn toggle_reveal(&mut self) {
+        let Some(dimension) = self.get_current_dimension() else {
+            return;
+        };
+        if !dimension.private {
+            let msg = format!("'{}' isn't private", dimension.name);
+            self.set_message(msg);
+            return;
+        }
+        let name = dimension.name.clone();
+        if !self.revealed.remove(&name) {
+            self.revealed.insert(name);
+        }
+    }
+
+    /// `c`: show or hide the selected dimension's tabs nested under it in the list
+    pub fn toggle_collapse_dimension(&mut self) {
+        let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) else {
+            return;
+        };
+        let name = dimension.name.clone();
+        if !self.expanded.remove(&name) {
+            self.expanded.insert(name);
+        }
+        if self.engine.persist {
+            expanded::save(&self.expanded);
+        }
+    }
+
+    /// `P`: hide or show commands, paths and previews for the whole UI
+    pub fn toggle_presentation(&mut self) {
+        self.presentation = !self.presentation;
+        let msg = if self.presentation {
+            "Presentation mode: commands, paths and previews hidden (P to show)"
+        } else {
+            "Presentation mode off"
+        };
+        self.set_message(msg.to_string());
+    }
+
+    /// `p`: show or hide the preview panel
+    pub fn toggle_preview_panel(&mut self) {
+        self.preview_panel = !self.preview_panel;
+    }
+
+    /// `m`: switch between the lists and the matrix view. The matrix always has a cell
+    /// selected when the dimension has any tabs.
+    pub fn toggle_matrix(&mut self) {
+        self.matrix = !self.matrix;
+        if self.matrix {
+            self.selected_orphan = None;
+            self.matrix_move(0, 0);
+        }
+    }
+
+    /// Move the matrix selection by `rows` dimensions and `columns` tabs, stopping at the
+    /// edges. Changing rows keeps the column position, or the last column of a shorter row.
+    pub fn matrix_move(&mut self, rows: isize, columns: isize) {
+        let order = self.dimension_order();
+        if order.is_empty() {
+            return;
+        }
+        let column = self
+            .selected_tab
+            .and_then(|tab| self.matrix_columns(self.selected_dimension).iter().position(|&index| index == tab))
+            .unwrap_or(0);
+        let row = self.selected_dimension_row(&order).saturating_add_signed(rows).min(order.len() - 1);
+        self.selected_dimension = order[row];
+        let cells = self.matrix_columns(self.selected_dimension);
+        let column = column.saturating_add_signed(columns).min(cells.len().saturating_sub(1));
+        self.select_tab(cells.get(column).copied());
+    }
+
+    /// A dimension's tabs in matrix order: the window indexes of its running session, or
+    /// the positions of its configured tabs
+    fn matrix_columns(&self, dimension: usize) -> Vec<usize> {
+        let Some(dimension) = self.engine.config.dimensions.get(dimension) else {
+            return vec![];
+        };
+        match self.tmux_state.windows(&dimension.name) {
+            Some(windows) => windows.into_iter().map(|(index, _)| index).collect(),
+            None => (0..dimension.configured_tabs.len()).collect(),
+        }
+    }
+
+    /// Left click: select the dimension or tab under the cursor, or switch to it when
+    /// it's already selected (so a double-click switches)
+    pub fn click(&mut self, column: u16, row: u16) -> Result<()> {
+        let targets = self.mouse_targets.clone();
+        if let Some(position) = targets.dimensions.and_then(|list| list.row_at(column, row)) {
+            match targets.dimension_items.get(position) {
+                Some(&DimensionItem::Dimension(index)) => {
+                    let selected = self.selected_orphan.is_none()
+                        && self.selected_dimension == index
+                        && self.selected_tab.is_none();
+                    if selected {
+                        return self.switch_or_pick_task();
+                    }
+                    self.selected_orphan = None;
+                    self.selected_dimension = index;
+                    self.select_tab(None);
+                }
+                Some(&DimensionItem::Tab(index, tab)) => {
+                    let selected = self.selected_orphan.is_none()
+                        && self.selected_dimension == index
+                        && self.selected_tab == Some(tab);
+                    if selected {
+                        return self.switch_or_pick_task();
+                    }
+                    self.selected_orphan = None;
+                    self.selected_dimension = index;
+                    self.select_tab(Some(tab));
+                }
+                Some(&DimensionItem::Orphan(orphan)) => {
+                    if self.selected_orphan == Some(orphan) {
+                        self.switch_to_orphan();
+                        return Ok(());
+                    }
+                    self.selected_orphan = Some(orphan);
+                    self.select_tab(None);
+                }
+                Some(DimensionItem::Header) | None => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(position) = targets.tabs.and_then(|list| list.row_at(column, row)) {
+            let Some(&tab) = targets.tab_indexes.get(position) else {
+                return Ok(());
+            };
+            if self.selected_tab == Some(tab) {
+                return self.switch_or_pick_task();
+            }
+            self.select_tab(Some(tab));
+        }
+        Ok(())
+    }
+
+    /// Scroll wheel: move the selection in the pane under the cursor
+    pub fn scroll(&mut self, column: u16, row: u16, down: bool) {
+        let over = |list: Option<ListArea>| list.is_some_and(|l| l.contains(column, row));
+        if over(self.mouse_targets.tabs) && self.selected_orphan.is_none() {
+            if down {
+                self.next_tab();
+            } else {
+                self.previous_tab_in_pane();
+            }
+        } else if over(self.mouse_targets.dimensions) {
+            if down {
+                self.next_dimension();
+            } else {
+                self.previous_dimension();
+            }
+        }
+    }
+
+    /// Enter in normal mode: task tabs ask which task to run, everything else switches
+    pub fn switch_or_pick_task(&mut self) -> Result<()> {
+        if self.selected_configured_tab().is_some_and(|t| t.is_task_tab()) {
+            self.task_selected = 0;
+            self.input_mode = InputMode::PickingTask;
+            self.clear_message();
+            return Ok(());
+        }
+        self.switch_to_dimension()
+    }
+
+    pub fn next_task(&mut self) {
+        let count = self.selected_configured_tab().map(|t| t.tasks.len()).unwrap_or(0);
+        if count > 0 {
+            self.task_selected = (self.task_selected + 1) % count;
+        }
+    }
+
+    pub fn previous_task(&mut self) {
+        let count = self.selected_configured_tab().map(|t| t.tasks.len()).unwrap_or(0);
+        if count > 0 {
+            self.task_selected = (self.task_selected + count - 1) % count;
+        }
+    }
+
+    /// Run the highlighted task in its tab's window and switch there
+    pub fn run_selected_task(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        let Some(tab) = self.selected_configured_tab().cloned() else {
+            return Ok(());
+        };
+        let Some(task) = tab.tasks.get(self.task_selected).cloned() else {
+            return Ok(());
+        };
+        let Some(name) = self.get_current_dimension().map(|d| d.name.clone()) else {
+            return Ok(());
+        };
+
+        if !self.engine.backend.session_exists(&name) {
+            self.task_override = Some((tab.name, task.command));
+            return self.switch_to_dimension();
+        }
+
+        let window = self.engine.backend.list_windows(&name)?
+            .into_iter()
+            .find(|(_, n)| *n == tab.name)
+            .map(|(idx, _)| idx);
+        usage::record(EventKind::Command, &name, Some(&task.command));
+        let window = match window {
+            Some(idx) => {
+                self.engine.backend.send_keys(&name, idx, &Tmux::record_exit_status(&task.command))?;
+                idx
+            }
+            // The window was closed; bring it back running the task, as the tab would start it
+            None => {
+                let task_tab = Tab { command: Some(task.command.clone()), ..tab.clone() };
+                let launched = match self.get_current_dimension() {
+                    Some(dimension) => dimension.launched_tab(&task_tab),
+                    None => task_tab,
+                };
+                self.engine.backend.new_window(&name, &tab.name, None, tab.working_dir.as_deref())?;
+                let idx = self.engine.backend.list_windows(&name)?
+                    .into_iter()
+                    .rev()
+                    .find(|(_, n)| *n == tab.name)
+                    .map(|(idx, _)| idx)
+                    .unwrap_or(0);
+                if let Some(command) = launched.launch_command() {
+                    self.engine.backend.send_keys(&name, idx, &Tmux::record_exit_status(&command))?;
+                }
+                idx
+            }
+        };
+        self.tmux_state.invalidate_all();
+        self.select_tab(Some(window));
+        self.complete_switch(&name, true);
+        Ok(())
+    }
+
+    /// `T`: choose a template to create a dimension from
+    pub fn start_template_picker(&mut self) {
+        if self.engine.config.templates.is_empty() {
+            self.set_message("No templates yet: add a [[templates]] section to the config".to_string());
+            return;
+        }
+        self.template_selected = 0;
+        self.input_mode = InputMode::PickingTemplate;
+        self.clear_message();
+    }
+
+    pub fn next_template(&mut self) {
+        let count = self.engine.config.templates.len();
+        if count > 0 {
+            self.template_selected = (self.template_selected + 1) % count;
+        }
+    }
+
+    pub fn previous_template(&mut self) {
+        let count = self.engine.config.templates.len();
+        if count > 0 {
+            self.template_selected = (self.template_selected + count - 1) % count;
+        }
+    }
+
+    /// Start asking for the highlighted template's variables, beginning with the name
+    pub fn start_template_fill(&mut self) -> Result<()> {
+        let Some(template) = self.engine.config.templates.get(self.template_selected) else {
+            self.cancel_input();
+            return Ok(());
+        };
+        self.template_fill = Some(template::Fill::new(self.template_selected, template)?);
+        self.input_mode = InputMode::FillingTemplate;
+        self.input_buffer.clear();
+        self.clear_message();
+        Ok(())
+    }
+
+    /// Enter while filling a template: keep the value and ask for the next variable,
+    /// or create the dimension once every variable has one
+    fn submit_template_variable(&mut self) -> Result<()> {
+        use crate::path_completion::PathCompleter;
+
+        let Some(variable) = self.template_fill.as_ref().and_then(|f| f.current()).map(str::to_string) else {
+            self.cancel_input();
+            return Ok(());
+        };
+        let input = self.input_buffer.trim().to_string();
+        let value = match variable.as_str() {
+            "name" if input.is_empty() => {
+                self.set_message("Name can't be empty".to_string());
+                return Ok(());
+            }
+            "name" if self.engine.config.name_in_use(&input) => {
+                self.set_message(format!("'{}' already exists", input));
+                return Ok(());
+            }
+            // Empty means no base directory, like when creating a dimension by hand
+            "path" if !input.is_empty() => match PathCompleter::validate_directory(&input) {
+                Ok(path) => path.to_string_lossy().into_owned(),
+                Err(err) => {
+                    self.set_message(err);
+                    return Ok(());
+                }
+            },
+            _ => input,
+        };
+
+        let Some(fill) = self.template_fill.as_mut() else {
+            return Ok(());
+        };
+        fill.values.insert(variable, value);
+        let current = fill.current().map(str::to_string);
+        self.input_buffer.clear();
+        self.clear_completion_state();
+        self.clear_message();
+        match current.as_deref() {
+            // Pre-fill the directory with the current one, as `n` does
+            Some("path") => {
+                if let Some(cwd) = std::env::current_dir().ok().and_then(|d| d.to_str().map(str::to_string)) {
+                    self.input_buffer = cwd;
+                }
+                return Ok(());
+            }
+            Some(_) => return Ok(()),
+            None => {}
+        }
+
+        let Some(fill) = self.template_fill.take() else {
+            return Ok(());
+        };
+        self.cancel_input();
+        let template = &self.engine.config.templates[fill.template];
+        let template_name = template.name.clone();
+        let dimension = template::instantiate(template, &fill.values)?;
+        let name = dimension.name.clone();
+        self.engine.config.add_dimension(dimension);
+        self.save_config()?;
+        self.refresh_path_health();
+        self.selected_dimension = self.engine.config.dimensions.len() - 1;
+        self.select_tab(None);
+        self.set_message(format!("Created '{}' from template '{}'", name, template_name));
+        Ok(())
+    }
+
+    /// Compare the selected dimension's tabs with its live windows and review the differences (`S`)
+    pub fn start_sync(&mut self) -> Result<()> {
+        let Some(dimension) = self.get_current_dimension() else {
+            return Ok(());
+        };
+        let session = dimension.name.clone();
+        if let Some(host) = &dimension.remote {
+            self.set_message(format!("'{}' runs on {}; its windows aren't synced", session, host));
+            return Ok(());
+        }
+        if !self.engine.backend.session_exists(&session) {
+            self.set_message(format!("'{}' isn't running; nothing to sync", session));
+            return Ok(());
+        }
+
+        let live: Vec<LiveWindow> = self.engine.backend.list_window_paths(&session)?
+            .into_iter()
+            .map(|(_, name, path)| LiveWindow { name, path })
+            .collect();
+        let items = sync::plan(&dimension.configured_tabs, &live);
+        if items.is_empty() {
+            self.set_message(format!("'{}' is already in sync", session));
+            return Ok(());
+        }
+
+        self.sync = Some(SyncState { session, live, items, selected: 0 });
+        self.input_mode = InputMode::Syncing;
+        self.clear_message();
+        Ok(())
+    }
+
+    pub fn next_sync_item(&mut self) {
+        if let Some(sync) = self.sync.as_mut() {
+            sync.selected = (sync.selected + 1) % sync.items.len();
+        }
+    }
+
+    pub fn previous_sync_item(&mut self) {
+        if let Some(sync) = self.sync.as_mut() {
+            sync.selected = (sync.selected + sync.items.len() - 1) % sync.items.len();
+        }
+    }
+
+    pub fn toggle_sync_item(&mut self) {
+        if let Some(item) = self.sync.as_mut().and_then(|s| s.items.get_mut(s.selected)) {
+            item.accepted = !item.accepted;
+        }
+    }
+
+    /// Write the accepted changes into the dimension's configured tabs
+    pub fn apply_sync(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        let Some(sync) = self.sync.take() else {
+            return Ok(());
+        };
+        let Some(dimension) = self.engine.config.dimensions.iter_mut().find(|d| d.name == sync.session) else {
+            return Ok(());
+        };
+
+        dimension.configured_tabs = sync::apply(&dimension.configured_tabs, &sync.live, &sync.items);
+        self.save_config()?;
+        let applied = sync.items.iter().filter(|i| i.accepted).count();
+        self.set_message(format!("Synced '{}' ({} change(s))", sync.session, applied));
+        Ok(())
     }
 
-    pub fn start_rename_dimension(&mut self) {
-        if let Some(dim) = self.config.dimensions.get(self.selected_dimension) {
-            self.input_buffer = dim.name.clone();
-            self.input_mode = InputMode::RenamingDimension;
+    /// Start re-pointing the selected dimension's base directory (path completion enabled)
+    pub fn start_repoint_directory(&mut self) {
+        if let Some(dim) = self.engine.config.dimensions.get(self.selected_dimension) {
+            self.input_buffer = dim
+                .base_dir
+                .as_ref()
+                .and_then(|p| p.to_str())
+                .unwrap_or_default()
+                .to_string();
+            self.input_mode = InputMode::RepointingDirectory;
             self.clear_message();
         }
     }
 
-    pub fn start_rename_tab(&mut self) {
-        if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
-            if let Some(tab_index) = self.selected_tab {
-                let current_name = if Tmux::session_exists(&dimension.name) {
-                    Tmux::list_windows(&dimension.name)
-                        .ok()
-                        .and_then(|windows| {
-                            windows.iter()
-                                .find(|(idx, _)| *idx == tab_index)
-                                .map(|(_, name)| name.clone())
-                        })
-                        .unwrap_or_default()
-                } else {
-                    dimension.configured_tabs
-                        .get(tab_index)
-                        .map(|t| t.name.clone())
-                        .unwrap_or_default()
-                };
-                self.input_buffer = current_name;
-                self.input_mode = InputMode::RenamingTab;
-                self.clear_message();
-            }
-        }
+    pub fn repoint_dimension(&mut self, new_dir: std::path::PathBuf) -> Result<()> {
+        let Some(dimension) = self.engine.config.dimensions.get_mut(self.selected_dimension) else {
+            return Ok(());
+        };
+        dimension.repoint_base_dir(new_dir.clone());
+        let name = dimension.name.clone();
+
+        self.save_config()?;
+        self.refresh_path_health();
+        self.set_message(format!("'{}' now points at {}", name, new_dir.display()));
+        Ok(())
     }
 
     pub fn start_delete_dimension(&mut self) {
+        // Killing live processes can need more than a stray `y`
+        self.delete_requires_name = self.engine.config.ui.confirm_delete == ConfirmStyle::TypeName
+            && self
+                .get_current_dimension()
+                .is_some_and(|d| self.engine.backend.session_exists(&d.name));
+        // A long build or a server in one of its panes is named in the confirmation
+        self.delete_running = match self.get_current_dimension() {
+            Some(dimension) => self.engine.running_programs(&dimension.name),
+            None => vec![],
+        };
+        self.input_buffer.clear();
         self.input_mode = InputMode::DeletingDimension;
         self.clear_message();
     }
 
+    /// Whether the typed confirmation matches the dimension being deleted
+    pub fn delete_name_matches(&self) -> bool {
+        self.get_current_dimension()
+            .is_some_and(|d| self.input_buffer.trim() == d.name)
+    }
+
     pub fn start_delete_tab(&mut self) {
         self.input_mode = InputMode::DeletingTab;
         self.clear_message();
@@ -658,31 +2463,35 @@ impl App {
             return Ok(());
         }
 
-        if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
+        if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
             if dimension.name == new_name {
                 return Ok(());
             }
         }
 
-        if self.config.dimensions.iter().any(|d| d.name == new_name) {
+        if self.engine.config.name_in_use(&new_name) {
             self.set_message(format!("Dimension '{}' already exists", new_name));
             return Ok(());
         }
 
-        if let Some(dimension) = self.config.dimensions.get_mut(self.selected_dimension) {
-            let old_name = dimension.name.clone();
-
-            if Tmux::session_exists(&old_name) {
-                Tmux::rename_session(&old_name, &new_name)?;
-            }
+        if let Some(old_name) = self.get_current_dimension().map(|d| d.name.clone()) {
+            let warning = self.engine.rename(&old_name, &new_name)?;
+            self.note_merge();
+            self.tmux_state.invalidate_all();
 
             if self.current_session.as_deref() == Some(old_name.as_str()) {
                 self.current_session = Some(new_name.clone());
             }
-
-            dimension.name = new_name.clone();
-            self.save_config()?;
-            self.set_message(format!("Renamed to '{}'", new_name));
+            if self.expanded.remove(&old_name) {
+                self.expanded.insert(new_name.clone());
+                if self.engine.persist {
+                    expanded::save(&self.expanded);
+                }
+            }
+            match warning {
+                Some(warning) => self.set_message(format!("Renamed to '{}' ({})", new_name, warning)),
+                None => self.set_message(format!("Renamed to '{}'", new_name)),
+            }
         }
 
         Ok(())
@@ -697,19 +2506,20 @@ impl App {
             return Ok(());
         };
 
-        let Some(dimension) = self.config.dimensions.get_mut(self.selected_dimension) else {
+        let Some(dimension) = self.engine.config.dimensions.get_mut(self.selected_dimension) else {
             return Ok(());
         };
 
         let session_name = dimension.name.clone();
 
-        if Tmux::session_exists(&session_name) {
-            let windows = Tmux::list_windows(&session_name)?;
+        if self.engine.backend.session_exists(&session_name) {
+            let windows = self.engine.backend.list_windows(&session_name)?;
             let old_name = windows.iter()
                 .find(|(idx, _)| *idx == tab_index)
                 .map(|(_, name)| name.clone());
 
-            Tmux::rename_window(&session_name, tab_index, &new_name)?;
+            self.engine.backend.rename_window(&session_name, tab_index, &new_name)?;
+            self.tmux_state.invalidate_all();
 
             if let Some(old_name) = old_name {
                 if let Some(tab) = dimension.configured_tabs.iter_mut().find(|t| t.name == old_name) {
@@ -725,6 +2535,13 @@ impl App {
         Ok(())
     }
 
+    /// `--pick`: open straight into search, listing everything until something is typed
+    pub fn start_pick(&mut self) {
+        self.pick = true;
+        self.start_search();
+        self.run_search();
+    }
+
     pub fn start_search(&mut self) {
         self.input_mode = InputMode::Searching;
         self.input_buffer.clear();
@@ -732,6 +2549,9 @@ impl App {
         self.last_computed_query.clear();
         self.search_results.clear();
         self.search_selected_index = 0;
+        self.search_history_index = None;
+        // Fresh snapshot of names/tabs for this search session
+        self.search_index = None;
 
         // Save current selection
         self.pre_search_dimension = self.selected_dimension;
@@ -740,6 +2560,55 @@ impl App {
         self.clear_message();
     }
 
+    /// Whether `↑`/`↓` walk the search history rather than the results: in an empty search
+    /// with nothing listed, and while a recalled query is shown untouched
+    pub fn is_recalling_search(&self) -> bool {
+        self.search_history_index.is_some() || (self.search_query.is_empty() && self.search_results.is_empty())
+    }
+
+    /// `↑` in search: the next older query from the history
+    pub fn previous_search_query(&mut self) {
+        let index = self.search_history_index.map_or(0, |i| i + 1);
+        if let Some(query) = self.search_history.get(index).cloned() {
+            self.search_history_index = Some(index);
+            self.recall_search_query(query);
+        }
+    }
+
+    /// `↓` in search: the next newer query, then back to an empty search
+    pub fn next_search_query(&mut self) {
+        match self.search_history_index {
+            None => {}
+            Some(0) => {
+                self.search_history_index = None;
+                self.recall_search_query(String::new());
+            }
+            Some(i) => {
+                self.search_history_index = Some(i - 1);
+                self.recall_search_query(self.search_history[i - 1].clone());
+            }
+        }
+    }
+
+    fn recall_search_query(&mut self, query: String) {
+        self.input_buffer = query.clone();
+        self.search_query = query;
+        self.compute_search_results();
+    }
+
+    /// Ctrl+R while searching: switch between regex and the configured matcher
+    pub fn toggle_search_regex(&mut self) {
+        self.search_regex = !self.search_regex;
+        self.run_search();
+    }
+
+    /// `:`: open the command line
+    pub fn start_command_line(&mut self) {
+        self.input_mode = InputMode::Command;
+        self.input_buffer.clear();
+        self.clear_message();
+    }
+
     pub fn start_jump_to_tab(&mut self) {
         self.input_mode = InputMode::JumpingToTab;
         self.input_buffer.clear();
@@ -747,10 +2616,20 @@ impl App {
     }
 
     pub fn cancel_input(&mut self) {
-        let was_searching = self.input_mode == InputMode::Searching;
+        let was_searching = matches!(self.input_mode, InputMode::Searching | InputMode::SearchActions);
+        if self.input_mode == InputMode::ConfirmingSharedAttach {
+            self.shared_attach = None;
+            self.should_attach = None;
+            self.should_select_window = None;
+        }
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
         self.pending_dimension_name = None;
+        self.pending_tab = None;
+        self.delete_requires_name = false;
+        self.sync = None;
+        self.template_fill = None;
+        self.suggestions.clear();
         self.clear_completion_state();
         if was_searching {
             self.search_query.clear();
@@ -759,7 +2638,12 @@ impl App {
 
             // Restore pre-search selection
             self.selected_dimension = self.pre_search_dimension;
-            self.selected_tab = self.pre_search_tab;
+            self.select_tab(self.pre_search_tab);
+
+            // Picking is only a search; leaving it picks nothing
+            if self.pick {
+                self.should_quit = true;
+            }
         }
         self.clear_message();
     }
@@ -779,6 +2663,7 @@ impl App {
         // Live search: update search query as user types
         if self.input_mode == InputMode::Searching {
             self.search_query = self.input_buffer.clone();
+            self.search_history_index = None;
         }
     }
 
@@ -794,6 +2679,7 @@ impl App {
         // Live search: update search query as user types
         if self.input_mode == InputMode::Searching {
             self.search_query = self.input_buffer.clone();
+            self.search_history_index = None;
         }
     }
 
@@ -813,18 +2699,20 @@ impl App {
 
     fn handle_tab_completion_direction(&mut self, direction: i32) {
         use crate::path_completion::PathCompleter;
+        use crate::ssh_config::HostCompleter;
 
-        // Only complete in directory input mode
-        if self.input_mode != InputMode::CreatingDimensionDirectory {
+        // Only complete directory inputs and ssh hosts
+        let Some(completion) = self.completion() else {
             return;
-        }
+        };
 
-        // If we're cycling through existing candidates
-        if !self.completion_candidates.is_empty() && !self.completion_base.is_empty() {
-            // Move to next/previous candidate
-            let len = self.completion_candidates.len() as i32;
-            self.completion_index = ((self.completion_index as i32 + direction + len) % len) as usize;
-            self.input_buffer = self.completion_candidates[self.completion_index].clone();
+        // Popup already open: move the highlighted candidate
+        if self.is_completion_popup_open() {
+            if direction < 0 {
+                self.previous_completion();
+            } else {
+                self.next_completion();
+            }
             return;
         }
 
@@ -833,8 +2721,61 @@ impl App {
             return;
         }
 
-        let input = self.input_buffer.trim();
-        let (candidates, common_prefix) = PathCompleter::complete_directory(input);
+        if let Completion::Command = completion {
+            let names: Vec<String> = self.engine.config.dimensions.iter().map(|d| d.name.clone()).collect();
+            let (head, candidates, common_prefix) = command_line::complete(&self.input_buffer, &names);
+            let partial_len = self.input_buffer.len() - head.len();
+            match candidates.len() {
+                0 => {}
+                1 => {
+                    self.input_buffer = format!("{}{}{}", head, candidates[0], command_line::separator(&candidates[0]));
+                    self.clear_completion_state();
+                }
+                _ => {
+                    self.completion_base = self.input_buffer.clone();
+                    if common_prefix.len() > partial_len {
+                        self.input_buffer = format!("{}{}", head, common_prefix);
+                    }
+                    self.completion_candidates = candidates;
+                    self.completion_index = 0;
+                }
+            }
+            return;
+        }
+
+        if let Completion::SshHost { head, partial } = completion {
+            let (candidates, common_prefix) = HostCompleter::complete_host(&partial);
+            match candidates.len() {
+                0 => {}
+                1 => {
+                    self.input_buffer = format!("{}{}", head, candidates[0]);
+                    self.clear_completion_state();
+                }
+                _ => {
+                    if common_prefix.len() > partial.len() {
+                        self.input_buffer = format!("{}{}", head, common_prefix);
+                    }
+                    self.completion_base = format!("{}{}", head, partial);
+                    self.completion_candidates = candidates;
+                    self.completion_index = 0;
+                }
+            }
+            return;
+        }
+
+        let input = self.input_buffer.trim().to_string();
+        // zoxide's most used directories first, then what's on disk
+        let mut candidates = match self.engine.config.ui.zoxide {
+            Some(false) => vec![],
+            _ => zoxide::matching(&self.engine.backend.frecent_directories(), &input),
+        };
+        let (on_disk, _) = PathCompleter::complete_directory(&input);
+        for dir in on_disk {
+            if !candidates.contains(&dir) {
+                candidates.push(dir);
+            }
+        }
+        let common_prefix = PathCompleter::common_prefix(&candidates);
 
         match candidates.len() {
             0 => {
@@ -842,33 +2783,98 @@ impl App {
             }
             1 => {
                 // Single match - complete it fully and add trailing slash
-                let completed = format!("{}/", &candidates[0]);
-                self.input_buffer = completed;
-                // Clear completion state
-                self.completion_candidates.clear();
-                self.completion_base.clear();
-                self.completion_index = 0;
+                self.input_buffer = format!("{}/", &candidates[0]);
+                self.clear_completion_state();
             }
             _ => {
-                // Multiple matches
-                if common_prefix.len() > input.len() {
-                    // There's a common prefix we can complete to
-                    self.input_buffer = common_prefix.clone();
-                    // Save state for cycling
-                    self.completion_base = common_prefix;
-                    self.completion_candidates = candidates;
-                    self.completion_index = 0;
-                } else {
-                    // No common prefix - start cycling through candidates
-                    self.completion_base = input.to_string();
-                    self.completion_candidates = candidates.clone();
-                    self.completion_index = 0;
-                    self.input_buffer = candidates[0].clone();
+                // Multiple matches: extend to the common prefix (if any) and open the popup
+                if common_prefix.len() > input.len() && common_prefix.starts_with(&input) {
+                    self.input_buffer = common_prefix;
                 }
+                self.completion_base = input;
+                self.completion_candidates = candidates;
+                self.completion_index = 0;
+            }
+        }
+    }
+
+    /// Whether the input is a directory path (and so supports completion)
+    pub fn is_directory_input(&self) -> bool {
+        matches!(
+            self.input_mode,
+            InputMode::CreatingDimensionDirectory
+                | InputMode::RepointingDirectory
+                | InputMode::AddingTabDirectory
+        ) || (self.input_mode == InputMode::FillingTemplate
+            && self.template_fill.as_ref().and_then(|f| f.current()) == Some("path"))
+    }
+
+    /// What Tab completes in the current input, if anything
+    fn completion(&self) -> Option<Completion> {
+        if self.is_directory_input() {
+            return Some(Completion::Directory);
+        }
+        if self.input_mode == InputMode::Command {
+            return Some(Completion::Command);
+        }
+        let (head, partial) = self.ssh_host_input()?;
+        Some(Completion::SshHost { head, partial })
+    }
+
+    /// `(head, partial)` when a new tab's command ends in the host argument of `ssh`,
+    /// e.g. `prod:ssh we` -> (`prod:ssh `, `we`)
+    fn ssh_host_input(&self) -> Option<(String, String)> {
+        if self.input_mode != InputMode::AddingTab {
+            return None;
+        }
+        let (_, command) = self.input_buffer.split_once(':')?;
+        let partial_start = command.rfind(' ')? + 1;
+        if command[..partial_start].split_whitespace().last() != Some("ssh") {
+            return None;
+        }
+        let partial = &command[partial_start..];
+        let head = &self.input_buffer[..self.input_buffer.len() - partial.len()];
+        Some((head.to_string(), partial.to_string()))
+    }
+
+    /// Whether the completion popup is showing (more than one candidate to pick from)
+    pub fn is_completion_popup_open(&self) -> bool {
+        self.completion().is_some() && self.completion_candidates.len() > 1
+    }
+
+    pub fn next_completion(&mut self) {
+        if !self.completion_candidates.is_empty() {
+            self.completion_index = (self.completion_index + 1) % self.completion_candidates.len();
+        }
+    }
+
+    pub fn previous_completion(&mut self) {
+        if !self.completion_candidates.is_empty() {
+            if self.completion_index == 0 {
+                self.completion_index = self.completion_candidates.len() - 1;
+            } else {
+                self.completion_index -= 1;
             }
         }
     }
 
+    /// Accept the highlighted completion candidate into the input buffer
+    pub fn accept_completion(&mut self) {
+        if let Some(candidate) = self.completion_candidates.get(self.completion_index) {
+            self.input_buffer = match self.completion() {
+                // Hosts replace only the partial word after `ssh `
+                Some(Completion::SshHost { head, .. }) => format!("{}{}", head, candidate),
+                // Commands replace the word being typed
+                Some(Completion::Command) => {
+                    let start = self.input_buffer.rfind(' ').map_or(0, |i| i + 1);
+                    format!("{}{}{}", &self.input_buffer[..start], candidate, command_line::separator(candidate))
+                }
+                _ => format!("{}/", candidate),
+            };
+        }
+        self.clear_completion_state();
+    }
+
     pub fn submit_input(&mut self) -> Result<()> {
         match self.input_mode {
             InputMode::CreatingDimension => {
@@ -915,6 +2921,21 @@ impl App {
                 self.cancel_input();
                 return Ok(());
             }
+            InputMode::RepointingDirectory => {
+                use crate::path_completion::PathCompleter;
+
+                match PathCompleter::validate_directory(self.input_buffer.trim()) {
+                    Ok(path) => self.repoint_dimension(path)?,
+                    Err(err) => {
+                        self.set_message(err);
+                        return Ok(()); // Stay in input mode to allow correction
+                    }
+                }
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.clear_completion_state();
+                return Ok(());
+            }
             InputMode::AddingTab => {
                 let input = self.input_buffer.trim();
                 if !input.is_empty() {
@@ -922,11 +2943,46 @@ impl App {
                     let parts: Vec<&str> = input.splitn(2, ':').collect();
                     let name = parts[0].to_string();
                     let command = parts.get(1).map(|s| s.to_string());
-                    self.add_tab_to_current_dimension(name, command)?;
+                    // Then ask where the tab starts, pre-filled with the dimension's base directory
+                    self.pending_tab = Some((name, command));
+                    self.input_mode = InputMode::AddingTabDirectory;
+                    self.input_buffer = self
+                        .get_current_dimension()
+                        .and_then(|d| d.base_dir.as_ref())
+                        .and_then(|p| p.to_str())
+                        .map(|s| s.to_string())
+                        .unwrap_or_default();
+                    self.clear_completion_state();
+                    return Ok(());
+                }
+            }
+            InputMode::AddingTabDirectory => {
+                use crate::path_completion::PathCompleter;
+
+                let input = self.input_buffer.trim();
+
+                // Empty input inherits the dimension's base directory
+                let working_dir = if input.is_empty() {
+                    None
+                } else {
+                    match PathCompleter::validate_directory(input) {
+                        Ok(path) => Some(path),
+                        Err(err) => {
+                            self.set_message(err);
+                            return Ok(()); // Stay in input mode to allow correction
+                        }
+                    }
+                };
+                self.clear_completion_state();
+                if let Some((name, command)) = self.pending_tab.take() {
+                    self.add_tab_to_current_dimension(name, command, working_dir)?;
                 }
             }
             InputMode::DeletingDimension => {
-                if let Some(dimension) = self.config.dimensions.get(self.selected_dimension) {
+                if self.delete_requires_name && !self.delete_name_matches() {
+                    return Ok(());
+                }
+                if let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) {
                     self.delete_dimension(&dimension.name.clone())?;
                 }
             }
@@ -935,12 +2991,12 @@ impl App {
             }
             InputMode::RenamingDimension => {
                 let name = self.input_buffer.trim().to_string();
-                let current_name = self.config.dimensions
+                let current_name = self.engine.config.dimensions
                     .get(self.selected_dimension)
                     .map(|d| d.name.clone())
                     .unwrap_or_default();
                 if !name.is_empty() && name != current_name
-                    && self.config.dimensions.iter().any(|d| d.name == name)
+                    && self.engine.config.dimensions.iter().any(|d| d.name == name)
                 {
                     self.set_message(format!("'{}' already exists", name));
                     return Ok(()); // Stay in rename mode so the user can correct it
@@ -951,6 +3007,46 @@ impl App {
                 let name = self.input_buffer.trim().to_string();
                 self.rename_tab(name)?;
             }
+            InputMode::RenamingCurrentWindow => {
+                let name = self.input_buffer.trim().to_string();
+                self.rename_current_window(name.clone())?;
+                self.cancel_input();
+                if !name.is_empty() {
+                    self.set_message(format!("Renamed current window to '{}'", name));
+                }
+                return Ok(());
+            }
+            InputMode::CurrentWindowMenu => {
+                // Menu choices are single keys (see handle_current_window_menu)
+                return Ok(());
+            }
+            InputMode::QuickActions => {
+                return self.run_quick_action();
+            }
+            InputMode::PickingTask => {
+                return self.run_selected_task();
+            }
+            InputMode::PickingLink => {
+                return self.open_selected_link();
+            }
+            InputMode::PickingSuggestion => {
+                return self.create_suggested_dimension();
+            }
+            InputMode::PickingTemplate => {
+                return self.start_template_fill();
+            }
+            InputMode::SearchActions => {
+                return self.run_search_action();
+            }
+            InputMode::FillingTemplate => {
+                return self.submit_template_variable();
+            }
+            InputMode::Syncing => {
+                return self.apply_sync();
+            }
+            InputMode::Command => {
+                return self.run_command_line();
+            }
             InputMode::Searching => {
                 // Live search updates query as user types, so nothing to do here
                 // Enter with results is handled in handle_input_mode -> select_search_result
@@ -963,6 +3059,9 @@ impl App {
                 }
                 return Ok(());
             }
+            InputMode::ConfirmingSharedAttach => {
+                self.confirm_shared_attach(false);
+            }
             InputMode::Normal => {}
         }
 
@@ -970,8 +3069,94 @@ impl App {
         Ok(())
     }
 
+    /// Run what was typed on the `:` command line
+    fn run_command_line(&mut self) -> Result<()> {
+        let parsed = command_line::parse(&self.input_buffer);
+        self.cancel_input();
+        let command = match parsed {
+            Ok(command) => command,
+            Err(e) => {
+                self.set_message(e);
+                return Ok(());
+            }
+        };
+
+        match command {
+            CommandLine::Switch { dimension, tab } => {
+                self.select_dimension_named(&dimension)?;
+                if let Some(tab) = tab {
+                    // The name typed may have been an alias
+                    let dimension = self.engine.config.dimensions[self.selected_dimension].name.clone();
+                    let windows = self.engine.backend.list_windows(&dimension).ok();
+                    let index = match windows {
+                        Some(windows) if self.engine.backend.session_exists(&dimension) => {
+                            windows.into_iter().find(|(_, name)| *name == tab).map(|(idx, _)| idx)
+                        }
+                        _ => self.get_current_dimension().and_then(|d| d.configured_tabs.iter().position(|t| t.name == tab)),
+                    };
+                    let Some(index) = index else {
+                        anyhow::bail!("Dimension '{}' has no tab '{}'", dimension, tab);
+                    };
+                    self.select_tab(Some(index));
+                }
+                self.switch_or_pick_task()
+            }
+            CommandLine::New { name, dir } => {
+                use crate::path_completion::PathCompleter;
+                let base_dir = dir
+                    .map(|dir| PathCompleter::validate_directory(&dir))
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                self.create_dimension(name, base_dir)
+            }
+            CommandLine::Rename { from, to } => {
+                if let Some(from) = from {
+                    self.select_dimension_named(&from)?;
+                }
+                if self.engine.config.name_in_use(&to) {
+                    anyhow::bail!("'{}' already exists", to);
+                }
+                self.rename_dimension(to)
+            }
+            CommandLine::Delete { dimension } => {
+                if let Some(dimension) = dimension {
+                    self.select_dimension_named(&dimension)?;
+                }
+                if self.get_current_dimension().is_some() {
+                    self.start_delete_dimension();
+                }
+                Ok(())
+            }
+            CommandLine::Tab(n) => {
+                self.focus_tabs();
+                self.jump_to_number(n);
+                if self.selected_tab != Some(n) {
+                    anyhow::bail!("No tab {}", n);
+                }
+                self.switch_or_pick_task()
+            }
+            CommandLine::Quit => {
+                self.quit();
+                Ok(())
+            }
+        }
+    }
+
+    fn select_dimension_named(&mut self, name: &str) -> Result<()> {
+        let Some(index) = self.engine.config.find_index(name) else {
+            anyhow::bail!("Dimension '{}' not found", name);
+        };
+        self.selected_orphan = None;
+        self.selected_dimension = index;
+        self.select_tab(None);
+        Ok(())
+    }
+
     pub fn get_current_dimension(&self) -> Option<&Dimension> {
-        self.config.dimensions.get(self.selected_dimension)
+        if self.selected_orphan.is_some() {
+            return None;
+        }
+        self.engine.config.dimensions.get(self.selected_dimension)
     }
 
     pub fn compute_search_results(&mut self) {
@@ -979,36 +3164,43 @@ impl App {
         if self.search_query == self.last_computed_query {
             return;
         }
+        self.run_search();
+    }
 
+    /// Search for `search_query`; an empty query finds nothing, except when picking
+    fn run_search(&mut self) {
         self.last_computed_query = self.search_query.clone();
         self.search_results.clear();
         self.search_selected_index = 0;
+        self.search_error = None;
 
-        if self.search_query.is_empty() {
+        if self.search_query.is_empty() && !self.pick {
             return;
         }
 
-        let matcher = SkimMatcherV2::default();
-
-        for (dim_idx, dimension) in self.config.dimensions.iter().enumerate() {
-            let dim_score = matcher.fuzzy_match(&dimension.name, &self.search_query);
-
-            // Get tabs from tmux if session exists, otherwise from config
-            let tabs: Vec<(usize, String)> = if Tmux::session_exists(&dimension.name) {
-                Tmux::list_windows(&dimension.name).unwrap_or_default()
-            } else {
-                dimension
-                    .configured_tabs
-                    .iter()
-                    .enumerate()
-                    .map(|(i, t)| (i, t.name.clone()))
-                    .collect()
-            };
-
-            if tabs.is_empty() && dim_score.is_some() {
+        let algorithm = if self.search_regex { MatchAlgorithm::Regex } else { self.engine.config.ui.matcher };
+        self.search_error = (algorithm == MatchAlgorithm::Regex)
+            .then(|| matcher::regex_error(&self.search_query))
+            .flatten();
+        let matcher = matcher::matcher(algorithm, &self.search_query);
+        // Nothing typed yet (when picking) lists everything
+        let empty = self.search_query.is_empty();
+        let score = |text: &str| if empty { Some(0) } else { matcher.score(text) };
+        let index = self
+            .search_index
+            .get_or_insert_with(|| SearchIndex::build(&self.engine.config.dimensions, &self.tmux_state));
+
+        for dimension in &index.dimensions {
+            // Dimension name or any of its tags/group
+            let dim_score = std::iter::once(&dimension.name_lower)
+                .chain(dimension.tags_lower.iter())
+                .filter_map(|text| score(text))
+                .max();
+
+            if dimension.tabs.is_empty() && dim_score.is_some() {
                 // Dimension matches but has no tabs - add dimension-only result
                 self.search_results.push(SearchResult {
-                    dimension_index: dim_idx,
+                    dimension_index: dimension.index,
                     dimension_name: dimension.name.clone(),
                     tab_index: 0,
                     tmux_window_index: 0,
@@ -1017,9 +3209,12 @@ impl App {
                     match_type: MatchType::DimensionOnly,
                 });
             } else {
-                // Check each tab
-                for (list_idx, (window_idx, tab_name)) in tabs.iter().enumerate() {
-                    let tab_score = matcher.fuzzy_match(tab_name, &self.search_query);
+                // Check each tab (name or command)
+                for tab in &dimension.tabs {
+                    let tab_score = std::iter::once(&tab.name_lower)
+                        .chain(tab.command_lower.iter())
+                        .filter_map(|text| score(text))
+                        .max();
 
                     // Include if dimension OR tab matches
                     let (final_score, match_type) = match (dim_score, tab_score) {
@@ -1039,11 +3234,11 @@ impl App {
                     };
 
                     self.search_results.push(SearchResult {
-                        dimension_index: dim_idx,
+                        dimension_index: dimension.index,
                         dimension_name: dimension.name.clone(),
-                        tab_index: list_idx,
-                        tmux_window_index: *window_idx,
-                        tab_name: tab_name.clone(),
+                        tab_index: tab.list_index,
+                        tmux_window_index: tab.window_index,
+                        tab_name: tab.name.clone(),
                         score: final_score,
                         match_type,
                     });
@@ -1061,16 +3256,16 @@ impl App {
             return;
         }
 
-        let Some(dimension) = self.config.dimensions.get(self.selected_dimension) else {
+        let Some(dimension) = self.engine.config.dimensions.get(self.selected_dimension) else {
             return;
         };
 
         // Only works if session exists
-        if !Tmux::session_exists(&dimension.name) {
+        if !self.engine.backend.session_exists(&dimension.name) {
             return;
         }
 
-        let Ok(windows) = Tmux::list_windows(&dimension.name) else {
+        let Ok(windows) = self.engine.backend.list_windows(&dimension.name) else {
             return;
         };
 
@@ -1102,7 +3297,7 @@ impl App {
         }
 
         if let Some(match_idx) = best_match {
-            self.selected_tab = Some(match_idx);
+            self.select_tab(Some(match_idx));
         }
     }
 
@@ -1123,27 +3318,105 @@ impl App {
     }
 
     pub fn select_search_result(&mut self) -> Result<()> {
-        if let Some(result) = self.search_results.get(self.search_selected_index) {
-            // Update selection based on search result
-            self.selected_dimension = result.dimension_index;
-            self.selected_tab = if Tmux::session_exists(&result.dimension_name) {
-                Some(result.tmux_window_index)
-            } else {
-                Some(result.tab_index)
-            };
-
-            // Clear search and return to normal mode
-            self.input_mode = InputMode::Normal;
-            self.search_query.clear();
-            self.search_results.clear();
-            self.last_computed_query.clear();
-
+        if self.focus_search_result() {
             // Immediately switch to the dimension
             self.switch_to_dimension()?;
         }
         Ok(())
     }
 
+    /// Select the highlighted search result in the lists and leave search. False without results.
+    fn focus_search_result(&mut self) -> bool {
+        let Some(result) = self.search_results.get(self.search_selected_index) else {
+            return false;
+        };
+        search_history::record(&mut self.search_history, &self.search_query);
+        if self.engine.persist {
+            search_history::save(&self.search_history);
+        }
+        // Update selection based on search result
+        self.selected_orphan = None;
+        self.selected_dimension = result.dimension_index;
+        let tab = if self.engine.backend.session_exists(&result.dimension_name) {
+            Some(result.tmux_window_index)
+        } else {
+            Some(result.tab_index)
+        };
+        self.select_tab(tab);
+
+        // Clear search and return to normal mode
+        self.input_mode = InputMode::Normal;
+        self.search_query.clear();
+        self.search_results.clear();
+        self.last_computed_query.clear();
+        true
+    }
+
+    /// `Tab` on a search result: choose what to do with it instead of switching
+    pub fn start_search_actions(&mut self) {
+        if self.search_results.is_empty() {
+            return;
+        }
+        self.search_action = 0;
+        self.input_mode = InputMode::SearchActions;
+    }
+
+    pub fn next_search_action(&mut self) {
+        self.search_action = (self.search_action + 1) % SearchAction::ALL.len();
+    }
+
+    pub fn previous_search_action(&mut self) {
+        let count = SearchAction::ALL.len();
+        self.search_action = (self.search_action + count - 1) % count;
+    }
+
+    /// Esc in the action menu: back to the results, query intact
+    pub fn close_search_actions(&mut self) {
+        self.input_mode = InputMode::Searching;
+    }
+
+    pub fn run_search_action(&mut self) -> Result<()> {
+        let Some(result) = self.search_results.get(self.search_selected_index).cloned() else {
+            self.close_search_actions();
+            return Ok(());
+        };
+        match SearchAction::ALL[self.search_action] {
+            SearchAction::Switch => self.select_search_result(),
+            SearchAction::SwitchReadOnly => {
+                self.should_attach_read_only = true;
+                let switched = self.select_search_result();
+                if switched.is_err() {
+                    self.should_attach_read_only = false;
+                }
+                switched
+            }
+            SearchAction::CopyTarget => {
+                // Running sessions have real window indexes; otherwise just the session name
+                let target = if result.is_tab() && self.engine.backend.session_exists(&result.dimension_name) {
+                    format!("{}:{}", result.dimension_name, result.tmux_window_index)
+                } else {
+                    result.dimension_name.clone()
+                };
+                self.engine.backend.copy_to_clipboard(&target)?;
+                self.close_search_actions();
+                self.set_message(format!("Copied {}", target));
+                Ok(())
+            }
+            SearchAction::Delete => {
+                // Through the usual confirmation: the tab when that's what matched,
+                // the whole dimension otherwise
+                self.focus_search_result();
+                if result.is_tab() {
+                    self.start_delete_tab();
+                } else {
+                    self.select_tab(None);
+                    self.start_delete_dimension();
+                }
+                Ok(())
+            }
+        }
+    }
+
     pub fn should_refresh_preview(&self) -> bool {
         let current_session = self.get_current_dimension().map(|d| d.name.as_str());
         let preview_session = self.preview_session.as_ref().map(|s| s.as_str());
@@ -1153,8 +3426,8 @@ impl App {
     }
 
     pub fn update_preview(&mut self) {
-        // Only proceed if we have a selected tab
-        let Some(tab_index) = self.selected_tab else {
+        // Only proceed if we have a selected tab, and never capture while presenting
+        let Some(tab_index) = self.selected_tab.filter(|_| !self.presentation) else {
             self.clear_preview();
             return;
         };
@@ -1169,13 +3442,13 @@ impl App {
         };
 
         // Only capture if session is running
-        if !Tmux::session_exists(&dimension_name) {
+        if !self.tmux_state.session_exists(&dimension_name) {
             self.clear_preview();
             return;
         }
 
         // Capture pane contents
-        match Tmux::capture_pane(&dimension_name, tab_index) {
+        match self.engine.backend.capture_pane(&dimension_name, tab_index) {
             Ok(content) => {
                 self.preview_content = Some(content);
                 self.preview_session = Some(dimension_name);
@@ -1197,19 +3470,35 @@ impl App {
     /// Find the first active dimension session other than `excluded`, or create a
     /// plain "scratch" session as a last resort. Returns (session_name, window_index).
     fn find_or_create_fallback_session(&self, excluded_session: &str) -> Result<(String, usize)> {
-        for dimension in &self.config.dimensions {
-            if dimension.name != excluded_session && Tmux::session_exists(&dimension.name) {
-                let window = Tmux::get_first_window_index(&dimension.name).unwrap_or(0);
+        for dimension in &self.engine.config.dimensions {
+            if dimension.name != excluded_session && self.engine.backend.session_exists(&dimension.name) {
+                let window = self.engine.backend.get_first_window_index(&dimension.name).unwrap_or(0);
                 return Ok((dimension.name.clone(), window));
             }
         }
 
         // No other dimension sessions — use a plain scratch session
         let name = "scratch";
-        if !Tmux::session_exists(name) {
-            Tmux::create_session(name, true)?;
+        if !self.engine.backend.session_exists(name) {
+            self.engine.backend.create_session(name, true)?;
+            self.tmux_state.invalidate_all();
         }
-        let window = Tmux::get_first_window_index(name).unwrap_or(0);
+        let window = self.engine.backend.get_first_window_index(name).unwrap_or(0);
         Ok((name.to_string(), window))
     }
 }
+
+/// The command handing a path or URL to the desktop: `open` on macOS, `xdg-open` elsewhere
+fn system_opener(target: &std::ffi::OsStr) -> std::process::Command {
+    let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    let mut command = std::process::Command::new(opener);
+    command
+        .arg(target)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    command
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,832 @@
+use super::*;
+use crate::backend::mock::MockBackend;
+use crate::dimension::{NamedLink, Task, LAST_ACTIVE_TAB};
+use crate::paths;
+use crate::remote;
+use crate::ssh_config;
+
+fn dimension(name: &str, tabs: &[(&str, Option<&str>)]) -> Dimension {
+    let mut dimension = Dimension::new_with_base_dir(name.to_string(), None);
+    for (tab, command) in tabs {
+        dimension.add_tab(Tab::new(tab.to_string(), command.map(str::to_string), None));
+    }
+    dimension
+}
+
+fn app(dimensions: Vec<Dimension>, backend: &MockBackend) -> App {
+    let mut config = DimensionConfig::default();
+    config.dimensions = dimensions;
+    app_with_config(config, backend)
+}
+
+fn app_with_config(config: DimensionConfig, backend: &MockBackend) -> App {
+    paths::use_temp_dir();
+    let mut app = App::with_backend(config, Rc::new(backend.clone())).unwrap();
+    app.engine.persist = false;
+    app
+}
+
+fn tab_names(app: &App, dimension: &str) -> Vec<String> {
+    let dimension = app.engine.config.get_dimension(dimension).unwrap();
+    dimension.configured_tabs.iter().map(|t| t.name.clone()).collect()
+}
+
+#[test]
+fn switching_starts_a_session_with_the_configured_tabs() {
+    let backend = MockBackend::new();
+    let mut app = app(
+        vec![dimension("api", &[("editor", Some("nvim")), ("server", Some("cargo run")), ("shell", None)])],
+        &backend,
+    );
+
+    app.switch_to_dimension().unwrap();
+    while app.startup.is_some() {
+        app.advance_startup().unwrap();
+    }
+
+    assert_eq!(backend.window_names("api").unwrap(), ["editor", "server", "shell"]);
+    let state = backend.state.borrow();
+    let (session, window, keys) = &state.sent_keys[0];
+    assert_eq!((session.as_str(), *window), ("api", 0));
+    assert!(keys.starts_with("nvim"));
+    let server = &state.session("api").unwrap().windows[1];
+    assert!(server.command.as_deref().unwrap().starts_with("cargo run"));
+    drop(state);
+
+    assert_eq!(app.should_attach.as_deref(), Some("api"));
+    assert!(app.should_quit);
+}
+
+#[test]
+fn switching_to_a_running_session_does_not_recreate_it() {
+    let backend = MockBackend::new().with_session("api", &["editor", "logs"]);
+    let mut app = app(vec![dimension("api", &[("editor", None)])], &backend);
+
+    app.switch_to_dimension().unwrap();
+
+    assert!(app.startup.is_none());
+    assert_eq!(backend.window_names("api").unwrap(), ["editor", "logs"]);
+    assert_eq!(app.should_attach.as_deref(), Some("api"));
+}
+
+#[test]
+fn switching_opens_the_default_tab() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server", "logs"]);
+    let mut web = dimension("web", &[("editor", None), ("server", None), ("logs", None)]);
+    web.default_tab = Some("server".to_string());
+    let mut api = dimension("api", &[("editor", None), ("server", None)]);
+    api.default_tab = Some("server".to_string());
+    let mut app = app(vec![web, api], &backend);
+
+    app.switch_to_dimension().unwrap();
+    assert_eq!(app.should_select_window, Some(1));
+
+    app.engine.config.dimensions[0].default_tab = Some(LAST_ACTIVE_TAB.to_string());
+    backend.state.borrow_mut().active_windows.insert("web".to_string(), 2);
+    app.switch_to_dimension().unwrap();
+    assert_eq!(app.should_select_window, Some(2));
+
+    // A session started by the switch opens on the named tab too
+    app.selected_dimension = 1;
+    app.switch_to_dimension().unwrap();
+    while app.startup.is_some() {
+        app.advance_startup().unwrap();
+    }
+    assert_eq!(app.should_select_window, Some(1));
+}
+
+#[test]
+fn deleting_a_dimension_kills_its_session() {
+    let backend = MockBackend::new()
+        .with_session("api", &["editor"])
+        .with_session("web", &["editor"]);
+    let mut app = app(vec![dimension("api", &[("editor", None)]), dimension("web", &[])], &backend);
+
+    app.delete_dimension("api").unwrap();
+
+    assert!(app.engine.config.get_dimension("api").is_none());
+    assert!(backend.window_names("api").is_none());
+    assert!(backend.window_names("web").is_some());
+}
+
+#[test]
+fn u_undoes_dimension_and_tab_deletions() {
+    let backend = MockBackend::new().with_session("api", &["editor", "server"]);
+    let mut app = app(
+        vec![
+            dimension("api", &[("editor", None), ("server", Some("cargo run"))]),
+            dimension("web", &[]),
+            dimension("db", &[]),
+        ],
+        &backend,
+    );
+
+    app.delete_dimension("web").unwrap();
+    assert_eq!(app.engine.config.trash.len(), 1);
+    app.selected_dimension = 0;
+    app.select_tab(Some(0));
+    app.remove_tab_from_current_dimension().unwrap();
+    assert_eq!(tab_names(&app, "api"), ["server"]);
+
+    // Back in its old place, window included
+    app.undo().unwrap();
+    assert_eq!(tab_names(&app, "api"), ["editor", "server"]);
+    assert_eq!(backend.window_names("api").unwrap(), ["editor", "server"]);
+    assert_eq!(app.selected_tab, Some(1));
+    assert_eq!(app.message.as_deref(), Some("Restored tab: editor"));
+
+    app.undo().unwrap();
+    let names: Vec<&str> = app.engine.config.dimensions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["api", "web", "db"]);
+    assert_eq!(app.selected_dimension, 1);
+    assert!(app.engine.config.trash.is_empty());
+
+    app.undo().unwrap();
+    assert_eq!(app.message.as_deref(), Some("Nothing to undo"));
+}
+
+#[test]
+fn delete_confirmation_names_programs_still_running() {
+    let backend = MockBackend::new()
+        .with_session("api", &["editor", "server", "shell"])
+        .with_session("web", &["shell"]);
+    backend.state.borrow_mut().pane_commands.insert(
+        "api".to_string(),
+        vec![
+            ("editor".to_string(), "nvim".to_string()),
+            ("server".to_string(), "cargo".to_string()),
+            ("shell".to_string(), "-zsh".to_string()),
+        ],
+    );
+    let mut app = app(vec![dimension("api", &[]), dimension("web", &[])], &backend);
+
+    app.start_delete_dimension();
+    assert_eq!(app.delete_running, ["nvim (editor)", "cargo (server)"]);
+
+    app.cancel_input();
+    app.selected_dimension = 1;
+    app.start_delete_dimension();
+    assert!(app.delete_running.is_empty());
+}
+
+#[test]
+fn killing_the_current_session_switches_away_first() {
+    let backend = MockBackend::new()
+        .with_session("api", &["editor"])
+        .with_session("web", &["editor"]);
+    backend.state.borrow_mut().current = Some(("api".to_string(), 0));
+    let mut app = app(vec![dimension("api", &[("editor", None)]), dimension("web", &[])], &backend);
+    assert_eq!(app.engine.config.dimensions[app.selected_dimension].name, "api");
+
+    app.kill_dimension_session().unwrap();
+
+    let state = backend.state.borrow();
+    assert!(state.session("api").is_none());
+    assert_eq!(state.current.as_ref().map(|(s, _)| s.as_str()), Some("web"));
+    drop(state);
+    assert!(app.should_quit);
+    assert!(app.engine.config.get_dimension("api").is_some());
+}
+
+#[test]
+fn hibernating_keeps_a_snapshot_only_of_killed_sessions() {
+    let backend = MockBackend::new().with_session("hibernate-app", &["editor", "logs"]);
+    let mut app = app(vec![dimension("hibernate-app", &[]), dimension("hibernate-idle", &[])], &backend);
+
+    app.hibernate_dimension_session().unwrap();
+    assert!(backend.window_names("hibernate-app").is_none());
+    assert_eq!(
+        app.message.as_deref(),
+        Some("Hibernated 'hibernate-app' (2 windows, 2 panes); switch to it to thaw")
+    );
+    assert!(snapshot::hibernated("hibernate-app").is_some());
+
+    app.selected_dimension = 1;
+    assert!(app.hibernate_dimension_session().is_err());
+    assert!(snapshot::hibernated("hibernate-idle").is_none());
+}
+
+#[test]
+fn sync_adopts_windows_created_by_hand() {
+    let backend = MockBackend::new().with_session("api", &["editor", "logs", "server"]);
+    let mut app = app(vec![dimension("api", &[("editor", None), ("server", None)])], &backend);
+
+    app.start_sync().unwrap();
+    assert_eq!(app.input_mode, InputMode::Syncing);
+    app.apply_sync().unwrap();
+
+    assert_eq!(tab_names(&app, "api"), ["editor", "logs", "server"]);
+}
+
+#[test]
+fn warns_about_conflicting_tmux_options_at_startup() {
+    let backend = MockBackend::new();
+    backend
+        .state
+        .borrow_mut()
+        .global_options
+        .insert("destroy-unattached".to_string(), "on".to_string());
+    let app = app(vec![dimension("api", &[])], &backend);
+
+    let message = app.message.unwrap();
+    assert!(message.contains("destroy-unattached"));
+    assert!(message.contains("set -g destroy-unattached off"));
+}
+
+#[test]
+fn escape_clears_transient_state_before_closing() {
+    let backend = MockBackend::new();
+    let mut app = app(vec![dimension("api", &[("editor", None)])], &backend);
+    app.select_tab(Some(0));
+    app.set_message("Saved".to_string());
+
+    app.escape();
+    assert!(app.message.is_none());
+    assert!(!app.should_quit);
+    app.escape();
+    assert_eq!(app.selected_tab, None);
+    assert!(!app.should_quit);
+    app.escape();
+    assert!(app.should_quit);
+    assert!(!app.should_detach);
+}
+
+#[test]
+fn escape_can_close_right_away() {
+    let backend = MockBackend::new();
+    let mut app = app(vec![dimension("api", &[("editor", None)])], &backend);
+    app.engine.config.ui.escape = EscapeBehavior::Close;
+    app.select_tab(Some(0));
+
+    app.escape();
+    assert!(app.should_quit);
+}
+
+#[test]
+fn typed_confirmation_guards_running_dimensions() {
+    let backend = MockBackend::new().with_session("api", &["editor"]);
+    let mut app = app(vec![dimension("api", &[("editor", None)])], &backend);
+    app.engine.config.ui.confirm_delete = ConfirmStyle::TypeName;
+
+    app.start_delete_dimension();
+    assert!(app.delete_requires_name);
+    app.input_buffer = "ap".to_string();
+    app.submit_input().unwrap();
+    assert!(app.engine.config.get_dimension("api").is_some());
+
+    app.input_buffer = "api".to_string();
+    app.submit_input().unwrap();
+    assert!(app.engine.config.get_dimension("api").is_none());
+    assert!(backend.window_names("api").is_none());
+}
+
+#[test]
+fn clicking_selects_then_switches() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server"]);
+    let mut app = app(vec![dimension("api", &[]), dimension("web", &[("editor", None)])], &backend);
+    app.mouse_targets = MouseTargets {
+        dimensions: Some(ListArea { area: Rect::new(1, 1, 20, 10), offset: 0 }),
+        dimension_items: vec![DimensionItem::Dimension(0), DimensionItem::Dimension(1)],
+        tabs: Some(ListArea { area: Rect::new(30, 1, 20, 10), offset: 0 }),
+        tab_indexes: vec![0, 1],
+    };
+
+    app.click(5, 2).unwrap();
+    assert_eq!(app.selected_dimension, 1);
+    assert!(!app.should_quit);
+
+    app.click(35, 2).unwrap();
+    assert_eq!(app.selected_tab, Some(1));
+    app.click(35, 2).unwrap();
+    assert_eq!(app.should_attach.as_deref(), Some("web"));
+    assert_eq!(app.should_select_window, Some(1));
+}
+
+#[test]
+fn expanded_dimensions_list_their_tabs() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server"]);
+    let mut app = app(vec![dimension("api", &[("shell", None)]), dimension("web", &[("editor", None)])], &backend);
+    app.selected_dimension = 1;
+    app.toggle_collapse_dimension();
+    assert!(app.expanded.contains("web"));
+
+    let view = crate::ui::view_model::dimension_list(&app);
+    assert_eq!(view.rows[0].nested_tabs, None);
+    assert_eq!(
+        view.items(),
+        [DimensionItem::Dimension(0), DimensionItem::Dimension(1), DimensionItem::Tab(1, 0), DimensionItem::Tab(1, 1)]
+    );
+
+    // Clicking a nested tab selects it; clicking it again switches to it
+    app.selected_dimension = 0;
+    app.mouse_targets = MouseTargets {
+        dimensions: Some(ListArea { area: Rect::new(1, 1, 20, 10), offset: 0 }),
+        dimension_items: view.items(),
+        ..Default::default()
+    };
+    app.click(5, 4).unwrap();
+    assert_eq!((app.selected_dimension, app.selected_tab), (1, Some(1)));
+    app.click(5, 4).unwrap();
+    assert_eq!(app.should_attach.as_deref(), Some("web"));
+    assert_eq!(app.should_select_window, Some(1));
+
+    app.toggle_collapse_dimension();
+    assert_eq!(crate::ui::view_model::dimension_list(&app).items().len(), 2);
+}
+
+#[test]
+fn number_keys_jump_to_dimensions_and_tabs() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server", "logs"]);
+    let mut app = app(vec![dimension("api", &[("shell", None)]), dimension("web", &[])], &backend);
+
+    app.jump_to_number(2);
+    assert_eq!((app.selected_dimension, app.selected_tab), (1, None));
+    app.jump_to_number(9);
+    assert_eq!(app.selected_dimension, 1);
+
+    // Tabs go by the number shown next to them: the window index of a running session
+    app.focus_tabs();
+    app.jump_to_number(2);
+    assert_eq!(app.selected_tab, Some(2));
+    app.jump_to_number(5);
+    assert_eq!(app.selected_tab, Some(2));
+
+    app.select_tab(None);
+    app.jump_to_number(1);
+    app.focus_tabs();
+    app.jump_to_number(0);
+    assert_eq!((app.selected_dimension, app.selected_tab), (0, Some(0)));
+
+    // Dimensions go by the row they are listed on
+    let mut config = DimensionConfig::default();
+    config.dimensions = vec![dimension("web", &[]), dimension("api", &[]), dimension("db", &[])];
+    config.startup.sort = DimensionSort::Name;
+    let mut app = app_with_config(config, &backend);
+    app.jump_to_number(2);
+    assert_eq!(app.get_current_dimension().unwrap().name, "db");
+    app.jump_to_number(3);
+    assert_eq!(app.get_current_dimension().unwrap().name, "web");
+
+    let mut config = DimensionConfig::default();
+    config.dimensions = vec![dimension("api", &[]), dimension("db", &[]), dimension("web", &[])];
+    config.startup.sort = DimensionSort::Active;
+    let mut app = app_with_config(config, &backend);
+    app.jump_to_number(1);
+    assert_eq!(app.get_current_dimension().unwrap().name, "web");
+    app.jump_to_number(2);
+    assert_eq!(app.get_current_dimension().unwrap().name, "api");
+}
+
+#[test]
+fn command_line_runs_ex_style_commands() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server"]);
+    let mut app = app(vec![dimension("api", &[]), dimension("web", &[])], &backend);
+    let run = |app: &mut App, line: &str| {
+        app.start_command_line();
+        line.chars().for_each(|c| app.handle_input_char(c));
+        app.submit_input()
+    };
+
+    run(&mut app, "new docs").unwrap();
+    assert!(app.engine.config.get_dimension("docs").is_some());
+    run(&mut app, "rename docs notes").unwrap();
+    assert!(app.engine.config.get_dimension("notes").is_some());
+    assert!(run(&mut app, "switch nope").is_err());
+
+    // Tab completes the command, then the dimension
+    app.start_command_line();
+    "sw w".chars().for_each(|c| app.handle_input_char(c));
+    app.handle_tab_completion();
+    assert_eq!(app.input_buffer, "sw web ");
+    "server".chars().for_each(|c| app.handle_input_char(c));
+    app.submit_input().unwrap();
+    assert_eq!((app.selected_dimension, app.selected_tab), (1, Some(1)));
+    assert_eq!(app.should_attach.as_deref(), Some("web"));
+}
+
+#[test]
+fn directory_completion_offers_zoxide_first() {
+    let root = std::env::temp_dir().join(format!("dimensions-zoxide-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("alpha")).unwrap();
+    std::fs::create_dir_all(root.join("beta")).unwrap();
+    let beta = root.join("beta").display().to_string();
+    let backend = MockBackend::new();
+    backend.state.borrow_mut().frecent = vec![beta.clone(), "/elsewhere/gamma".to_string()];
+    let mut app = app(vec![dimension("api", &[])], &backend);
+    let alpha = root.join("alpha").display().to_string();
+
+    app.start_repoint_directory();
+    app.input_buffer = format!("{}/", root.display());
+    app.handle_tab_completion();
+    assert_eq!(app.completion_candidates, [beta.clone(), alpha.clone()]);
+
+    app.engine.config.ui.zoxide = Some(false);
+    app.clear_completion_state();
+    app.input_buffer = format!("{}/", root.display());
+    app.handle_tab_completion();
+    assert_eq!(app.completion_candidates, [alpha, beta]);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn recent_messages_outlive_the_popup() {
+    let backend = MockBackend::new();
+    let mut app = app(vec![], &backend);
+    assert_eq!(app.exit_notice(), None);
+
+    app.create_dimension("api".to_string(), None).unwrap();
+    assert_eq!(app.exit_notice().as_deref(), Some("Created dimension: api"));
+    app.clear_message();
+    assert_eq!(app.exit_notice(), None);
+}
+
+#[test]
+fn picking_lists_everything_and_cancelling_quits() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server"]);
+    let mut app = app(vec![dimension("api", &[]), dimension("web", &[])], &backend);
+
+    app.start_pick();
+    assert_eq!(app.input_mode, InputMode::Searching);
+    assert_eq!(app.search_results.len(), 3);
+
+    app.cancel_input();
+    assert!(app.should_quit);
+    assert_eq!(app.should_attach, None);
+}
+
+#[test]
+fn here_renames_the_current_window_and_its_tab() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server"]);
+    let mut app = app(vec![dimension("api", &[]), dimension("web", &[("editor", None)])], &backend);
+    app.current_session = Some("web".to_string());
+    app.current_window = Some(0);
+
+    app.start_here();
+    assert_eq!((app.input_mode, app.input_buffer.as_str()), (InputMode::RenamingCurrentWindow, "editor"));
+    app.input_buffer = "nvim".to_string();
+    app.submit_input().unwrap();
+
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(backend.window_names("web").unwrap(), ["nvim", "server"]);
+    assert_eq!(tab_names(&app, "web"), ["nvim"]);
+}
+
+#[test]
+fn private_dimensions_stay_concealed_until_revealed() {
+    let backend = MockBackend::new();
+    let mut secret = dimension("secret", &[("editor", None)]);
+    secret.private = true;
+    let mut app = app(vec![secret, dimension("api", &[])], &backend);
+
+    assert!(app.is_concealed("secret"));
+    assert!(!app.is_concealed("api"));
+    app.toggle_reveal();
+    assert!(!app.is_concealed("secret"));
+    app.toggle_reveal();
+    assert!(app.is_concealed("secret"));
+}
+
+#[test]
+fn presentation_mode_shows_names_only() {
+    let backend = MockBackend::new();
+    let mut api = dimension("api", &[("server", Some("TOKEN=abc cargo run"))]);
+    api.base_dir = Some("/srv/api".into());
+    let mut app = app(vec![api], &backend);
+    app.select_tab(Some(0));
+
+    let tabs = crate::ui::view_model::tab_list(&app).unwrap();
+    assert!(tabs.rows[0].command.is_some());
+
+    app.toggle_presentation();
+    let tabs = crate::ui::view_model::tab_list(&app).unwrap();
+    assert_eq!(tabs.rows[0].name, "server");
+    assert_eq!(tabs.rows[0].command, None);
+    assert_eq!(tabs.title, "Tabs");
+    assert_eq!(crate::ui::view_model::dimension_list(&app).rows[0].path, None);
+    assert!(crate::ui::view_model::preview(&app).is_none());
+}
+
+#[test]
+fn templates_prompt_for_variables_then_create_the_dimension() {
+    let backend = MockBackend::new();
+    let mut app = app(vec![dimension("api", &[])], &backend);
+    let mut web = dimension("web", &[("editor", Some("nvim")), ("server", Some("PORT={{port}} npm run dev"))]);
+    web.base_dir = Some("{{path}}".into());
+    app.engine.config.templates.push(web);
+
+    app.start_template_picker();
+    app.submit_input().unwrap();
+    for value in ["api", "shop", "", "3000"] {
+        app.input_buffer = value.to_string();
+        app.submit_input().unwrap();
+    }
+
+    // "api" is taken, so the name was asked again
+    assert_eq!(app.input_mode, InputMode::Normal);
+    let shop = app.engine.config.get_dimension("shop").unwrap();
+    assert_eq!(shop.base_dir, None);
+    assert_eq!(shop.configured_tabs[1].command.as_deref(), Some("PORT=3000 npm run dev"));
+    assert_eq!(app.get_current_dimension().unwrap().name, "shop");
+}
+
+#[test]
+fn search_actions_copy_the_target_then_return_to_the_results() {
+    let backend = MockBackend::new().with_session("api", &["editor", "server"]);
+    let mut app = app(vec![dimension("api", &[("editor", None), ("server", None)])], &backend);
+
+    app.start_search();
+    for c in "server".chars() {
+        app.handle_input_char(c);
+    }
+    app.compute_search_results();
+    app.start_search_actions();
+    while SearchAction::ALL[app.search_action] != SearchAction::CopyTarget {
+        app.next_search_action();
+    }
+    app.run_search_action().unwrap();
+
+    assert_eq!(backend.state.borrow().clipboard.as_deref(), Some("api:1"));
+    assert_eq!(app.input_mode, InputMode::Searching);
+    assert_eq!(app.search_query, "server");
+}
+
+#[test]
+fn reordering_moves_dimensions_and_swaps_live_windows() {
+    let backend = MockBackend::new().with_session("web", &["editor", "server", "scratch"]);
+    let mut app = app(
+        vec![dimension("api", &[]), dimension("web", &[("editor", None), ("server", None)])],
+        &backend,
+    );
+
+    app.move_dimension(1).unwrap();
+    assert_eq!(app.selected_dimension, 1);
+    app.move_dimension(-1).unwrap();
+    app.move_dimension(-1).unwrap();
+    let names: Vec<_> = app.engine.config.dimensions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["api", "web"]);
+
+    app.selected_dimension = 1;
+    app.select_tab(Some(0));
+    app.move_tab(1).unwrap();
+    assert_eq!(backend.window_names("web").unwrap(), ["server", "editor", "scratch"]);
+    assert_eq!(tab_names(&app, "web"), ["server", "editor"]);
+    assert_eq!(app.selected_tab, Some(1));
+
+    // The hand-made window has no saved tab, so only tmux changes
+    app.move_tab(1).unwrap();
+    assert_eq!(backend.window_names("web").unwrap(), ["server", "scratch", "editor"]);
+    assert_eq!(tab_names(&app, "web"), ["server", "editor"]);
+}
+
+#[test]
+fn link_picker_opens_the_highlighted_link() {
+    let backend = MockBackend::new();
+    let mut web = dimension("web", &[]);
+    web.links = vec![
+        NamedLink { name: "Jira".to_string(), url: "https://jira.example.com/PROJ-142".to_string() },
+        NamedLink { name: "PR".to_string(), url: "https://github.com/acme/web/pull/7".to_string() },
+    ];
+    let mut app = app(vec![dimension("api", &[]), web], &backend);
+
+    assert!(app.start_link_picker().is_err());
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.selected_dimension = 1;
+    app.start_link_picker().unwrap();
+    assert_eq!(app.input_mode, InputMode::PickingLink);
+    app.previous_link();
+    assert_eq!(app.link_selected, 1);
+    app.submit_input().unwrap();
+
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.message.as_deref(), Some("Opened PR (https://github.com/acme/web/pull/7)"));
+    let spawned = &backend.state.borrow().spawned;
+    assert_eq!(spawned.len(), 1);
+    assert_eq!(spawned[0][1..], ["https://github.com/acme/web/pull/7"]);
+}
+
+#[test]
+fn suggestions_create_dimensions_for_new_projects() {
+    let root = std::env::temp_dir().join(format!("dimensions-suggestions-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    for project in ["api", "web", "docs"] {
+        std::fs::create_dir_all(root.join(project).join(".git")).unwrap();
+    }
+    let root = root.canonicalize().unwrap();
+    let backend = MockBackend::new();
+    let mut app = app(vec![Dimension::new_with_base_dir("api".to_string(), Some(root.join("api")))], &backend);
+
+    app.start_suggestions();
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.engine.config.discover.roots = vec![root.to_string_lossy().into_owned()];
+    app.start_suggestions();
+    assert_eq!(app.input_mode, InputMode::PickingSuggestion);
+    assert_eq!(app.suggestions, [root.join("docs"), root.join("web")]);
+
+    app.next_suggestion();
+    app.submit_input().unwrap();
+    assert_eq!(app.engine.config.get_dimension("web").unwrap().base_dir.as_deref(), Some(root.join("web").as_path()));
+    assert_eq!((app.input_mode, app.suggestion_selected), (InputMode::PickingSuggestion, 0));
+    app.submit_input().unwrap();
+    assert!(app.engine.config.get_dimension("docs").is_some());
+    assert_eq!(app.input_mode, InputMode::Normal);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn ssh_host_tabs_run_through_ssh() {
+    let backend = MockBackend::new();
+    let mut prod = dimension("prod", &[("shell", None), ("htop", Some("htop"))]);
+    prod.ssh_host = Some("prod-1".to_string());
+    let mut app = app(vec![prod], &backend);
+
+    app.switch_to_dimension().unwrap();
+    while app.startup.is_some() {
+        app.advance_startup().unwrap();
+    }
+
+    let state = backend.state.borrow();
+    assert!(state.sent_keys[0].2.starts_with("ssh 'prod-1'"));
+    let htop = &state.session("prod").unwrap().windows[1];
+    assert!(htop.command.as_deref().unwrap().starts_with("ssh -t 'prod-1' 'htop'"));
+}
+
+#[test]
+fn tasks_rerun_in_a_closed_window_record_their_exit_status() {
+    let backend = MockBackend::new().with_session("prod", &["shell", "tasks"]);
+    let mut prod = dimension("prod", &[("shell", None)]);
+    prod.ssh_host = Some("prod-1".to_string());
+    let mut tasks = Tab::new("tasks".to_string(), None, None);
+    tasks.tasks = vec![Task { name: "deploy".to_string(), command: "make deploy".to_string() }];
+    prod.add_tab(tasks);
+    let mut app = app(vec![prod], &backend);
+
+    app.select_tab(Some(1));
+    assert!(app.selected_configured_tab().is_some());
+    // Closed since the app last looked
+    backend.kill_window("prod", 1).unwrap();
+    app.run_selected_task().unwrap();
+
+    assert_eq!(backend.window_names("prod").unwrap(), ["shell", "tasks"]);
+    let state = backend.state.borrow();
+    let (_, window, keys) = state.sent_keys.last().unwrap();
+    assert_eq!(*window, 1);
+    assert_eq!(*keys, Tmux::record_exit_status(&ssh_config::ssh_command("prod-1", Some("make deploy"))));
+}
+
+#[test]
+fn remote_dimensions_attach_over_ssh() {
+    let backend = MockBackend::new();
+    let mut build = dimension("build", &[("shell", None), ("logs", Some("tail -f build.log"))]);
+    build.remote = Some("ci-box".to_string());
+    let mut app = app(vec![build], &backend);
+
+    app.switch_to_dimension().unwrap();
+
+    assert!(app.startup.is_none());
+    assert_eq!(backend.window_names("build").unwrap(), ["ci-box"]);
+    assert_eq!(app.should_attach.as_deref(), Some("build"));
+    {
+        let state = backend.state.borrow();
+        assert_eq!(state.sent_keys[0].2, remote::attach_command("ci-box", "build"));
+        assert_eq!(state.remote_sessions, [("ci-box".to_string(), "build".to_string())]);
+    }
+    assert_eq!(tab_names(&app, "build"), ["shell", "logs"]);
+
+    let build = app.engine.config.get_dimension("build").unwrap().clone();
+    app.engine.kill_session(&build).unwrap();
+    assert!(backend.state.borrow().remote_sessions.is_empty());
+    assert!(!backend.session_exists("build"));
+}
+
+#[test]
+fn ctrl_r_searches_by_regex_and_reports_bad_patterns() {
+    let backend = MockBackend::new();
+    let mut app = app(
+        vec![dimension("api", &[("server", Some("cargo run")), ("tests", Some("cargo test")), ("shell", None)])],
+        &backend,
+    );
+
+    app.start_search();
+    for c in "cargo (run|test)".chars() {
+        app.handle_input_char(c);
+    }
+    app.compute_search_results();
+    assert!(app.search_results.is_empty());
+
+    app.toggle_search_regex();
+    let tabs: Vec<&str> = app.search_results.iter().map(|r| r.tab_name.as_str()).collect();
+    assert_eq!(tabs, ["server", "tests"]);
+    assert_eq!(app.search_error, None);
+
+    app.handle_input_char('(');
+    app.compute_search_results();
+    assert!(app.search_results.is_empty());
+    assert_eq!(app.search_error.as_deref(), Some("unclosed group"));
+
+    app.toggle_search_regex();
+    assert_eq!(app.search_error, None);
+}
+
+#[test]
+fn up_in_an_empty_search_recalls_past_queries() {
+    let backend = MockBackend::new();
+    let mut app = app(vec![dimension("api", &[("server", None)]), dimension("web", &[("editor", None)])], &backend);
+
+    for query in ["web", "serv"] {
+        app.start_search();
+        for c in query.chars() {
+            app.handle_input_char(c);
+        }
+        app.compute_search_results();
+        app.select_search_result().unwrap();
+    }
+    assert_eq!(app.search_history, ["serv", "web"]);
+
+    app.start_search();
+    assert!(app.is_recalling_search());
+    app.previous_search_query();
+    app.previous_search_query();
+    assert_eq!(app.input_buffer, "web");
+    assert_eq!(app.search_results[0].dimension_name, "web");
+    app.previous_search_query();
+    assert_eq!(app.search_history_index, Some(1));
+
+    app.next_search_query();
+    assert_eq!(app.search_query, "serv");
+    app.next_search_query();
+    assert_eq!(app.search_query, "");
+    assert_eq!(app.search_history_index, None);
+
+    // Editing a recalled query hands the arrows back to the results
+    app.previous_search_query();
+    app.handle_input_char('e');
+    assert!(!app.is_recalling_search());
+}
+
+#[test]
+fn matrix_moves_in_two_dimensions_and_shows_window_states() {
+    use crate::tmux::WindowStatus;
+    use crate::ui::view_model::{self, CellState};
+
+    let backend = MockBackend::new().with_session("api", &["editor", "server", "logs"]);
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+    backend.state.borrow_mut().window_statuses.insert(
+        "api".to_string(),
+        HashMap::from([
+            (1, WindowStatus { exit_status: Some(2), activity: 0 }),
+            (2, WindowStatus { exit_status: None, activity: now }),
+        ]),
+    );
+    let mut app = app(
+        vec![
+            dimension("api", &[("editor", None), ("server", Some("cargo run")), ("logs", None)]),
+            dimension("web", &[("shell", None)]),
+            dimension("db", &[]),
+        ],
+        &backend,
+    );
+
+    app.toggle_matrix();
+    assert_eq!((app.selected_dimension, app.selected_tab), (0, Some(0)));
+    app.matrix_move(0, 5);
+    assert_eq!(app.selected_tab, Some(2));
+
+    let view = view_model::matrix(&app);
+    let states: Vec<CellState> = view.rows[0].cells.iter().map(|cell| cell.state).collect();
+    assert_eq!(states, [CellState::Running, CellState::Failed(2), CellState::Active]);
+    assert_eq!(view.rows[1].cells[0].state, CellState::Stopped);
+    assert_eq!(view.selected, Some((0, Some(2))));
+
+    // The column carries over, clamped to shorter rows
+    app.matrix_move(1, 0);
+    assert_eq!((app.selected_dimension, app.selected_tab), (1, Some(0)));
+    app.matrix_move(1, 0);
+    assert_eq!((app.selected_dimension, app.selected_tab), (2, None));
+    app.matrix_move(1, 0);
+    assert_eq!(app.selected_dimension, 2);
+    app.matrix_move(-2, 1);
+    assert_eq!((app.selected_dimension, app.selected_tab), (0, Some(1)));
+}
+
+#[test]
+fn startup_sort_orders_the_list_but_not_the_config() {
+    let backend = MockBackend::new().with_session("web", &["shell"]);
+    let mut config = DimensionConfig::default();
+    config.dimensions = vec![dimension("web", &[]), dimension("api", &[]), dimension("db", &[])];
+    config.startup.sort = DimensionSort::Name;
+    let mut app = app_with_config(config, &backend);
+
+    let listed: Vec<String> = crate::ui::view_model::dimension_list(&app).rows.into_iter().map(|r| r.name).collect();
+    assert_eq!(listed, ["api", "db", "web"]);
+    assert_eq!(app.selected_dimension, 0);
+    app.next_dimension();
+    assert_eq!(app.get_current_dimension().unwrap().name, "api");
+    app.next_dimension();
+    assert_eq!(app.get_current_dimension().unwrap().name, "db");
+
+    app.save_config().unwrap();
+    let names: Vec<&str> = app.engine.config.dimensions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["web", "api", "db"]);
+}
@@ -0,0 +1,475 @@
+//! In-memory stand-in for tmux. Clones share state, so a test can keep one handle
+//! and give another to `App` to inspect what the app did.
+
+use super::MultiplexerBackend;
+use crate::dimension::{Dimension, PopupSettings};
+use crate::snapshot;
+use crate::tmux::{PaneInfo, TmuxClient, WindowStatus};
+use anyhow::Result;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct MockWindow {
+    pub index: usize,
+    pub name: String,
+    pub command: Option<String>,
+    pub path: Option<PathBuf>,
+    /// Working directories of the panes split off the first one
+    pub splits: Vec<Option<PathBuf>>,
+    pub layout: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MockSession {
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub windows: Vec<MockWindow>,
+    pub options: HashMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+pub struct MockState {
+    pub sessions: Vec<MockSession>,
+    /// Session and window we're "inside", like `$TMUX`
+    pub current: Option<(String, usize)>,
+    pub clients: Vec<(String, TmuxClient)>,
+    pub global_options: HashMap<String, String>,
+    /// Every `send_keys` and `send_keys_to_pane` as (session, window, keys)
+    pub sent_keys: Vec<(String, usize, String)>,
+    /// Every `switch_session` target
+    pub switches: Vec<String>,
+    /// Last `copy_to_clipboard` text
+    pub clipboard: Option<String>,
+    /// Window with the latest activity, per session
+    pub active_windows: HashMap<String, usize>,
+    /// Exit statuses and activity times, per session and window; unset windows are idle
+    pub window_statuses: HashMap<String, HashMap<usize, WindowStatus>>,
+    /// (window name, foreground program) of each pane, per session; unset sessions sit at shells
+    pub pane_commands: HashMap<String, Vec<(String, String)>>,
+    /// Sessions with a key table bound
+    pub key_tables: Vec<String>,
+    /// Global hooks set, by hook name
+    pub global_hooks: HashMap<String, String>,
+    /// Every `spawn_detached` as the program followed by its arguments
+    pub spawned: Vec<Vec<String>>,
+    /// What `frecent_directories` returns, most frecent first
+    pub frecent: Vec<String>,
+    /// Sessions running on remote hosts, as (host, session)
+    pub remote_sessions: Vec<(String, String)>,
+}
+
+impl MockState {
+    pub fn session(&self, name: &str) -> Option<&MockSession> {
+        self.sessions.iter().find(|s| s.name == name)
+    }
+
+    fn session_mut(&mut self, name: &str) -> Result<&mut MockSession> {
+        match self.sessions.iter_mut().find(|s| s.name == name) {
+            Some(session) => Ok(session),
+            None => anyhow::bail!("can't find session: {}", name),
+        }
+    }
+
+    fn window_mut(&mut self, session: &str, window_index: usize) -> Result<&mut MockWindow> {
+        match self.session_mut(session)?.windows.iter_mut().find(|w| w.index == window_index) {
+            Some(window) => Ok(window),
+            None => anyhow::bail!("can't find window: {}:{}", session, window_index),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MockBackend {
+    pub state: Rc<RefCell<MockState>>,
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a running session with windows named `windows`, indexed from 0
+    pub fn with_session(self, name: &str, windows: &[&str]) -> Self {
+        self.state.borrow_mut().sessions.push(MockSession {
+            name: name.to_string(),
+            path: None,
+            windows: windows
+                .iter()
+                .enumerate()
+                .map(|(index, window)| MockWindow {
+                    index,
+                    name: window.to_string(),
+                    command: None,
+                    path: None,
+                    splits: vec![],
+                    layout: None,
+                })
+                .collect(),
+            options: HashMap::new(),
+        });
+        self
+    }
+
+    /// Window names of `session`, or None when it isn't running
+    pub fn window_names(&self, session: &str) -> Option<Vec<String>> {
+        let state = self.state.borrow();
+        let session = state.session(session)?;
+        Some(session.windows.iter().map(|w| w.name.clone()).collect())
+    }
+
+    fn add_session(&self, name: &str, path: Option<PathBuf>) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        if state.session(name).is_some() {
+            anyhow::bail!("duplicate session: {}", name);
+        }
+        state.sessions.push(MockSession {
+            name: name.to_string(),
+            path: path.clone(),
+            windows: vec![MockWindow {
+                index: 0,
+                name: "shell".to_string(),
+                command: None,
+                path,
+                splits: vec![],
+                layout: None,
+            }],
+            options: HashMap::new(),
+        });
+        Ok(())
+    }
+}
+
+impl MultiplexerBackend for MockBackend {
+    fn is_inside_session(&self) -> bool {
+        self.state.borrow().current.is_some()
+    }
+
+    fn get_current_session(&self) -> Result<String> {
+        match &self.state.borrow().current {
+            Some((session, _)) => Ok(session.clone()),
+            None => anyhow::bail!("not inside tmux"),
+        }
+    }
+
+    fn get_current_window_index(&self) -> Result<usize> {
+        match &self.state.borrow().current {
+            Some((_, window)) => Ok(*window),
+            None => anyhow::bail!("not inside tmux"),
+        }
+    }
+
+    fn current_client(&self) -> Option<TmuxClient> {
+        None
+    }
+
+    fn list_clients(&self, session: &str) -> Result<Vec<TmuxClient>> {
+        Ok(self
+            .state
+            .borrow()
+            .clients
+            .iter()
+            .filter(|(s, _)| s == session)
+            .map(|(_, client)| client.clone())
+            .collect())
+    }
+
+    fn attached_client_counts(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for (session, _) in &self.state.borrow().clients {
+            *counts.entry(session.clone()).or_default() += 1;
+        }
+        counts
+    }
+
+    fn global_option(&self, option: &str) -> Option<String> {
+        self.state.borrow().global_options.get(option).cloned()
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>> {
+        Ok(self.state.borrow().sessions.iter().map(|s| s.name.clone()).collect())
+    }
+
+    fn session_exists(&self, name: &str) -> bool {
+        self.state.borrow().session(name).is_some()
+    }
+
+    fn create_session(&self, name: &str, _detached: bool) -> Result<()> {
+        self.add_session(name, None)
+    }
+
+    fn create_session_with_dir(&self, name: &str, _detached: bool, start_dir: &str) -> Result<()> {
+        self.add_session(name, Some(PathBuf::from(start_dir)))
+    }
+
+    fn kill_session(&self, name: &str) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.session_mut(name)?;
+        state.sessions.retain(|s| s.name != name);
+        Ok(())
+    }
+
+    fn ensure_remote_session(&self, host: &str, dimension: &Dimension) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        let running = (host.to_string(), dimension.name.clone());
+        if !state.remote_sessions.contains(&running) {
+            state.remote_sessions.push(running);
+        }
+        Ok(())
+    }
+
+    fn kill_remote_session(&self, host: &str, session: &str) -> Result<()> {
+        self.state.borrow_mut().remote_sessions.retain(|(h, s)| h != host || s != session);
+        Ok(())
+    }
+
+    fn rename_session(&self, old_name: &str, new_name: &str) -> Result<()> {
+        self.state.borrow_mut().session_mut(old_name)?.name = new_name.to_string();
+        Ok(())
+    }
+
+    fn switch_session(&self, target: &str) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        let (session, window) = match target.split_once(':') {
+            Some((session, window)) => (session, window.parse().unwrap_or(0)),
+            None => (target, 0),
+        };
+        state.session_mut(session)?;
+        state.current = Some((session.to_string(), window));
+        state.switches.push(target.to_string());
+        Ok(())
+    }
+
+    fn session_path(&self, session: &str) -> Option<PathBuf> {
+        self.state.borrow().session(session)?.path.clone()
+    }
+
+    fn set_session_option(&self, session: &str, option: &str, value: &str) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.session_mut(session)?.options.insert(option.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn apply_key_table(&self, dimension: &Dimension, _popup: &PopupSettings) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        if !dimension.keys.is_empty() && !state.key_tables.contains(&dimension.name) {
+            state.key_tables.push(dimension.name.clone());
+        }
+        Ok(())
+    }
+
+    fn remove_key_table(&self, session: &str) {
+        self.state.borrow_mut().key_tables.retain(|s| s != session);
+    }
+
+    fn list_windows(&self, session: &str) -> Result<Vec<(usize, String)>> {
+        let state = self.state.borrow();
+        let Some(session) = state.session(session) else {
+            anyhow::bail!("can't find session: {}", session);
+        };
+        Ok(session.windows.iter().map(|w| (w.index, w.name.clone())).collect())
+    }
+
+    fn snapshot(&self) -> Result<HashMap<String, Vec<(usize, String)>>> {
+        Ok(self
+            .state
+            .borrow()
+            .sessions
+            .iter()
+            .map(|s| (s.name.clone(), s.windows.iter().map(|w| (w.index, w.name.clone())).collect()))
+            .collect())
+    }
+
+    fn list_window_paths(&self, session: &str) -> Result<Vec<(usize, String, Option<PathBuf>)>> {
+        let state = self.state.borrow();
+        let Some(session) = state.session(session) else {
+            anyhow::bail!("can't find session: {}", session);
+        };
+        Ok(session
+            .windows
+            .iter()
+            .map(|w| (w.index, w.name.clone(), w.path.clone()))
+            .collect())
+    }
+
+    fn window_exit_statuses(&self, _session: &str) -> Result<HashMap<usize, i32>> {
+        Ok(HashMap::new())
+    }
+
+    fn window_statuses(&self) -> Result<HashMap<String, HashMap<usize, WindowStatus>>> {
+        Ok(self.state.borrow().window_statuses.clone())
+    }
+
+    fn pane_commands(&self, session: &str) -> Result<Vec<(String, String)>> {
+        Ok(self.state.borrow().pane_commands.get(session).cloned().unwrap_or_default())
+    }
+
+    fn list_panes(&self, session: &str) -> Result<Vec<PaneInfo>> {
+        let state = self.state.borrow();
+        let Some(mock) = state.session(session) else {
+            anyhow::bail!("can't find session: {}", session);
+        };
+        let programs = state.pane_commands.get(session);
+        let mut panes = vec![];
+        for window in &mock.windows {
+            let program = programs
+                .and_then(|p| p.iter().find(|(name, _)| *name == window.name))
+                .map(|(_, program)| program.clone());
+            let paths = std::iter::once(&window.path).chain(&window.splits);
+            for (pane_index, path) in paths.enumerate() {
+                panes.push(PaneInfo {
+                    window_index: window.index,
+                    window_name: window.name.clone(),
+                    window_active: state.active_windows.get(session) == Some(&window.index),
+                    layout: window.layout.clone().unwrap_or_default(),
+                    pane_index,
+                    pid: 0,
+                    command: program.clone().filter(|_| pane_index == 0).unwrap_or_else(|| "zsh".to_string()),
+                    path: path.clone(),
+                });
+            }
+        }
+        Ok(panes)
+    }
+
+    fn pane_command_line(&self, pane: &PaneInfo) -> Option<String> {
+        (!snapshot::is_shell(&pane.command)).then(|| pane.command.clone())
+    }
+
+    fn last_active_window(&self, session: &str) -> Option<usize> {
+        self.state.borrow().active_windows.get(session).copied()
+    }
+
+    fn get_first_window_index(&self, session: &str) -> Result<usize> {
+        match self.list_windows(session)?.first() {
+            Some((index, _)) => Ok(*index),
+            None => anyhow::bail!("No windows found in session"),
+        }
+    }
+
+    fn new_window(&self, session: &str, name: &str, command: Option<&str>, working_dir: Option<&Path>) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        let session = state.session_mut(session)?;
+        let index = session.windows.iter().map(|w| w.index + 1).max().unwrap_or(0);
+        let path = working_dir.map(Path::to_path_buf).or_else(|| session.path.clone());
+        session.windows.push(MockWindow {
+            index,
+            name: name.to_string(),
+            command: command.map(str::to_string),
+            path,
+            splits: vec![],
+            layout: None,
+        });
+        Ok(())
+    }
+
+    fn rename_window(&self, session: &str, window_index: usize, new_name: &str) -> Result<()> {
+        self.state.borrow_mut().window_mut(session, window_index)?.name = new_name.to_string();
+        Ok(())
+    }
+
+    fn kill_window(&self, session: &str, window_index: usize) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, window_index)?;
+        let target = state.session_mut(session)?;
+        target.windows.retain(|w| w.index != window_index);
+        // Like tmux, a session goes away with its last window
+        if target.windows.is_empty() {
+            state.sessions.retain(|s| s.name != session);
+        }
+        Ok(())
+    }
+
+    fn move_window(&self, session: &str, window_index: usize, target_session: &str) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        let window = state.window_mut(session, window_index)?.clone();
+        state.session_mut(target_session)?;
+        state.session_mut(session)?.windows.retain(|w| w.index != window_index);
+        let target = state.session_mut(target_session)?;
+        let index = target.windows.iter().map(|w| w.index + 1).max().unwrap_or(0);
+        target.windows.push(MockWindow { index, ..window });
+        Ok(())
+    }
+
+    fn swap_windows(&self, session: &str, first: usize, second: usize) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, first)?.index = usize::MAX;
+        state.window_mut(session, second)?.index = first;
+        state.window_mut(session, usize::MAX)?.index = second;
+        state.session_mut(session)?.windows.sort_by_key(|w| w.index);
+        Ok(())
+    }
+
+    fn select_window(&self, session: &str, window_index: usize) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, window_index)?;
+        state.active_windows.insert(session.to_string(), window_index);
+        Ok(())
+    }
+
+    fn split_window(&self, session: &str, window_index: usize, working_dir: Option<&Path>) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, window_index)?.splits.push(working_dir.map(Path::to_path_buf));
+        Ok(())
+    }
+
+    fn select_layout(&self, session: &str, window_index: usize, layout: &str) -> Result<()> {
+        self.state.borrow_mut().window_mut(session, window_index)?.layout = Some(layout.to_string());
+        Ok(())
+    }
+
+    fn send_keys(&self, session: &str, window_index: usize, keys: &str) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, window_index)?;
+        state.sent_keys.push((session.to_string(), window_index, keys.to_string()));
+        Ok(())
+    }
+
+    fn send_keys_to_pane(&self, session: &str, window_index: usize, _pane_index: usize, keys: &str) -> Result<()> {
+        self.send_keys(session, window_index, keys)
+    }
+
+    fn pane_current_path(&self, session: &str, window_index: usize) -> Option<PathBuf> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, window_index).ok()?.path.clone()
+    }
+
+    fn capture_pane(&self, session: &str, window_index: usize) -> Result<String> {
+        let mut state = self.state.borrow_mut();
+        state.window_mut(session, window_index)?;
+        Ok(String::new())
+    }
+
+    fn copy_to_clipboard(&self, text: &str) -> Result<()> {
+        self.state.borrow_mut().clipboard = Some(text.to_string());
+        Ok(())
+    }
+
+    fn frecent_directories(&self) -> Vec<String> {
+        self.state.borrow().frecent.clone()
+    }
+
+    fn control_mode_command(&self) -> Option<Command> {
+        None
+    }
+
+    fn set_global_hook(&self, hook: &str, command: &str) -> Result<()> {
+        self.state.borrow_mut().global_hooks.insert(hook.to_string(), command.to_string());
+        Ok(())
+    }
+
+    fn unset_global_hook(&self, hook: &str) -> Result<()> {
+        self.state.borrow_mut().global_hooks.remove(hook);
+        Ok(())
+    }
+
+    fn spawn_detached(&self, command: Command) -> Result<()> {
+        let program = std::iter::once(command.get_program()).chain(command.get_args());
+        let spawned = program.map(|arg| arg.to_string_lossy().into_owned()).collect();
+        self.state.borrow_mut().spawned.push(spawned);
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+//! The terminal multiplexer operations `App` relies on, behind a trait so its logic
+//! (switching, deleting, syncing tabs) can run against a mock in unit tests.
+
+#[cfg(test)]
+pub mod mock;
+
+use crate::dimension::{Dimension, PopupSettings};
+use crate::key_table;
+use crate::remote;
+use crate::snapshot;
+use crate::tmux::{PaneInfo, Tmux, TmuxClient, WindowStatus};
+use crate::zoxide;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub trait MultiplexerBackend {
+    /// Whether we run inside a client of the server being driven
+    fn is_inside_session(&self) -> bool;
+    fn get_current_session(&self) -> Result<String>;
+    fn get_current_window_index(&self) -> Result<usize>;
+    fn current_client(&self) -> Option<TmuxClient>;
+    fn list_clients(&self, session: &str) -> Result<Vec<TmuxClient>>;
+    fn attached_client_counts(&self) -> HashMap<String, usize>;
+    fn global_option(&self, option: &str) -> Option<String>;
+
+    fn list_sessions(&self) -> Result<Vec<String>>;
+    fn session_exists(&self, name: &str) -> bool;
+    fn create_session(&self, name: &str, detached: bool) -> Result<()>;
+    fn create_session_with_dir(&self, name: &str, detached: bool, start_dir: &str) -> Result<()>;
+    fn kill_session(&self, name: &str) -> Result<()>;
+    /// Create `dimension`'s session on `host` unless it is already running there (see `remote`)
+    fn ensure_remote_session(&self, host: &str, dimension: &Dimension) -> Result<()>;
+    /// Kill `session` on `host`; a session that isn't running is not an error
+    fn kill_remote_session(&self, host: &str, session: &str) -> Result<()>;
+    fn rename_session(&self, old_name: &str, new_name: &str) -> Result<()>;
+    fn switch_session(&self, target: &str) -> Result<()>;
+    fn session_path(&self, session: &str) -> Option<PathBuf>;
+    fn set_session_option(&self, session: &str, option: &str, value: &str) -> Result<()>;
+    /// Bind `dimension.keys` as its session's key table (see `key_table::apply`)
+    fn apply_key_table(&self, dimension: &Dimension, popup: &PopupSettings) -> Result<()>;
+    fn remove_key_table(&self, session: &str);
+
+    fn list_windows(&self, session: &str) -> Result<Vec<(usize, String)>>;
+    /// Windows of every session, keyed by session name
+    fn snapshot(&self) -> Result<HashMap<String, Vec<(usize, String)>>>;
+    fn list_window_paths(&self, session: &str) -> Result<Vec<(usize, String, Option<PathBuf>)>>;
+    fn window_exit_statuses(&self, session: &str) -> Result<HashMap<usize, i32>>;
+    /// (window name, foreground program) of every pane in `session`
+    fn pane_commands(&self, session: &str) -> Result<Vec<(String, String)>>;
+    fn list_panes(&self, session: &str) -> Result<Vec<PaneInfo>>;
+    /// Full command line of the pane's foreground program; None when it sits at a shell
+    fn pane_command_line(&self, pane: &PaneInfo) -> Option<String>;
+    /// Exit status and last activity of every window, keyed by session
+    fn window_statuses(&self) -> Result<HashMap<String, HashMap<usize, WindowStatus>>>;
+    fn last_active_window(&self, session: &str) -> Option<usize>;
+    fn get_first_window_index(&self, session: &str) -> Result<usize>;
+    fn new_window(&self, session: &str, name: &str, command: Option<&str>, working_dir: Option<&Path>) -> Result<()>;
+    fn rename_window(&self, session: &str, window_index: usize, new_name: &str) -> Result<()>;
+    fn kill_window(&self, session: &str, window_index: usize) -> Result<()>;
+    fn move_window(&self, session: &str, window_index: usize, target_session: &str) -> Result<()>;
+    fn swap_windows(&self, session: &str, first: usize, second: usize) -> Result<()>;
+    fn select_window(&self, session: &str, window_index: usize) -> Result<()>;
+    fn split_window(&self, session: &str, window_index: usize, working_dir: Option<&Path>) -> Result<()>;
+    fn select_layout(&self, session: &str, window_index: usize, layout: &str) -> Result<()>;
+    fn send_keys(&self, session: &str, window_index: usize, keys: &str) -> Result<()>;
+    fn send_keys_to_pane(&self, session: &str, window_index: usize, pane_index: usize, keys: &str) -> Result<()>;
+    fn pane_current_path(&self, session: &str, window_index: usize) -> Option<PathBuf>;
+    fn capture_pane(&self, session: &str, window_index: usize) -> Result<String>;
+    fn copy_to_clipboard(&self, text: &str) -> Result<()>;
+    /// zoxide's directories, most frecent first (see `zoxide`)
+    fn frecent_directories(&self) -> Vec<String>;
+    /// A control-mode client attached to one of the sessions, not yet started; None when
+    /// there is no session to attach to
+    fn control_mode_command(&self) -> Option<Command>;
+    fn set_global_hook(&self, hook: &str, command: &str) -> Result<()>;
+    fn unset_global_hook(&self, hook: &str) -> Result<()>;
+    /// Start a program nothing waits on (event delivery, hooks, status updates); its stdio is the caller's to set up
+    fn spawn_detached(&self, command: Command) -> Result<()>;
+}
+
+/// The real thing: every call goes to the tmux binary via `Tmux`
+pub struct TmuxBackend;
+
+impl MultiplexerBackend for TmuxBackend {
+    fn is_inside_session(&self) -> bool {
+        Tmux::is_inside_session()
+    }
+
+    fn get_current_session(&self) -> Result<String> {
+        Tmux::get_current_session()
+    }
+
+    fn get_current_window_index(&self) -> Result<usize> {
+        Tmux::get_current_window_index()
+    }
+
+    fn current_client(&self) -> Option<TmuxClient> {
+        Tmux::current_client()
+    }
+
+    fn list_clients(&self, session: &str) -> Result<Vec<TmuxClient>> {
+        Tmux::list_clients(session)
+    }
+
+    fn attached_client_counts(&self) -> HashMap<String, usize> {
+        Tmux::attached_client_counts()
+    }
+
+    fn global_option(&self, option: &str) -> Option<String> {
+        Tmux::global_option(option)
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>> {
+        Tmux::list_sessions()
+    }
+
+    fn session_exists(&self, name: &str) -> bool {
+        Tmux::session_exists(name)
+    }
+
+    fn create_session(&self, name: &str, detached: bool) -> Result<()> {
+        Tmux::create_session(name, detached)
+    }
+
+    fn create_session_with_dir(&self, name: &str, detached: bool, start_dir: &str) -> Result<()> {
+        Tmux::create_session_with_dir(name, detached, start_dir)
+    }
+
+    fn kill_session(&self, name: &str) -> Result<()> {
+        Tmux::kill_session(name)
+    }
+
+    fn ensure_remote_session(&self, host: &str, dimension: &Dimension) -> Result<()> {
+        remote::ensure_session(host, dimension)
+    }
+
+    fn kill_remote_session(&self, host: &str, session: &str) -> Result<()> {
+        remote::kill_session(host, session)
+    }
+
+    fn rename_session(&self, old_name: &str, new_name: &str) -> Result<()> {
+        Tmux::rename_session(old_name, new_name)
+    }
+
+    fn switch_session(&self, target: &str) -> Result<()> {
+        Tmux::switch_session(target)
+    }
+
+    fn session_path(&self, session: &str) -> Option<PathBuf> {
+        Tmux::session_path(session)
+    }
+
+    fn set_session_option(&self, session: &str, option: &str, value: &str) -> Result<()> {
+        Tmux::set_session_option(session, option, value)
+    }
+
+    fn apply_key_table(&self, dimension: &Dimension, popup: &PopupSettings) -> Result<()> {
+        key_table::apply(dimension, popup)
+    }
+
+    fn remove_key_table(&self, session: &str) {
+        key_table::remove(session)
+    }
+
+    fn list_windows(&self, session: &str) -> Result<Vec<(usize, String)>> {
+        Tmux::list_windows(session)
+    }
+
+    fn snapshot(&self) -> Result<HashMap<String, Vec<(usize, String)>>> {
+        Tmux::snapshot()
+    }
+
+    fn list_window_paths(&self, session: &str) -> Result<Vec<(usize, String, Option<PathBuf>)>> {
+        Tmux::list_window_paths(session)
+    }
+
+    fn window_exit_statuses(&self, session: &str) -> Result<HashMap<usize, i32>> {
+        Tmux::window_exit_statuses(session)
+    }
+
+    fn window_statuses(&self) -> Result<HashMap<String, HashMap<usize, WindowStatus>>> {
+        Tmux::window_statuses()
+    }
+
+    fn pane_commands(&self, session: &str) -> Result<Vec<(String, String)>> {
+        let panes = Tmux::list_panes(session)?;
+        Ok(panes.into_iter().map(|pane| (pane.window_name, pane.command)).collect())
+    }
+
+    fn list_panes(&self, session: &str) -> Result<Vec<PaneInfo>> {
+        Tmux::list_panes(session)
+    }
+
+    fn pane_command_line(&self, pane: &PaneInfo) -> Option<String> {
+        snapshot::foreground_command(pane)
+    }
+
+    fn last_active_window(&self, session: &str) -> Option<usize> {
+        Tmux::last_active_window(session)
+    }
+
+    fn get_first_window_index(&self, session: &str) -> Result<usize> {
+        Tmux::get_first_window_index(session)
+    }
+
+    fn new_window(&self, session: &str, name: &str, command: Option<&str>, working_dir: Option<&Path>) -> Result<()> {
+        Tmux::new_window(session, name, command, working_dir)
+    }
+
+    fn rename_window(&self, session: &str, window_index: usize, new_name: &str) -> Result<()> {
+        Tmux::rename_window(session, window_index, new_name)
+    }
+
+    fn kill_window(&self, session: &str, window_index: usize) -> Result<()> {
+        Tmux::kill_window(session, window_index)
+    }
+
+    fn move_window(&self, session: &str, window_index: usize, target_session: &str) -> Result<()> {
+        Tmux::move_window(session, window_index, target_session)
+    }
+
+    fn swap_windows(&self, session: &str, first: usize, second: usize) -> Result<()> {
+        Tmux::swap_windows(session, first, second)
+    }
+
+    fn select_window(&self, session: &str, window_index: usize) -> Result<()> {
+        Tmux::select_window(session, window_index)
+    }
+
+    fn split_window(&self, session: &str, window_index: usize, working_dir: Option<&Path>) -> Result<()> {
+        Tmux::split_window(session, window_index, working_dir)
+    }
+
+    fn select_layout(&self, session: &str, window_index: usize, layout: &str) -> Result<()> {
+        Tmux::select_layout(session, window_index, layout)
+    }
+
+    fn send_keys(&self, session: &str, window_index: usize, keys: &str) -> Result<()> {
+        Tmux::send_keys(session, window_index, keys)
+    }
+
+    fn send_keys_to_pane(&self, session: &str, window_index: usize, pane_index: usize, keys: &str) -> Result<()> {
+        Tmux::send_keys_to_pane(session, window_index, pane_index, keys)
+    }
+
+    fn pane_current_path(&self, session: &str, window_index: usize) -> Option<PathBuf> {
+        Tmux::pane_current_path(session, window_index)
+    }
+
+    fn capture_pane(&self, session: &str, window_index: usize) -> Result<String> {
+        Tmux::capture_pane(session, window_index)
+    }
+
+    fn copy_to_clipboard(&self, text: &str) -> Result<()> {
+        Tmux::copy_to_clipboard(text)
+    }
+
+    fn frecent_directories(&self) -> Vec<String> {
+        zoxide::directories()
+    }
+
+    fn control_mode_command(&self) -> Option<Command> {
+        let session = Tmux::list_sessions().ok()?.into_iter().next()?;
+        Some(Tmux::control_mode_command(&session))
+    }
+
+    fn set_global_hook(&self, hook: &str, command: &str) -> Result<()> {
+        Tmux::set_global_hook(hook, command)
+    }
+
+    fn unset_global_hook(&self, hook: &str) -> Result<()> {
+        Tmux::unset_global_hook(hook)
+    }
+
+    fn spawn_detached(&self, mut command: Command) -> Result<()> {
+        command
+            .spawn()
+            .with_context(|| format!("Failed to run {}", command.get_program().to_string_lossy()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,443 @@
+//! Command-line interface: flags for the TUI plus non-interactive subcommands for scripting.
+
+use crate::config_store;
+use crate::dimension::DimensionConfig;
+use crate::engine::{DimensionsEngine, SwitchTarget};
+use crate::events::EventType;
+use crate::interop::ExportFormat;
+use crate::path_completion::PathCompleter;
+use crate::tmux::Tmux;
+use crate::uri;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "dimensions", about = "Terminal Tab Manager", disable_version_flag = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Use <dir> instead of ~/.config/dimensions
+    #[arg(long, global = true, value_name = "DIR")]
+    pub config: Option<PathBuf>,
+
+    /// Drive the tmux server with this socket name (tmux -L)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub tmux_socket_name: Option<String>,
+
+    /// Drive the tmux server at this socket path (tmux -S)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub tmux_socket_path: Option<PathBuf>,
+
+    /// Only show the dimensions of a scope from the config
+    #[arg(long, value_name = "NAME")]
+    pub scope: Option<String>,
+
+    /// Only show dimensions matching these globs (repeatable, comma-separated)
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub only: Vec<String>,
+
+    /// Hide dimensions matching these globs (repeatable, comma-separated)
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Only search, then print the chosen session:window to stdout instead of switching to it
+    #[arg(long)]
+    pub pick: bool,
+
+    /// Only prompt to rename the current tmux window (Tab: adopt or move it), then close
+    #[arg(long, conflicts_with = "pick")]
+    pub here: bool,
+
+    /// Print timings of the startup steps and exit after the first frame
+    #[arg(long)]
+    pub profile_startup: bool,
+
+    /// Print the version
+    #[arg(short = 'v', long)]
+    pub version: bool,
+
+    /// Check for a newer release and install it
+    #[arg(short = 'u', long)]
+    pub update: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Walk through Dimensions in a sandbox
+    Tutorial,
+    /// Rewrite tab commands across every dimension
+    Replace {
+        pattern: String,
+        replacement: String,
+        /// Treat <pattern> as a regular expression
+        #[arg(long)]
+        regex: bool,
+        /// Apply without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Report dimensions whose directories no longer exist
+    Check,
+    /// List dimensions and whether their sessions are running
+    List,
+    /// Create a dimension
+    New {
+        name: String,
+        /// Base directory for the dimension's tabs
+        #[arg(long, value_name = "DIR")]
+        dir: Option<String>,
+    },
+    /// Switch to the dimension of the current git repo (or directory), creating it first if needed
+    Here {
+        /// Template for a new dimension; defaults to `here.template` from the config
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Find projects under <dir> (or `discover.roots`) without a dimension and create dimensions for them
+    Discover {
+        dir: Option<String>,
+        /// How many directory levels to look down (default from `discover.depth`, else 2)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Create dimensions for every project found without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Create dimensions for ssh hosts, whose tabs run on the host; offers ~/.ssh/config's hosts when none are given
+    Ssh {
+        hosts: Vec<String>,
+        /// Create dimensions for every offered host without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Create a dimension from a template in a git repo, cloning its project
+    Init {
+        /// Template repo, optionally with the template's name: `<repo>#<template>`
+        #[arg(long, value_name = "REPO[#TEMPLATE]")]
+        from: String,
+        /// Name of the new dimension (asked for when omitted)
+        #[arg(long)]
+        name: Option<String>,
+        /// Where the project goes; defaults to ./<name>
+        #[arg(long, value_name = "DIR")]
+        dir: Option<String>,
+        /// Value for a template variable
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+    /// Switch to a dimension, starting its session first if needed
+    Switch { name: String },
+    /// Switch to the next dimension in config order, wrapping around (for a tmux binding)
+    Next,
+    /// Switch to the previous dimension in config order, wrapping around
+    Prev,
+    /// Delete a dimension and kill its session
+    Delete {
+        name: String,
+        /// Delete without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Bring back a deleted dimension from the config's trash
+    Undelete {
+        /// Which one; the most recently deleted when omitted
+        name: Option<String>,
+    },
+    /// List dimensions whose directories have been idle for months and archive the ones picked
+    Gc {
+        /// Only list them
+        #[arg(long)]
+        suggest: bool,
+        /// Idle this many months counts as stale (default from `tidy.stale_after_months`, else 6)
+        #[arg(long)]
+        months: Option<u32>,
+        /// Archive every stale dimension without asking
+        #[arg(short, long, conflicts_with = "suggest")]
+        yes: bool,
+    },
+    /// Save the live layout of a dimension's session (windows, panes, directories, programs)
+    Snapshot {
+        name: Option<String>,
+        /// Every running dimension
+        #[arg(long, conflicts_with = "name")]
+        all: bool,
+    },
+    /// Recreate a dimension's session from its last snapshot
+    Restore {
+        name: Option<String>,
+        /// Every dimension with a snapshot that isn't running
+        #[arg(long, conflicts_with = "name")]
+        all: bool,
+    },
+    /// Snapshot a dimension's session and kill it to free resources; switching to it thaws it
+    Hibernate { name: String },
+    /// Recreate a hibernated dimension's session without switching to it
+    Thaw { name: String },
+    /// Inspect the config
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Print a dimension as a tmuxinator or tmuxp project, or as JSON
+    Export {
+        name: String,
+        #[arg(long, value_enum, default_value = "tmuxinator")]
+        format: ExportFormat,
+    },
+    /// Print a shell script of tmux commands that recreates a dimension's session without Dimensions
+    ExportScript { name: String },
+    /// Add a dimension from a shell script of tmux new-session/new-window/send-keys commands
+    ImportScript {
+        /// The script, or - for stdin
+        file: String,
+        /// Dimension name; defaults to the script's new-session -s
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Switch to a `dimensions://<name>[/<tab>]` link
+    Open { uri: String },
+    /// Serve a read-only dashboard and JSON API of dimensions, sessions and health checks
+    Serve {
+        #[arg(long, default_value_t = 7777)]
+        port: u16,
+        /// Address to listen on; use 0.0.0.0 to watch from other machines (switching stays local-only)
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+    },
+    /// Open Dimensions in a tmux popup, sized by the `popup` config unless overridden here
+    Popup {
+        /// Width, as a percentage ("60%") or in cells
+        #[arg(long)]
+        width: Option<String>,
+        #[arg(long)]
+        height: Option<String>,
+        /// Horizontal position: C (centered), R, P, M, W, S or a number
+        #[arg(short, long)]
+        x: Option<String>,
+        #[arg(short, long)]
+        y: Option<String>,
+        /// Border lines: single, rounded, double, heavy, simple, padded or none
+        #[arg(long)]
+        border: Option<String>,
+        #[arg(long)]
+        title: Option<String>,
+        /// Arguments for the Dimensions inside, e.g. `-- --scope work`
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Register Dimensions as the handler for `dimensions://` links
+    RegisterHandler,
+    /// Summarize recent usage: time per dimension, sessions, most run commands
+    Report {
+        /// How many days back to look
+        #[arg(long, default_value_t = 7)]
+        days: u64,
+        /// Print JSON instead of markdown
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run a command, rerunning it whenever files matching <glob> change (used by `watch` tabs)
+    #[command(hide = true)]
+    Watch { glob: String, command: String },
+    /// Send an event to the configured webhook/MQTT topic (used by tmux hooks and tab commands)
+    #[command(hide = true)]
+    Emit { event: EventType, dimension: Option<String> },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the config as Dimensions sees it, with this machine's overrides applied
+    Show {
+        #[arg(long, conflicts_with = "toml")]
+        json: bool,
+        /// The default
+        #[arg(long)]
+        toml: bool,
+    },
+}
+
+/// `dimensions list`: one line per dimension, columns separated by tabs
+pub fn list(config: DimensionConfig) -> Result<()> {
+    for dimension in DimensionsEngine::new(config).list() {
+        let dir = dimension
+            .base_dir
+            .as_ref()
+            .map(|d| d.display().to_string())
+            .unwrap_or_default();
+        println!(
+            "{}\t{}\t{}\t{}",
+            dimension.name,
+            if dimension.running { "running" } else { "stopped" },
+            dimension.tabs.len(),
+            dir
+        );
+    }
+    Ok(())
+}
+
+/// `dimensions config show [--json|--toml]`: the loaded config, after migrating a legacy
+/// config.json and applying the `machines` entry for this host. Settings still at their
+/// defaults are left out, as when saving.
+pub fn config_show(config: &DimensionConfig, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(config)?);
+        return Ok(());
+    }
+    println!("# {}", DimensionConfig::config_path().display());
+    if let Some(applied) = &config.applied_machine {
+        println!("# with the overrides for machine '{}' applied", applied.hostname);
+    }
+    print!("{}", config_store::to_toml(config, None)?);
+    Ok(())
+}
+
+/// `dimensions new <name> [--dir <dir>]`
+pub fn new(config: DimensionConfig, name: &str, dir: Option<&str>) -> Result<()> {
+    let name = name.trim();
+    let base_dir = dir
+        .map(PathCompleter::validate_directory)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    DimensionsEngine::new(config).create(name, base_dir)?;
+    println!("Created dimension: {}", name);
+    Ok(())
+}
+
+/// `dimensions switch <name>`: same as picking it in the TUI, without drawing anything
+pub fn switch(config: DimensionConfig, name: &str) -> Result<()> {
+    let target = DimensionsEngine::new(config).switch(name, None)?;
+    switch_or_attach(target)
+}
+
+/// `dimensions next` / `dimensions prev`: step from the current session's dimension to its
+/// neighbour without any UI. Run from a tmux binding (`run-shell`), the client that pressed
+/// it is switched; outside tmux the neighbour of nothing is the first (or last) dimension.
+pub fn cycle(config: DimensionConfig, forward: bool) -> Result<()> {
+    let current = Tmux::is_inside_session().then(Tmux::get_current_session).and_then(Result::ok);
+    let Some(name) = config.neighbour(current.as_deref(), forward).map(|d| d.name.clone()) else {
+        anyhow::bail!("No dimensions configured");
+    };
+    let target = DimensionsEngine::new(config).switch(&name, None)?;
+    switch_or_attach(target)
+}
+
+/// `dimensions undelete [<name>]`: like `u` in the TUI, but for any dimension still in the trash
+pub fn undelete(config: DimensionConfig, name: Option<&str>) -> Result<()> {
+    let name = DimensionsEngine::new(config).restore(name)?;
+    println!("Restored dimension: {}", name);
+    Ok(())
+}
+
+/// `dimensions here [--template <name>]`: like `g` in the TUI
+pub fn here(config: DimensionConfig, template: Option<&str>) -> Result<()> {
+    let template = template.map(str::to_string).or_else(|| config.here.template.clone());
+    let mut engine = DimensionsEngine::new(config);
+    let (name, created) = engine.here(&std::env::current_dir()?, template.as_deref())?;
+    if created {
+        eprintln!("Created dimension: {}", name);
+    }
+    let target = engine.switch(&name, None)?;
+    switch_or_attach(target)
+}
+
+/// `dimensions open dimensions://<name>[/<tab>]`. Without a terminal (a browser or
+/// launcher opening the link), the most recently used tmux client is switched instead.
+pub fn open(config: DimensionConfig, link: &str) -> Result<()> {
+    let link = uri::parse(link)?;
+    let target = DimensionsEngine::new(config).switch(&link.dimension, link.tab.as_deref())?;
+
+    if Tmux::is_inside_session() || std::io::stdin().is_terminal() {
+        return switch_or_attach(target);
+    }
+    switch_recent_client(target)
+}
+
+/// Tell the user what `switch` warned about, then switch this client to `target` (or attach)
+fn switch_or_attach(target: SwitchTarget) -> Result<()> {
+    if let Some(warning) = &target.warning {
+        eprintln!("{}", warning);
+    }
+    Tmux::switch_or_attach(&target.session, target.window, target.detach_others)
+}
+
+/// Point the most recently used tmux client at `target`, for callers without a terminal of their own
+pub fn switch_recent_client(target: SwitchTarget) -> Result<()> {
+    if let Some(warning) = &target.warning {
+        eprintln!("{}", warning);
+    }
+    let Some(tty) = Tmux::most_recent_client() else {
+        anyhow::bail!("No tmux client to switch; attach to tmux first or open the link from a terminal");
+    };
+    let target = match target.window {
+        Some(window) => format!("{}:{}", target.session, window),
+        None => target.session,
+    };
+    Tmux::switch_client(&tty, &target)
+}
+
+/// `dimensions hibernate <name>`: snapshot the session, then kill it
+pub fn hibernate(config: DimensionConfig, name: &str) -> Result<()> {
+    let Some(name) = config.find(name).map(|d| d.name.clone()) else {
+        anyhow::bail!("Dimension '{}' not found", name);
+    };
+    let (snapshot, teardown_error) = DimensionsEngine::new(config).hibernate(&name)?;
+    if let Some(err) = teardown_error {
+        eprintln!("'{}': {}", name, err);
+    }
+    let panes: usize = snapshot.windows.iter().map(|w| w.panes.len()).sum();
+    println!("Hibernated '{}' ({} windows, {} panes)", name, snapshot.windows.len(), panes);
+    Ok(())
+}
+
+/// `dimensions thaw <name>`: bring a hibernated session back without switching to it
+pub fn thaw(config: DimensionConfig, name: &str) -> Result<()> {
+    let Some(name) = config.find(name).map(|d| d.name.clone()) else {
+        anyhow::bail!("Dimension '{}' not found", name);
+    };
+    let name = name.as_str();
+    if Tmux::session_exists(name) {
+        anyhow::bail!("'{}' is already running", name);
+    }
+    if !DimensionsEngine::new(config).thaw(name)? {
+        anyhow::bail!("'{}' isn't hibernated", name);
+    }
+    println!("Thawed '{}'", name);
+    Ok(())
+}
+
+/// `dimensions delete <name> [--yes]`
+pub fn delete(config: DimensionConfig, name: &str, yes: bool) -> Result<()> {
+    let Some(name) = config.find(name).map(|d| d.name.clone()) else {
+        anyhow::bail!("Dimension '{}' not found", name);
+    };
+    let name = name.as_str();
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to delete without confirmation; pass --yes");
+        }
+        let running = DimensionsEngine::new(config.clone()).running_programs(name);
+        if !running.is_empty() {
+            eprintln!("'{}' is still running: {}", name, running.join(", "));
+        }
+        eprint!("Delete dimension '{}' and kill its session? [y/N] ", name);
+        std::io::stderr().flush().ok();
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).ok();
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    match DimensionsEngine::new(config).delete(name)? {
+        Some(err) => println!("Deleted dimension: {} ({})", name, err),
+        None => println!("Deleted dimension: {}", name),
+    }
+    Ok(())
+}
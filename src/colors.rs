@@ -0,0 +1,63 @@
+use crate::dimension::{ColorSettings, Dimension};
+use ratatui::style::Color;
+use std::str::FromStr;
+
+const DEFAULT_PALETTE: [&str; 8] = [
+    "cyan", "magenta", "yellow", "blue", "lightgreen", "lightred", "lightcyan", "lightmagenta",
+];
+
+/// Group or first tag, whichever identifies the dimension's color
+fn color_key(dimension: &Dimension) -> Option<&str> {
+    dimension
+        .group
+        .as_deref()
+        .or_else(|| dimension.tags.first().map(|t| t.as_str()))
+        .filter(|k| !k.is_empty())
+}
+
+/// FNV-1a; stable across runs and platforms so a group keeps its color
+fn stable_hash(key: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in key.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Configured color spec for a dimension, or None when it has no group/tag
+pub fn dimension_color(settings: &ColorSettings, dimension: &Dimension) -> Option<String> {
+    let key = color_key(dimension)?;
+    if let Some(color) = settings.groups.get(key) {
+        return Some(color.clone());
+    }
+
+    let index = |len: usize| (stable_hash(key) % len as u64) as usize;
+    if settings.palette.is_empty() {
+        Some(DEFAULT_PALETTE[index(DEFAULT_PALETTE.len())].to_string())
+    } else {
+        Some(settings.palette[index(settings.palette.len())].clone())
+    }
+}
+
+/// Parse a color spec for the TUI
+pub fn to_ratatui(spec: &str) -> Option<Color> {
+    Color::from_str(spec.trim()).ok()
+}
+
+/// Convert a color spec to tmux syntax (`colour208` for indices, `bright*` for light colors)
+pub fn to_tmux(spec: &str) -> String {
+    let spec = spec.trim().to_lowercase();
+    if let Ok(index) = spec.parse::<u8>() {
+        return format!("colour{}", index);
+    }
+    match spec.as_str() {
+        "gray" | "grey" => "white".to_string(),
+        "darkgray" | "darkgrey" => "brightblack".to_string(),
+        "white" => "brightwhite".to_string(),
+        _ => match spec.strip_prefix("light") {
+            Some(base) => format!("bright{}", base),
+            None => spec,
+        },
+    }
+}
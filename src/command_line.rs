@@ -0,0 +1,144 @@
+//! The `:` command line in the TUI: ex-style commands (`:switch web`, `:new api`,
+//! `:rename old new`, `:delete`, `:3`) and what Tab completes in them.
+
+use crate::path_completion::PathCompleter;
+
+/// Command names, for completion; each also works abbreviated to a unique prefix
+pub const COMMANDS: &[&str] = &["delete", "new", "quit", "rename", "switch"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandLine {
+    /// `:switch <dimension> [tab]`
+    Switch { dimension: String, tab: Option<String> },
+    /// `:new <name> [dir]`
+    New { name: String, dir: Option<String> },
+    /// `:rename <new>` for the selected dimension, or `:rename <old> <new>`
+    Rename { from: Option<String>, to: String },
+    /// `:delete [dimension]`, confirmed like `d`
+    Delete { dimension: Option<String> },
+    /// `:<n>`: switch to the selected dimension's tab numbered `n`
+    Tab(usize),
+    Quit,
+}
+
+/// The command name `word` abbreviates, if exactly one
+fn command_name(word: &str) -> Result<&'static str, String> {
+    if let Some(exact) = COMMANDS.iter().find(|c| **c == word) {
+        return Ok(exact);
+    }
+    let matches: Vec<&&str> = COMMANDS.iter().filter(|c| c.starts_with(word)).collect();
+    match matches.as_slice() {
+        [only] => Ok(only),
+        [] => Err(format!("Unknown command '{}' (try {})", word, COMMANDS.join(", "))),
+        _ => Err(format!("Ambiguous command '{}'", word)),
+    }
+}
+
+pub fn parse(input: &str) -> Result<CommandLine, String> {
+    let input = input.trim().trim_start_matches(':').trim_start();
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let Some((first, args)) = words.split_first() else {
+        return Err("Type a command, e.g. switch <dimension>".to_string());
+    };
+    if let Ok(n) = first.parse::<usize>() {
+        return Ok(CommandLine::Tab(n));
+    }
+
+    let name = command_name(first)?;
+    let arg = |i: usize| args.get(i).map(|s| s.to_string());
+    let command = match (name, args.len()) {
+        ("switch", 1 | 2) => CommandLine::Switch { dimension: args[0].to_string(), tab: arg(1) },
+        ("new", 1) => CommandLine::New { name: args[0].to_string(), dir: None },
+        // Directories may contain spaces, so everything after the name is the directory
+        ("new", _) if args.len() > 1 => {
+            let dir = input.split_once(char::is_whitespace).and_then(|(_, rest)| {
+                rest.trim_start().split_once(char::is_whitespace).map(|(_, dir)| dir.trim().to_string())
+            });
+            CommandLine::New { name: args[0].to_string(), dir }
+        }
+        ("rename", 1) => CommandLine::Rename { from: None, to: args[0].to_string() },
+        ("rename", 2) => CommandLine::Rename { from: arg(0), to: args[1].to_string() },
+        ("delete", 0 | 1) => CommandLine::Delete { dimension: arg(0) },
+        ("quit", 0) => CommandLine::Quit,
+        ("switch", _) => return Err("Usage: switch <dimension> [tab]".to_string()),
+        ("new", _) => return Err("Usage: new <name> [dir]".to_string()),
+        ("rename", _) => return Err("Usage: rename [old] <new>".to_string()),
+        ("delete", _) => return Err("Usage: delete [dimension]".to_string()),
+        _ => return Err(format!("'{}' takes no arguments", name)),
+    };
+    Ok(command)
+}
+
+/// Completions for the word being typed: `(head, candidates, common_prefix)`, where `head` is
+/// the input before that word. Command names come first, then dimension names where a
+/// command takes one, or directories for `new`'s second argument.
+pub fn complete(input: &str, dimensions: &[String]) -> (String, Vec<String>, String) {
+    let start = input.rfind(' ').map_or(0, |i| i + 1);
+    let (head, partial) = input.split_at(start);
+    let before: Vec<&str> = head.split_whitespace().collect();
+
+    let mut candidates: Vec<String> = match before.as_slice() {
+        [] => COMMANDS.iter().map(|c| c.to_string()).collect(),
+        [command, rest @ ..] => match (command_name(command), rest.len()) {
+            (Ok("switch" | "rename" | "delete"), 0) => dimensions.to_vec(),
+            (Ok("new"), 1) => {
+                let (candidates, common_prefix) = PathCompleter::complete_directory(partial);
+                return (head.to_string(), candidates, common_prefix);
+            }
+            _ => vec![],
+        },
+    };
+    candidates.retain(|c| c.starts_with(partial));
+    candidates.sort();
+    let common_prefix = PathCompleter::common_prefix(&candidates);
+    (head.to_string(), candidates, common_prefix)
+}
+
+/// What follows a completed word: a slash after a directory, so Tab can keep descending,
+/// otherwise a space for the next argument
+pub fn separator(candidate: &str) -> &'static str {
+    if candidate.contains('/') { "/" } else { " " }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_and_abbreviations() {
+        assert_eq!(
+            parse(":switch web server"),
+            Ok(CommandLine::Switch { dimension: "web".to_string(), tab: Some("server".to_string()) })
+        );
+        assert_eq!(parse("s web"), Ok(CommandLine::Switch { dimension: "web".to_string(), tab: None }));
+        assert_eq!(
+            parse("new api ~/code/my api"),
+            Ok(CommandLine::New { name: "api".to_string(), dir: Some("~/code/my api".to_string()) })
+        );
+        assert_eq!(
+            parse(": new api /tmp/x"),
+            Ok(CommandLine::New { name: "api".to_string(), dir: Some("/tmp/x".to_string()) })
+        );
+        assert_eq!(
+            parse(": new üa x"),
+            Ok(CommandLine::New { name: "üa".to_string(), dir: Some("x".to_string()) })
+        );
+        assert_eq!(parse("ren old new"), Ok(CommandLine::Rename { from: Some("old".to_string()), to: "new".to_string() }));
+        assert_eq!(parse("delete"), Ok(CommandLine::Delete { dimension: None }));
+        assert_eq!(parse("3"), Ok(CommandLine::Tab(3)));
+        assert_eq!(parse("q"), Ok(CommandLine::Quit));
+        assert!(parse("switch").is_err());
+        assert!(parse("frobnicate").is_err());
+    }
+
+    #[test]
+    fn completes_commands_then_dimensions() {
+        let dimensions = vec!["web".to_string(), "api".to_string(), "website".to_string()];
+        assert_eq!(complete("re", &dimensions), ("".to_string(), vec!["rename".to_string()], "rename".to_string()));
+        assert_eq!(
+            complete("switch we", &dimensions),
+            ("switch ".to_string(), vec!["web".to_string(), "website".to_string()], "web".to_string())
+        );
+        assert!(complete("quit x", &dimensions).1.is_empty());
+    }
+}
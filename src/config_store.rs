@@ -0,0 +1,287 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
+
+/// Give up waiting for another instance's lock after this long
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+
+/// A lock left behind by a crashed instance is ignored after this long
+const STALE_LOCK: Duration = Duration::from_secs(10);
+
+/// Exclusive lock on the config file, held while reading and rewriting it.
+/// Two popups on different tmux clients may save at the same time.
+pub struct ConfigLock {
+    path: PathBuf,
+}
+
+impl ConfigLock {
+    pub fn acquire(config_path: &Path) -> Result<Self> {
+        let path = config_path.with_extension("toml.lock");
+        let started = SystemTime::now();
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed().unwrap_or_default() >= LOCK_WAIT {
+                        anyhow::bail!(
+                            "Config is locked by another dimensions instance (remove {} if it is stale)",
+                            path.display()
+                        );
+                    }
+                    thread::sleep(Duration::from_millis(20));
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age >= STALE_LOCK)
+}
+
+/// Parse config TOML into the JSON value model the merge below works on
+pub fn toml_to_value(contents: &str) -> Result<Value> {
+    let table: toml::Table = toml::from_str(contents)?;
+    Ok(serde_json::to_value(table)?)
+}
+
+/// `config` as the merge sees it once written: `None` fields absent rather than null
+pub fn to_value<T: Serialize>(config: &T) -> Result<Value> {
+    toml_to_value(&toml::to_string(config)?)
+}
+
+/// Serialize `config` as TOML. Whatever survives from `previous` (the file as it is on
+/// disk) keeps its comments and formatting; only changed values are rewritten.
+pub fn to_toml<T: Serialize>(config: &T, previous: Option<&str>) -> Result<String> {
+    let fresh: DocumentMut = toml::to_string_pretty(config)?.parse()?;
+    let Some(mut doc) = previous.and_then(|p| p.parse::<DocumentMut>().ok()) else {
+        return Ok(fresh.to_string());
+    };
+    sync_table(doc.as_table_mut(), fresh.as_table());
+    let mut position = 1;
+    renumber(doc.as_table_mut(), &mut position);
+    Ok(doc.to_string())
+}
+
+fn sync_table(old: &mut Table, new: &Table) {
+    let stale: Vec<String> = old
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !new.contains_key(key))
+        .collect();
+    for key in stale {
+        old.remove(&key);
+    }
+    for (key, item) in new.iter() {
+        match old.get_mut(key) {
+            Some(existing) => sync_item(existing, item),
+            None => {
+                old.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+fn sync_item(old: &mut Item, new: &Item) {
+    match (old, new) {
+        (Item::Table(old), Item::Table(new)) => sync_table(old, new),
+        (Item::ArrayOfTables(old), Item::ArrayOfTables(new)) => {
+            // Entries are matched by name (dimensions, tabs, tasks), else by position
+            let previous: Vec<Table> = old.iter().cloned().collect();
+            let mut synced = ArrayOfTables::new();
+            for (i, table) in new.iter().enumerate() {
+                let matching = match table_name(table) {
+                    Some(name) => previous.iter().find(|t| table_name(t) == Some(name)),
+                    None => previous.get(i),
+                };
+                synced.push(match matching {
+                    Some(matching) => {
+                        let mut kept = matching.clone();
+                        sync_table(&mut kept, table);
+                        kept
+                    }
+                    None => table.clone(),
+                });
+            }
+            *old = synced;
+        }
+        (Item::Value(old), Item::Value(new)) => {
+            if bare(old) != bare(new) {
+                let decor = old.decor().clone();
+                *old = new.clone();
+                *old.decor_mut() = decor;
+            }
+        }
+        (old, new) => *old = new.clone(),
+    }
+}
+
+fn table_name(table: &Table) -> Option<&str> {
+    table.get("name").and_then(Item::as_str)
+}
+
+/// A value as written, without surrounding whitespace and comments
+fn bare(value: &toml_edit::Value) -> String {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+    value.to_string()
+}
+
+/// Tables are written in position order; after syncing, that has to follow the
+/// document order again (moved dimensions, new tabs)
+fn renumber(table: &mut Table, position: &mut usize) {
+    for (_, item) in table.iter_mut() {
+        match item {
+            Item::Table(table) => {
+                table.set_position(*position);
+                *position += 1;
+                renumber(table, position);
+            }
+            Item::ArrayOfTables(array) => {
+                for table in array.iter_mut() {
+                    table.set_position(*position);
+                    *position += 1;
+                    renumber(table, position);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Three-way merge of the config JSON: `base` is the file as we loaded it, `ours` what
+/// we want to write, `theirs` what another instance wrote since. Dimensions merge by
+/// name; anything only one side changed keeps that change, and ours wins when both did.
+pub fn merge(base: &Value, ours: &Value, theirs: &Value) -> Value {
+    let (Some(ours_map), Some(theirs_map)) = (ours.as_object(), theirs.as_object()) else {
+        return ours.clone();
+    };
+
+    let mut merged = theirs_map.clone();
+    let keys: Vec<&String> = ours_map.keys().chain(theirs_map.keys()).collect();
+    for key in keys {
+        let b = base.get(key);
+        let o = ours_map.get(key);
+        let t = theirs_map.get(key);
+
+        let value = if key == "dimensions" {
+            Some(merge_dimensions(
+                b.unwrap_or(&Value::Null),
+                o.unwrap_or(&Value::Null),
+                t.unwrap_or(&Value::Null),
+            ))
+        } else if o == b {
+            t.cloned()
+        } else {
+            o.cloned()
+        };
+
+        match value {
+            Some(v) => merged.insert(key.clone(), v),
+            None => merged.remove(key),
+        };
+    }
+    Value::Object(merged)
+}
+
+fn by_name<'a>(list: &'a Value, name: &str) -> Option<&'a Value> {
+    list.as_array()?
+        .iter()
+        .find(|d| d.get("name").and_then(Value::as_str) == Some(name))
+}
+
+fn names(list: &Value) -> Vec<&str> {
+    list.as_array()
+        .map(|a| a.iter().filter_map(|d| d.get("name").and_then(Value::as_str)).collect())
+        .unwrap_or_default()
+}
+
+fn merge_dimensions(base: &Value, ours: &Value, theirs: &Value) -> Value {
+    let mut merged = vec![];
+
+    // Their order first, so their additions and reordering survive
+    for name in names(theirs) {
+        let (b, o, t) = (by_name(base, name), by_name(ours, name), by_name(theirs, name));
+        let keep = match (b, o) {
+            // We deleted it: honor that unless they changed it meanwhile
+            (Some(b), None) => (t != Some(b)).then(|| t.cloned()).flatten(),
+            // They added it
+            (None, None) => t.cloned(),
+            (b, Some(o)) if b == Some(o) => t.cloned(),
+            (_, Some(o)) => Some(o.clone()),
+        };
+        merged.extend(keep);
+    }
+
+    // Ours not in theirs: our additions, or ones they deleted (kept only if we changed them)
+    for name in names(ours) {
+        if by_name(theirs, name).is_some() {
+            continue;
+        }
+        let (b, o) = (by_name(base, name), by_name(ours, name));
+        if b.is_none() || b != o {
+            merged.extend(o.cloned());
+        }
+    }
+
+    Value::Array(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rewriting_keeps_comments_of_unchanged_entries() {
+        let previous = r#"# my workspaces
+[ui]
+tick_ms = 500 # slower
+
+# the backend
+[[dimensions]]
+name = "api"
+tabs = []
+"#;
+        let config = json!({
+            "ui": { "tick_ms": 250 },
+            "dimensions": [
+                { "name": "web", "tabs": [] },
+                { "name": "api", "tabs": [] },
+            ],
+        });
+
+        let written = to_toml(&config, Some(previous)).unwrap();
+        assert!(written.starts_with("# my workspaces\n[ui]\ntick_ms = 250 # slower\n"));
+        assert!(written.contains("# the backend\n[[dimensions]]\nname = \"api\""));
+        assert!(written.find("\"web\"").unwrap() < written.find("\"api\"").unwrap());
+        assert_eq!(toml_to_value(&written).unwrap(), config);
+    }
+}
@@ -1,25 +1,72 @@
-use anyhow::Result;
+use crate::config_store::{self, ConfigLock};
+use crate::keymap::{Action, KeySpec};
+use crate::machine::{self, AppliedOverride, MachineOverride};
+use crate::paths;
+use crate::scope::Scope;
+use crate::ssh_config;
+use crate::watch;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents a single tab (tmux window) in a dimension
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Tab {
     pub name: String,
     pub command: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<PathBuf>,
+
+    // Named commands for a task tab: picking the tab offers these instead of one fixed command
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<Task>,
+
+    // Glob of files (relative to the tab's directory) whose changes rerun the command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch: Option<String>,
 }
 
 impl Tab {
     pub fn new(name: String, command: Option<String>, working_dir: Option<PathBuf>) -> Self {
-        Self { name, command, working_dir }
+        Self { name, command, working_dir, tasks: vec![], watch: None }
+    }
+
+    /// The command to launch in the tab's window, rerun on changes when `watch` is set
+    pub fn launch_command(&self) -> Option<String> {
+        let command = self.command.as_ref()?;
+        Some(match &self.watch {
+            Some(glob) => watch::wrap_command(glob, command),
+            None => command.clone(),
+        })
+    }
+
+    pub fn is_task_tab(&self) -> bool {
+        !self.tasks.is_empty()
     }
 }
 
+/// One entry of a task tab's menu (e.g. build, test, lint)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Task {
+    pub name: String,
+    pub command: String,
+}
+
+/// A named URL kept with a dimension (ticket, pull request, staging site)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamedLink {
+    pub name: String,
+    pub url: String,
+}
+
 /// Represents a dimension (tmux session with multiple tabs)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Dimension {
     pub name: String,
 
@@ -30,14 +77,203 @@ pub struct Dimension {
     // Tabs persisted in config (used as a template when creating a tmux session).
     #[serde(rename = "tabs", default)]
     pub configured_tabs: Vec<Tab>,
+
+    // Host (from ~/.ssh/config) the tabs connect to: each runs `ssh <host>`, and a tab's
+    // command runs on the host instead of locally
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_host: Option<String>,
+
+    // Host whose tmux runs the session: it is created there over ssh (base_dir and tab
+    // directories are paths on that host) and switching attaches with `ssh -t <host> tmux attach`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+
+    // Tab to open when switching without picking one: a tab name, or `LAST_ACTIVE_TAB` for
+    // the window with the most recent activity. The first tab when unset or not found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_tab: Option<String>,
+
+    // Shell command run once in base_dir when the tmux session is first created,
+    // before any windows are set up (e.g. `docker compose up -d`), bounded by
+    // `setup_timeout_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup_timeout_secs: Option<u64>,
+
+    // Shell command run in base_dir when dimensions kills this session
+    // (e.g. stop containers), bounded by `teardown_timeout_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teardown: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teardown_timeout_secs: Option<u64>,
+
+    // Lifecycle hooks, run in the background in base_dir after the global ones in `[hooks]`:
+    // when the dimension is created, switched to, left (switching elsewhere or detaching
+    // with `q`) and deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_attach: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_detach: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_delete: Option<String>,
+
+    // Readiness check run after a fresh session is created; switching waits for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<WaitFor>,
+
+    // Grouping used for color coding (group wins over tags)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    // Other names it answers to in search and on the command line (ticket numbers, codenames)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    // Links shown under its tabs and opened with `O`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<NamedLink>,
+
+    // Prefix-free tmux bindings active only in this dimension's session, key -> action
+    // ("next-tab", "previous-tab", "dimensions", "run:<command>" or a tmux command)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, String>,
+
+    // Hide tab names, commands, paths and the preview until revealed with `v`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private: bool,
+
+    // Replaces `events.status_command` for this dimension; empty to skip it here
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_command: Option<String>,
 }
 
+/// Condition that must hold before a freshly created dimension counts as usable.
+/// When both `port` and `command` are set, both must succeed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaitFor {
+    // TCP port that must accept connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    // Host for the port check (default 127.0.0.1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    // Shell command that must exit 0 (run in base_dir)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    // Give up after this long (default 60s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl WaitFor {
+    pub fn describe(&self) -> String {
+        let mut parts = vec![];
+        if let Some(port) = self.port {
+            parts.push(format!("port {}", port));
+        }
+        if let Some(cmd) = &self.command {
+            parts.push(format!("`{}`", cmd));
+        }
+        if parts.is_empty() {
+            "nothing".to_string()
+        } else {
+            parts.join(" + ")
+        }
+    }
+}
+
+/// Setup commands get this long to finish unless configured otherwise
+pub const DEFAULT_SETUP_TIMEOUT_SECS: u64 = 120;
+
+/// Teardown commands get this long to finish unless configured otherwise
+pub const DEFAULT_TEARDOWN_TIMEOUT_SECS: u64 = 30;
+
+/// `default_tab` value that picks whichever window was active last
+pub const LAST_ACTIVE_TAB: &str = "last_active";
+
 impl Dimension {
     pub fn new_with_base_dir(name: String, base_dir: Option<PathBuf>) -> Self {
         Self {
             name,
             base_dir,
             configured_tabs: vec![],
+            ssh_host: None,
+            remote: None,
+            default_tab: None,
+            setup: None,
+            setup_timeout_secs: None,
+            teardown: None,
+            teardown_timeout_secs: None,
+            on_create: None,
+            on_attach: None,
+            on_detach: None,
+            on_delete: None,
+            wait_for: None,
+            group: None,
+            tags: vec![],
+            aliases: vec![],
+            links: vec![],
+            keys: BTreeMap::new(),
+            private: false,
+            status_command: None,
+        }
+    }
+
+    /// `tab` as its window is started: on an ssh host dimension its command is run through `ssh`
+    pub fn launched_tab(&self, tab: &Tab) -> Tab {
+        let mut tab = tab.clone();
+        if let Some(host) = &self.ssh_host {
+            tab.command = Some(ssh_config::ssh_command(host, tab.command.as_deref()));
+            // Files to watch would be on the other machine
+            tab.watch = None;
+        }
+        tab
+    }
+
+    /// Configured directories (base_dir, tab working dirs) that no longer exist
+    pub fn missing_paths(&self) -> Vec<&Path> {
+        let mut missing: Vec<&Path> = vec![];
+        let dirs = self
+            .base_dir
+            .iter()
+            .chain(self.configured_tabs.iter().filter_map(|t| t.working_dir.as_ref()));
+        for dir in dirs {
+            if !dir.is_dir() && !missing.contains(&dir.as_path()) {
+                missing.push(dir.as_path());
+            }
+        }
+        missing
+    }
+
+    /// Point base_dir at `new_dir`. Tab working dirs under the old base are moved along;
+    /// other missing tab dirs fall back to the new base.
+    pub fn repoint_base_dir(&mut self, new_dir: PathBuf) {
+        let old_base = self.base_dir.replace(new_dir.clone());
+        for tab in &mut self.configured_tabs {
+            let Some(dir) = tab.working_dir.as_ref() else {
+                continue;
+            };
+            let rebased = old_base
+                .as_ref()
+                .and_then(|old| dir.strip_prefix(old).ok())
+                .map(|rest| new_dir.join(rest));
+            match rebased {
+                Some(path) => tab.working_dir = Some(path),
+                None if !dir.is_dir() => tab.working_dir = Some(new_dir.clone()),
+                None => {}
+            }
         }
     }
 
@@ -54,15 +290,626 @@ impl Dimension {
     }
 }
 
+/// Result of `DimensionConfig::save`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveOutcome {
+    Written,
+    // Another instance saved meanwhile; the config now includes its changes too
+    Merged,
+}
+
+/// A config file that failed to parse, with enough context for a friendly diagnostic
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    // Dotted path to the offending field, e.g. `dimensions[2].tabs[0].name`
+    pub field: Option<String>,
+    pub message: String,
+    // The offending source line, for the snippet under the message
+    pub source_line: Option<String>,
+}
+
+impl ConfigError {
+    fn new(field: String, line: usize, column: usize, message: String, contents: &str) -> Self {
+        let field = if field.is_empty() || field == "." { None } else { Some(field) };
+        let source_line = line
+            .checked_sub(1)
+            .and_then(|i| contents.lines().nth(i))
+            .map(|l| l.to_string());
+
+        Self { path: None, line, column, field, message, source_line }
+    }
+
+    fn from_toml(err: serde_path_to_error::Error<toml::de::Error>, contents: &str) -> Self {
+        let field = err.path().to_string();
+        let inner = err.into_inner();
+
+        // toml reports a byte span; turn its start into a 1-based line and column
+        let (line, column) = match inner.span() {
+            Some(span) => {
+                let before = &contents[..span.start.min(contents.len())];
+                let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+                (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+            }
+            None => (0, 0),
+        };
+        Self::new(field, line, column, inner.message().to_string(), contents)
+    }
+
+    fn from_json(err: serde_path_to_error::Error<serde_json::Error>, contents: &str) -> Self {
+        let field = err.path().to_string();
+        let inner = err.into_inner();
+
+        // serde_json appends " at line X column Y"; we report the location separately
+        let full = inner.to_string();
+        let message = full
+            .rsplit_once(" at line ")
+            .map(|(msg, _)| msg.to_string())
+            .unwrap_or(full);
+        Self::new(field, inner.line(), inner.column(), message, contents)
+    }
+
+    fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Multi-line diagnostic: location, message, offending field and a caret snippet
+    pub fn diagnostic(&self) -> String {
+        let location = match &self.path {
+            Some(path) => format!("{}:{}:{}", path.display(), self.line, self.column),
+            None => format!("line {} column {}", self.line, self.column),
+        };
+
+        let mut out = format!("Invalid config at {}\n  {}", location, self.message);
+        if let Some(field) = &self.field {
+            out.push_str(&format!("\n  field: {}", field));
+        }
+        if let Some(src) = &self.source_line {
+            let gutter = format!("{} | ", self.line);
+            let caret_pad = " ".repeat(gutter.len() + self.column.saturating_sub(1));
+            out.push_str(&format!("\n\n  {}{}\n  {}^", gutter, src, caret_pad));
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "{} (at `{}`, line {} column {})", self.message, field, self.line, self.column),
+            None => write!(f, "{} (line {} column {})", self.message, self.line, self.column),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Order the dimensions list is shown in when the TUI opens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DimensionSort {
+    // As written in the config file
+    #[default]
+    Config,
+    // Alphabetical by name
+    Name,
+    // Dimensions with a live tmux session first, otherwise config order
+    Active,
+}
+
+/// Which pane has focus when the TUI opens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupFocus {
+    #[default]
+    Dimensions,
+    Tabs,
+    Search,
+}
+
+/// `startup` section of the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StartupSettings {
+    pub sort: DimensionSort,
+    pub focus: StartupFocus,
+}
+
+impl StartupSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `attach` section of the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AttachSettings {
+    // Always detach other clients when switching (like `tmux attach -d`)
+    pub detach_others: bool,
+}
+
+impl AttachSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// What `Esc` does in normal mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EscapeBehavior {
+    // Clear the message / tab selection first, close the popup once nothing is left
+    #[default]
+    Clear,
+    // Always close the popup right away
+    Close,
+}
+
+/// How deleting a dimension whose session is running is confirmed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmStyle {
+    // Press y
+    #[default]
+    YesNo,
+    // Type the dimension's name and press Enter
+    TypeName,
+}
+
+/// Which glyphs mark a dimension's detected project type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IconStyle {
+    #[default]
+    Emoji,
+    // Nerd Font devicons; needs a patched terminal font
+    NerdFont,
+    None,
+}
+
+/// Algorithm behind `/` search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchAlgorithm {
+    #[default]
+    Skim,
+    FzfV2,
+    Substring,
+    Regex,
+}
+
+/// `ui` section of the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiSettings {
+    // How often the event loop wakes up to check for background results, in ms
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_ms: Option<u64>,
+    #[serde(skip_serializing_if = "is_default_escape")]
+    pub escape: EscapeBehavior,
+    #[serde(skip_serializing_if = "is_default_confirm")]
+    pub confirm_delete: ConfirmStyle,
+    // Start in presentation mode: names only, no commands, paths or previews
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub presentation: bool,
+    #[serde(skip_serializing_if = "is_default_icons")]
+    pub icons: IconStyle,
+    // Offer zoxide's directories first when completing paths; on when zoxide is installed unless false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoxide: Option<bool>,
+    #[serde(skip_serializing_if = "is_default_matcher")]
+    pub matcher: MatchAlgorithm,
+}
+
+fn is_default_escape(escape: &EscapeBehavior) -> bool {
+    *escape == EscapeBehavior::default()
+}
+
+fn is_default_confirm(confirm: &ConfirmStyle) -> bool {
+    *confirm == ConfirmStyle::default()
+}
+
+fn is_default_icons(icons: &IconStyle) -> bool {
+    *icons == IconStyle::default()
+}
+
+fn is_default_matcher(matcher: &MatchAlgorithm) -> bool {
+    *matcher == MatchAlgorithm::default()
+}
+
+/// Event loop tick unless `ui.tick_ms` says otherwise
+pub const DEFAULT_TICK_MS: u64 = 250;
+
+impl UiSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn tick(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.tick_ms.unwrap_or(DEFAULT_TICK_MS).max(10))
+    }
+}
+
+/// Where "open directory" shows a dimension's base directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenDirWith {
+    // The desktop file manager, via `open` (macOS) or `xdg-open`
+    #[default]
+    System,
+    // A terminal file manager in a new tmux window
+    Window,
+}
+
+/// `open_dir` section of the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OpenDirSettings {
+    pub with: OpenDirWith,
+
+    // Program run in the window for `window` (e.g. "yazi"); defaults to $FILE_MANAGER
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+impl OpenDirSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `discover` section of the config: where `D` and `dimensions discover` look for projects
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscoverSettings {
+    // Directories holding projects, like "~/code"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roots: Vec<String>,
+
+    // How many levels under each root to look (default 2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+}
+
+/// Directory levels searched for projects unless `discover.depth` says otherwise
+pub const DEFAULT_DISCOVER_DEPTH: usize = 2;
+
+impl DiscoverSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth.unwrap_or(DEFAULT_DISCOVER_DEPTH)
+    }
+}
+
+/// `here` section of the config: what `g` and `dimensions here` create
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HereSettings {
+    // Template for new dimensions, filled with the repo's folder name and path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+impl HereSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `tmux` section of the config: which server Dimensions controls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TmuxSettings {
+    // Like `tmux -L <name>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket_name: Option<String>,
+
+    // Like `tmux -S <path>`; wins over socket_name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket_path: Option<PathBuf>,
+
+    // Follow changes through a `tmux -C` connection instead of re-reading state every second;
+    // on unless false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub control_mode: Option<bool>,
+}
+
+impl TmuxSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `colors` section of the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorSettings {
+    // Colors assigned to groups/tags by hash; names ("cyan"), "#rrggbb" or 256-color indices ("208")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub palette: Vec<String>,
+
+    // Explicit color per group/tag, overriding the palette
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, String>,
+
+    // Also color the tmux status bar of each dimension's session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmux_status: Option<bool>,
+}
+
+impl ColorSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `tidy` section of the config: when dimensions count as stale for `dimensions gc`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TidySettings {
+    // Months without commits or file changes in the base directory (default 6)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_after_months: Option<u32>,
+
+    // Mention stale dimensions in the TUI about once a month (default true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<bool>,
+}
+
+impl TidySettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn months(&self) -> u32 {
+        self.stale_after_months.unwrap_or(6)
+    }
+}
+
+/// `snapshots` section of the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotSettings {
+    // Programs restarted when a snapshot is restored ("*" for any); a built-in list of editors,
+    // pagers and monitors when unset. Other panes come back as shells in the same directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restore_processes: Option<Vec<String>>,
+}
+
+impl SnapshotSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `events` section of the config: where switches, closed sessions and finished commands are sent
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventSettings {
+    // URL each event is POSTed to as JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    // MQTT topic each event is published to (with `mosquitto_pub`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mqtt_topic: Option<String>,
+
+    // MQTT broker (default localhost)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mqtt_host: Option<String>,
+
+    // Shell command run in the background on each switch, with `{dimension}` replaced by the
+    // dimension's name (e.g. to set a "currently working on" status)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_command: Option<String>,
+}
+
+impl EventSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.webhook.is_some() || self.mqtt_topic.is_some()
+    }
+}
+
+/// `hooks` section of the config: lifecycle commands run for every dimension, before its own
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HookSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_attach: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_detach: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_delete: Option<String>,
+}
+
+impl HookSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `popup` section of the config: the tmux popup Dimensions opens itself in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PopupSettings {
+    // Size as a percentage of the client ("80%") or in cells ("120")
+    pub width: String,
+    pub height: String,
+
+    // Position, as display-popup takes it: "C" (centered), "R", "P", "M", "W", "S" or a number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+
+    // Border lines (tmux 3.3+): single, rounded, double, heavy, simple, padded or none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+
+    // Title on the top border (tmux 3.3+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Default for PopupSettings {
+    fn default() -> Self {
+        Self {
+            width: "80%".to_string(),
+            height: "80%".to_string(),
+            x: None,
+            y: None,
+            border: None,
+            title: None,
+        }
+    }
+}
+
+impl PopupSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How long a deleted dimension stays in the trash
+pub const TRASH_DAYS: u64 = 7;
+
+/// A deleted dimension, kept in the config's `trash` for `TRASH_DAYS` so the deletion
+/// can be undone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrashedDimension {
+    // Seconds since the epoch
+    pub deleted_at: u64,
+    // Where it stood in the list
+    pub position: usize,
+    pub dimension: Dimension,
+}
+
 /// Configuration for all dimensions
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct DimensionConfig {
+    #[serde(default, skip_serializing_if = "StartupSettings::is_default")]
+    pub startup: StartupSettings,
+
+    #[serde(default, skip_serializing_if = "AttachSettings::is_default")]
+    pub attach: AttachSettings,
+
+    #[serde(default, skip_serializing_if = "TmuxSettings::is_default")]
+    pub tmux: TmuxSettings,
+
+    #[serde(default, skip_serializing_if = "UiSettings::is_default")]
+    pub ui: UiSettings,
+
+    #[serde(default, skip_serializing_if = "OpenDirSettings::is_default")]
+    pub open_dir: OpenDirSettings,
+
+    #[serde(default, skip_serializing_if = "HereSettings::is_default")]
+    pub here: HereSettings,
+
+    #[serde(default, skip_serializing_if = "DiscoverSettings::is_default")]
+    pub discover: DiscoverSettings,
+
+    #[serde(default, skip_serializing_if = "ColorSettings::is_default")]
+    pub colors: ColorSettings,
+
+    #[serde(default, skip_serializing_if = "SnapshotSettings::is_default")]
+    pub snapshots: SnapshotSettings,
+
+    #[serde(default, skip_serializing_if = "TidySettings::is_default")]
+    pub tidy: TidySettings,
+
+    #[serde(default, skip_serializing_if = "EventSettings::is_default")]
+    pub events: EventSettings,
+
+    #[serde(default, skip_serializing_if = "PopupSettings::is_default")]
+    pub popup: PopupSettings,
+
+    #[serde(default, skip_serializing_if = "HookSettings::is_default")]
+    pub hooks: HookSettings,
+
+    // Per-hostname overrides, resolved on load
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub machines: BTreeMap<String, MachineOverride>,
+
+    // Override applied for this machine (undone again when saving)
+    #[serde(skip)]
+    pub applied_machine: Option<AppliedOverride>,
+
+    // Named include/exclude filters, selected with `--scope <name>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scopes: BTreeMap<String, Scope>,
+
+    // Dimensions with `{{variable}}` placeholders that `T` creates new dimensions from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<Dimension>,
+
+    // Remapped keys for the main view, action -> key or list of keys
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<Action, KeySpec>,
+
+    // Dimensions filtered out by the launch scope, with their positions (restored on save)
+    #[serde(skip)]
+    pub scoped_out: Vec<(usize, Dimension)>,
+
+    // Label of the active launch scope, shown in the title bar
+    #[serde(skip)]
+    pub scope_label: Option<String>,
+
+    // Scope applied at launch, re-applied when a save reloads the file
+    #[serde(skip)]
+    active_scope: Option<Scope>,
+
+    // The file as last loaded or written, to detect saves from other instances
+    #[serde(skip)]
+    disk_base: Option<serde_json::Value>,
+
+    // Recently deleted dimensions, newest last
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trash: Vec<TrashedDimension>,
+
     pub dimensions: Vec<Dimension>,
 }
 
 impl Default for DimensionConfig {
     fn default() -> Self {
         Self {
+            startup: StartupSettings::default(),
+            attach: AttachSettings::default(),
+            tmux: TmuxSettings::default(),
+            ui: UiSettings::default(),
+            open_dir: OpenDirSettings::default(),
+            here: HereSettings::default(),
+            discover: DiscoverSettings::default(),
+            colors: ColorSettings::default(),
+            snapshots: SnapshotSettings::default(),
+            tidy: TidySettings::default(),
+            events: EventSettings::default(),
+            popup: PopupSettings::default(),
+            hooks: HookSettings::default(),
+            machines: BTreeMap::new(),
+            applied_machine: None,
+            scopes: BTreeMap::new(),
+            templates: Vec::new(),
+            keybindings: BTreeMap::new(),
+            scoped_out: vec![],
+            scope_label: None,
+            active_scope: None,
+            disk_base: None,
+            trash: vec![],
             dimensions: vec![],
         }
     }
@@ -71,40 +918,166 @@ impl Default for DimensionConfig {
 impl DimensionConfig {
     /// Get the config file path
     pub fn config_path() -> PathBuf {
-        let config_dir = dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("dimensions");
+        paths::config_dir().join("config.toml")
+    }
 
-        fs::create_dir_all(&config_dir).ok();
-        config_dir.join("config.json")
+    /// Where configs were kept before the switch to TOML
+    pub fn legacy_config_path() -> PathBuf {
+        paths::config_dir().join("config.json")
     }
 
-    /// Load configuration from disk
+    /// Load configuration from disk, migrating a JSON config first if that's all there is
     pub fn load() -> Result<Self> {
         let path = Self::config_path();
 
         if !path.exists() {
-            return Ok(Self::default());
+            let legacy = Self::legacy_config_path();
+            if !legacy.exists() {
+                return Ok(Self::default());
+            }
+            Self::migrate_json(&legacy, &path)?;
         }
 
-        let contents = fs::read_to_string(path)?;
-        let config: DimensionConfig = serde_json::from_str(&contents)?;
+        let contents = fs::read_to_string(&path)?;
+        let mut config = Self::parse(&contents).map_err(|e| e.with_path(path))?;
+        config.disk_base = config_store::toml_to_value(&contents).ok();
+        config.resolve_machine_overrides();
         Ok(config)
     }
 
-    /// Save configuration to disk
-    pub fn save(&self) -> Result<()> {
-        let path = Self::config_path();
-        let contents = serde_json::to_string_pretty(self)?;
+    /// Rewrite `legacy` (config.json) as TOML at `path`; the JSON file is kept as `config.json.bak`
+    fn migrate_json(legacy: &Path, path: &Path) -> Result<()> {
+        let contents = fs::read_to_string(legacy)?;
+        let config = Self::parse_json(&contents).map_err(|e| e.with_path(legacy.to_path_buf()))?;
 
-        // Atomic write: write to temp file first, then rename
-        let temp_path = path.with_extension("json.tmp");
-        fs::write(&temp_path, contents)?;
+        let temp_path = path.with_extension("toml.tmp");
+        fs::write(&temp_path, config_store::to_toml(&config, None)?)?;
         fs::rename(temp_path, path)?;
+        let backup = legacy.with_extension("json.bak");
+        fs::rename(legacy, &backup)?;
 
+        eprintln!(
+            "Migrated {} to {} (the old file is kept as {})",
+            legacy.display(),
+            path.display(),
+            backup.display()
+        );
         Ok(())
     }
 
+    /// Apply the `machines` entry for this host, if any
+    fn resolve_machine_overrides(&mut self) {
+        if self.machines.is_empty() {
+            return;
+        }
+        let Some(hostname) = machine::hostname() else {
+            return;
+        };
+        if let Some(overrides) = machine::find_override(&self.machines, &hostname).cloned() {
+            self.applied_machine = Some(machine::apply(hostname, &overrides, &mut self.dimensions));
+        }
+    }
+
+    /// Hide dimensions outside `scope` for this run; they are kept when saving
+    pub fn apply_scope(&mut self, scope: &Scope, label: String) {
+        if scope.is_empty() {
+            return;
+        }
+        let mut kept = vec![];
+        for (pos, dimension) in std::mem::take(&mut self.dimensions).into_iter().enumerate() {
+            if scope.matches(&dimension) {
+                kept.push(dimension);
+            } else {
+                self.scoped_out.push((pos, dimension));
+            }
+        }
+        self.dimensions = kept;
+        self.scope_label = Some(label);
+        self.active_scope = Some(scope.clone());
+    }
+
+    /// Whether `name` belongs to any dimension, including ones hidden by scope or machine overrides
+    pub fn name_in_use(&self, name: &str) -> bool {
+        self.find(name).is_some()
+            || self.scoped_out.iter().any(|(_, d)| d.name == name)
+            || self.applied_machine.as_ref().is_some_and(|a| a.hides(name))
+    }
+
+    /// Parse config TOML, reporting the location and field of the first problem
+    pub fn parse(contents: &str) -> std::result::Result<Self, ConfigError> {
+        let de = toml::Deserializer::new(contents);
+        serde_path_to_error::deserialize(de).map_err(|e| ConfigError::from_toml(e, contents))
+    }
+
+    /// Parse a pre-TOML config.json, for migrating it
+    pub fn parse_json(contents: &str) -> std::result::Result<Self, ConfigError> {
+        let de = &mut serde_json::Deserializer::from_str(contents);
+        serde_path_to_error::deserialize(de).map_err(|e| ConfigError::from_json(e, contents))
+    }
+
+    /// Save configuration to disk. If another instance saved since we loaded, both
+    /// sets of changes are merged and this config is reloaded from the result.
+    pub fn save(&mut self) -> Result<SaveOutcome> {
+        let path = Self::config_path();
+        let _lock = ConfigLock::acquire(&path)?;
+
+        let ours = self.disk_form();
+        let on_disk = fs::read_to_string(&path).ok();
+        let theirs: Option<serde_json::Value> = match &on_disk {
+            Some(contents) => Some(
+                config_store::toml_to_value(contents)
+                    .with_context(|| format!("{} was changed to invalid TOML; not overwriting it", path.display()))?,
+            ),
+            None => None,
+        };
+
+        let (to_write, outcome) = match theirs {
+            Some(theirs) if Some(&theirs) != self.disk_base.as_ref() => {
+                let base = self.disk_base.clone().unwrap_or(serde_json::Value::Null);
+                let merged = config_store::merge(&base, &config_store::to_value(&ours)?, &theirs);
+                let merged: DimensionConfig = serde_json::from_value(merged)
+                    .context("Failed to merge with changes saved by another dimensions instance")?;
+                (merged, SaveOutcome::Merged)
+            }
+            _ => (ours, SaveOutcome::Written),
+        };
+
+        // Atomic write: write to temp file first, then rename. Comments in the file survive.
+        let contents = config_store::to_toml(&to_write, on_disk.as_deref())?;
+        let temp_path = path.with_extension("toml.tmp");
+        fs::write(&temp_path, &contents)?;
+        fs::rename(temp_path, path)?;
+        self.disk_base = config_store::toml_to_value(&contents).ok();
+
+        if outcome == SaveOutcome::Merged {
+            self.reload_from(to_write);
+        }
+        Ok(outcome)
+    }
+
+    /// The full shared config as written to disk, not the scoped, machine-resolved view of it
+    fn disk_form(&self) -> DimensionConfig {
+        let mut on_disk = self.clone();
+        for (pos, dimension) in std::mem::take(&mut on_disk.scoped_out) {
+            let pos = pos.min(on_disk.dimensions.len());
+            on_disk.dimensions.insert(pos, dimension);
+        }
+        if let Some(applied) = on_disk.applied_machine.take() {
+            machine::unapply(&applied, &mut on_disk.dimensions);
+        }
+        on_disk
+    }
+
+    /// Replace this config with freshly merged file contents, keeping machine overrides and scope
+    fn reload_from(&mut self, mut fresh: DimensionConfig) {
+        fresh.disk_base = self.disk_base.take();
+        fresh.resolve_machine_overrides();
+        if let (Some(scope), Some(label)) = (self.active_scope.take(), self.scope_label.take()) {
+            fresh.apply_scope(&scope, label);
+        }
+        *self = fresh;
+    }
+
     /// Add a new dimension
     pub fn add_dimension(&mut self, dimension: Dimension) {
         self.dimensions.push(dimension);
@@ -119,9 +1092,141 @@ impl DimensionConfig {
         }
     }
 
+    /// Remove a dimension into the trash (at `now`, seconds since the epoch), dropping
+    /// entries older than `TRASH_DAYS` on the way. The trash is shared between machines,
+    /// so it keeps the dimension as written in the file, without this machine's overrides.
+    pub fn trash_dimension(&mut self, name: &str, now: u64) -> Option<Dimension> {
+        let position = self.dimensions.iter().position(|d| d.name == name)?;
+        let dimension = self.dimensions.remove(position);
+        let mut shared = dimension.clone();
+        if let Some(applied) = &self.applied_machine {
+            applied.unresolve(&mut shared);
+        }
+        self.trash.retain(|t| t.dimension.name != name && now.saturating_sub(t.deleted_at) < TRASH_DAYS * 86_400);
+        self.trash.push(TrashedDimension { deleted_at: now, position, dimension: shared });
+        Some(dimension)
+    }
+
+    /// Put a trashed dimension back where it was: `name`, or the most recently deleted one.
+    /// Returns its name.
+    pub fn restore_from_trash(&mut self, name: Option<&str>) -> Result<String> {
+        let index = match name {
+            Some(name) => self.trash.iter().rposition(|t| t.dimension.name == name),
+            None => self.trash.len().checked_sub(1),
+        };
+        let Some(index) = index else {
+            if self.trash.is_empty() {
+                anyhow::bail!("The trash is empty");
+            }
+            let trashed: Vec<&str> = self.trash.iter().rev().map(|t| t.dimension.name.as_str()).collect();
+            anyhow::bail!("'{}' isn't in the trash (it has: {})", name.unwrap_or_default(), trashed.join(", "));
+        };
+        let name = self.trash[index].dimension.name.clone();
+        if self.name_in_use(&name) {
+            anyhow::bail!("A dimension named '{}' exists again; rename it first", name);
+        }
+        let mut trashed = self.trash.remove(index);
+        if let Some(applied) = self.applied_machine.as_mut() {
+            applied.resolve(&mut trashed.dimension);
+        }
+        let position = trashed.position.min(self.dimensions.len());
+        self.dimensions.insert(position, trashed.dimension);
+        Ok(name)
+    }
+
     /// Get a dimension by name
     pub fn get_dimension(&self, name: &str) -> Option<&Dimension> {
         self.dimensions.iter().find(|d| d.name == name)
     }
 
+    /// A dimension by name, or else by one of its aliases (ignoring case, as in `proj-142`)
+    pub fn find(&self, name_or_alias: &str) -> Option<&Dimension> {
+        self.find_index(name_or_alias).map(|index| &self.dimensions[index])
+    }
+
+    pub fn find_index(&self, name_or_alias: &str) -> Option<usize> {
+        self.dimensions.iter().position(|d| d.name == name_or_alias).or_else(|| {
+            self.dimensions
+                .iter()
+                .position(|d| d.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name_or_alias)))
+        })
+    }
+
+    /// The dimension after (or before) `current` in config order, wrapping around. From
+    /// outside any dimension that's the first (or last) one.
+    pub fn neighbour(&self, current: Option<&str>, forward: bool) -> Option<&Dimension> {
+        let count = self.dimensions.len();
+        if count == 0 {
+            return None;
+        }
+        let index = match (current.and_then(|name| self.find_index(name)), forward) {
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        self.dimensions.get(index)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_and_prev_wrap_around() {
+        let config = DimensionConfig {
+            dimensions: ["api", "web", "docs"].iter().map(|n| Dimension::new_with_base_dir(n.to_string(), None)).collect(),
+            ..Default::default()
+        };
+        let neighbour = |current: Option<&str>, forward| config.neighbour(current, forward).map(|d| d.name.clone());
+
+        assert_eq!(neighbour(Some("api"), true).as_deref(), Some("web"));
+        assert_eq!(neighbour(Some("docs"), true).as_deref(), Some("api"));
+        assert_eq!(neighbour(Some("api"), false).as_deref(), Some("docs"));
+        assert_eq!(neighbour(Some("scratch"), true).as_deref(), Some("api"));
+        assert_eq!(neighbour(None, false).as_deref(), Some("docs"));
+        assert!(DimensionConfig::default().neighbour(None, true).is_none());
+    }
+
+    #[test]
+    fn trash_keeps_deleted_dimensions_for_a_week() {
+        let mut config = DimensionConfig {
+            dimensions: ["api", "web"].iter().map(|name| Dimension::new_with_base_dir(name.to_string(), None)).collect(),
+            ..Default::default()
+        };
+        let day = 86_400;
+
+        config.trash_dimension("api", 0).unwrap();
+        config.trash_dimension("web", TRASH_DAYS * day).unwrap();
+        // The week-old "api" was dropped when "web" went in
+        assert_eq!(config.trash.len(), 1);
+        assert!(config.restore_from_trash(Some("api")).unwrap_err().to_string().contains("has: web"));
+
+        config.dimensions.push(Dimension::new_with_base_dir("web".to_string(), None));
+        assert!(config.restore_from_trash(None).is_err());
+        config.dimensions.clear();
+        assert_eq!(config.restore_from_trash(None).unwrap(), "web");
+        assert_eq!(config.restore_from_trash(None).unwrap_err().to_string(), "The trash is empty");
+    }
+
+    #[test]
+    fn trash_keeps_paths_as_written_in_the_file() {
+        let mut config = DimensionConfig {
+            dimensions: vec![Dimension::new_with_base_dir("api".to_string(), Some("/home/me/api".into()))],
+            ..Default::default()
+        };
+        let overrides = crate::machine::MachineOverride {
+            path_prefixes: [("/home/me".into(), "/Users/me".into())].into(),
+            ..Default::default()
+        };
+        config.applied_machine = Some(crate::machine::apply("mac".to_string(), &overrides, &mut config.dimensions));
+
+        let removed = config.trash_dimension("api", 0).unwrap();
+        assert_eq!(removed.base_dir, Some(PathBuf::from("/Users/me/api")));
+        assert_eq!(config.trash[0].dimension.base_dir, Some(PathBuf::from("/home/me/api")));
+        config.restore_from_trash(None).unwrap();
+        assert_eq!(config.dimensions[0].base_dir, Some(PathBuf::from("/Users/me/api")));
+    }
 }
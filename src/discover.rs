@@ -0,0 +1,228 @@
+//! `dimensions discover [<dir>]`: find projects under a directory (or the configured
+//! `discover.roots`) that have no dimension yet and create dimensions for them in bulk.
+//! The TUI offers the same projects as suggestions under `D`.
+
+use crate::dimension::{Dimension, DimensionConfig};
+use crate::icons;
+use crate::path_completion::PathCompleter;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// Project directories under `root`, at most `depth` levels down: git repos and directories
+/// with a known project file. Hidden directories are skipped and projects aren't searched inside.
+pub fn scan(root: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut found = vec![];
+    walk(root, depth, &mut found);
+    found.sort();
+    found
+}
+
+fn walk(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if hidden || !path.is_dir() {
+            continue;
+        }
+        if is_project(&path) {
+            found.push(path);
+        } else if depth > 1 {
+            walk(&path, depth - 1, found);
+        }
+    }
+}
+
+fn is_project(dir: &Path) -> bool {
+    dir.join(".git").exists() || icons::detect(dir).is_some()
+}
+
+/// Projects that no dimension uses as its base directory
+pub fn undiscovered(config: &DimensionConfig, projects: Vec<PathBuf>) -> Vec<PathBuf> {
+    let known: HashSet<PathBuf> = config
+        .dimensions
+        .iter()
+        .filter_map(|d| d.base_dir.as_deref())
+        .map(canonical)
+        .collect();
+    projects.into_iter().filter(|p| !known.contains(&canonical(p))).collect()
+}
+
+/// Projects under the configured `discover.roots` that no dimension points at yet
+pub fn suggestions(config: &DimensionConfig) -> Vec<PathBuf> {
+    undiscovered(config, scan_roots(&config.discover.roots, config.discover.depth()))
+}
+
+/// `scan` of each root in turn, skipping roots that don't exist
+pub fn scan_roots(roots: &[String], depth: usize) -> Vec<PathBuf> {
+    let mut projects = vec![];
+    for root in roots {
+        let Ok(root) = PathCompleter::validate_directory(root) else {
+            continue;
+        };
+        for project in scan(&root, depth) {
+            if !projects.contains(&project) {
+                projects.push(project);
+            }
+        }
+    }
+    projects
+}
+
+/// The dimension whose base directory is `dir`
+pub fn owner<'a>(config: &'a DimensionConfig, dir: &Path) -> Option<&'a Dimension> {
+    let dir = canonical(dir);
+    config.dimensions.iter().find(|d| d.base_dir.as_deref().is_some_and(|base| canonical(base) == dir))
+}
+
+/// Top of the git repo containing `dir`, or `dir` itself outside of one
+pub fn repo_root(dir: &Path) -> PathBuf {
+    let dir = canonical(dir);
+    dir.ancestors().find(|d| d.join(".git").exists()).map(Path::to_path_buf).unwrap_or(dir)
+}
+
+fn canonical(dir: &Path) -> PathBuf {
+    dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Parse a selection like `1,3-5` (1-based) into indexes below `count`; `all` or empty picks everything
+pub fn parse_selection(input: &str, count: usize) -> Result<Vec<usize>> {
+    let input = input.trim();
+    if input.is_empty() || input.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+    let mut picked = vec![];
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (from, to) = part.split_once('-').unwrap_or((part, part));
+        let (Ok(from), Ok(to)) = (from.trim().parse::<usize>(), to.trim().parse::<usize>()) else {
+            anyhow::bail!("Not a number or range: '{}'", part);
+        };
+        if from == 0 || to > count || from > to {
+            anyhow::bail!("Out of range: '{}' (1-{})", part, count);
+        }
+        picked.extend((from - 1)..to);
+    }
+    picked.sort_unstable();
+    picked.dedup();
+    Ok(picked)
+}
+
+/// A name for the project at `dir`: its folder name, numbered if already taken
+pub fn unique_name(config: &DimensionConfig, dir: &Path) -> String {
+    let base = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| dir.display().to_string());
+    let mut name = base.clone();
+    let mut n = 2;
+    while config.name_in_use(&name) {
+        name = format!("{}-{}", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// `dimensions discover [<dir>] [--depth N] [--yes]`; without a directory, the `discover.roots`
+pub fn discover_cli(config: &mut DimensionConfig, dir: Option<&str>, depth: Option<usize>, yes: bool) -> Result<()> {
+    let (projects, searched) = match dir {
+        Some(dir) => {
+            let root = PathCompleter::validate_directory(dir).map_err(|e| anyhow::anyhow!(e))?;
+            let depth = depth.unwrap_or_else(|| config.discover.depth());
+            (undiscovered(config, scan(&root, depth)), root.display().to_string())
+        }
+        None => {
+            if config.discover.roots.is_empty() {
+                anyhow::bail!("Pass a directory, or list project directories in `discover.roots`");
+            }
+            let depth = depth.unwrap_or_else(|| config.discover.depth());
+            let projects = scan_roots(&config.discover.roots, depth);
+            (undiscovered(config, projects), config.discover.roots.join(", "))
+        }
+    };
+    if projects.is_empty() {
+        println!("No new projects under {}", searched);
+        return Ok(());
+    }
+
+    for (i, project) in projects.iter().enumerate() {
+        println!("{:>3}  {}", i + 1, project.display());
+    }
+    let picked = if yes {
+        (0..projects.len()).collect()
+    } else {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to create dimensions without confirmation; pass --yes");
+        }
+        eprint!("Create dimensions for which? [all, none, or e.g. 1,3-5] ");
+        std::io::stderr().flush().ok();
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if input.trim().eq_ignore_ascii_case("none") {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+        parse_selection(&input, projects.len())?
+    };
+
+    // Like `n`: named after the folder, rooted at it, opening with a single shell
+    for &i in &picked {
+        let name = unique_name(config, &projects[i]);
+        config.add_dimension(Dimension::new_with_base_dir(name.clone(), Some(projects[i].clone())));
+        println!("Created dimension: {}", name);
+    }
+    config.save()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn finds_projects_down_to_the_depth_without_entering_them() {
+        let root = std::env::temp_dir().join(format!("dimensions-discover-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("api/.git")).unwrap();
+        fs::create_dir_all(root.join("api/vendor/lib/.git")).unwrap();
+        fs::create_dir_all(root.join("clients/web")).unwrap();
+        fs::write(root.join("clients/web/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("deep/er/still")).unwrap();
+        fs::write(root.join("deep/er/still/Cargo.toml"), "").unwrap();
+        fs::create_dir_all(root.join(".cache/x/.git")).unwrap();
+
+        assert_eq!(scan(&root, 2), [root.join("api"), root.join("clients/web")]);
+        assert_eq!(scan(&root, 3).len(), 3);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(parse_selection("", 3).unwrap(), [0, 1, 2]);
+        assert_eq!(parse_selection("3, 1-2,2", 4).unwrap(), [0, 1, 2]);
+        assert!(parse_selection("0", 3).is_err());
+        assert!(parse_selection("2-5", 3).is_err());
+    }
+
+    #[test]
+    fn repo_root_walks_up_to_the_git_directory() {
+        let root = std::env::temp_dir().join(format!("dimensions-repo-root-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("web/.git")).unwrap();
+        fs::create_dir_all(root.join("web/src/components")).unwrap();
+        fs::create_dir_all(root.join("notes")).unwrap();
+        let root = root.canonicalize().unwrap();
+
+        assert_eq!(repo_root(&root.join("web/src/components")), root.join("web"));
+        assert_eq!(repo_root(&root.join("notes")), root.join("notes"));
+
+        // Matched by where it points, however it's spelled
+        let mut config = DimensionConfig::default();
+        let base_dir = root.join("web/src/..");
+        config.add_dimension(Dimension::new_with_base_dir("web".to_string(), Some(base_dir)));
+        assert_eq!(owner(&config, &root.join("web")).map(|d| d.name.as_str()), Some("web"));
+        assert!(owner(&config, &root.join("notes")).is_none());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
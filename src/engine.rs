@@ -0,0 +1,838 @@
+//! `DimensionsEngine`: the config and the multiplexer behind one API (list, switch, create,
+//! rename, reorder, delete, subscribe to changes) for frontends other than the TUI. The TUI's
+//! `App` owns an engine and goes through it for these operations too, keeping only
+//! selection, prompts and messages for itself.
+
+use crate::backend::{MultiplexerBackend, TmuxBackend};
+use crate::colors;
+use crate::dimension::{
+    DEFAULT_SETUP_TIMEOUT_SECS, DEFAULT_TEARDOWN_TIMEOUT_SECS, Dimension, DimensionConfig, LAST_ACTIVE_TAB, SaveOutcome, Tab,
+};
+use crate::discover;
+use crate::events::{self, EventType};
+use crate::hooks::{self, Lifecycle};
+use crate::readiness::{self, ReadyEvent};
+use crate::remote;
+use crate::snapshot::{self, Snapshot};
+use crate::template;
+use crate::tmux::Tmux;
+use crate::usage::{self, EventKind};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A change made through the engine, as told to subscribers
+#[derive(Debug, Clone, PartialEq)]
+pub enum EngineEvent {
+    Created(String),
+    Renamed { from: String, to: String },
+    Deleted(String),
+    Reordered,
+    SessionKilled(String),
+    Switched { session: String, window: Option<usize> },
+    // A save picked up changes written by another instance; anything may have changed
+    Merged,
+}
+
+/// One dimension as a frontend would list it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionSummary {
+    pub name: String,
+    pub base_dir: Option<PathBuf>,
+    pub running: bool,
+    // Live windows when running, configured tabs otherwise
+    pub tabs: Vec<String>,
+}
+
+/// Where a frontend should attach or switch to once `switch` returns
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchTarget {
+    pub session: String,
+    pub window: Option<usize>,
+    pub detach_others: bool,
+    // Something went wrong along the way that didn't stop the switch, for the user
+    pub warning: Option<String>,
+}
+
+/// How far `start_session` got
+#[derive(Debug)]
+pub enum SessionStart {
+    // It was already running
+    Running,
+    // Recreated from its hibernation snapshot
+    Thawed,
+    // Created with all its windows
+    Started,
+    // Created; its configured windows come one `step_startup` at a time
+    Starting(StartupProgress),
+}
+
+/// A tmux session being created from a dimension's configured tabs
+#[derive(Debug, Clone)]
+pub struct StartupProgress {
+    pub session: String,
+    pub tabs: Vec<Tab>,
+    pub windows_created: usize,
+    pub commands_launched: usize,
+}
+
+impl StartupProgress {
+    pub fn total_commands(&self) -> usize {
+        self.tabs.iter().filter(|t| t.command.is_some()).count()
+    }
+}
+
+/// A freshly created session whose `wait_for` condition hasn't held yet
+pub struct WaitingForReady {
+    pub session: String,
+    pub description: String,
+    pub started: Instant,
+    cancel: Arc<AtomicBool>,
+    rx: mpsc::Receiver<ReadyEvent>,
+}
+
+impl WaitingForReady {
+    /// The outcome, if the check has finished
+    pub fn poll(&self) -> Option<ReadyEvent> {
+        match self.rx.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(ReadyEvent::TimedOut),
+        }
+    }
+
+    /// Block until the check finishes
+    pub fn wait(&self) -> ReadyEvent {
+        self.rx.recv().unwrap_or(ReadyEvent::TimedOut)
+    }
+
+    /// Stop the check's thread
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Callback registered through `subscribe`
+type Subscriber = Box<dyn FnMut(&EngineEvent)>;
+
+pub struct DimensionsEngine {
+    pub config: DimensionConfig,
+    // tmux, or a mock in tests
+    pub backend: Rc<dyn MultiplexerBackend>,
+    // Whether changes are written to disk (off in tests)
+    pub persist: bool,
+    subscribers: Vec<Subscriber>,
+    // A save merged another instance's changes since `take_merged` was last called
+    merged: bool,
+}
+
+impl DimensionsEngine {
+    /// The engine for the user's config and tmux server
+    pub fn load() -> Result<Self> {
+        Ok(Self::new(DimensionConfig::load()?))
+    }
+
+    pub fn new(config: DimensionConfig) -> Self {
+        Self::with_backend(config, Rc::new(TmuxBackend))
+    }
+
+    pub fn with_backend(config: DimensionConfig, backend: Rc<dyn MultiplexerBackend>) -> Self {
+        Self { config, backend, persist: true, subscribers: vec![], merged: false }
+    }
+
+    /// Call `subscriber` after every change made through this engine
+    pub fn subscribe(&mut self, subscriber: impl FnMut(&EngineEvent) + 'static) {
+        self.subscribers.push(Box::new(subscriber));
+    }
+
+    fn emit(&mut self, event: EngineEvent) {
+        for subscriber in &mut self.subscribers {
+            subscriber(&event);
+        }
+    }
+
+    /// Every dimension in config order, with its session state
+    pub fn list(&self) -> Vec<DimensionSummary> {
+        self.config
+            .dimensions
+            .iter()
+            .map(|dimension| {
+                let running = self.backend.session_exists(&dimension.name);
+                let tabs = if running {
+                    let windows = self.backend.list_windows(&dimension.name).unwrap_or_default();
+                    windows.into_iter().map(|(_, name)| name).collect()
+                } else {
+                    dimension.configured_tabs.iter().map(|t| t.name.clone()).collect()
+                };
+                DimensionSummary { name: dimension.name.clone(), base_dir: dimension.base_dir.clone(), running, tabs }
+            })
+            .collect()
+    }
+
+    /// Write the config (unless persistence is off)
+    pub fn save(&mut self) -> Result<()> {
+        if !self.persist {
+            return Ok(());
+        }
+        if self.config.save()? == SaveOutcome::Merged {
+            self.merged = true;
+            self.emit(EngineEvent::Merged);
+        }
+        Ok(())
+    }
+
+    /// Whether a save merged changes from another instance since the last call
+    pub fn take_merged(&mut self) -> bool {
+        std::mem::take(&mut self.merged)
+    }
+
+    /// Add a dimension to the config; its session starts on the first switch
+    pub fn create(&mut self, name: &str, base_dir: Option<PathBuf>) -> Result<()> {
+        if name.is_empty() {
+            anyhow::bail!("Dimension name cannot be empty");
+        }
+        if self.config.name_in_use(name) {
+            anyhow::bail!("Dimension '{}' already exists", name);
+        }
+        self.add(Dimension::new_with_base_dir(name.to_string(), base_dir))
+    }
+
+    fn add(&mut self, dimension: Dimension) -> Result<()> {
+        let name = dimension.name.clone();
+        self.config.add_dimension(dimension);
+        self.save()?;
+        if let Some(dimension) = self.config.get_dimension(&name) {
+            hooks::run_lifecycle(self.backend.as_ref(), &self.config.hooks, dimension, Lifecycle::Create);
+        }
+        self.emit(EngineEvent::Created(name));
+        Ok(())
+    }
+
+    /// The dimension for the git repo containing `dir` (or `dir` itself outside a repo),
+    /// created after the repo's folder when none points there yet, from `template` if given.
+    /// Returns its name and whether it was just created.
+    pub fn here(&mut self, dir: &Path, template: Option<&str>) -> Result<(String, bool)> {
+        let root = discover::repo_root(dir);
+        if let Some(dimension) = discover::owner(&self.config, &root) {
+            return Ok((dimension.name.clone(), false));
+        }
+
+        let name = discover::unique_name(&self.config, &root);
+        let dimension = match template {
+            None => Dimension::new_with_base_dir(name.clone(), Some(root)),
+            Some(template) => {
+                let Some(template) = self.config.templates.iter().find(|t| t.name == template) else {
+                    anyhow::bail!("No template named '{}'", template);
+                };
+                let values = HashMap::from([
+                    ("name".to_string(), name.clone()),
+                    ("path".to_string(), root.to_string_lossy().into_owned()),
+                ]);
+                let missing: Vec<String> = template::variables(template)?
+                    .into_iter()
+                    .filter(|v| !values.contains_key(v))
+                    .collect();
+                if !missing.is_empty() {
+                    anyhow::bail!("Template '{}' also asks for {}; create it with T instead", template.name, missing.join(", "));
+                }
+                template::instantiate(template, &values)?
+            }
+        };
+        self.add(dimension)?;
+        Ok((name, true))
+    }
+
+    /// Rename a dimension and its live session. Returns a warning when the
+    /// session's key table couldn't be rebuilt under the new name.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<Option<String>> {
+        if self.config.name_in_use(to) {
+            anyhow::bail!("Dimension '{}' already exists", to);
+        }
+        let Some(index) = self.config.dimensions.iter().position(|d| d.name == from) else {
+            anyhow::bail!("Dimension '{}' not found", from);
+        };
+
+        let running = self.backend.session_exists(from);
+        if running {
+            self.backend.rename_session(from, to)?;
+        }
+        self.config.dimensions[index].name = to.to_string();
+        self.save()?;
+
+        // The key table is named after the session, so rebuild it under the new name
+        let mut warning = None;
+        if running {
+            self.backend.remove_key_table(from);
+            if let Err(e) = self.backend.apply_key_table(&self.config.dimensions[index], &self.config.popup) {
+                warning = Some(format!("key table not applied: {}", e));
+            }
+        }
+        self.emit(EngineEvent::Renamed { from: from.to_string(), to: to.to_string() });
+        Ok(warning)
+    }
+
+    /// Swap the dimensions at `from` and `to` in the config
+    pub fn move_dimension(&mut self, from: usize, to: usize) -> Result<()> {
+        if from >= self.config.dimensions.len() || to >= self.config.dimensions.len() {
+            return Ok(());
+        }
+        self.config.dimensions.swap(from, to);
+        self.save()?;
+        self.emit(EngineEvent::Reordered);
+        Ok(())
+    }
+
+    /// Move a dimension from the config to its trash. Its session, if running, is left
+    /// for the caller to kill (the TUI may need to switch away from it first).
+    pub fn remove(&mut self, name: &str) -> Result<Dimension> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let Some(removed) = self.config.trash_dimension(name, now) else {
+            anyhow::bail!("Dimension '{}' not found", name);
+        };
+        self.save()?;
+        hooks::run_lifecycle(self.backend.as_ref(), &self.config.hooks, &removed, Lifecycle::Delete);
+        self.emit(EngineEvent::Deleted(name.to_string()));
+        Ok(removed)
+    }
+
+    /// Put a deleted dimension back from the trash: `name`, or the most recently deleted.
+    /// Returns its name; its session starts on the next switch.
+    pub fn restore(&mut self, name: Option<&str>) -> Result<String> {
+        let name = self.config.restore_from_trash(name)?;
+        self.save()?;
+        self.emit(EngineEvent::Created(name.clone()));
+        Ok(name)
+    }
+
+    /// Remove a dimension and kill its session. Returns a teardown failure, if any.
+    pub fn delete(&mut self, name: &str) -> Result<Option<String>> {
+        let removed = self.remove(name)?;
+        if !self.backend.session_exists(name) {
+            return Ok(None);
+        }
+        self.kill_session(&removed)
+    }
+
+    /// What the panes of `name`'s session are running other than shells, as "program (window)".
+    /// Empty when the session isn't running here (a remote dimension's panes are on its host).
+    pub fn running_programs(&self, name: &str) -> Vec<String> {
+        let remote = self.config.get_dimension(name).is_some_and(|d| d.remote.is_some());
+        if remote || !self.backend.session_exists(name) {
+            return vec![];
+        }
+        let mut programs: Vec<String> = self
+            .backend
+            .pane_commands(name)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, command)| !snapshot::is_shell(command))
+            .map(|(window, command)| format!("{} ({})", command, window))
+            .collect();
+        programs.dedup();
+        programs
+    }
+
+    /// Run `dimension`'s teardown, then kill its session. Returns a short description
+    /// of a teardown failure; teardown problems never block the kill.
+    pub fn kill_session(&mut self, dimension: &Dimension) -> Result<Option<String>> {
+        let teardown_error = run_teardown(dimension);
+        if let Some(host) = &dimension.remote {
+            self.backend.kill_remote_session(host, &dimension.name)?;
+        }
+        self.backend.kill_session(&dimension.name)?;
+        usage::record(EventKind::SessionKilled, &dimension.name, None);
+        self.backend.remove_key_table(&dimension.name);
+        self.emit(EngineEvent::SessionKilled(dimension.name.clone()));
+        Ok(teardown_error)
+    }
+
+    /// Start `name`'s session if needed (optionally landing on `tab`) exactly like the TUI does,
+    /// blocking until it's ready. Returns where to switch or attach; doing so is up to the frontend.
+    pub fn switch(&mut self, name: &str, tab: Option<&str>) -> Result<SwitchTarget> {
+        let Some(index) = self.config.find_index(name) else {
+            anyhow::bail!("Dimension '{}' not found", name);
+        };
+        // An alias stands for the dimension's name from here on
+        let name = self.config.dimensions[index].name.clone();
+        let name = name.as_str();
+
+        // Window index for a live session, configured position otherwise
+        let mut selected = None;
+        if let Some(tab) = tab {
+            selected = if self.backend.session_exists(name) {
+                self.backend
+                    .list_windows(name)?
+                    .into_iter()
+                    .find(|(_, window)| window == tab)
+                    .map(|(idx, _)| idx)
+            } else {
+                self.config.dimensions[index].configured_tabs.iter().position(|t| t.name == tab)
+            };
+            if selected.is_none() {
+                anyhow::bail!("Dimension '{}' has no tab '{}'", name, tab);
+            }
+        }
+
+        let leaving = self.backend.is_inside_session().then(|| self.backend.get_current_session().ok()).flatten();
+        let (start, mut warning) = self.start_session(name, None)?;
+        let preexisted = match start {
+            SessionStart::Running => true,
+            // A thawed session's windows are the snapshot's, not the configured ones
+            SessionStart::Thawed => {
+                selected = None;
+                true
+            }
+            SessionStart::Started => false,
+            SessionStart::Starting(mut progress) => {
+                while self.step_startup(&mut progress)? {}
+                let timed_out = self.wait_for_ready(name).filter(|waiting| waiting.wait() == ReadyEvent::TimedOut);
+                if let Some(waiting) = timed_out {
+                    // The session exists, so go anyway
+                    warning = Some(format!(
+                        "'{}' not ready ({}) after {}s",
+                        name,
+                        waiting.description,
+                        waiting.started.elapsed().as_secs()
+                    ));
+                }
+                false
+            }
+        };
+
+        let window = self.landing_window(name, selected, preexisted);
+        self.enter_session(name, leaving.as_deref());
+        let target = SwitchTarget {
+            session: name.to_string(),
+            window: Some(window),
+            detach_others: self.config.attach.detach_others,
+            warning,
+        };
+        self.emit(EngineEvent::Switched { session: target.session.clone(), window: target.window });
+        Ok(target)
+    }
+
+    /// Bring `name`'s session up if it isn't running: from its host when remote, from its
+    /// hibernation snapshot, or freshly from the configured tabs (after `setup`). A picked
+    /// task, as (tab, command), replaces that tab's command. Returns how far it got, and a
+    /// warning when the key table couldn't be applied.
+    pub fn start_session(
+        &mut self,
+        name: &str,
+        task_override: Option<(String, String)>,
+    ) -> Result<(SessionStart, Option<String>)> {
+        if self.backend.session_exists(name) {
+            return Ok((SessionStart::Running, None));
+        }
+        let Some(dimension) = self.config.get_dimension(name) else {
+            anyhow::bail!("Dimension '{}' not found", name);
+        };
+        let base_dir = dimension.base_dir.clone();
+        let mut tabs = dimension.configured_tabs.clone();
+        let picked = task_override
+            .and_then(|(tab_name, command)| tabs.iter().position(|t| t.name == tab_name).map(|i| (i, command)));
+        if let Some((i, command)) = picked {
+            tabs[i].command = Some(command);
+        }
+        let tabs: Vec<Tab> = tabs.iter().map(|tab| dimension.launched_tab(tab)).collect();
+
+        // A remote dimension's windows live on its host; locally it is one window attached there
+        if let Some(host) = dimension.remote.clone() {
+            self.backend.ensure_remote_session(&host, dimension)?;
+            self.backend.create_session(name, true)?;
+            usage::record(EventKind::SessionCreated, name, None);
+            events::install_hooks(self.backend.as_ref());
+            let first_idx = self.backend.get_first_window_index(name).unwrap_or(0);
+            self.backend.rename_window(name, first_idx, &host)?;
+            self.backend.send_keys(name, first_idx, &remote::attach_command(&host, name))?;
+            return Ok((SessionStart::Started, None));
+        }
+
+        // A hibernated dimension comes back as it was, not from its configured tabs
+        if self.thaw(name)? {
+            return Ok((SessionStart::Thawed, None));
+        }
+
+        // Run the session-level setup command before any windows exist
+        if let Some(dimension) = self.config.get_dimension(name) {
+            run_setup(dimension)?;
+        }
+
+        // Create session in base_dir if available
+        if let Some(dir) = base_dir.as_ref() {
+            self.backend.create_session_with_dir(name, true, dir.to_str().unwrap_or("."))?;
+        } else {
+            self.backend.create_session(name, true)?;
+        }
+        usage::record(EventKind::SessionCreated, name, None);
+        events::install_hooks(self.backend.as_ref());
+
+        // Bad bindings shouldn't keep the session from starting
+        let warning = self
+            .config
+            .get_dimension(name)
+            .and_then(|dimension| self.backend.apply_key_table(dimension, &self.config.popup).err())
+            .map(|e| format!("Key table for '{}' not applied: {}", name, e));
+
+        if !tabs.is_empty() {
+            let progress = StartupProgress { session: name.to_string(), tabs, windows_created: 0, commands_launched: 0 };
+            return Ok((SessionStart::Starting(progress), warning));
+        }
+
+        // No configured tabs: create and save an initial tab
+        let initial_tab_name = format!("{}-1", name);
+        let first_idx = self.backend.get_first_window_index(name).unwrap_or(0);
+        self.backend.rename_window(name, first_idx, &initial_tab_name)?;
+
+        // Save this initial tab to config so it persists across restarts
+        if let Some(dimension) = self.config.dimensions.iter_mut().find(|d| d.name == name) {
+            dimension.add_tab(Tab::new(initial_tab_name, None, base_dir));
+            self.save()?;
+        }
+        Ok((SessionStart::Started, warning))
+    }
+
+    /// Snapshot `name`'s session, then kill it; switching to it later thaws it.
+    /// Returns the snapshot and a teardown failure, if any.
+    pub fn hibernate(&mut self, name: &str) -> Result<(Snapshot, Option<String>)> {
+        let Some(dimension) = self.config.get_dimension(name).cloned() else {
+            anyhow::bail!("Dimension '{}' not found", name);
+        };
+        let snapshot = snapshot::capture(self.backend.as_ref(), name)?;
+        let teardown_error = self.kill_session(&dimension)?;
+        Ok((snapshot::hibernate(snapshot)?, teardown_error))
+    }
+
+    /// Recreate a hibernated dimension's session from its snapshot, running its setup first.
+    /// Returns false when the dimension isn't hibernated.
+    pub fn thaw(&mut self, name: &str) -> Result<bool> {
+        let Some(snapshot) = snapshot::hibernated(name) else {
+            return Ok(false);
+        };
+        if let Some(dimension) = self.config.get_dimension(name) {
+            run_setup(dimension)?;
+        }
+        snapshot::thaw(self.backend.as_ref(), snapshot, &self.config.snapshots)?;
+        usage::record(EventKind::SessionCreated, name, None);
+        events::install_hooks(self.backend.as_ref());
+        Ok(true)
+    }
+
+    /// Create the next configured window of a session being started.
+    /// Returns false once every tab has its window.
+    pub fn step_startup(&mut self, progress: &mut StartupProgress) -> Result<bool> {
+        let name = progress.session.as_str();
+        let Some(tab) = progress.tabs.get(progress.windows_created) else {
+            return Ok(false);
+        };
+
+        if progress.windows_created == 0 {
+            // First window is created with the session, rename it to match first tab
+            let first_idx = self.backend.get_first_window_index(name).unwrap_or(0);
+            self.backend.rename_window(name, first_idx, &tab.name)?;
+
+            // Build command for first tab (with working dir if needed)
+            let full_command = match (&tab.working_dir, tab.launch_command()) {
+                (Some(dir), Some(cmd)) => {
+                    // Both working_dir and command: cd then run command
+                    Tmux::record_exit_status(&format!("cd {:?} && {}", dir, cmd))
+                }
+                (Some(dir), None) => {
+                    // Only working_dir: just cd
+                    format!("cd {:?}", dir)
+                }
+                (None, Some(cmd)) => {
+                    // Only command: just run it
+                    Tmux::record_exit_status(&cmd)
+                }
+                (None, None) => String::new(),
+            };
+
+            // Send command if we have one
+            if !full_command.is_empty() {
+                self.backend.send_keys(name, first_idx, &full_command)?;
+            }
+        } else {
+            self.backend.new_window(name, &tab.name, tab.launch_command().as_deref(), tab.working_dir.as_deref())?;
+        }
+
+        if let Some(command) = &tab.command {
+            usage::record(EventKind::Command, name, Some(command));
+            progress.commands_launched += 1;
+        }
+        progress.windows_created += 1;
+        Ok(true)
+    }
+
+    /// Start watching for `name`'s `wait_for` condition, if it has one
+    pub fn wait_for_ready(&self, name: &str) -> Option<WaitingForReady> {
+        let dimension = self.config.get_dimension(name)?;
+        let wait_for = dimension.wait_for.clone()?;
+        let cancel = Arc::new(AtomicBool::new(false));
+        Some(WaitingForReady {
+            session: name.to_string(),
+            description: wait_for.describe(),
+            started: Instant::now(),
+            cancel: cancel.clone(),
+            rx: readiness::spawn_wait(name.to_string(), wait_for, dimension.base_dir.clone(), cancel),
+        })
+    }
+
+    /// Window to open in `name`: `selected` (a window index when the session already ran,
+    /// else a configured tab position) if it still exists, otherwise the default window
+    pub fn landing_window(&self, name: &str, selected: Option<usize>, session_preexisted: bool) -> usize {
+        let Some(selected) = selected else {
+            return self.default_window(name, session_preexisted);
+        };
+        let windows = self.backend.list_windows(name).unwrap_or_default();
+        let window = if session_preexisted {
+            windows.iter().any(|(idx, _)| *idx == selected).then_some(selected)
+        } else {
+            // Configured tabs became windows in order
+            windows.get(selected).map(|(idx, _)| *idx)
+        };
+        window
+            .or_else(|| windows.first().map(|(idx, _)| *idx))
+            .unwrap_or_else(|| self.backend.get_first_window_index(name).unwrap_or(0))
+    }
+
+    /// Window to open when switching to `name` without a tab selected: the dimension's
+    /// `default_tab` when it names a window (or asks for the last active one), else the first
+    fn default_window(&self, name: &str, session_preexisted: bool) -> usize {
+        let default_tab = self.config.get_dimension(name).and_then(|d| d.default_tab.as_deref());
+        let window = match default_tab {
+            // A session that was just created has no history to go by
+            Some(LAST_ACTIVE_TAB) if session_preexisted => self.backend.last_active_window(name),
+            Some(LAST_ACTIVE_TAB) | None => None,
+            Some(tab) => self
+                .backend
+                .list_windows(name)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|(_, window)| window == tab)
+                .map(|(idx, _)| idx),
+        };
+        window.unwrap_or_else(|| self.backend.get_first_window_index(name).unwrap_or(0))
+    }
+
+    /// Everything that goes with arriving in `name`'s session from `leaving`: its status bar
+    /// color, usage and event records, and the detach/attach hooks when the session changes
+    pub fn enter_session(&mut self, name: &str, leaving: Option<&str>) {
+        self.apply_session_color(name);
+        usage::record(EventKind::Switch, name, None);
+        events::emit(self.backend.as_ref(), EventType::Switched, name);
+        events::update_status(self.backend.as_ref(), &self.config, name);
+        if leaving == Some(name) {
+            return;
+        }
+        if let Some(left) = leaving.and_then(|s| self.config.get_dimension(s)) {
+            hooks::run_lifecycle(self.backend.as_ref(), &self.config.hooks, left, Lifecycle::Detach);
+        }
+        if let Some(dimension) = self.config.get_dimension(name) {
+            hooks::run_lifecycle(self.backend.as_ref(), &self.config.hooks, dimension, Lifecycle::Attach);
+        }
+    }
+
+    /// Carry the dimension's group color into its tmux status bar (best-effort)
+    fn apply_session_color(&self, name: &str) {
+        if self.config.colors.tmux_status == Some(false) {
+            return;
+        }
+        let Some(dimension) = self.config.get_dimension(name) else {
+            return;
+        };
+        if let Some(color) = colors::dimension_color(&self.config.colors, dimension) {
+            let style = format!("bg={},fg=black", colors::to_tmux(&color));
+            let _ = self.backend.set_session_option(name, "status-style", &style);
+        }
+    }
+}
+
+/// Run a dimension's setup command (if any), killing it after its timeout; on failure the
+/// output is saved to a log and the error names it so the user can inspect what went wrong.
+fn run_setup(dimension: &Dimension) -> Result<()> {
+    let Some(setup) = dimension.setup.as_deref() else {
+        return Ok(());
+    };
+    let name = dimension.name.as_str();
+    let timeout = Duration::from_secs(dimension.setup_timeout_secs.unwrap_or(DEFAULT_SETUP_TIMEOUT_SECS));
+    let outcome = hooks::run_shell(name, setup, dimension.base_dir.as_deref(), Some(timeout))?;
+    if outcome.success {
+        return Ok(());
+    }
+
+    let reason = outcome.failure_reason(Some(timeout));
+    let log = hooks::write_log(name, "setup", &outcome.output);
+    let hint = outcome.last_line().map(|l| format!(": {}", l)).unwrap_or_default();
+    match log {
+        Some(path) => anyhow::bail!(
+            "Setup for '{}' failed ({}){} — output in {}",
+            name,
+            reason,
+            hint,
+            path.display()
+        ),
+        None => anyhow::bail!("Setup for '{}' failed ({}){}", name, reason, hint),
+    }
+}
+
+/// Run a dimension's teardown command (if any) before its session is killed.
+/// Returns a short failure description; teardown problems never block the kill.
+pub fn run_teardown(dimension: &Dimension) -> Option<String> {
+    let teardown = dimension.teardown.as_deref()?;
+    let timeout = Duration::from_secs(dimension.teardown_timeout_secs.unwrap_or(DEFAULT_TEARDOWN_TIMEOUT_SECS));
+
+    let outcome = match hooks::run_shell(&dimension.name, teardown, dimension.base_dir.as_deref(), Some(timeout)) {
+        Ok(outcome) => outcome,
+        Err(e) => return Some(format!("teardown failed: {}", e)),
+    };
+    if outcome.success {
+        return None;
+    }
+
+    let reason = outcome.failure_reason(Some(timeout));
+    match hooks::write_log(&dimension.name, "teardown", &outcome.output) {
+        Some(path) => Some(format!("teardown failed ({}) — output in {}", reason, path.display())),
+        None => Some(format!("teardown failed ({})", reason)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::mock::MockBackend;
+    use crate::paths;
+    use std::cell::RefCell;
+
+    fn engine(config: DimensionConfig, backend: &MockBackend) -> DimensionsEngine {
+        paths::use_temp_dir();
+        let mut engine = DimensionsEngine::with_backend(config, Rc::new(backend.clone()));
+        engine.persist = false;
+        engine
+    }
+
+    #[test]
+    fn changes_are_listed_and_announced() {
+        let backend = MockBackend::new().with_session("web", &["editor", "server"]);
+        let mut engine = engine(DimensionConfig::default(), &backend);
+        let events = Rc::new(RefCell::new(vec![]));
+        let seen = events.clone();
+        engine.subscribe(move |event| seen.borrow_mut().push(event.clone()));
+
+        engine.create("api", None).unwrap();
+        engine.create("web", None).unwrap();
+        assert!(engine.create("api", None).is_err());
+        engine.rename("web", "site").unwrap();
+        engine.move_dimension(0, 1).unwrap();
+
+        let listed: Vec<_> = engine.list().into_iter().map(|d| (d.name, d.running, d.tabs.len())).collect();
+        assert_eq!(listed, [("site".to_string(), true, 2), ("api".to_string(), false, 0)]);
+
+        engine.delete("site").unwrap();
+        assert!(backend.window_names("site").is_none());
+        assert_eq!(
+            *events.borrow(),
+            [
+                EngineEvent::Created("api".to_string()),
+                EngineEvent::Created("web".to_string()),
+                EngineEvent::Renamed { from: "web".to_string(), to: "site".to_string() },
+                EngineEvent::Reordered,
+                EngineEvent::Deleted("site".to_string()),
+                EngineEvent::SessionKilled("site".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn hibernated_sessions_thaw_as_they_were() {
+        let backend = MockBackend::new().with_session("hibernating", &["editor", "logs"]);
+        backend.state.borrow_mut().pane_commands.insert(
+            "hibernating".to_string(),
+            vec![("editor".to_string(), "nvim".to_string()), ("logs".to_string(), "zsh".to_string())],
+        );
+        backend.split_window("hibernating", 1, None).unwrap();
+        let mut config = DimensionConfig::default();
+        config.dimensions = vec![Dimension::new_with_base_dir("hibernating".to_string(), None)];
+        let mut engine = engine(config, &backend);
+
+        engine.hibernate("hibernating").unwrap();
+        assert!(backend.window_names("hibernating").is_none());
+        let target = engine.switch("hibernating", None).unwrap();
+
+        assert_eq!(target.session, "hibernating");
+        assert_eq!(backend.window_names("hibernating").unwrap(), ["editor", "logs"]);
+        let state = backend.state.borrow();
+        assert_eq!(state.session("hibernating").unwrap().windows[1].splits.len(), 1);
+        assert_eq!(state.sent_keys, [("hibernating".to_string(), 0, "nvim".to_string())]);
+        drop(state);
+        assert!(snapshot::hibernated("hibernating").is_none());
+        assert!(!engine.thaw("hibernating").unwrap());
+    }
+
+    #[test]
+    fn aliases_find_their_dimension() {
+        let backend = MockBackend::new().with_session("client-a", &["editor"]);
+        let mut dimension = Dimension::new_with_base_dir("client-a".to_string(), None);
+        dimension.aliases = vec!["PROJ-142".to_string()];
+        let mut config = DimensionConfig::default();
+        config.dimensions = vec![dimension];
+        let mut engine = engine(config, &backend);
+
+        assert_eq!(engine.config.find("proj-142").map(|d| d.name.as_str()), Some("client-a"));
+        assert!(engine.config.name_in_use("PROJ-142"));
+        assert_eq!(engine.switch("PROJ-142", None).unwrap().session, "client-a");
+        assert!(engine.switch("PROJ-143", None).is_err());
+    }
+
+    #[test]
+    fn switch_starts_sessions_and_key_tables_follow_them() {
+        let backend = MockBackend::new();
+        let mut dimension = Dimension::new_with_base_dir("api".to_string(), None);
+        dimension.add_tab(Tab::new("editor".to_string(), None, None));
+        dimension.add_tab(Tab::new("server".to_string(), Some("cargo run".to_string()), None));
+        dimension.keys.insert("M-t".to_string(), "next-tab".to_string());
+        let mut config = DimensionConfig::default();
+        config.dimensions = vec![dimension];
+        let mut engine = engine(config, &backend);
+
+        let target = engine.switch("api", Some("server")).unwrap();
+        assert_eq!((target.session.as_str(), target.window), ("api", Some(1)));
+        assert_eq!(backend.window_names("api").unwrap(), ["editor", "server"]);
+        assert_eq!(backend.state.borrow().key_tables, ["api"]);
+
+        engine.rename("api", "backend").unwrap();
+        assert_eq!(backend.state.borrow().key_tables, ["backend"]);
+        let dimension = engine.config.dimensions[0].clone();
+        engine.kill_session(&dimension).unwrap();
+        assert!(backend.state.borrow().key_tables.is_empty());
+    }
+
+    #[test]
+    fn here_creates_one_dimension_per_repo() {
+        let root = std::env::temp_dir().join(format!("dimensions-here-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("web/.git")).unwrap();
+        std::fs::create_dir_all(root.join("web/src")).unwrap();
+        let root = root.canonicalize().unwrap();
+
+        let mut rust = Dimension::new_with_base_dir("rust".to_string(), None);
+        rust.add_tab(crate::dimension::Tab::new("build".to_string(), Some("cargo watch".to_string()), None));
+        let mut ticket = Dimension::new_with_base_dir("ticket".to_string(), None);
+        ticket.aliases = vec!["{{ticket}}".to_string()];
+        let mut config = DimensionConfig::default();
+        config.templates = vec![rust, ticket];
+        let mut engine = engine(config, &MockBackend::new());
+
+        assert!(engine.here(&root.join("web"), Some("ticket")).is_err());
+        assert_eq!(engine.here(&root.join("web/src"), Some("rust")).unwrap(), ("web".to_string(), true));
+        assert_eq!(engine.here(&root.join("web"), None).unwrap(), ("web".to_string(), false));
+        let web = engine.config.get_dimension("web").unwrap();
+        assert_eq!(web.base_dir.as_deref(), Some(root.join("web").as_path()));
+        assert_eq!(web.configured_tabs[0].command.as_deref(), Some("cargo watch"));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
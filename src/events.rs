@@ -0,0 +1,257 @@
+//! Outgoing events (dimension switched, session closed, command finished), POSTed to a webhook
+//! and/or published to an MQTT topic so home automation or team dashboards can react.
+//! Delivery happens in a detached `dimensions emit` process, so a slow endpoint never holds up
+//! a switch; tmux runs the same command when a session closes or a tab's command finishes.
+//! A status command (say, setting your Slack status) can also run on every switch.
+
+use crate::backend::MultiplexerBackend;
+use crate::dimension::{Dimension, DimensionConfig, EventSettings};
+use crate::hooks::{self, shell_quote};
+use crate::machine;
+use crate::tmux::Tmux;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Hook slot for the session-closed hook; an index leaves the user's own hooks alone
+const SESSION_CLOSED_HOOK: &str = "session-closed[77]";
+
+/// Set from the config at startup; nothing is sent (or wrapped around commands) when off
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    Switched,
+    SessionClosed,
+    CommandFinished,
+}
+
+/// What gets sent, as JSON
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Payload {
+    pub event: EventType,
+    pub dimension: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_status: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    pub ts: u64,
+}
+
+pub fn configure(settings: &EventSettings) {
+    ENABLED.store(settings.enabled(), Ordering::Relaxed);
+}
+
+fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn exe() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.to_str().map(str::to_string))
+        .unwrap_or_else(|| "dimensions".to_string())
+}
+
+/// Shell appended to a tab's command (after its exit status is recorded) to report it finished
+pub fn exit_hook() -> Option<String> {
+    enabled().then(|| format!("; {} emit command-finished", shell_quote(&exe())))
+}
+
+/// Have tmux report closed sessions, or stop it once events are turned off; called
+/// whenever a dimension's session is created
+pub fn install_hooks(backend: &dyn MultiplexerBackend) {
+    if !enabled() {
+        let _ = backend.unset_global_hook(SESSION_CLOSED_HOOK);
+        return;
+    }
+    let _ = backend.set_global_hook(SESSION_CLOSED_HOOK, &session_closed_command(&exe()));
+}
+
+/// tmux command for the session-closed hook. `q:` has tmux quote the session name for the
+/// shell, so names with quotes or spaces arrive as one argument, and `--` keeps a name
+/// starting with `-` from being read as a flag.
+fn session_closed_command(exe: &str) -> String {
+    format!("run-shell -b \"{} emit session-closed -- #{{q:hook_session_name}}\"", shell_quote(exe))
+}
+
+/// Send an event in the background; failures only ever show up in that process
+pub fn emit(backend: &dyn MultiplexerBackend, event: EventType, dimension: &str) {
+    if !enabled() {
+        return;
+    }
+    let event = match event {
+        EventType::Switched => "switched",
+        EventType::SessionClosed => "session-closed",
+        EventType::CommandFinished => "command-finished",
+    };
+    let mut command = Command::new(exe());
+    command
+        .args(["emit", event, "--", dimension])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let _ = backend.spawn_detached(command);
+}
+
+/// The status command for `dimension` (its own, else `events.status_command`), with
+/// `{dimension}` filled in. None when there isn't one.
+pub fn status_command(settings: &EventSettings, dimension: &Dimension) -> Option<String> {
+    let template = dimension.status_command.as_ref().or(settings.status_command.as_ref())?;
+    (!template.trim().is_empty()).then(|| template.replace("{dimension}", &shell_quote(&dimension.name)))
+}
+
+/// Run the status command after switching to `name`; it runs detached, so a slow status
+/// service never delays the switch
+pub fn update_status(backend: &dyn MultiplexerBackend, config: &DimensionConfig, name: &str) {
+    let Some(dimension) = config.get_dimension(name) else {
+        return;
+    };
+    if let Some(command) = status_command(&config.events, dimension) {
+        let shell = hooks::detached_shell(name, &command, dimension.base_dir.as_deref(), None);
+        let _ = backend.spawn_detached(shell);
+    }
+}
+
+/// `dimensions emit <event> [<dimension>]`: build the payload and deliver it. Without a
+/// dimension (a finished command), the session and window come from `$TMUX_PANE`.
+pub fn emit_cli(config: &DimensionConfig, event: EventType, dimension: Option<&str>) -> Result<()> {
+    if !config.events.enabled() {
+        return Ok(());
+    }
+    let (dimension, tab, exit_status) = match dimension {
+        Some(dimension) => (dimension.to_string(), None, None),
+        None => {
+            let pane = std::env::var("TMUX_PANE").context("Not inside a tmux pane; pass the dimension")?;
+            let Some((session, index, name)) = Tmux::pane_window(&pane) else {
+                anyhow::bail!("Could not look up tmux pane {}", pane);
+            };
+            let status = Tmux::window_exit_statuses(&session).ok().and_then(|s| s.get(&index).copied());
+            (session, Some(name), status)
+        }
+    };
+    // tmux reports every session closing; only dimensions are of interest
+    if config.get_dimension(&dimension).is_none() {
+        return Ok(());
+    }
+
+    let payload = Payload {
+        event,
+        dimension,
+        tab,
+        exit_status,
+        host: machine::hostname(),
+        ts: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+    };
+    deliver(&config.events, &payload)
+}
+
+fn deliver(settings: &EventSettings, payload: &Payload) -> Result<()> {
+    let body = serde_json::to_string(payload)?;
+    let mut errors = vec![];
+    if let Some(url) = &settings.webhook {
+        let agent = ureq::AgentBuilder::new().timeout(Duration::from_secs(5)).build();
+        if let Err(e) = agent.post(url).set("Content-Type", "application/json").send_string(&body) {
+            errors.push(format!("webhook: {}", e));
+        }
+    }
+    if let Some(topic) = &settings.mqtt_topic {
+        let host = settings.mqtt_host.as_deref().unwrap_or("localhost");
+        match Command::new("mosquitto_pub").args(["-h", host, "-t", topic, "-m", &body]).status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => errors.push(format!("mosquitto_pub exited with {}", status)),
+            Err(e) => errors.push(format!("mosquitto_pub: {}", e)),
+        }
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("Failed to send {:?} event: {}", payload.event, errors.join("; "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::mock::MockBackend;
+
+    #[test]
+    fn payloads_leave_out_what_they_dont_know() {
+        let payload = Payload {
+            event: EventType::CommandFinished,
+            dimension: "api".to_string(),
+            tab: Some("tests".to_string()),
+            exit_status: Some(1),
+            host: None,
+            ts: 7,
+        };
+        assert_eq!(
+            serde_json::to_string(&payload).unwrap(),
+            r#"{"event":"command_finished","dimension":"api","tab":"tests","exit_status":1,"ts":7}"#
+        );
+    }
+
+    #[test]
+    fn events_go_out_only_while_enabled() {
+        let backend = MockBackend::new();
+        configure(&EventSettings { webhook: Some("http://localhost/events".to_string()), ..Default::default() });
+        let hook = exit_hook();
+        install_hooks(&backend);
+        emit(&backend, EventType::Switched, "-api");
+        configure(&EventSettings::default());
+
+        assert!(hook.is_some_and(|hook| hook.ends_with(" emit command-finished")));
+        assert!(backend.state.borrow().global_hooks.contains_key(SESSION_CLOSED_HOOK));
+        assert_eq!(backend.state.borrow().spawned[0][1..], ["emit", "switched", "--", "-api"]);
+
+        assert_eq!(exit_hook(), None);
+        install_hooks(&backend);
+        emit(&backend, EventType::Switched, "api");
+        assert!(backend.state.borrow().global_hooks.is_empty());
+        assert_eq!(backend.state.borrow().spawned.len(), 1);
+    }
+
+    #[test]
+    fn session_closed_hook_leaves_quoting_to_tmux() {
+        assert_eq!(
+            session_closed_command("/opt/my tools/dimensions"),
+            r#"run-shell -b "'/opt/my tools/dimensions' emit session-closed -- #{q:hook_session_name}""#
+        );
+    }
+
+    #[test]
+    fn status_commands_fill_in_the_dimension() {
+        let settings = EventSettings {
+            status_command: Some("slack-status set {dimension}".to_string()),
+            ..Default::default()
+        };
+        let mut dimension = Dimension::new_with_base_dir("client's app".to_string(), None);
+        assert_eq!(
+            status_command(&settings, &dimension).as_deref(),
+            Some(r"slack-status set 'client'\''s app'")
+        );
+        dimension.status_command = Some(String::new());
+        assert_eq!(status_command(&settings, &dimension), None);
+        assert_eq!(status_command(&EventSettings::default(), &Dimension::new_with_base_dir("a".to_string(), None)), None);
+    }
+
+    #[test]
+    fn switching_runs_the_status_command() {
+        let backend = MockBackend::new();
+        let mut config = DimensionConfig::default();
+        config.dimensions = vec![
+            Dimension::new_with_base_dir("api".to_string(), None),
+            Dimension::new_with_base_dir("web".to_string(), None),
+        ];
+        config.dimensions[0].status_command = Some("slack-status set {dimension}".to_string());
+
+        update_status(&backend, &config, "api");
+        update_status(&backend, &config, "web");
+        update_status(&backend, &config, "docs");
+        assert_eq!(backend.state.borrow().spawned, [["sh", "-c", "slack-status set 'api'"]]);
+    }
+}
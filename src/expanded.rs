@@ -0,0 +1,39 @@
+//! Dimensions expanded with `c`, kept in the state directory: which ones are open is this
+//! machine's view of the list, not something to write into the shared config.
+
+use crate::paths;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+fn path() -> PathBuf {
+    paths::state_dir().join("expanded")
+}
+
+/// Names of the expanded dimensions; empty when none are
+pub fn load() -> HashSet<String> {
+    fs::read_to_string(path())
+        .map(|contents| contents.lines().filter(|l| !l.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Save `expanded`; failures are ignored, since losing it only folds the list back up
+pub fn save(expanded: &HashSet<String>) {
+    let mut names: Vec<&str> = expanded.iter().map(String::as_str).collect();
+    names.sort();
+    let _ = fs::write(path(), names.iter().map(|name| format!("{}\n", name)).collect::<String>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_names_load_back() {
+        paths::use_temp_dir();
+        let expanded: HashSet<String> = ["expanded-web", "expanded api"].iter().map(|n| n.to_string()).collect();
+        save(&expanded);
+        assert_eq!(fs::read_to_string(path()).unwrap(), "expanded api\nexpanded-web\n");
+        assert_eq!(load(), expanded);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::backend::MultiplexerBackend;
+use crate::dimension::{Dimension, HookSettings};
+use crate::paths;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Single-quote `s` for `sh -c`
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Result of running a dimension lifecycle command
+#[derive(Debug)]
+pub struct CommandOutcome {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    // Combined stdout + stderr
+    pub output: String,
+}
+
+impl CommandOutcome {
+    /// Short human-readable reason for a failure, e.g. "exit 1" or "timed out after 30s"
+    pub fn failure_reason(&self, timeout: Option<Duration>) -> String {
+        if self.timed_out {
+            match timeout {
+                Some(t) => format!("timed out after {}s", t.as_secs()),
+                None => "timed out".to_string(),
+            }
+        } else {
+            match self.exit_code {
+                Some(code) => format!("exit {}", code),
+                None => "killed by signal".to_string(),
+            }
+        }
+    }
+
+    /// Last non-empty output line, useful as a one-line status hint
+    pub fn last_line(&self) -> Option<&str> {
+        self.output.lines().rev().map(str::trim).find(|l| !l.is_empty())
+    }
+}
+
+/// Run `command` through `sh -c` for `dimension`, capturing output.
+/// With a timeout, the process is killed once it runs too long.
+pub fn run_shell(
+    dimension: &str,
+    command: &str,
+    cwd: Option<&Path>,
+    timeout: Option<Duration>,
+) -> Result<CommandOutcome> {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .env("DIMENSION_NAME", dimension)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+
+    // Drain both pipes on background threads so a chatty command can't block on a full pipe
+    let (tx, rx) = mpsc::channel();
+    if let Some(mut out) = child.stdout.take() {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = out.read_to_string(&mut buf);
+            let _ = tx.send(buf);
+        });
+    }
+    if let Some(mut err) = child.stderr.take() {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = err.read_to_string(&mut buf);
+            let _ = tx.send(buf);
+        });
+    }
+    drop(tx);
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if timeout.is_some_and(|t| started.elapsed() >= t) {
+            // Kill the whole process group so backgrounded children don't linger
+            let _ = Command::new("kill")
+                .args(["-KILL", "--", &format!("-{}", child.id())])
+                .output();
+            let _ = child.kill();
+            let _ = child.wait();
+            timed_out = true;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    // A daemonized grandchild may keep the pipes open; don't wait on it forever
+    let mut output = String::new();
+    while let Ok(chunk) = rx.recv_timeout(Duration::from_millis(500)) {
+        output.push_str(&chunk);
+    }
+
+    Ok(CommandOutcome {
+        success: status.is_some_and(|s| s.success()),
+        exit_code: status.and_then(|s| s.code()),
+        timed_out,
+        output,
+    })
+}
+
+/// `command` through `sh -c` for `dimension`, to be left running on its own with its output
+/// going to `log` (or nowhere). For commands nothing waits on, like status updates.
+pub fn detached_shell(dimension: &str, command: &str, cwd: Option<&Path>, log: Option<&Path>) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .env("DIMENSION_NAME", dimension)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0);
+    if let Some(file) = log.and_then(|path| fs::File::create(path).ok()) {
+        if let Ok(copy) = file.try_clone() {
+            cmd.stderr(copy);
+        }
+        cmd.stdout(file);
+    }
+    if let Some(dir) = cwd.filter(|dir| dir.is_dir()) {
+        cmd.current_dir(dir);
+    }
+    cmd
+}
+
+/// Points in a dimension's life where the `on_*` hooks run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lifecycle {
+    Create,
+    Attach,
+    Detach,
+    Delete,
+}
+
+impl Lifecycle {
+    fn key(self) -> &'static str {
+        match self {
+            Lifecycle::Create => "on_create",
+            Lifecycle::Attach => "on_attach",
+            Lifecycle::Detach => "on_detach",
+            Lifecycle::Delete => "on_delete",
+        }
+    }
+}
+
+/// The commands to run for `event`: the global hook, then the dimension's own
+pub fn lifecycle_commands<'a>(global: &'a HookSettings, dimension: &'a Dimension, event: Lifecycle) -> Vec<&'a str> {
+    let (global, own) = match event {
+        Lifecycle::Create => (&global.on_create, &dimension.on_create),
+        Lifecycle::Attach => (&global.on_attach, &dimension.on_attach),
+        Lifecycle::Detach => (&global.on_detach, &dimension.on_detach),
+        Lifecycle::Delete => (&global.on_delete, &dimension.on_delete),
+    };
+    [global, own].into_iter().flatten().map(|c| c.trim()).filter(|c| !c.is_empty()).collect()
+}
+
+/// Run `dimension`'s hooks for `event` in the background, in its base_dir, one after the
+/// other; their output goes to `<state>/logs/<dimension>-on_<event>.log`
+pub fn run_lifecycle(backend: &dyn MultiplexerBackend, global: &HookSettings, dimension: &Dimension, event: Lifecycle) {
+    let commands = lifecycle_commands(global, dimension, event);
+    if commands.is_empty() {
+        return;
+    }
+    let log = write_log(&dimension.name, event.key(), "");
+    let shell = detached_shell(&dimension.name, &commands.join("\n"), dimension.base_dir.as_deref(), log.as_deref());
+    let _ = backend.spawn_detached(shell);
+}
+
+/// Persist a command's output to `<state>/logs/<dimension>-<kind>.log` and return the path
+pub fn write_log(dimension: &str, kind: &str, output: &str) -> Option<PathBuf> {
+    let dir = paths::state_dir().join("logs");
+    fs::create_dir_all(&dir).ok()?;
+    let file_name: String = format!("{}-{}.log", dimension, kind)
+        .chars()
+        .map(|c| if c == '/' { '_' } else { c })
+        .collect();
+    let path = dir.join(file_name);
+    fs::write(&path, output).ok()?;
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::mock::MockBackend;
+
+    #[test]
+    fn lifecycle_hooks_run_global_first() {
+        let global = HookSettings {
+            on_attach: Some("echo global".to_string()),
+            on_delete: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let mut dimension = Dimension::new_with_base_dir("api".to_string(), None);
+        dimension.on_attach = Some("docker compose up -d".to_string());
+        dimension.on_delete = Some("docker compose down".to_string());
+
+        assert_eq!(lifecycle_commands(&global, &dimension, Lifecycle::Attach), ["echo global", "docker compose up -d"]);
+        assert_eq!(lifecycle_commands(&global, &dimension, Lifecycle::Delete), ["docker compose down"]);
+        assert!(lifecycle_commands(&global, &dimension, Lifecycle::Create).is_empty());
+    }
+
+    #[test]
+    fn lifecycle_hooks_run_in_one_shell() {
+        paths::use_temp_dir();
+        let backend = MockBackend::new();
+        let global = HookSettings { on_attach: Some("echo global".to_string()), ..Default::default() };
+        let mut dimension = Dimension::new_with_base_dir("hooked".to_string(), None);
+        dimension.on_attach = Some("docker compose up -d".to_string());
+
+        run_lifecycle(&backend, &global, &dimension, Lifecycle::Attach);
+        run_lifecycle(&backend, &global, &dimension, Lifecycle::Create);
+        assert_eq!(backend.state.borrow().spawned, [["sh", "-c", "echo global\ndocker compose up -d"]]);
+        assert!(paths::state_dir().join("logs/hooked-on_attach.log").exists());
+    }
+}
@@ -0,0 +1,83 @@
+//! Per-dimension icons, picked from the project files in its base directory.
+
+use crate::dimension::IconStyle;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectKind {
+    Rust,
+    Node,
+    Python,
+    Go,
+    Ruby,
+    Java,
+    Elixir,
+    Docker,
+}
+
+/// Marker files, checked in order; the first one present decides
+const MARKERS: &[(&str, ProjectKind)] = &[
+    ("Cargo.toml", ProjectKind::Rust),
+    ("package.json", ProjectKind::Node),
+    ("pyproject.toml", ProjectKind::Python),
+    ("setup.py", ProjectKind::Python),
+    ("requirements.txt", ProjectKind::Python),
+    ("go.mod", ProjectKind::Go),
+    ("Gemfile", ProjectKind::Ruby),
+    ("pom.xml", ProjectKind::Java),
+    ("build.gradle", ProjectKind::Java),
+    ("build.gradle.kts", ProjectKind::Java),
+    ("mix.exs", ProjectKind::Elixir),
+    ("Dockerfile", ProjectKind::Docker),
+    ("compose.yaml", ProjectKind::Docker),
+    ("docker-compose.yml", ProjectKind::Docker),
+];
+
+/// Project type of `dir`, from the first marker file found in it
+pub fn detect(dir: &Path) -> Option<ProjectKind> {
+    MARKERS
+        .iter()
+        .find(|(file, _)| dir.join(file).is_file())
+        .map(|(_, kind)| *kind)
+}
+
+impl ProjectKind {
+    pub fn icon(self, style: IconStyle) -> Option<&'static str> {
+        let (emoji, nerd_font) = match self {
+            ProjectKind::Rust => ("🦀", "\u{e7a8}"),
+            ProjectKind::Node => ("🟩", "\u{e718}"),
+            ProjectKind::Python => ("🐍", "\u{e73c}"),
+            ProjectKind::Go => ("🐹", "\u{e626}"),
+            ProjectKind::Ruby => ("💎", "\u{e739}"),
+            ProjectKind::Java => ("🍵", "\u{e738}"),
+            ProjectKind::Elixir => ("💧", "\u{e62d}"),
+            ProjectKind::Docker => ("🐳", "\u{f308}"),
+        };
+        match style {
+            IconStyle::Emoji => Some(emoji),
+            IconStyle::NerdFont => Some(nerd_font),
+            IconStyle::None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn first_marker_wins() {
+        let dir = std::env::temp_dir().join(format!("dimensions-icons-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(detect(&dir), None);
+
+        fs::write(dir.join("Dockerfile"), "").unwrap();
+        assert_eq!(detect(&dir), Some(ProjectKind::Docker));
+        fs::write(dir.join("Cargo.toml"), "").unwrap();
+        assert_eq!(detect(&dir), Some(ProjectKind::Rust));
+        assert_eq!(ProjectKind::Rust.icon(IconStyle::None), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,445 @@
+//! Converting dimensions to and from other session managers' formats
+//! (tmuxinator, tmuxp, plain tmux scripts), so a layout can be shared with teammates who
+//! don't use Dimensions.
+
+use crate::dimension::{Dimension, DimensionConfig, Tab};
+use crate::hooks::shell_quote;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Tmuxinator,
+    Tmuxp,
+    Json,
+    // A shell script of tmux commands (`dimensions export-script`)
+    Script,
+}
+
+/// `dimensions export <name> [--format tmuxinator|tmuxp|json|script]`: prints the dimension to stdout
+pub fn export_cli(config: &DimensionConfig, name: &str, format: ExportFormat) -> Result<()> {
+    let Some(dimension) = config.find(name) else {
+        anyhow::bail!("Dimension '{}' not found", name);
+    };
+    print!("{}", export(dimension, format)?);
+    Ok(())
+}
+
+/// Render `dimension` in `format`. Task tabs have no single command, so they export as plain shells;
+/// watch tabs export their command without the rerun-on-change wrapper.
+pub fn export(dimension: &Dimension, format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Tmuxinator => Ok(tmuxinator(dimension)),
+        ExportFormat::Tmuxp => Ok(tmuxp(dimension)),
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(dimension)? + "\n"),
+        ExportFormat::Script => Ok(script(dimension)),
+    }
+}
+
+/// A `sh` script that creates the session with plain tmux (running `setup` first), then
+/// attaches to it, or switches to it from inside tmux. An existing session is left alone.
+fn script(dimension: &Dimension) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "#!/bin/sh");
+    let _ = writeln!(out, "# Recreates the '{}' dimension with tmux alone", dimension.name);
+    let _ = writeln!(out, "set -e");
+    let _ = writeln!(out, "session={}", shell_quote(&dimension.name));
+    let _ = writeln!(out);
+    let _ = writeln!(out, "if ! tmux has-session -t \"=$session\" 2>/dev/null; then");
+    if let Some(setup) = &dimension.setup {
+        match &dimension.base_dir {
+            Some(dir) => {
+                let _ = writeln!(out, "  (cd {} && {})", shell_quote(&dir.display().to_string()), setup);
+            }
+            None => {
+                let _ = writeln!(out, "  {}", setup);
+            }
+        }
+    }
+    for (i, tab) in dimension.configured_tabs.iter().enumerate() {
+        let tab = dimension.launched_tab(tab);
+        let create = if i == 0 {
+            "new-session -d -s \"$session\"".to_string()
+        } else {
+            "new-window -t \"=$session:\"".to_string()
+        };
+        let mut line = format!("  window=$(tmux {} -P -F '#{{window_id}}' -n {}", create, shell_quote(&tab.name));
+        if let Some(dir) = tab.working_dir.as_ref().or(dimension.base_dir.as_ref()) {
+            let _ = write!(line, " -c {}", shell_quote(&dir.display().to_string()));
+        }
+        let _ = writeln!(out, "{})", line);
+        if i == 0 {
+            let _ = writeln!(out, "  first=$window");
+        }
+        if let Some(command) = &tab.command {
+            let _ = writeln!(out, "  tmux send-keys -t \"$window\" {} Enter", shell_quote(command));
+        }
+    }
+    if dimension.configured_tabs.is_empty() {
+        let mut line = "  tmux new-session -d -s \"$session\"".to_string();
+        if let Some(dir) = &dimension.base_dir {
+            let _ = write!(line, " -c {}", shell_quote(&dir.display().to_string()));
+        }
+        let _ = writeln!(out, "{}", line);
+    } else {
+        let _ = writeln!(out, "  tmux select-window -t \"$first\"");
+    }
+    let _ = writeln!(out, "fi");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "if [ -n \"$TMUX\" ]; then");
+    let _ = writeln!(out, "  tmux switch-client -t \"=$session\"");
+    let _ = writeln!(out, "else");
+    let _ = writeln!(out, "  tmux attach-session -t \"=$session\"");
+    let _ = writeln!(out, "fi");
+    out
+}
+
+fn tmuxinator(dimension: &Dimension) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "name: {}", quote(&dimension.name));
+    if let Some(dir) = &dimension.base_dir {
+        let _ = writeln!(out, "root: {}", path(dir));
+    }
+    if let Some(setup) = &dimension.setup {
+        let _ = writeln!(out, "on_project_first_start: {}", quote(setup));
+    }
+    if let Some(teardown) = &dimension.teardown {
+        let _ = writeln!(out, "on_project_stop: {}", quote(teardown));
+    }
+    let _ = writeln!(out, "windows:");
+    for tab in &dimension.configured_tabs {
+        let name = quote(&tab.name);
+        match (&tab.working_dir, &tab.command) {
+            (None, None) => {
+                let _ = writeln!(out, "  - {}:", name);
+            }
+            (None, Some(command)) => {
+                let _ = writeln!(out, "  - {}: {}", name, quote(command));
+            }
+            (Some(dir), command) => {
+                let _ = writeln!(out, "  - {}:", name);
+                let _ = writeln!(out, "      root: {}", path(dir));
+                let _ = writeln!(out, "      panes:");
+                let _ = writeln!(out, "        - {}", command.as_deref().map(quote).unwrap_or_default());
+            }
+        }
+    }
+    out
+}
+
+fn tmuxp(dimension: &Dimension) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "session_name: {}", quote(&dimension.name));
+    if let Some(dir) = &dimension.base_dir {
+        let _ = writeln!(out, "start_directory: {}", path(dir));
+    }
+    let _ = writeln!(out, "windows:");
+    for tab in &dimension.configured_tabs {
+        let _ = writeln!(out, "  - window_name: {}", quote(&tab.name));
+        if let Some(dir) = &tab.working_dir {
+            let _ = writeln!(out, "    start_directory: {}", path(dir));
+        }
+        let _ = writeln!(out, "    panes:");
+        match &tab.command {
+            Some(command) => {
+                let _ = writeln!(out, "      - {}", quote(command));
+            }
+            None => {
+                let _ = writeln!(out, "      - null");
+            }
+        }
+    }
+    out
+}
+
+/// `dimensions import-script <file> [--name <name>]`: add the session a tmux script builds
+/// as a dimension; `-` reads the script from stdin
+pub fn import_script_cli(config: &mut DimensionConfig, file: &str, name: Option<&str>) -> Result<()> {
+    let contents = if file == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?
+    };
+    let dimension = parse_script(&contents, name)?;
+    if config.name_in_use(&dimension.name) {
+        anyhow::bail!("Dimension '{}' already exists; pick another with --name", dimension.name);
+    }
+    println!("Imported dimension '{}' with {} tabs", dimension.name, dimension.configured_tabs.len());
+    config.add_dimension(dimension);
+    config.save()?;
+    Ok(())
+}
+
+/// A window being built up while reading a script
+struct ScriptWindow {
+    name: Option<String>,
+    dir: Option<PathBuf>,
+    commands: Vec<String>,
+}
+
+/// Read the session a shell script of tmux commands creates: `new-session` and `new-window`
+/// (`-s`, `-n`, `-c`, and a trailing command) and `send-keys` ending in `Enter`, which becomes
+/// its window's command. Simple `var=value` assignments and `cd` are followed; everything
+/// else is skipped. The session's `-c` (or the directory cd'd to) becomes the base directory.
+pub fn parse_script(contents: &str, name: Option<&str>) -> Result<Dimension> {
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let mut cwd: Option<PathBuf> = None;
+    let mut session: Option<String> = None;
+    let mut base_dir: Option<PathBuf> = None;
+    let mut windows: Vec<ScriptWindow> = vec![];
+
+    for line in contents.replace("\\\n", " ").lines() {
+        let words = shell_words(line, &vars);
+        let Some(first) = words.first() else {
+            continue;
+        };
+        if first == "cd" {
+            cwd = words.get(1).map(PathBuf::from);
+            continue;
+        }
+        if let Some((var, value)) = first.split_once('=') {
+            let is_name = !var.is_empty() && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if is_name && words.len() == 1 {
+                vars.insert(var.to_string(), value.to_string());
+                continue;
+            }
+        }
+        // `tmux ...`, possibly inside `$(...)` or after `if !`
+        let Some(start) = words.iter().position(|w| w == "tmux" || w.ends_with("(tmux")) else {
+            continue;
+        };
+        let mut args: Vec<String> = words[start + 1..].to_vec();
+        if let Some(last) = args.last_mut().filter(|last| last.ends_with(')')) {
+            last.pop();
+        }
+        let Some((command, args)) = args.split_first() else {
+            continue;
+        };
+        match command.as_str() {
+            "new-session" | "new" | "new-window" | "neww" => {
+                let (flags, rest) = tmux_flags(args, &["s", "n", "c", "t", "F", "x", "y", "e"]);
+                let dir = flags.get("c").map(PathBuf::from).or_else(|| cwd.clone());
+                if matches!(command.as_str(), "new-session" | "new") {
+                    session = session.or_else(|| flags.get("s").cloned());
+                    base_dir = base_dir.or_else(|| dir.clone());
+                }
+                let command = rest.join(" ");
+                windows.push(ScriptWindow {
+                    name: flags.get("n").cloned(),
+                    dir,
+                    commands: if command.is_empty() { vec![] } else { vec![command] },
+                });
+            }
+            "send-keys" | "send" => {
+                let (flags, keys) = tmux_flags(args, &["t", "N"]);
+                let Some((enter, keys)) = keys.split_last() else {
+                    continue;
+                };
+                if !matches!(enter.as_str(), "Enter" | "C-m") || keys.is_empty() {
+                    continue;
+                }
+                // `session:window` names a window; anything else goes to the newest one
+                let target = flags.get("t").and_then(|t| t.rsplit_once(':')).map(|(_, w)| w.to_string());
+                let window = match target.and_then(|t| windows.iter().position(|w| w.name.as_deref() == Some(t.as_str()))) {
+                    Some(i) => windows.get_mut(i),
+                    None => windows.last_mut(),
+                };
+                if let Some(window) = window {
+                    window.commands.push(keys.join(" "));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Some(name) = name.map(str::to_string).or(session) else {
+        anyhow::bail!("The script doesn't name its session (new-session -s); pass --name");
+    };
+    if windows.is_empty() {
+        anyhow::bail!("No new-session or new-window commands found");
+    }
+    let mut dimension = Dimension::new_with_base_dir(name.clone(), base_dir.clone());
+    for (i, window) in windows.into_iter().enumerate() {
+        let tab_name = window.name.unwrap_or_else(|| format!("{}-{}", name, i + 1));
+        let command = (!window.commands.is_empty()).then(|| window.commands.join(" && "));
+        let dir = window.dir.filter(|dir| Some(dir) != base_dir.as_ref());
+        dimension.add_tab(Tab::new(tab_name, command, dir));
+    }
+    Ok(dimension)
+}
+
+/// Split tmux arguments into `-x value` flags (for the letters in `with_value`) and the rest
+fn tmux_flags(args: &[String], with_value: &[&str]) -> (HashMap<String, String>, Vec<String>) {
+    let mut flags = HashMap::new();
+    let mut i = 0;
+    while let Some(arg) = args.get(i) {
+        let Some(letters) = arg.strip_prefix('-').filter(|l| !l.is_empty()) else {
+            break;
+        };
+        i += 1;
+        // Flags can be bunched (`-dP`); one that takes a value ends the bunch
+        for (pos, letter) in letters.char_indices() {
+            let letter = letter.to_string();
+            if with_value.contains(&letter.as_str()) {
+                let attached = &letters[pos + 1..];
+                let value = if attached.is_empty() {
+                    i += 1;
+                    args.get(i - 1).cloned().unwrap_or_default()
+                } else {
+                    attached.to_string()
+                };
+                flags.insert(letter, value);
+                break;
+            }
+        }
+    }
+    (flags, args[i.min(args.len())..].to_vec())
+}
+
+/// Words of a shell command line: quotes and backslashes removed, `$var` / `${var}` expanded
+/// from `vars` (outside single quotes), and a `#` comment cut off
+fn shell_words(line: &str, vars: &HashMap<String, String>) -> Vec<String> {
+    let mut words = vec![];
+    let mut word: Option<String> = None;
+    let mut chars = line.chars().peekable();
+    let mut double = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if !double => {
+                let current = word.get_or_insert_with(String::new);
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    current.push(c);
+                }
+            }
+            '"' => {
+                double = !double;
+                word.get_or_insert_with(String::new);
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    word.get_or_insert_with(String::new).push(next);
+                }
+            }
+            '$' if matches!(chars.peek(), Some(c) if c.is_ascii_alphabetic() || *c == '_' || *c == '{') => {
+                let braced = chars.next_if_eq(&'{').is_some();
+                let mut var = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    var.push(c);
+                }
+                if braced {
+                    chars.next_if_eq(&'}');
+                }
+                let current = word.get_or_insert_with(String::new);
+                match vars.get(&var) {
+                    Some(value) => current.push_str(value),
+                    None if braced => current.push_str(&format!("${{{}}}", var)),
+                    None => current.push_str(&format!("${}", var)),
+                }
+            }
+            '#' if !double && word.is_none() => break,
+            c if c.is_whitespace() && !double => {
+                if let Some(done) = word.take() {
+                    words.push(done);
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+/// A double-quoted YAML scalar; JSON string escaping is valid YAML, so serde_json does the work
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+fn path(dir: &Path) -> String {
+    quote(&dir.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api() -> Dimension {
+        let mut dimension = Dimension::new_with_base_dir("api".to_string(), Some("/srv/api".into()));
+        dimension.configured_tabs = vec![
+            Tab::new("editor".to_string(), Some("nvim".to_string()), None),
+            Tab::new("server".to_string(), Some("cargo run".to_string()), Some("/srv/api/server".into())),
+            Tab::new("shell".to_string(), None, None),
+        ];
+        dimension
+    }
+
+    #[test]
+    fn exports_tmuxinator_and_tmuxp_yaml() {
+        assert_eq!(
+            export(&api(), ExportFormat::Tmuxinator).unwrap(),
+            "name: \"api\"\nroot: \"/srv/api\"\nwindows:\n  - \"editor\": \"nvim\"\n  - \"server\":\n      root: \"/srv/api/server\"\n      panes:\n        - \"cargo run\"\n  - \"shell\":\n"
+        );
+        assert_eq!(
+            export(&api(), ExportFormat::Tmuxp).unwrap(),
+            "session_name: \"api\"\nstart_directory: \"/srv/api\"\nwindows:\n  - window_name: \"editor\"\n    panes:\n      - \"nvim\"\n  - window_name: \"server\"\n    start_directory: \"/srv/api/server\"\n    panes:\n      - \"cargo run\"\n  - window_name: \"shell\"\n    panes:\n      - null\n"
+        );
+    }
+
+    #[test]
+    fn exports_a_tmux_script() {
+        let mut dimension = api();
+        dimension.setup = Some("docker compose up -d".to_string());
+        let script = export(&dimension, ExportFormat::Script).unwrap();
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(lines[0], "#!/bin/sh");
+        assert!(lines.contains(&"  (cd '/srv/api' && docker compose up -d)"));
+        assert!(lines.contains(&"  window=$(tmux new-session -d -s \"$session\" -P -F '#{window_id}' -n 'editor' -c '/srv/api')"));
+        assert!(lines.contains(&"  window=$(tmux new-window -t \"=$session:\" -P -F '#{window_id}' -n 'server' -c '/srv/api/server')"));
+        assert!(lines.contains(&"  tmux send-keys -t \"$window\" 'cargo run' Enter"));
+        assert_eq!(script.matches("send-keys").count(), 2);
+        assert!(script.ends_with("  tmux attach-session -t \"=$session\"\nfi\n"));
+    }
+
+    fn tabs(dimension: &Dimension) -> Vec<(String, Option<String>, Option<PathBuf>)> {
+        dimension.configured_tabs.iter().map(|t| (t.name.clone(), t.command.clone(), t.working_dir.clone())).collect()
+    }
+
+    #[test]
+    fn imports_an_exported_script() {
+        let script = export(&api(), ExportFormat::Script).unwrap();
+        let imported = parse_script(&script, None).unwrap();
+        assert_eq!(imported.name, "api");
+        assert_eq!(imported.base_dir, Some("/srv/api".into()));
+        assert_eq!(tabs(&imported), tabs(&api()));
+    }
+
+    #[test]
+    fn imports_a_hand_written_script() {
+        let script = "#!/bin/bash\n\
+            cd ~/code/web\n\
+            tmux new-session -d -s web -n code\n\
+            tmux send-keys -t web:code \"nvim .\" C-m\n\
+            tmux new-window -t web -n logs -c /var/log 'tail -f app.log' # follow\n\
+            tmux new-window -t web\n\
+            tmux send-keys -t web:code 'echo ready' Enter\n\
+            tmux send-keys -t web:3 'npm run dev' Enter\n\
+            tmux attach -t web\n";
+        let imported = parse_script(script, Some("frontend")).unwrap();
+        assert_eq!(imported.name, "frontend");
+        assert_eq!(imported.base_dir, Some("~/code/web".into()));
+        assert_eq!(
+            tabs(&imported),
+            vec![
+                ("code".to_string(), Some("nvim . && echo ready".to_string()), None),
+                ("logs".to_string(), Some("tail -f app.log".to_string()), Some("/var/log".into())),
+                ("frontend-3".to_string(), Some("npm run dev".to_string()), None),
+            ]
+        );
+        assert!(parse_script("echo hi\n", Some("x")).is_err());
+        assert!(parse_script("tmux new-window\n", None).is_err());
+    }
+}
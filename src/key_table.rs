@@ -0,0 +1,93 @@
+use crate::dimension::{Dimension, PopupSettings};
+use crate::popup;
+use crate::tmux::Tmux;
+use anyhow::Result;
+use regex::{NoExpand, Regex};
+
+/// tmux key table holding a dimension's bindings
+pub fn table_name(session: &str) -> String {
+    format!("dimensions-{}", session)
+}
+
+/// tmux command, as arguments, for a configured key action
+pub fn action_command(action: &str, popup: &PopupSettings) -> Vec<String> {
+    match action {
+        "next-tab" => vec!["next-window".to_string()],
+        "previous-tab" => vec!["previous-window".to_string()],
+        "dimensions" => popup::args(popup, &[popup::exe()]),
+        _ => match action.strip_prefix("run:") {
+            // Typed into the active pane, e.g. "run:cargo test"
+            Some(command) => vec!["send-keys".to_string(), command.trim().to_string(), "Enter".to_string()],
+            None => split_words(action),
+        },
+    }
+}
+
+/// Split a tmux command line on whitespace, keeping quoted words together
+fn split_words(input: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_word = false;
+    for ch in input.chars() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '\'' || ch == '"' => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+/// Make `dimension.keys` the default key table of its session. tmux only consults
+/// a session's own table (not root) for prefix-free keys, so root bindings such as
+/// the mouse ones are copied in first and the configured keys layered on top.
+pub fn apply(dimension: &Dimension, popup: &PopupSettings) -> Result<()> {
+    if dimension.keys.is_empty() {
+        return Ok(());
+    }
+    let table = table_name(&dimension.name);
+    Tmux::unbind_table(&table);
+
+    let root = Tmux::list_keys("root")?;
+    let retarget = Regex::new(r"-T\s+root\b").unwrap();
+    let quoted_table = format!("'{}'", table.replace('\'', "'\\''"));
+    let copied: Vec<String> = root
+        .lines()
+        .filter(|line| line.starts_with("bind-key"))
+        .map(|line| retarget.replacen(line, 1, NoExpand(&format!("-T {}", quoted_table))).into_owned())
+        .collect();
+    if !copied.is_empty() {
+        let path = std::env::temp_dir().join(format!("dimensions-keys-{}.conf", std::process::id()));
+        std::fs::write(&path, copied.join("\n"))?;
+        let sourced = Tmux::source_file(&path);
+        let _ = std::fs::remove_file(&path);
+        sourced?;
+    }
+
+    for (key, action) in &dimension.keys {
+        Tmux::bind_key(&table, key, &action_command(action, popup))?;
+    }
+    Tmux::set_session_option(&dimension.name, "key-table", &table)
+}
+
+/// Drop a killed session's key table
+pub fn remove(session: &str) {
+    Tmux::unbind_table(&table_name(session));
+}
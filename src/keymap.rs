@@ -0,0 +1,313 @@
+//! Remappable keys for the main view, from the `keybindings` section of the config.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Something a key does in the main view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Down,
+    Up,
+    Left,
+    Right,
+    FocusPane,
+    Switch,
+    LastTab,
+    NewDimension,
+    NewFromTemplate,
+    Here,
+    Suggestions,
+    NewTab,
+    Delete,
+    Undo,
+    Rename,
+    Repoint,
+    Search,
+    Jump,
+    Command,
+    WindowMenu,
+    QuickActions,
+    OpenDir,
+    OpenLink,
+    Sync,
+    Reveal,
+    Presentation,
+    MoveDimensionDown,
+    MoveDimensionUp,
+    MoveTabLeft,
+    MoveTabRight,
+    ToggleCollapse,
+    Preview,
+    Matrix,
+    Close,
+    Quit,
+}
+
+const DEFAULTS: &[(Action, &[&str])] = &[
+    (Action::Down, &["j", "down"]),
+    (Action::Up, &["k", "up"]),
+    (Action::Left, &["h", "left"]),
+    (Action::Right, &["l", "right"]),
+    (Action::FocusPane, &["tab"]),
+    (Action::Switch, &["enter"]),
+    (Action::LastTab, &["G"]),
+    (Action::NewDimension, &["n"]),
+    (Action::NewFromTemplate, &["T"]),
+    (Action::Here, &["g"]),
+    (Action::Suggestions, &["D"]),
+    (Action::NewTab, &["t"]),
+    (Action::Delete, &["d"]),
+    (Action::Undo, &["u"]),
+    (Action::Rename, &["r"]),
+    (Action::Repoint, &["R"]),
+    (Action::Search, &["/"]),
+    // `:3` on the command line does what `jump` did on `:`
+    (Action::Jump, &[]),
+    (Action::Command, &[":"]),
+    (Action::WindowMenu, &["w"]),
+    (Action::QuickActions, &["a"]),
+    (Action::OpenDir, &["o"]),
+    (Action::OpenLink, &["O"]),
+    (Action::Sync, &["S"]),
+    (Action::Reveal, &["v"]),
+    (Action::Presentation, &["P"]),
+    (Action::MoveDimensionDown, &["J"]),
+    (Action::MoveDimensionUp, &["K"]),
+    (Action::MoveTabLeft, &["H"]),
+    (Action::MoveTabRight, &["L"]),
+    (Action::ToggleCollapse, &["c"]),
+    (Action::Preview, &["p"]),
+    (Action::Matrix, &["m"]),
+    (Action::Close, &["esc"]),
+    (Action::Quit, &["q"]),
+];
+
+/// A key with modifiers, written like `j`, `G`, `enter`, `ctrl+n` or `alt+down`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        loop {
+            let lower = rest.to_lowercase();
+            if let Some(stripped) = lower.strip_prefix("ctrl+") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[rest.len() - stripped.len()..];
+            } else if let Some(stripped) = lower.strip_prefix("alt+") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[rest.len() - stripped.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let code = match rest.to_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower => match (lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()), single_char(rest)) {
+                (Some(n), _) if (1..=12).contains(&n) => KeyCode::F(n),
+                (_, Some(c)) => KeyCode::Char(c),
+                _ => return Err(format!("unknown key '{}'", spec)),
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    fn from_event(event: &KeyEvent) -> Self {
+        // Shift is already part of an uppercase/symbol character
+        let modifiers = match event.code {
+            KeyCode::Char(_) => event.modifiers.difference(KeyModifiers::SHIFT),
+            _ => event.modifiers,
+        };
+        Self { code: event.code, modifiers }
+    }
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// A key as written in the config, checked when the config is parsed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyName(String);
+
+impl TryFrom<String> for KeyName {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, String> {
+        Key::parse(&spec)?;
+        Ok(Self(spec))
+    }
+}
+
+impl From<KeyName> for String {
+    fn from(name: KeyName) -> Self {
+        name.0
+    }
+}
+
+/// Keys for one action in the config: a single key or a list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(KeyName),
+    Many(Vec<KeyName>),
+}
+
+impl KeySpec {
+    fn keys(&self) -> Vec<Key> {
+        let names = match self {
+            KeySpec::One(name) => std::slice::from_ref(name),
+            KeySpec::Many(names) => names.as_slice(),
+        };
+        names.iter().filter_map(|name| Key::parse(&name.0).ok()).collect()
+    }
+}
+
+/// Key → action lookup. A configured action replaces all of its default keys, and
+/// configured keys win over defaults bound to something else.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    actions: HashMap<Key, Action>,
+    keys: HashMap<Action, Vec<Key>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+impl Keymap {
+    pub fn new(bindings: &BTreeMap<Action, KeySpec>) -> Self {
+        let mut keys: HashMap<Action, Vec<Key>> = HashMap::new();
+        for (action, defaults) in DEFAULTS {
+            keys.insert(*action, defaults.iter().filter_map(|k| Key::parse(k).ok()).collect());
+        }
+        for (action, spec) in bindings {
+            keys.insert(*action, spec.keys());
+        }
+
+        let mut actions = HashMap::new();
+        for (action, _) in DEFAULTS {
+            if !bindings.contains_key(action) {
+                for key in &keys[action] {
+                    actions.insert(*key, *action);
+                }
+            }
+        }
+        for action in bindings.keys() {
+            for key in &keys[action] {
+                actions.insert(*key, *action);
+            }
+        }
+        Self { actions, keys }
+    }
+
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        self.actions.get(&Key::from_event(event)).copied()
+    }
+
+    /// Whether `code` (without modifiers) triggers `action`; for the small menus
+    pub fn is(&self, action: Action, code: KeyCode) -> bool {
+        self.actions.get(&Key { code, modifiers: KeyModifiers::NONE }) == Some(&action)
+    }
+
+    /// First key bound to `action`, for help text
+    pub fn label(&self, action: Action) -> String {
+        self.keys
+            .get(&action)
+            .and_then(|keys| keys.first())
+            .map(|key| key.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(spec: &str) -> KeyName {
+        KeyName::try_from(spec.to_string()).unwrap()
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn parses_named_and_modified_keys() {
+        assert_eq!(Key::parse("G").unwrap().code, KeyCode::Char('G'));
+        assert_eq!(Key::parse("Enter").unwrap().code, KeyCode::Enter);
+        let ctrl_n = Key::parse("ctrl+n").unwrap();
+        assert_eq!((ctrl_n.code, ctrl_n.modifiers), (KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(Key::parse("f5").unwrap().code, KeyCode::F(5));
+        assert!(Key::parse("nope").is_err());
+    }
+
+    #[test]
+    fn unknown_keys_fail_to_parse() {
+        let parsed: Result<BTreeMap<Action, KeySpec>, _> = serde_json::from_str(r#"{ "down": "nope" }"#);
+        assert!(parsed.is_err());
+        let parsed: BTreeMap<Action, KeySpec> = serde_json::from_str(r#"{ "down": ["n", "down"] }"#).unwrap();
+        assert_eq!(parsed[&Action::Down], KeySpec::Many(vec![key("n"), key("down")]));
+    }
+
+    #[test]
+    fn configured_keys_replace_defaults_and_win_conflicts() {
+        let bindings = BTreeMap::from([
+            (Action::Down, KeySpec::Many(vec![key("n"), key("down")])),
+            (Action::Up, KeySpec::One(key("e"))),
+        ]);
+        let keymap = Keymap::new(&bindings);
+        assert_eq!(keymap.action(&press(KeyCode::Char('n'))), Some(Action::Down));
+        assert_eq!(keymap.action(&press(KeyCode::Char('e'))), Some(Action::Up));
+        assert_eq!(keymap.action(&press(KeyCode::Char('j'))), None);
+        assert_eq!(keymap.action(&press(KeyCode::Char('t'))), Some(Action::NewTab));
+        assert_eq!(keymap.label(Action::Up), "e");
+    }
+}
@@ -0,0 +1,95 @@
+//! The `dimensions` tmux tab manager. The `dimensions` binary is one frontend; others (a GTK
+//! window, a Raycast extension, a web page) can drive the same dimensions through [`engine`],
+//! which is the supported API. The other modules are public for the binary's sake and may change.
+
+#[doc(hidden)]
+pub mod app;
+#[doc(hidden)]
+pub mod backend;
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod colors;
+#[doc(hidden)]
+pub mod command_line;
+#[doc(hidden)]
+pub mod config_store;
+#[doc(hidden)]
+pub mod dimension;
+#[doc(hidden)]
+pub mod discover;
+pub mod engine;
+#[doc(hidden)]
+pub mod events;
+#[doc(hidden)]
+pub mod expanded;
+#[doc(hidden)]
+pub mod hooks;
+#[doc(hidden)]
+pub mod icons;
+#[doc(hidden)]
+pub mod interop;
+#[doc(hidden)]
+pub mod key_table;
+#[doc(hidden)]
+pub mod keymap;
+#[doc(hidden)]
+pub mod machine;
+#[doc(hidden)]
+pub mod maintenance;
+#[doc(hidden)]
+pub mod path_completion;
+#[doc(hidden)]
+pub mod matcher;
+#[doc(hidden)]
+pub mod paths;
+#[doc(hidden)]
+pub mod popup;
+#[doc(hidden)]
+pub mod profile;
+#[doc(hidden)]
+pub mod readiness;
+#[doc(hidden)]
+pub mod remote;
+#[doc(hidden)]
+pub mod remote_template;
+#[doc(hidden)]
+pub mod scope;
+#[doc(hidden)]
+pub mod search_history;
+#[doc(hidden)]
+pub mod search_index;
+#[doc(hidden)]
+pub mod serve;
+#[doc(hidden)]
+pub mod snapshot;
+#[doc(hidden)]
+pub mod ssh_config;
+#[doc(hidden)]
+pub mod stale;
+#[doc(hidden)]
+pub mod sync;
+#[doc(hidden)]
+pub mod template;
+#[doc(hidden)]
+pub mod tmux;
+#[doc(hidden)]
+pub mod tmux_control;
+#[doc(hidden)]
+pub mod tmux_options;
+#[doc(hidden)]
+pub mod tmux_state;
+#[doc(hidden)]
+pub mod tutorial;
+#[doc(hidden)]
+pub mod ui;
+#[doc(hidden)]
+pub mod update;
+#[doc(hidden)]
+pub mod usage;
+#[doc(hidden)]
+pub mod uri;
+#[doc(hidden)]
+pub mod watch;
+#[doc(hidden)]
+pub mod zoxide;
@@ -0,0 +1,150 @@
+use crate::dimension::Dimension;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Per-host tweaks from the `machines` config section, keyed by hostname
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MachineOverride {
+    // Dimensions hidden on this machine
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<String>,
+
+    // Replacement base_dir per dimension name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub base_dirs: BTreeMap<String, PathBuf>,
+
+    // Path prefix rewrites applied to every base/working dir (e.g. /home/me -> /Users/me)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub path_prefixes: BTreeMap<PathBuf, PathBuf>,
+}
+
+/// What was changed while resolving an override, so saving can write the
+/// shared, machine-independent config back unchanged
+#[derive(Debug, Clone, Default)]
+pub struct AppliedOverride {
+    pub hostname: String,
+    overrides: MachineOverride,
+    // Disabled dimensions with their original position in the file
+    hidden: Vec<(usize, Dimension)>,
+    // base_dir as written in the file, for dimensions with a `base_dirs` entry
+    original_base_dirs: BTreeMap<String, Option<PathBuf>>,
+}
+
+impl AppliedOverride {
+    /// Whether `name` is one of the dimensions disabled on this machine
+    pub fn hides(&self, name: &str) -> bool {
+        self.hidden.iter().any(|(_, d)| d.name == name)
+    }
+
+    /// Rewrite one dimension's paths for this machine, e.g. one coming back from the trash
+    pub fn resolve(&mut self, dimension: &mut Dimension) {
+        rewrite_paths(dimension, &self.overrides.path_prefixes);
+        if let Some(dir) = self.overrides.base_dirs.get(&dimension.name) {
+            self.original_base_dirs.insert(dimension.name.clone(), dimension.base_dir.clone());
+            dimension.base_dir = Some(dir.clone());
+        }
+    }
+
+    /// Undo `resolve` on a copy of one dimension, giving back its paths as in the file
+    pub fn unresolve(&self, dimension: &mut Dimension) {
+        let reversed: BTreeMap<PathBuf, PathBuf> = self
+            .overrides
+            .path_prefixes
+            .iter()
+            .map(|(from, to)| (to.clone(), from.clone()))
+            .collect();
+
+        // Overridden base_dir that wasn't edited since load: restore the file's value
+        let restored_base = match self.original_base_dirs.get(&dimension.name) {
+            Some(original) if dimension.base_dir.as_ref() == self.overrides.base_dirs.get(&dimension.name) => {
+                Some(original.clone())
+            }
+            _ => None,
+        };
+
+        rewrite_paths(dimension, &reversed);
+        if let Some(original) = restored_base {
+            dimension.base_dir = original;
+        }
+    }
+}
+
+/// This machine's hostname; `DIMENSIONS_HOSTNAME` overrides detection
+pub fn hostname() -> Option<String> {
+    if let Ok(name) = std::env::var("DIMENSIONS_HOSTNAME") {
+        return Some(name);
+    }
+    let output = Command::new("hostname").output().ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Find the override for `hostname`: exact match first, then the short name (before the first '.')
+pub fn find_override<'a>(
+    machines: &'a BTreeMap<String, MachineOverride>,
+    hostname: &str,
+) -> Option<&'a MachineOverride> {
+    machines.get(hostname).or_else(|| {
+        let short = hostname.split('.').next()?;
+        machines.get(short)
+    })
+}
+
+fn rewrite_prefix(path: &Path, prefixes: &BTreeMap<PathBuf, PathBuf>) -> Option<PathBuf> {
+    prefixes
+        .iter()
+        .find_map(|(from, to)| path.strip_prefix(from).ok().map(|rest| to.join(rest)))
+}
+
+fn rewrite_paths(dimension: &mut Dimension, prefixes: &BTreeMap<PathBuf, PathBuf>) {
+    let rewrite = |path: &mut Option<PathBuf>| {
+        if let Some(new) = path.as_deref().and_then(|p| rewrite_prefix(p, prefixes)) {
+            *path = Some(new);
+        }
+    };
+    rewrite(&mut dimension.base_dir);
+    for tab in &mut dimension.configured_tabs {
+        rewrite(&mut tab.working_dir);
+    }
+}
+
+/// Apply `overrides` to the loaded dimensions in place
+pub fn apply(
+    hostname: String,
+    overrides: &MachineOverride,
+    dimensions: &mut Vec<Dimension>,
+) -> AppliedOverride {
+    let mut applied = AppliedOverride {
+        hostname,
+        overrides: overrides.clone(),
+        ..Default::default()
+    };
+
+    let mut kept = vec![];
+    for (pos, mut dimension) in std::mem::take(dimensions).into_iter().enumerate() {
+        if overrides.disabled.contains(&dimension.name) {
+            applied.hidden.push((pos, dimension));
+            continue;
+        }
+        applied.resolve(&mut dimension);
+        kept.push(dimension);
+    }
+
+    *dimensions = kept;
+    applied
+}
+
+/// Undo `apply` on a copy of the dimensions before writing them to disk
+pub fn unapply(applied: &AppliedOverride, dimensions: &mut Vec<Dimension>) {
+    for dimension in dimensions.iter_mut() {
+        applied.unresolve(dimension);
+    }
+
+    for (pos, dimension) in &applied.hidden {
+        let pos = (*pos).min(dimensions.len());
+        dimensions.insert(pos, dimension.clone());
+    }
+}
@@ -1,29 +1,64 @@
-mod app;
-mod dimension;
-mod path_completion;
-mod tmux;
-mod ui;
-mod update;
+use dimensions::{
+    app, cli, dimension, discover, events, hooks, interop, keymap, maintenance, paths, popup, profile,
+    remote_template, scope, serve, snapshot, ssh_config, stale, tmux, tutorial, ui, update, usage, uri, watch,
+};
 
 use anyhow::Result;
-use app::{App, InputMode};
+use app::{App, InputMode, Pane};
+use clap::Parser;
+use cli::{Cli, Command, ConfigCommand};
+use keymap::Action;
+use dimension::{ConfigError, DimensionConfig};
+use scope::Scope;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
-use tmux::Tmux;
+use tmux::{Tmux, TmuxServer};
 
 fn main() -> Result<()> {
-    // Lightweight CLI flags (before terminal init).
-    let args: Vec<String> = std::env::args().collect();
-    if args.iter().any(|a| a == "--version" || a == "-v") {
+    let launched = std::time::Instant::now();
+    let cli = Cli::parse();
+    if cli.profile_startup {
+        profile::enable();
+    }
+    // The tutorial claims the config dir override first, so it never reads the real config
+    let tutorial = if matches!(cli.command, Some(Command::Tutorial)) {
+        Some(tutorial::Tutorial::setup()?)
+    } else {
+        None
+    };
+    if let Some(dir) = &cli.config {
+        paths::set_config_dir_override(dir.clone());
+    }
+    match &cli.command {
+        Some(Command::Replace { pattern, replacement, regex, yes }) => {
+            return maintenance::replace_commands_cli(pattern, replacement, *regex, *yes).or_else(exit_with_error);
+        }
+        Some(Command::Watch { glob, command }) => return watch::run(glob, command).or_else(exit_with_error),
+        Some(Command::Report { days, json }) => return usage::report_cli(*days, *json).or_else(exit_with_error),
+        Some(Command::RegisterHandler) => return uri::register_handler().or_else(exit_with_error),
+        Some(Command::Check) => match maintenance::check_cli() {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => {}
+    }
+    if cli.version {
         println!("dimensions v{}", env!("CARGO_PKG_VERSION"));
         return Ok(());
     }
-    if args.iter().any(|a| a == "--update" || a == "-u") {
+    if cli.update {
         let current = env!("CARGO_PKG_VERSION");
         let Some(tag) = update::latest_tag() else {
             eprintln!("Could not check for updates right now.");
@@ -105,17 +140,112 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    // Load config before touching the terminal so parse errors print cleanly
+    let mut config = match profile::time("config load", DimensionConfig::load) {
+        Ok(config) => config,
+        Err(e) => {
+            report_config_error(&e);
+            std::process::exit(1);
+        }
+    };
+
+    // Which tmux server to drive: --tmux-socket-name/--tmux-socket-path beat the config
+    let socket_path = cli.tmux_socket_path.clone().or_else(|| config.tmux.socket_path.clone());
+    let socket_name = cli.tmux_socket_name.clone().or_else(|| config.tmux.socket_name.clone());
+    if let Some(path) = socket_path {
+        Tmux::use_server(TmuxServer::SocketPath(path));
+    } else if let Some(name) = socket_name {
+        Tmux::use_server(TmuxServer::SocketName(name));
+    }
+
+    events::configure(&config.events);
+
+    // Scripting subcommands act on the same server, without the TUI
+    match cli.command {
+        Some(Command::List) => return cli::list(config).or_else(exit_with_error),
+        Some(Command::New { name, dir }) => {
+            return cli::new(config, &name, dir.as_deref()).or_else(exit_with_error);
+        }
+        Some(Command::Switch { name }) => return cli::switch(config, &name).or_else(exit_with_error),
+        Some(Command::Next) => return cli::cycle(config, true).or_else(exit_with_error),
+        Some(Command::Prev) => return cli::cycle(config, false).or_else(exit_with_error),
+        Some(Command::Open { uri }) => return cli::open(config, &uri).or_else(exit_with_error),
+        Some(Command::Here { template }) => return cli::here(config, template.as_deref()).or_else(exit_with_error),
+        Some(Command::Delete { name, yes }) => return cli::delete(config, &name, yes).or_else(exit_with_error),
+        Some(Command::Undelete { name }) => return cli::undelete(config, name.as_deref()).or_else(exit_with_error),
+        Some(Command::Hibernate { name }) => return cli::hibernate(config, &name).or_else(exit_with_error),
+        Some(Command::Thaw { name }) => return cli::thaw(config, &name).or_else(exit_with_error),
+        Some(Command::Discover { dir, depth, yes }) => {
+            return discover::discover_cli(&mut config, dir.as_deref(), depth, yes).or_else(exit_with_error);
+        }
+        Some(Command::Ssh { hosts, yes }) => {
+            return ssh_config::ssh_cli(&mut config, &hosts, yes).or_else(exit_with_error);
+        }
+        Some(Command::Init { from, name, dir, vars }) => {
+            return remote_template::init_cli(&mut config, &from, name.as_deref(), dir.as_deref(), &vars)
+                .or_else(exit_with_error);
+        }
+        Some(Command::Gc { suggest, months, yes }) => {
+            return stale::gc_cli(&mut config, suggest, months, yes).or_else(exit_with_error);
+        }
+        Some(Command::Snapshot { name, all }) => {
+            return snapshot::snapshot_cli(&config, name.as_deref(), all).or_else(exit_with_error);
+        }
+        Some(Command::Restore { name, all }) => {
+            return snapshot::restore_cli(&config, name.as_deref(), all).or_else(exit_with_error);
+        }
+        Some(Command::Export { name, format }) => {
+            return interop::export_cli(&config, &name, format).or_else(exit_with_error);
+        }
+        Some(Command::ExportScript { name }) => {
+            return interop::export_cli(&config, &name, interop::ExportFormat::Script).or_else(exit_with_error);
+        }
+        Some(Command::ImportScript { file, name }) => {
+            return interop::import_script_cli(&mut config, &file, name.as_deref()).or_else(exit_with_error);
+        }
+        Some(Command::Config { command: ConfigCommand::Show { json, toml: _ } }) => {
+            return cli::config_show(&config, json).or_else(exit_with_error);
+        }
+        Some(Command::Emit { event, dimension }) => {
+            return events::emit_cli(&config, event, dimension.as_deref()).or_else(exit_with_error);
+        }
+        Some(Command::Serve { port, bind }) => return serve::serve_cli(port, &bind).or_else(exit_with_error),
+        Some(Command::Popup { width, height, x, y, border, title, args }) => {
+            let mut settings = config.popup.clone();
+            settings.width = width.unwrap_or(settings.width);
+            settings.height = height.unwrap_or(settings.height);
+            settings.x = x.or(settings.x);
+            settings.y = y.or(settings.y);
+            settings.border = border.or(settings.border);
+            settings.title = title.or(settings.title);
+            return popup::popup_cli(&settings, &args).or_else(exit_with_error);
+        }
+        _ => {}
+    }
+
+    // Optional scoping, e.g. one popup binding for `--scope work`, another for `--only 'home/*'`
+    match launch_scope(&cli, &config) {
+        Ok(Some((scope, label))) => config.apply_scope(&scope, label),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+
     // Setup terminal
     if let Err(e) = enable_raw_mode() {
         eprintln!("Error: Cannot start Dimensions from within another TUI application.");
         eprintln!("       Exit the current TUI first, or use a tmux popup keybinding.");
         eprintln!("       Tip: bind any key (commonly Ctrl+G) to a popup in ~/.tmux.conf, e.g.:");
-        eprintln!("         bind -n C-g display-popup -E -w 80% -h 80% \"dimensions\"");
+        eprintln!("         bind -n C-g {}", popup::tmux_line(&config.popup, "dimensions"));
         eprintln!("\nTechnical error: {:?}", e);
         std::process::exit(1);
     }
 
-    let mut stdout = io::stdout();
+    // When picking, stdout is for the answer (often captured by a script), so draw on stderr
+    let pick = cli.pick;
+    let mut stdout: Box<dyn io::Write> = if pick { Box::new(io::stderr()) } else { Box::new(io::stdout()) };
     if let Err(e) = execute!(stdout, EnterAlternateScreen, EnableMouseCapture) {
         eprintln!("Error: Cannot initialize terminal interface.");
         eprintln!("       Make sure you're running this in a proper terminal.");
@@ -127,7 +257,14 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new()?;
+    let mut app = profile::time("tmux state snapshot (App::new)", || App::new(config))?;
+    app.tutorial = tutorial;
+    if pick {
+        app.start_pick();
+    }
+    if cli.here {
+        app.start_here();
+    }
 
     // Run the app
     let res = run_app(&mut terminal, &mut app);
@@ -136,6 +273,11 @@ fn main() -> Result<()> {
     let should_attach = app.should_attach.clone();
     let should_select_window = app.should_select_window;
     let should_detach = app.should_detach;
+    let should_detach_others = app.should_detach_others;
+    let should_attach_read_only = app.should_attach_read_only;
+    let should_edit_config = app.should_edit_config;
+    let notice = app.exit_notice();
+    let here = app.here;
 
     // Restore terminal
     disable_raw_mode()?;
@@ -146,61 +288,278 @@ fn main() -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    if profile::is_enabled() {
+        profile::report(launched.elapsed());
+        return res;
+    }
+
+    if let Some(tutorial) = app.tutorial.take() {
+        tutorial.cleanup();
+        if tutorial.is_finished() {
+            println!("Tutorial complete. Run `dimensions` to set up your own dimensions.");
+        } else {
+            println!("Tutorial closed. Run `dimensions tutorial` to start over.");
+        }
+        return Ok(());
+    }
+
     if let Err(err) = res {
         eprintln!("Error: {:?}", err);
         return Ok(());
     }
 
+    if pick {
+        // Nothing picked exits non-zero, so scripts can tell
+        let Some(session) = should_attach else {
+            std::process::exit(1);
+        };
+        match should_select_window {
+            Some(window) => println!("{}:{}", session, window),
+            None => println!("{}", session),
+        }
+        return Ok(());
+    }
+
     // Handle post-TUI actions
+    if should_edit_config {
+        let editor = std::env::var("EDITOR").unwrap_or_default();
+        let path = DimensionConfig::config_path();
+        let cmd = format!("{} {}", editor, hooks::shell_quote(&path.display().to_string()));
+        if let Err(e) = std::process::Command::new("sh").arg("-c").arg(cmd).status() {
+            eprintln!("Failed to launch editor: {e}");
+        }
+        return Ok(());
+    }
+
     if should_detach && Tmux::is_inside_session() {
         // User pressed 'q' and we're in tmux - detach
         Tmux::detach()?;
+        return Ok(());
     } else if let Some(session) = should_attach {
-        // Build target with window index if specified
-        let target = if let Some(window_index) = should_select_window {
-            format!("{}:{}", session, window_index)
+        if should_attach_read_only {
+            Tmux::switch_or_attach_read_only(&session, should_select_window)?;
         } else {
-            session.clone()
-        };
+            Tmux::switch_or_attach(&session, should_select_window, should_detach_others)?;
+        }
+    }
 
-        // Switch/attach to the target session
+    // The popup (and its status bar) is gone; have tmux repeat the last word on the client
+    if let Some(notice) = notice {
         if Tmux::is_inside_session() {
-            // We're in tmux, switch client
-            Tmux::switch_session(&target)?;
-        } else {
-            // Not in tmux, attach to session
-            Tmux::attach_session(&target)?;
+            let _ = Tmux::display_message(&notice, std::time::Duration::from_secs(3));
+        } else if here {
+            // e.g. "Not inside a tmux window": `--here` closed before showing anything
+            eprintln!("{}", notice);
         }
     }
 
     Ok(())
 }
 
+/// Print a config load failure; for parse errors, offer to jump to the spot in $EDITOR
+fn report_config_error(err: &anyhow::Error) {
+    use std::io::IsTerminal;
+
+    let Some(config_err) = err.downcast_ref::<ConfigError>() else {
+        eprintln!("Error: failed to load config: {:#}", err);
+        return;
+    };
+
+    eprintln!("{}", config_err.diagnostic());
+
+    let Some(path) = config_err.path.as_ref() else {
+        return;
+    };
+    let Ok(editor) = std::env::var("EDITOR") else {
+        eprintln!("\nFix the file above and run dimensions again.");
+        return;
+    };
+    if !std::io::stdin().is_terminal() {
+        return;
+    }
+
+    eprint!("\nOpen {} in {} at this location? [y/N] ", path.display(), editor);
+    use std::io::Write;
+    std::io::stderr().flush().ok();
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).ok();
+    let answer = input.trim().to_lowercase();
+    if answer != "y" && answer != "yes" {
+        return;
+    }
+
+    // Most terminal editors accept `+LINE file`; VS Code-style editors want `-g file:LINE:COL`
+    let quoted_path = hooks::shell_quote(&path.display().to_string());
+    let is_vscode_like = editor
+        .split_whitespace()
+        .next()
+        .map(|bin| bin.ends_with("code") || bin.ends_with("codium") || bin.ends_with("cursor"))
+        .unwrap_or(false);
+    let cmd = if is_vscode_like {
+        format!("{} -g {}:{}:{}", editor, quoted_path, config_err.line, config_err.column)
+    } else {
+        format!("{} +{} {}", editor, config_err.line, quoted_path)
+    };
+
+    if let Err(e) = std::process::Command::new("sh").arg("-c").arg(cmd).status() {
+        eprintln!("Failed to launch editor: {e}");
+    }
+}
+
+/// Scope from `--scope <name>`, `--only <glob>` and `--exclude <glob>` (repeatable), with its title label
+fn launch_scope(cli: &Cli, config: &DimensionConfig) -> Result<Option<(Scope, String)>> {
+    let mut scope = Scope::default();
+    if let Some(name) = &cli.scope {
+        let Some(configured) = config.scopes.get(name) else {
+            let known: Vec<&str> = config.scopes.keys().map(String::as_str).collect();
+            anyhow::bail!(
+                "Unknown scope '{}' (configured: {})",
+                name,
+                if known.is_empty() { "none".to_string() } else { known.join(", ") }
+            );
+        };
+        scope.extend(configured);
+    }
+    let globs = |values: &[String]| -> Vec<String> {
+        values.iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+    };
+    scope.include.extend(globs(&cli.only));
+    scope.exclude.extend(globs(&cli.exclude));
+
+    if scope.is_empty() {
+        return Ok(None);
+    }
+    let label = cli.scope.clone().unwrap_or_else(|| scope.describe());
+    Ok(Some((scope, label)))
+}
+
+/// Print a subcommand failure and exit non-zero
+fn exit_with_error(err: anyhow::Error) -> Result<()> {
+    eprintln!("Error: {:#}", err);
+    std::process::exit(1);
+}
+
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> Result<()> {
+    let tick = app.engine.config.ui.tick();
     loop {
         app.poll_update();
-        terminal.draw(|f| ui::render(f, app))?;
+        app.poll_tidy();
+        app.poll_tmux_events();
+        app.update_tutorial();
+
+        // Render on change only; progress/spinner popups animate on every tick
+        if app.dirty || app.startup.is_some() || app.waiting.is_some() {
+            profile::time("first frame render", || terminal.draw(|f| ui::render(f, app)))?;
+            app.dirty = false;
+        }
+
+        // Profiling measures startup only: stop after the first frame
+        if profile::is_enabled() {
+            break;
+        }
 
-        if app.should_quit {
+        // `--here` is done once its prompt is (an error shows up via the exit notice)
+        if app.should_quit || (app.here && app.input_mode == InputMode::Normal) {
             break;
         }
 
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+        // A session is being created: build one window per frame so progress stays visible
+        if app.startup.is_some() {
+            // Also redraw once after it finishes, to clear the progress popup
+            app.dirty = true;
+            if let Err(e) = app.advance_startup() {
+                app.cancel_input();
+                app.set_message(format!("Error: {}", e));
+            }
+            // Keys pressed mid-startup would act on a half-built session; drop them
+            while event::poll(std::time::Duration::from_millis(0))? {
+                event::read()?;
+            }
+            continue;
+        }
+
+        // Waiting for a fresh session's readiness check: only Enter (switch now) and Esc (cancel)
+        if app.waiting.is_some() {
+            app.dirty = true;
+            app.poll_ready();
+            if event::poll(tick)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                match key.code {
+                    KeyCode::Enter => app.skip_wait(),
+                    KeyCode::Esc => app.cancel_wait(),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+
+        if event::poll(tick)? {
+            let event = event::read()?;
+            if let Event::Resize(..) = event {
+                app.dirty = true;
+            }
+            if let Event::Mouse(mouse) = event
+                && app.input_mode == InputMode::Normal
+                && handle_mouse(app, mouse)
+            {
+                app.dirty = true;
+                if app.should_refresh_preview() {
+                    app.update_preview();
+                }
+            }
+            if let Event::Key(key) = event {
                 // Only process key press events, not release
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
+                app.dirty = true;
+
+                // Ctrl+R while searching toggles regex matching instead of refreshing
+                if app.input_mode == InputMode::Searching
+                    && key.code == KeyCode::Char('r')
+                    && key.modifiers.contains(KeyModifiers::CONTROL)
+                {
+                    app.toggle_search_regex();
+                    continue;
+                }
+
+                // Refresh works in every other mode too (where Ctrl+R would otherwise type)
+                let refresh = key.code == KeyCode::F(5)
+                    || (key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL));
+                if refresh {
+                    app.refresh_tmux_state();
+                    continue;
+                }
 
                 let result = match app.input_mode {
                     InputMode::Normal => handle_normal_mode(app, key),
-                    InputMode::CreatingDimension | InputMode::CreatingDimensionDirectory | InputMode::AddingTab | InputMode::Searching | InputMode::JumpingToTab | InputMode::RenamingDimension | InputMode::RenamingTab => {
+                    // Alt+Enter on a search result: switch and detach other clients
+                    InputMode::Searching
+                        if key.code == KeyCode::Enter
+                            && key.modifiers.contains(KeyModifiers::ALT)
+                            && !app.search_results.is_empty() =>
+                    {
+                        app.switch_detaching_others(App::select_search_result)
+                    }
+                    InputMode::CreatingDimension | InputMode::CreatingDimensionDirectory | InputMode::RepointingDirectory | InputMode::AddingTab | InputMode::AddingTabDirectory | InputMode::Searching | InputMode::JumpingToTab | InputMode::RenamingDimension | InputMode::RenamingTab | InputMode::RenamingCurrentWindow | InputMode::FillingTemplate | InputMode::Command => {
                         handle_input_mode(app, key.code)
                     }
                     InputMode::DeletingDimension | InputMode::DeletingTab => handle_delete_mode(app, key.code),
+                    InputMode::ConfirmingSharedAttach => handle_shared_attach_mode(app, key.code),
+                    InputMode::CurrentWindowMenu => handle_current_window_menu(app, key.code),
+                    InputMode::QuickActions => handle_quick_actions(app, key.code),
+                    InputMode::PickingTask => handle_task_picker(app, key.code),
+                    InputMode::PickingLink => handle_link_picker(app, key.code),
+                    InputMode::PickingSuggestion => handle_suggestions(app, key.code),
+                    InputMode::PickingTemplate => handle_template_picker(app, key.code),
+                    InputMode::SearchActions => handle_search_actions(app, key.code),
+                    InputMode::Syncing => handle_sync_mode(app, key.code),
                 };
 
                 // Display errors in status bar instead of crashing
@@ -220,17 +579,93 @@ fn run_app<B: ratatui::backend::Backend>(
     Ok(())
 }
 
+/// Click to select, click again (or double-click) to switch, wheel to move the selection.
+/// Returns whether the event did anything.
+fn handle_mouse(app: &mut App, mouse: MouseEvent) -> bool {
+    let result = match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => app.click(mouse.column, mouse.row),
+        MouseEventKind::ScrollDown => {
+            app.scroll(mouse.column, mouse.row, true);
+            Ok(())
+        }
+        MouseEventKind::ScrollUp => {
+            app.scroll(mouse.column, mouse.row, false);
+            Ok(())
+        }
+        _ => return false,
+    };
+    if let Err(e) = result {
+        app.set_message(format!("Error: {}", e));
+    }
+    true
+}
+
 fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    let action = app.keymap.action(&key);
+    if app.selected_orphan.is_some() && handle_orphan_row(app, key.code, action)? {
+        return Ok(());
+    }
+
+    // Navigation keys depend on which pane has focus, or move through the matrix
+    let handled = match (app.focus, action) {
+        (_, Some(action)) if app.matrix => handle_matrix(app, action),
+        (Pane::Dimensions, Some(action)) => handle_dimensions_pane(app, action),
+        (Pane::Tabs, Some(action)) => handle_tabs_pane(app, action),
+        (_, None) => false,
+    };
+    if handled {
+        return Ok(());
+    }
+
+    // Not remappable
     match key.code {
-        KeyCode::Char('q') => app.quit(),
-        KeyCode::Esc => app.close_popup(),
-        KeyCode::Char('j') | KeyCode::Down => app.next_dimension(),
-        KeyCode::Char('k') | KeyCode::Up => app.previous_dimension(),
-        KeyCode::Char('l') | KeyCode::Right => app.next_tab(),
-        KeyCode::Char('h') | KeyCode::Left => app.previous_tab(),
-        KeyCode::Char('n') => app.start_create_dimension(),
-        KeyCode::Char('t') => app.start_add_tab(),
-        KeyCode::Char('d') => {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.close_popup();
+            return Ok(());
+        }
+        // Quick jump, unless the digit has been bound to an action
+        KeyCode::Char(c @ '0'..='9') if action.is_none() => {
+            app.jump_to_number(c as usize - '0' as usize);
+            return Ok(());
+        }
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+            // Take the session over: detach other clients so they can't shrink it
+            if let Err(e) = app.switch_detaching_others(App::switch_to_dimension) {
+                app.set_message(format!("Error: {}", e));
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let Some(action) = action else {
+        return Ok(());
+    };
+    match action {
+        Action::Quit => app.quit(),
+        Action::Close => app.escape(),
+        Action::FocusPane => app.cycle_focus(),
+        Action::NewDimension => app.start_create_dimension(),
+        Action::NewFromTemplate => app.start_template_picker(),
+        Action::Here => app.switch_to_here()?,
+        Action::Suggestions => app.start_suggestions(),
+        Action::Repoint => app.start_repoint_directory(),
+        Action::NewTab => app.start_add_tab(),
+        Action::WindowMenu => app.start_current_window_menu(),
+        Action::QuickActions => app.start_quick_actions(),
+        Action::OpenDir => app.open_dimension_dir()?,
+        Action::OpenLink => app.start_link_picker()?,
+        Action::Sync => app.start_sync()?,
+        Action::Reveal => app.toggle_reveal(),
+        Action::Presentation => app.toggle_presentation(),
+        Action::ToggleCollapse => app.toggle_collapse_dimension(),
+        Action::Preview => app.toggle_preview_panel(),
+        Action::Matrix => app.toggle_matrix(),
+        Action::MoveDimensionDown => app.move_dimension(1)?,
+        Action::MoveDimensionUp => app.move_dimension(-1)?,
+        Action::MoveTabLeft => app.move_tab(-1)?,
+        Action::MoveTabRight => app.move_tab(1)?,
+        Action::Delete => {
             // Context-sensitive delete: tab if selected, otherwise dimension
             if app.selected_tab.is_some() {
                 app.start_delete_tab();
@@ -238,7 +673,8 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.start_delete_dimension();
             }
         }
-        KeyCode::Char('r') => {
+        Action::Undo => app.undo()?,
+        Action::Rename => {
             // Context-sensitive rename: tab if selected, otherwise dimension
             if app.selected_tab.is_some() {
                 app.start_rename_tab();
@@ -246,33 +682,87 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.start_rename_dimension();
             }
         }
-        KeyCode::Char('/') => app.start_search(),
-        KeyCode::Char(':') => {
+        Action::Search => app.start_search(),
+        Action::Command => app.start_command_line(),
+        Action::Jump => {
             // Only allow jump mode when dimension is selected
-            if !app.config.dimensions.is_empty() {
+            if !app.engine.config.dimensions.is_empty() {
                 app.start_jump_to_tab();
             }
         }
-        KeyCode::Enter => {
-            if let Err(e) = app.switch_to_dimension() {
+        Action::Switch => {
+            if let Err(e) = app.switch_or_pick_task() {
                 app.set_message(format!("Error: {}", e));
             }
         }
-        KeyCode::Char('G') => {
+        Action::LastTab => {
             // Switch to last/newest tab in the selected dimension
             if let Err(e) = app.switch_to_last_tab_in_dimension() {
                 app.set_message(format!("Error: {}", e));
             }
         }
-        _ => {}
+        Action::Down | Action::Up | Action::Left | Action::Right => {}
     }
     Ok(())
 }
 
+/// Navigation while the dimensions list has focus. Returns false for keys it doesn't handle.
+fn handle_dimensions_pane(app: &mut App, action: Action) -> bool {
+    match action {
+        Action::Down => app.next_dimension(),
+        Action::Up => app.previous_dimension(),
+        Action::Right => app.focus_tabs(),
+        _ => return false,
+    }
+    true
+}
+
+/// Navigation in the matrix view: rows are dimensions, columns their tabs
+fn handle_matrix(app: &mut App, action: Action) -> bool {
+    match action {
+        Action::Down => app.matrix_move(1, 0),
+        Action::Up => app.matrix_move(-1, 0),
+        Action::Right => app.matrix_move(0, 1),
+        Action::Left => app.matrix_move(0, -1),
+        _ => return false,
+    }
+    true
+}
+
+/// Keys on an orphan session row. Dimension actions don't apply there, so anything
+/// other than navigation, attach, adopt, search and quitting is ignored.
+fn handle_orphan_row(app: &mut App, key: KeyCode, action: Option<Action>) -> Result<bool> {
+    match action {
+        Some(Action::Down) => app.next_dimension(),
+        Some(Action::Up) => app.previous_dimension(),
+        Some(Action::Switch) => app.switch_to_orphan(),
+        Some(Action::Quit | Action::Close | Action::Search) => return Ok(false),
+        _ if key == KeyCode::Char('a') => app.adopt_orphan()?,
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// Navigation while the tabs list has focus. Returns false for keys it doesn't handle.
+fn handle_tabs_pane(app: &mut App, action: Action) -> bool {
+    match action {
+        Action::Down => app.next_tab(),
+        Action::Up => app.previous_tab_in_pane(),
+        Action::Right => app.next_tab(),
+        // Stepping left off the first tab returns focus to the dimensions list
+        Action::Left => app.previous_tab(),
+        _ => return false,
+    }
+    true
+}
+
 fn handle_input_mode(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
         KeyCode::Enter => {
-            if app.input_mode == InputMode::Searching && !app.search_results.is_empty() {
+            if app.is_completion_popup_open() {
+                // Accept the highlighted completion instead of submitting
+                app.accept_completion();
+            } else if app.input_mode == InputMode::Searching && !app.search_results.is_empty() {
                 // In search mode with results, Enter selects and switches
                 app.select_search_result()?;
             } else {
@@ -280,6 +770,11 @@ fn handle_input_mode(app: &mut App, key: KeyCode) -> Result<()> {
                 app.submit_input()?;
             }
         }
+        KeyCode::Tab if app.input_mode == InputMode::Searching => app.start_search_actions(),
+        // `--here`: from the rename prompt to adopting or moving the window
+        KeyCode::Tab if app.here && app.input_mode == InputMode::RenamingCurrentWindow => {
+            app.start_current_window_menu()
+        }
         KeyCode::Tab => {
             // Handle tab completion for directory input
             app.handle_tab_completion();
@@ -290,10 +785,31 @@ fn handle_input_mode(app: &mut App, key: KeyCode) -> Result<()> {
         }
         KeyCode::Char(c) => app.handle_input_char(c),
         KeyCode::Backspace => app.handle_input_backspace(),
-        KeyCode::Esc => app.cancel_input(),
+        KeyCode::Esc => {
+            if app.is_completion_popup_open() {
+                // Close the completion popup but keep typing
+                app.clear_completion_state();
+            } else {
+                app.cancel_input();
+            }
+        }
         KeyCode::Up | KeyCode::Down => {
-            // In search mode, navigate results
-            if app.input_mode == InputMode::Searching {
+            if app.is_completion_popup_open() {
+                // Navigate completion candidates
+                if key == KeyCode::Up {
+                    app.previous_completion();
+                } else {
+                    app.next_completion();
+                }
+            } else if app.input_mode == InputMode::Searching && app.is_recalling_search() {
+                // Empty search (or a recalled query): walk the history
+                if key == KeyCode::Up {
+                    app.previous_search_query();
+                } else {
+                    app.next_search_query();
+                }
+            } else if app.input_mode == InputMode::Searching {
+                // In search mode, navigate results
                 if key == KeyCode::Up {
                     app.previous_search_result();
                 } else {
@@ -306,7 +822,135 @@ fn handle_input_mode(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
+fn handle_current_window_menu(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Char('a') => app.adopt_current_window()?,
+        KeyCode::Char('m') => app.move_current_window()?,
+        KeyCode::Char('r') => app.start_rename_current_window(),
+        KeyCode::Up | KeyCode::Char('k') => app.cycle_window_target(false),
+        KeyCode::Down | KeyCode::Char('j') => app.cycle_window_target(true),
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_quick_actions(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Right | KeyCode::Tab => app.next_quick_action(),
+        KeyCode::Left | KeyCode::BackTab => app.previous_quick_action(),
+        _ if app.keymap.is(Action::Right, key) => app.next_quick_action(),
+        _ if app.keymap.is(Action::Left, key) => app.previous_quick_action(),
+        KeyCode::Enter => app.run_quick_action()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_task_picker(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Down | KeyCode::Tab => app.next_task(),
+        KeyCode::Up | KeyCode::BackTab => app.previous_task(),
+        _ if app.keymap.is(Action::Down, key) => app.next_task(),
+        _ if app.keymap.is(Action::Up, key) => app.previous_task(),
+        KeyCode::Enter => app.run_selected_task()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_link_picker(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Down | KeyCode::Tab => app.next_link(),
+        KeyCode::Up | KeyCode::BackTab => app.previous_link(),
+        _ if app.keymap.is(Action::Down, key) => app.next_link(),
+        _ if app.keymap.is(Action::Up, key) => app.previous_link(),
+        KeyCode::Enter => app.open_selected_link()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_suggestions(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Down | KeyCode::Tab => app.next_suggestion(),
+        KeyCode::Up | KeyCode::BackTab => app.previous_suggestion(),
+        _ if app.keymap.is(Action::Down, key) => app.next_suggestion(),
+        _ if app.keymap.is(Action::Up, key) => app.previous_suggestion(),
+        KeyCode::Enter => app.create_suggested_dimension()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_search_actions(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Down | KeyCode::Tab => app.next_search_action(),
+        KeyCode::Up | KeyCode::BackTab => app.previous_search_action(),
+        _ if app.keymap.is(Action::Down, key) => app.next_search_action(),
+        _ if app.keymap.is(Action::Up, key) => app.previous_search_action(),
+        KeyCode::Enter => app.run_search_action()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.close_search_actions(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_template_picker(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Down | KeyCode::Tab => app.next_template(),
+        KeyCode::Up | KeyCode::BackTab => app.previous_template(),
+        _ if app.keymap.is(Action::Down, key) => app.next_template(),
+        _ if app.keymap.is(Action::Up, key) => app.previous_template(),
+        KeyCode::Enter => app.start_template_fill()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_sync_mode(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Down | KeyCode::Tab => app.next_sync_item(),
+        KeyCode::Up | KeyCode::BackTab => app.previous_sync_item(),
+        _ if app.keymap.is(Action::Down, key) => app.next_sync_item(),
+        _ if app.keymap.is(Action::Up, key) => app.previous_sync_item(),
+        KeyCode::Char(' ') => app.toggle_sync_item(),
+        KeyCode::Enter => app.apply_sync()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_shared_attach_mode(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Char('d') | KeyCode::Char('D') => app.confirm_shared_attach(true),
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_shared_attach(false),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_delete_mode(app: &mut App, key: KeyCode) -> Result<()> {
+    if app.delete_requires_name {
+        match key {
+            KeyCode::Enter if app.delete_name_matches() => app.submit_input()?,
+            KeyCode::Char(c) => app.input_buffer.push(c),
+            KeyCode::Backspace => {
+                app.input_buffer.pop();
+            }
+            KeyCode::Esc => app.cancel_input(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
     match key {
         KeyCode::Char('y') | KeyCode::Char('Y') => app.submit_input()?,
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_input(),
@@ -0,0 +1,169 @@
+use crate::dimension::DimensionConfig;
+use crate::tmux::Tmux;
+use crate::tmux_options;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::io::{IsTerminal, Write};
+
+/// One tab command that a replace would rewrite
+#[derive(Debug, Clone)]
+pub struct CommandChange {
+    pub dimension_index: usize,
+    pub tab_index: usize,
+    pub dimension: String,
+    pub tab: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// How to match commands in a workspace-wide replace
+pub enum Pattern {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Pattern {
+    pub fn new(pattern: &str, regex: bool) -> Result<Self> {
+        if regex {
+            let re = Regex::new(pattern).with_context(|| format!("Invalid regex '{}'", pattern))?;
+            Ok(Self::Regex(re))
+        } else {
+            Ok(Self::Literal(pattern.to_string()))
+        }
+    }
+
+    fn replace(&self, input: &str, replacement: &str) -> String {
+        match self {
+            Self::Literal(needle) => input.replace(needle.as_str(), replacement),
+            Self::Regex(re) => re.replace_all(input, replacement).into_owned(),
+        }
+    }
+}
+
+/// Compute every tab command `pattern` would change, without touching the config
+pub fn plan_replace(config: &DimensionConfig, pattern: &Pattern, replacement: &str) -> Vec<CommandChange> {
+    let mut changes = vec![];
+    for (dim_idx, dimension) in config.dimensions.iter().enumerate() {
+        for (tab_idx, tab) in dimension.configured_tabs.iter().enumerate() {
+            let Some(before) = tab.command.as_deref() else {
+                continue;
+            };
+            let after = pattern.replace(before, replacement);
+            if after != before {
+                changes.push(CommandChange {
+                    dimension_index: dim_idx,
+                    tab_index: tab_idx,
+                    dimension: dimension.name.clone(),
+                    tab: tab.name.clone(),
+                    before: before.to_string(),
+                    after,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Write planned changes into the config
+pub fn apply_replace(config: &mut DimensionConfig, changes: &[CommandChange]) {
+    for change in changes {
+        if let Some(tab) = config
+            .dimensions
+            .get_mut(change.dimension_index)
+            .and_then(|d| d.configured_tabs.get_mut(change.tab_index))
+        {
+            tab.command = Some(change.after.clone());
+        }
+    }
+}
+
+/// Unified-diff-style preview of planned changes
+pub fn format_preview(changes: &[CommandChange]) -> String {
+    let mut out = String::new();
+    for change in changes {
+        out.push_str(&format!("{} ▸ {}\n", change.dimension, change.tab));
+        out.push_str(&format!("  - {}\n", change.before));
+        out.push_str(&format!("  + {}\n", change.after));
+    }
+    out
+}
+
+/// `dimensions replace <pattern> <replacement> [--regex] [--yes]`
+pub fn replace_commands_cli(pattern: &str, replacement: &str, regex: bool, yes: bool) -> Result<()> {
+    let mut config = DimensionConfig::load()?;
+    let pattern = Pattern::new(pattern, regex)?;
+    let changes = plan_replace(&config, &pattern, replacement);
+
+    if changes.is_empty() {
+        println!("No tab commands match.");
+        return Ok(());
+    }
+
+    print!("{}", format_preview(&changes));
+    println!("\n{} command(s) would change.", changes.len());
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to apply without confirmation; pass --yes");
+        }
+        eprint!("Apply? [y/N] ");
+        std::io::stderr().flush().ok();
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).ok();
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    apply_replace(&mut config, &changes);
+    config.save()?;
+    println!("Updated {} command(s). Running sessions pick them up when recreated.", changes.len());
+    Ok(())
+}
+
+/// `dimensions check`: report dimensions whose directories no longer exist and
+/// tmux options that conflict with how Dimensions manages sessions.
+/// Returns true when everything is healthy.
+pub fn check_cli() -> Result<bool> {
+    let config = DimensionConfig::load()?;
+    let mut healthy = true;
+
+    if let Some(applied) = &config.applied_machine {
+        println!("Using machine overrides for '{}'.\n", applied.hostname);
+    }
+
+    for dimension in &config.dimensions {
+        let missing = dimension.missing_paths();
+        if missing.is_empty() {
+            continue;
+        }
+        healthy = false;
+        println!("⚠ {}", dimension.name);
+        for path in missing {
+            println!("    missing: {}", path.display());
+        }
+    }
+
+    let missing_dirs = !healthy;
+    let conflicts = tmux_options::conflicts(Tmux::global_option);
+    if !conflicts.is_empty() {
+        if missing_dirs {
+            println!();
+        }
+        healthy = false;
+        println!("⚠ tmux options");
+        for conflict in &conflicts {
+            println!("    {} is {}: {}", conflict.option, conflict.value, conflict.problem);
+            println!("      fix: add `{}` to ~/.tmux.conf", conflict.fix);
+        }
+    }
+
+    if healthy {
+        println!("All {} dimension(s) look good.", config.dimensions.len());
+    } else if missing_dirs {
+        println!("\nRe-point a dimension from the TUI: select it and press R.");
+    }
+    Ok(healthy)
+}
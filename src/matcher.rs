@@ -0,0 +1,232 @@
+//! The search algorithms behind `/` search, picked with `ui.matcher`: skim's fuzzy scoring
+//! (the default), fzf's v2 algorithm, plain substring, or a regular expression.
+
+use crate::dimension::MatchAlgorithm;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use regex::{Regex, RegexBuilder};
+
+/// A search query, ready to score candidates
+pub trait Matcher {
+    /// How well `text` (already lowercased) matches, higher is better; `None` when it doesn't
+    fn score(&self, text: &str) -> Option<i64>;
+}
+
+/// `query` compiled for `algorithm`
+pub fn matcher(algorithm: MatchAlgorithm, query: &str) -> Box<dyn Matcher> {
+    let lower = query.to_lowercase();
+    match algorithm {
+        MatchAlgorithm::Skim => Box::new(Skim { inner: SkimMatcherV2::default(), query: lower }),
+        MatchAlgorithm::FzfV2 => Box::new(FzfV2 { query: lower.chars().collect() }),
+        MatchAlgorithm::Substring => Box::new(Substring { query: lower }),
+        // Case-insensitive rather than lowercased, so escapes like `\D` keep their meaning
+        MatchAlgorithm::Regex => Box::new(Pattern {
+            regex: RegexBuilder::new(query).case_insensitive(true).build().ok(),
+        }),
+    }
+}
+
+struct Skim {
+    inner: SkimMatcherV2,
+    query: String,
+}
+
+impl Matcher for Skim {
+    fn score(&self, text: &str) -> Option<i64> {
+        self.inner.fuzzy_match(text, &self.query)
+    }
+}
+
+/// Earlier matches rank higher
+struct Substring {
+    query: String,
+}
+
+impl Matcher for Substring {
+    fn score(&self, text: &str) -> Option<i64> {
+        text.find(&self.query).map(position_score)
+    }
+}
+
+/// Earlier matches rank higher; an invalid pattern (say, half typed) matches nothing
+struct Pattern {
+    regex: Option<Regex>,
+}
+
+impl Matcher for Pattern {
+    fn score(&self, text: &str) -> Option<i64> {
+        self.regex.as_ref()?.find(text).map(|m| position_score(m.start()))
+    }
+}
+
+/// Why `query` isn't a valid pattern, in one line; `None` when it is
+pub fn regex_error(query: &str) -> Option<String> {
+    let error = RegexBuilder::new(query).case_insensitive(true).build().err()?;
+    // Syntax errors repeat the pattern with a caret before the last line, "error: <what>"
+    let message = error.to_string();
+    let last = message.lines().last().unwrap_or_default().trim();
+    Some(last.strip_prefix("error: ").unwrap_or(last).to_string())
+}
+
+fn position_score(start: usize) -> i64 {
+    100 - start.min(99) as i64
+}
+
+// fzf's scoring constants (algo.go)
+const SCORE_MATCH: i64 = 16;
+const SCORE_GAP_START: i64 = -3;
+const SCORE_GAP_EXTENSION: i64 = -1;
+const BONUS_BOUNDARY: i64 = SCORE_MATCH / 2;
+const BONUS_NON_WORD: i64 = SCORE_MATCH / 2;
+const BONUS_CAMEL_123: i64 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i64 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+const BONUS_BOUNDARY_WHITE: i64 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i64 = BONUS_BOUNDARY + 1;
+
+#[derive(Clone, Copy)]
+enum CharClass {
+    White,
+    NonWord,
+    Delimiter,
+    Lower,
+    Upper,
+    Number,
+}
+
+fn char_class(c: char) -> CharClass {
+    match c {
+        c if c.is_whitespace() => CharClass::White,
+        '/' | ',' | ':' | ';' | '|' => CharClass::Delimiter,
+        c if c.is_lowercase() => CharClass::Lower,
+        c if c.is_uppercase() => CharClass::Upper,
+        c if c.is_numeric() => CharClass::Number,
+        c if c.is_alphabetic() => CharClass::Lower,
+        _ => CharClass::NonWord,
+    }
+}
+
+/// Bonus for matching a character of class `current` that follows one of class `previous`.
+/// Candidates arrive lowercased, so unlike fzf there is no camelCase bonus; digits after
+/// letters still get one.
+fn bonus(previous: CharClass, current: CharClass) -> i64 {
+    use CharClass::*;
+    match (previous, current) {
+        (White, Lower | Upper | Number) => BONUS_BOUNDARY_WHITE,
+        (Delimiter, Lower | Upper | Number) => BONUS_BOUNDARY_DELIMITER,
+        (NonWord, Lower | Upper | Number) => BONUS_BOUNDARY,
+        (Lower | Upper, Number) => BONUS_CAMEL_123,
+        (_, NonWord | Delimiter) => BONUS_NON_WORD,
+        (_, White) => BONUS_BOUNDARY_WHITE,
+        _ => 0,
+    }
+}
+
+/// fzf's v2 algorithm: the best-scoring alignment of the query over the text, rewarding
+/// matches at word boundaries and consecutive runs, and penalizing gaps
+struct FzfV2 {
+    query: Vec<char>,
+}
+
+/// Best alignment of the query so far ending on a given text character
+#[derive(Clone, Copy)]
+struct Cell {
+    score: i64,
+    // Bonus of the first character of the consecutive run this one ends
+    run_bonus: i64,
+}
+
+impl Matcher for FzfV2 {
+    fn score(&self, text: &str) -> Option<i64> {
+        let text: Vec<char> = text.chars().collect();
+        if self.query.is_empty() || self.query.len() > text.len() {
+            return None;
+        }
+        let bonuses: Vec<i64> = text
+            .iter()
+            .scan(CharClass::White, |previous, &c| {
+                let class = char_class(c);
+                let bonus = bonus(*previous, class);
+                *previous = class;
+                Some(bonus)
+            })
+            .collect();
+
+        // The first query character can start anywhere; leading text is free
+        let mut row: Vec<Option<Cell>> = text
+            .iter()
+            .zip(&bonuses)
+            .map(|(&c, &bonus)| {
+                (c == self.query[0]).then_some(Cell { score: SCORE_MATCH + bonus * BONUS_FIRST_CHAR_MULTIPLIER, run_bonus: bonus })
+            })
+            .collect();
+
+        for &q in &self.query[1..] {
+            let mut next: Vec<Option<Cell>> = vec![None; text.len()];
+            // Best previous-row score followed by a gap of at least one character
+            let mut gapped: Option<i64> = None;
+            for j in 1..text.len() {
+                if j >= 2 {
+                    let opened = row[j - 2].map(|cell| cell.score + SCORE_GAP_START);
+                    gapped = gapped.map(|score| score + SCORE_GAP_EXTENSION).max(opened);
+                }
+                if text[j] != q {
+                    continue;
+                }
+                let bonus = bonuses[j];
+                let consecutive = row[j - 1].map(|cell| {
+                    let run_bonus = if bonus >= BONUS_BOUNDARY && bonus > cell.run_bonus { bonus } else { cell.run_bonus };
+                    let score = cell.score + SCORE_MATCH + bonus.max(run_bonus).max(BONUS_CONSECUTIVE);
+                    Cell { score, run_bonus }
+                });
+                let after_gap = gapped.map(|score| Cell { score: score + SCORE_MATCH + bonus, run_bonus: bonus });
+                next[j] = match (consecutive, after_gap) {
+                    (Some(a), Some(b)) => Some(if a.score >= b.score { a } else { b }),
+                    (a, b) => a.or(b),
+                };
+            }
+            row = next;
+        }
+        row.iter().flatten().map(|cell| cell.score).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(algorithm: MatchAlgorithm, query: &str, text: &str) -> Option<i64> {
+        matcher(algorithm, query).score(text)
+    }
+
+    #[test]
+    fn fzf_prefers_word_boundaries_and_runs() {
+        let fzf = |text| score(MatchAlgorithm::FzfV2, "srv", text);
+        assert!(fzf("dev-srv") > fzf("observer"));
+        assert!(fzf("dev server") > fzf("observer"));
+        assert!(fzf("cargo run") < fzf("run-srv"));
+        assert_eq!(fzf("rsv"), None);
+        assert_eq!(fzf("sr"), None);
+        assert!(score(MatchAlgorithm::FzfV2, "s", "s").is_some());
+    }
+
+    #[test]
+    fn substring_and_regex_match_literally() {
+        assert!(score(MatchAlgorithm::Substring, "Run", "cargo run").is_some());
+        assert_eq!(score(MatchAlgorithm::Substring, "run", "rnu"), None);
+        assert!(score(MatchAlgorithm::Substring, "api", "api-gw") > score(MatchAlgorithm::Substring, "api", "my-api"));
+
+        assert!(score(MatchAlgorithm::Regex, "^cargo (run|test)$", "cargo test").is_some());
+        assert_eq!(score(MatchAlgorithm::Regex, "^cargo (run|test)$", "cargo build"), None);
+        assert!(score(MatchAlgorithm::Regex, "\\D+\\d", "tab2").is_some());
+        assert_eq!(score(MatchAlgorithm::Regex, "(", "("), None);
+        assert_eq!(regex_error("(").as_deref(), Some("unclosed group"));
+        assert_eq!(regex_error("a|b"), None);
+    }
+
+    #[test]
+    fn skim_is_the_default() {
+        assert_eq!(MatchAlgorithm::default(), MatchAlgorithm::Skim);
+        assert!(score(MatchAlgorithm::Skim, "dm", "dimensions").is_some());
+    }
+}
@@ -70,7 +70,7 @@ impl PathCompleter {
     }
 
     /// Expand ~ and environment variables in path
-    fn expand_path(input: &str) -> String {
+    pub fn expand_path(input: &str) -> String {
         if input.is_empty() {
             return String::from(".");
         }
@@ -114,7 +114,7 @@ impl PathCompleter {
     }
 
     /// Find the longest common prefix among a set of strings
-    fn common_prefix(candidates: &[String]) -> String {
+    pub fn common_prefix(candidates: &[String]) -> String {
         if candidates.is_empty() {
             return String::new();
         }
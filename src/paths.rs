@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const APP_DIR: &str = "dimensions";
+
+/// Set from `--config <dir>`; takes precedence over `DIMENSIONS_CONFIG_DIR`
+static CONFIG_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Override the config directory for this process (from the `--config` flag)
+pub fn set_config_dir_override(dir: PathBuf) {
+    let _ = CONFIG_DIR_OVERRIDE.set(dir);
+}
+
+/// Explicit override from `--config` or `DIMENSIONS_CONFIG_DIR`, if any
+fn override_dir() -> Option<PathBuf> {
+    if let Some(dir) = CONFIG_DIR_OVERRIDE.get() {
+        return Some(dir.clone());
+    }
+    std::env::var_os("DIMENSIONS_CONFIG_DIR")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Resolve `$XDG_*_HOME/dimensions`, falling back to the platform default
+fn xdg_dir(var: &str, fallback: Option<PathBuf>) -> PathBuf {
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or(fallback)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+fn ensure(dir: PathBuf) -> PathBuf {
+    fs::create_dir_all(&dir).ok();
+    dir
+}
+
+/// Directory holding the hand-edited config (`config.toml`)
+pub fn config_dir() -> PathBuf {
+    ensure(override_dir().unwrap_or_else(|| xdg_dir("XDG_CONFIG_HOME", dirs::config_dir())))
+}
+
+/// Directory for state the app writes on its own (history, usage, snapshots)
+pub fn state_dir() -> PathBuf {
+    // With an override everything stays under one directory (portable setups, tests)
+    if let Some(dir) = override_dir() {
+        return ensure(dir.join("state"));
+    }
+    let fallback = dirs::state_dir().or_else(|| dirs::home_dir().map(|h| h.join(".local/state")));
+    ensure(xdg_dir("XDG_STATE_HOME", fallback))
+}
+
+/// Directory for disposable caches (update check results)
+pub fn cache_dir() -> PathBuf {
+    if let Some(dir) = override_dir() {
+        return ensure(dir.join("cache"));
+    }
+    ensure(xdg_dir("XDG_CACHE_HOME", dirs::cache_dir()))
+}
+
+/// 16 bytes from the system's random source, in hex: for names nobody can guess ahead of
+/// time, like a private temp dir, and for secrets
+pub fn random_suffix() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    fs::File::open("/dev/urandom")
+        .and_then(|mut random| random.read_exact(&mut bytes))
+        .context("Failed to read /dev/urandom")?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Point every directory at one temp dir for the rest of this test process, so tests that
+/// go through the real state files never touch the user's
+#[cfg(test)]
+pub fn use_temp_dir() {
+    set_config_dir_override(std::env::temp_dir().join(format!("dimensions-test-{}", std::process::id())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_suffixes_differ_between_calls() {
+        let suffix = random_suffix().unwrap();
+        assert_eq!(suffix.len(), 32);
+        assert!(suffix.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(suffix, random_suffix().unwrap());
+    }
+}
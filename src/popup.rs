@@ -0,0 +1,84 @@
+//! The tmux popup Dimensions runs in: its geometry comes from the `popup` config section,
+//! for key-table bindings, the startup tip and `dimensions popup`.
+
+use crate::dimension::PopupSettings;
+use crate::tmux::Tmux;
+use anyhow::Result;
+
+/// Path of this executable, so popups run the same build
+pub fn exe() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.to_str().map(str::to_string))
+        .unwrap_or_else(|| "dimensions".to_string())
+}
+
+/// `display-popup` arguments running `command` (already split into words) with this geometry
+pub fn args(settings: &PopupSettings, command: &[String]) -> Vec<String> {
+    let mut args: Vec<String> = ["display-popup", "-E", "-w", &settings.width, "-h", &settings.height]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let optional = [("-x", &settings.x), ("-y", &settings.y), ("-b", &settings.border), ("-T", &settings.title)];
+    for (flag, value) in optional {
+        if let Some(value) = value {
+            args.push(flag.to_string());
+            args.push(value.clone());
+        }
+    }
+    args.extend(command.iter().cloned());
+    args
+}
+
+/// The same as a line for `~/.tmux.conf`, e.g. `display-popup -E -w 80% -h 80% "dimensions"`
+pub fn tmux_line(settings: &PopupSettings, command: &str) -> String {
+    args(settings, &[command.to_string()])
+        .into_iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || "\"'#;$".contains(c)) || arg == command {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            } else {
+                arg
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `dimensions popup [-- <args>]`: (re)open Dimensions in a popup on the current client,
+/// passing `args` (e.g. `--scope work`) through
+pub fn popup_cli(settings: &PopupSettings, forward: &[String]) -> Result<()> {
+    if !Tmux::is_inside_session() {
+        anyhow::bail!("Not inside tmux; popups open on a tmux client");
+    }
+    let mut command = vec![exe()];
+    command.extend(forward.iter().cloned());
+    Tmux::display_popup(&args(settings, &command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_flags_follow_the_config() {
+        let defaults = PopupSettings::default();
+        assert_eq!(tmux_line(&defaults, "dimensions"), r#"display-popup -E -w 80% -h 80% "dimensions""#);
+
+        let settings = PopupSettings {
+            width: "60%".to_string(),
+            x: Some("R".to_string()),
+            border: Some("rounded".to_string()),
+            title: Some(" Dimensions ".to_string()),
+            ..PopupSettings::default()
+        };
+        assert_eq!(
+            args(&settings, &["dimensions".to_string(), "--scope".to_string(), "work".to_string()]),
+            [
+                "display-popup", "-E", "-w", "60%", "-h", "80%", "-x", "R", "-b", "rounded", "-T", " Dimensions ",
+                "dimensions", "--scope", "work"
+            ]
+        );
+        assert!(tmux_line(&settings, "dimensions").contains(r#"-T " Dimensions ""#));
+    }
+}
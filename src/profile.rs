@@ -0,0 +1,80 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Set by `--profile-startup`
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// (label, start, elapsed)
+static TIMINGS: Mutex<Vec<(&'static str, Instant, Duration)>> = Mutex::new(Vec::new());
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn record(label: &'static str, started: Instant) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut timings) = TIMINGS.lock() {
+        timings.push((label, started, started.elapsed()));
+    }
+}
+
+/// Run `f`, recording how long it took when profiling is on
+pub fn time<T>(label: &'static str, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() {
+        return f();
+    }
+    let started = Instant::now();
+    let out = f();
+    record(label, started);
+    out
+}
+
+/// Timer for a scope inside hot paths (startup, render); records on drop.
+/// Only compiled in with `--features profiling`, otherwise a no-op.
+#[cfg(feature = "profiling")]
+pub struct Span {
+    label: &'static str,
+    started: Instant,
+}
+
+#[cfg(feature = "profiling")]
+impl Drop for Span {
+    fn drop(&mut self) {
+        record(self.label, self.started);
+    }
+}
+
+#[cfg(feature = "profiling")]
+pub fn span(label: &'static str) -> Span {
+    Span { label, started: Instant::now() }
+}
+
+#[cfg(not(feature = "profiling"))]
+pub struct Span;
+
+#[cfg(not(feature = "profiling"))]
+pub fn span(_label: &'static str) -> Span {
+    Span
+}
+
+/// Print recorded timings to stderr, in the order they started (nested steps follow their parent)
+pub fn report(total: Duration) {
+    let mut timings = TIMINGS.lock().map(|t| t.clone()).unwrap_or_default();
+    timings.sort_by_key(|(_, started, _)| *started);
+    let width = timings.iter().map(|(label, _, _)| label.len()).max().unwrap_or(0).max(5);
+
+    eprintln!("dimensions startup profile");
+    for (label, _, elapsed) in &timings {
+        eprintln!("  {:<width$}  {:>9.2} ms", label, elapsed.as_secs_f64() * 1000.0, width = width);
+    }
+    eprintln!("  {:<width$}  {:>9.2} ms", "total", total.as_secs_f64() * 1000.0, width = width);
+    if !cfg!(feature = "profiling") {
+        eprintln!("\n(build with `--features profiling` for a per-step breakdown)");
+    }
+}
@@ -0,0 +1,74 @@
+use crate::dimension::WaitFor;
+use crate::hooks;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReadyEvent {
+    Ready,
+    TimedOut,
+}
+
+/// Poll `wait_for` on a background thread until it holds, times out, or `cancel` is set.
+/// Sends exactly one event unless cancelled.
+pub fn spawn_wait(
+    dimension: String,
+    wait_for: WaitFor,
+    cwd: Option<PathBuf>,
+    cancel: Arc<AtomicBool>,
+) -> mpsc::Receiver<ReadyEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let timeout = Duration::from_secs(wait_for.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        let started = Instant::now();
+        while !cancel.load(Ordering::Relaxed) {
+            if is_ready(&dimension, &wait_for, cwd.as_deref()) {
+                let _ = tx.send(ReadyEvent::Ready);
+                return;
+            }
+            if started.elapsed() >= timeout {
+                let _ = tx.send(ReadyEvent::TimedOut);
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+    rx
+}
+
+fn is_ready(dimension: &str, wait_for: &WaitFor, cwd: Option<&Path>) -> bool {
+    if let Some(port) = wait_for.port {
+        let host = wait_for.host.as_deref().unwrap_or("127.0.0.1");
+        if !port_open(host, port) {
+            return false;
+        }
+    }
+
+    if let Some(cmd) = wait_for.command.as_deref() {
+        let ok = hooks::run_shell(dimension, cmd, cwd, Some(Duration::from_secs(5)))
+            .map(|outcome| outcome.success)
+            .unwrap_or(false);
+        if !ok {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn port_open(host: &str, port: u16) -> bool {
+    let Ok(addrs) = (host, port).to_socket_addrs() else {
+        return false;
+    };
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, Duration::from_millis(300)).is_ok())
+}
@@ -0,0 +1,138 @@
+//! Dimensions whose tmux session lives on another machine (`remote = "<host>"`): the session
+//! is created there over `ssh`, and switching lands in a local session of the same name whose
+//! window runs `ssh -t <host> tmux attach`.
+
+use crate::dimension::{Dimension, Tab};
+use crate::hooks::shell_quote;
+use crate::ssh_config;
+use crate::tmux;
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+/// How long ssh may take to reach a host
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a whole round trip may take, including the dimension's `setup` on the host
+const SSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Create `dimension`'s session on its remote host unless it is already running there,
+/// with its setup and tabs, in one `ssh` round trip
+pub fn ensure_session(host: &str, dimension: &Dimension) -> Result<()> {
+    let output = run_ssh(host, &start_script(dimension))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Starting '{}' on {} failed: {}", dimension.name, host, stderr.trim());
+    }
+    Ok(())
+}
+
+/// Kill `session` on `host`; a session that isn't running is not an error
+pub fn kill_session(host: &str, session: &str) -> Result<()> {
+    let remote = format!("tmux kill-session -t {} 2>/dev/null || true", shell_quote(&format!("={}", session)));
+    if !run_ssh(host, &remote)?.status.success() {
+        anyhow::bail!("Could not reach {} to kill '{}'", host, session);
+    }
+    Ok(())
+}
+
+/// Run `script` on `host`, giving up once `SSH_TIMEOUT` passes so an unreachable host
+/// can't hold up the caller
+fn run_ssh(host: &str, script: &str) -> Result<Output> {
+    let connect_timeout = format!("ConnectTimeout={}", CONNECT_TIMEOUT.as_secs());
+    let mut command = Command::new("ssh");
+    command.args(["-o", "BatchMode=yes", "-o", &connect_timeout, host, script]);
+    let output = match tmux::output_within(&mut command, SSH_TIMEOUT) {
+        Err(e) if e.kind() == io::ErrorKind::TimedOut => anyhow::bail!("{} is not responding", host),
+        result => result.with_context(|| format!("Failed to run ssh {}", host))?,
+    };
+    // ssh itself exits 255 when it can't get through, e.g. on a connect timeout
+    if output.status.code() == Some(255) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{} is not responding: {}", host, stderr.trim());
+    }
+    Ok(output)
+}
+
+/// The command a local window runs to attach to `session` on `host`
+pub fn attach_command(host: &str, session: &str) -> String {
+    let attach = format!("tmux attach-session -t {}", shell_quote(&format!("={}", session)));
+    ssh_config::ssh_command(host, Some(&attach))
+}
+
+/// Shell script run on the host: create the session (after `setup`) when it doesn't exist.
+/// Tabs start as shells with their command typed in, so they survive the command exiting.
+fn start_script(dimension: &Dimension) -> String {
+    let target = shell_quote(&format!("={}", dimension.name));
+    let mut out = format!("tmux has-session -t {} 2>/dev/null && exit 0\n", target);
+    if let Some(setup) = &dimension.setup {
+        match &dimension.base_dir {
+            Some(dir) => {
+                let _ = writeln!(out, "(cd {} && {})", remote_path(dir), setup);
+            }
+            None => {
+                let _ = writeln!(out, "{}", setup);
+            }
+        }
+    }
+    let fallback = Tab::new(format!("{}-1", dimension.name), None, None);
+    let tabs = if dimension.configured_tabs.is_empty() {
+        std::slice::from_ref(&fallback)
+    } else {
+        dimension.configured_tabs.as_slice()
+    };
+    for (i, tab) in tabs.iter().enumerate() {
+        let create = if i == 0 {
+            format!("tmux new-session -d -s {}", shell_quote(&dimension.name))
+        } else {
+            format!("tmux new-window -t {}", shell_quote(&format!("={}:", dimension.name)))
+        };
+        let _ = write!(out, "{} -n {}", create, shell_quote(&tab.name));
+        if let Some(dir) = tab.working_dir.as_ref().or(dimension.base_dir.as_ref()) {
+            let _ = write!(out, " -c {}", remote_path(dir));
+        }
+        out.push('\n');
+        if let Some(command) = &tab.command {
+            let window = shell_quote(&format!("={}:{{end}}", dimension.name));
+            let _ = writeln!(out, "tmux send-keys -t {} {} Enter", window, shell_quote(command));
+        }
+    }
+    out
+}
+
+/// A path on the host for its shell: quoted, but with a leading `~` left for it to expand
+fn remote_path(dir: &Path) -> String {
+    let dir = dir.display().to_string();
+    match dir.strip_prefix("~/") {
+        Some(rest) => format!("~/{}", shell_quote(rest)),
+        None if dir == "~" => dir,
+        None => shell_quote(&dir),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_script_creates_the_session_once() {
+        let mut dimension = Dimension::new_with_base_dir("build".to_string(), Some("~/src/app".into()));
+        dimension.setup = Some("make deps".to_string());
+        dimension.configured_tabs = vec![
+            Tab::new("shell".to_string(), None, None),
+            Tab::new("logs".to_string(), Some("tail -f /var/log/build.log".to_string()), Some("/var/log".into())),
+        ];
+        assert_eq!(
+            start_script(&dimension),
+            "tmux has-session -t '=build' 2>/dev/null && exit 0\n\
+             (cd ~/'src/app' && make deps)\n\
+             tmux new-session -d -s 'build' -n 'shell' -c ~/'src/app'\n\
+             tmux new-window -t '=build:' -n 'logs' -c '/var/log'\n\
+             tmux send-keys -t '=build:{end}' 'tail -f /var/log/build.log' Enter\n"
+        );
+        assert_eq!(attach_command("ci", "build"), "ssh -t 'ci' 'tmux attach-session -t '\\''=build'\\'''");
+    }
+}
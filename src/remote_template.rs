@@ -0,0 +1,231 @@
+//! `dimensions init --from <repo>[#<template>]`: templates shared in a git repo. The repo holds a
+//! `dimensions.toml` with the same `[[templates]]` as the config, each optionally naming a project
+//! `repo` to clone into the new dimension's base directory.
+
+use crate::dimension::{Dimension, DimensionConfig};
+use crate::path_completion::PathCompleter;
+use crate::paths;
+use crate::template;
+use anyhow::{Context, Result};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::Path;
+use std::process::Command;
+
+/// File at the root of a template repo listing its templates
+const TEMPLATE_FILE: &str = "dimensions.toml";
+
+/// A template from a template repo, plus the project it sets up
+#[derive(Debug, Clone)]
+pub struct RemoteTemplate {
+    pub dimension: Dimension,
+    // Cloned into the dimension's base directory; may hold placeholders like the rest
+    pub repo: Option<String>,
+}
+
+/// Split `<repo>#<template>` on its last `#`
+pub fn parse_source(source: &str) -> (&str, Option<&str>) {
+    match source.rsplit_once('#') {
+        Some((repo, name)) if !name.is_empty() => (repo, Some(name)),
+        Some((repo, _)) => (repo, None),
+        None => (source, None),
+    }
+}
+
+/// Templates listed in a template repo's `dimensions.toml`
+pub fn parse(contents: &str) -> Result<Vec<RemoteTemplate>> {
+    let mut file: toml::Table = toml::from_str(contents).with_context(|| format!("Invalid {}", TEMPLATE_FILE))?;
+    let Some(toml::Value::Array(entries)) = file.remove("templates") else {
+        anyhow::bail!("{} has no [[templates]]", TEMPLATE_FILE);
+    };
+    entries
+        .into_iter()
+        .map(|entry| {
+            let toml::Value::Table(mut table) = entry else {
+                anyhow::bail!("Each [[templates]] entry must be a table");
+            };
+            let repo = match table.remove("repo") {
+                Some(toml::Value::String(repo)) => Some(repo),
+                Some(_) => anyhow::bail!("A template's `repo` must be a string"),
+                None => None,
+            };
+            let dimension: Dimension = toml::Value::Table(table).try_into().context("Invalid template")?;
+            Ok(RemoteTemplate { dimension, repo })
+        })
+        .collect()
+}
+
+/// The template called `name`, or the only one when no name is given
+pub fn pick(templates: Vec<RemoteTemplate>, name: Option<&str>) -> Result<RemoteTemplate> {
+    let names = templates.iter().map(|t| t.dimension.name.clone()).collect::<Vec<_>>().join(", ");
+    match name {
+        Some(name) => templates
+            .into_iter()
+            .find(|t| t.dimension.name == name)
+            .ok_or_else(|| anyhow::anyhow!("No template '{}' (available: {})", name, names)),
+        None if templates.len() == 1 => Ok(templates.into_iter().next().unwrap()),
+        None => anyhow::bail!("Pick a template with <repo>#<template> (available: {})", names),
+    }
+}
+
+/// `dimensions init --from <repo>[#<template>] [--name <name>] [--dir <dir>] [--var key=value]...`
+pub fn init_cli(
+    config: &mut DimensionConfig,
+    source: &str,
+    name: Option<&str>,
+    dir: Option<&str>,
+    vars: &[String],
+) -> Result<()> {
+    let (repo, template_name) = parse_source(source);
+    let remote = fetch(repo, template_name)?;
+
+    let mut values = HashMap::new();
+    for var in vars {
+        let Some((key, value)) = var.split_once('=') else {
+            anyhow::bail!("Expected --var key=value, got '{}'", var);
+        };
+        values.insert(key.trim().to_string(), value.to_string());
+    }
+
+    let name = match name {
+        Some(name) => name.trim().to_string(),
+        None => ask("name", Some(&remote.dimension.name))?,
+    };
+    if name.is_empty() {
+        anyhow::bail!("Dimension name cannot be empty");
+    }
+    if config.name_in_use(&name) {
+        anyhow::bail!("Dimension '{}' already exists", name);
+    }
+    values.insert("name".to_string(), name.clone());
+
+    // The project lands in ./<name> unless told otherwise
+    let dir = match dir {
+        Some(dir) => dir.to_string(),
+        None => std::env::current_dir()?.join(&name).to_string_lossy().into_owned(),
+    };
+    values.insert("path".to_string(), PathCompleter::expand_path(&dir));
+
+    for variable in template::variables(&remote.dimension)? {
+        if let Entry::Vacant(entry) = values.entry(variable) {
+            let value = ask(entry.key(), None)?;
+            entry.insert(value);
+        }
+    }
+
+    let dimension = template::instantiate(&remote.dimension, &values)?;
+    if let Some(base_dir) = &dimension.base_dir {
+        match &remote.repo {
+            Some(repo) => clone_project(&template::fill(repo, &values), base_dir)?,
+            None => std::fs::create_dir_all(base_dir)
+                .with_context(|| format!("Failed to create {}", base_dir.display()))?,
+        }
+    }
+
+    config.add_dimension(dimension);
+    config.save()?;
+    println!("Created dimension '{}' from template '{}'", name, remote.dimension.name);
+    Ok(())
+}
+
+/// Shallow-clone the template repo and read the chosen template out of it
+fn fetch(repo: &str, name: Option<&str>) -> Result<RemoteTemplate> {
+    let checkout = std::env::temp_dir().join(format!("dimensions-init-{}", paths::random_suffix()?));
+    // Fails rather than reuse a directory someone else put there; git clones into it while empty
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&checkout)
+        .with_context(|| format!("Failed to create {}", checkout.display()))?;
+    let result = git_clone(repo, &checkout, true).and_then(|()| {
+        let path = checkout.join(TEMPLATE_FILE);
+        let contents =
+            std::fs::read_to_string(&path).with_context(|| format!("{} has no {}", repo, TEMPLATE_FILE))?;
+        pick(parse(&contents)?, name)
+    });
+    let _ = std::fs::remove_dir_all(&checkout);
+    result
+}
+
+/// Clone the project into `dir`, leaving a directory that already has files in it alone
+fn clone_project(repo: &str, dir: &Path) -> Result<()> {
+    let occupied = std::fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some());
+    if occupied {
+        eprintln!("{} already exists; not cloning {}", dir.display(), repo);
+        return Ok(());
+    }
+    eprintln!("Cloning {} into {}", repo, dir.display());
+    git_clone(repo, dir, false)
+}
+
+fn git_clone(repo: &str, dir: &Path, shallow: bool) -> Result<()> {
+    let mut command = Command::new("git");
+    command.args(["clone", "--quiet"]);
+    if shallow {
+        command.args(["--depth", "1"]);
+    }
+    let status = command
+        .arg("--")
+        .arg(repo)
+        .arg(dir)
+        .status()
+        .context("Failed to run git")?;
+    if !status.success() {
+        anyhow::bail!("git clone {} failed", repo);
+    }
+    Ok(())
+}
+
+/// Prompt for a variable on the terminal; `default` is used when the answer is empty
+fn ask(variable: &str, default: Option<&str>) -> Result<String> {
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("Template needs '{}'; pass --var {}=<value>", variable, variable);
+    }
+    match default {
+        Some(default) => eprint!("{} [{}]: ", variable, default),
+        None => eprint!("{}: ", variable),
+    }
+    std::io::stderr().flush().ok();
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+    Ok(match default {
+        Some(default) if input.is_empty() => default.to_string(),
+        _ => input.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_templates_and_their_project_repos() {
+        assert_eq!(parse_source("git@github.com:org/dev-templates.git#web"), ("git@github.com:org/dev-templates.git", Some("web")));
+        assert_eq!(parse_source("https://example.com/t.git"), ("https://example.com/t.git", None));
+
+        let templates = parse(
+            r#"
+[[templates]]
+name = "web"
+repo = "git@github.com:org/{{name}}.git"
+
+[[templates.tabs]]
+name = "server"
+command = "npm run dev"
+
+[[templates]]
+name = "api"
+"#,
+        )
+        .unwrap();
+        assert_eq!(templates.len(), 2);
+
+        let web = pick(templates.clone(), Some("web")).unwrap();
+        assert_eq!(web.repo.as_deref(), Some("git@github.com:org/{{name}}.git"));
+        assert_eq!(web.dimension.configured_tabs[0].command.as_deref(), Some("npm run dev"));
+        assert!(pick(templates.clone(), Some("mobile")).is_err());
+        assert!(pick(templates, None).is_err());
+    }
+}
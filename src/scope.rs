@@ -0,0 +1,89 @@
+use crate::dimension::Dimension;
+use serde::{Deserialize, Serialize};
+
+/// Include/exclude globs limiting which dimensions the picker shows.
+/// Used for named `scopes` in the config and for `--only` / `--exclude`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scope {
+    // Show only dimensions matching one of these (all when empty)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    // Hide dimensions matching any of these, even if included
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl Scope {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Merge another scope's patterns into this one
+    pub fn extend(&mut self, other: &Scope) {
+        self.include.extend(other.include.iter().cloned());
+        self.exclude.extend(other.exclude.iter().cloned());
+    }
+
+    pub fn matches(&self, dimension: &Dimension) -> bool {
+        let keys = match_keys(dimension);
+        let hit = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|p| keys.iter().any(|k| glob_match(p, k)))
+        };
+        (self.include.is_empty() || hit(&self.include)) && !hit(&self.exclude)
+    }
+
+    /// Short description for the title bar, e.g. `work/*, !*-old`
+    pub fn describe(&self) -> String {
+        self.include
+            .iter()
+            .cloned()
+            .chain(self.exclude.iter().map(|p| format!("!{}", p)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Names a pattern is matched against: the dimension name, plus
+/// `<group>/<name>` and `<tag>/<name>` so `work/*` selects a whole group
+fn match_keys(dimension: &Dimension) -> Vec<String> {
+    let mut keys = vec![dimension.name.clone()];
+    for prefix in dimension.group.iter().chain(dimension.tags.iter()) {
+        keys.push(format!("{}/{}", prefix, dimension.name));
+    }
+    keys
+}
+
+/// Shell-style glob: `*` matches any run of characters, `?` exactly one
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    // Position of the last `*` and the text index it was tried at, for backtracking
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        match p.get(pi) {
+            Some('*') => {
+                star = Some((pi, ti));
+                pi += 1;
+            }
+            Some(&c) if c == '?' || c == t[ti] => {
+                pi += 1;
+                ti += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    pi = sp + 1;
+                    ti = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
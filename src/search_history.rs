@@ -0,0 +1,74 @@
+//! Recent `/` search queries, newest first, kept in the state directory so `↑` in an empty
+//! search brings them back across runs.
+
+use crate::paths;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Most queries remembered
+const LIMIT: usize = 50;
+
+fn path() -> PathBuf {
+    paths::state_dir().join("search-history")
+}
+
+/// Saved queries, newest first; empty when there are none yet
+pub fn load() -> Vec<String> {
+    load_from(&path())
+}
+
+fn load_from(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|contents| contents.lines().filter(|l| !l.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Move `query` to the front of `history`
+pub fn record(history: &mut Vec<String>, query: &str) {
+    let query = query.trim();
+    if query.is_empty() || query.contains('\n') {
+        return;
+    }
+    history.retain(|q| q != query);
+    history.insert(0, query.to_string());
+    history.truncate(LIMIT);
+}
+
+/// Save `history`; failures are ignored, since history must never get in the way of searching
+pub fn save(history: &[String]) {
+    save_to(&path(), history);
+}
+
+fn save_to(path: &Path, history: &[String]) {
+    let _ = fs::write(path, history.join("\n") + "\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_moves_a_query_to_the_front() {
+        let mut history = vec!["api".to_string(), "web".to_string()];
+        record(&mut history, " web ");
+        record(&mut history, "");
+        assert_eq!(history, ["web", "api"]);
+
+        for i in 0..LIMIT {
+            record(&mut history, &format!("q{}", i));
+        }
+        assert_eq!(history.len(), LIMIT);
+        assert_eq!(history[0], format!("q{}", LIMIT - 1));
+    }
+
+    #[test]
+    fn saved_history_loads_back() {
+        let path = std::env::temp_dir().join(format!("dimensions-search-history-{}", std::process::id()));
+        assert!(load_from(&path).is_empty());
+
+        let history = vec!["web".to_string(), "api server".to_string()];
+        save_to(&path, &history);
+        assert_eq!(load_from(&path), history);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use crate::dimension::Dimension;
+use crate::tmux_state::TmuxStateCache;
+
+/// A tab as seen by search: a live tmux window or a configured tab
+pub struct IndexedTab {
+    // Position in the dimension's tab/window list
+    pub list_index: usize,
+    // tmux window index for live sessions, configured index otherwise
+    pub window_index: usize,
+    pub name: String,
+    pub name_lower: String,
+    pub command_lower: Option<String>,
+}
+
+pub struct IndexedDimension {
+    pub index: usize,
+    pub name: String,
+    pub name_lower: String,
+    // Group, tags and aliases
+    pub tags_lower: Vec<String>,
+    pub tabs: Vec<IndexedTab>,
+}
+
+/// Pre-extracted, lowercased strings for search, built once per search session
+/// so typing never waits on tmux
+pub struct SearchIndex {
+    pub dimensions: Vec<IndexedDimension>,
+}
+
+impl SearchIndex {
+    pub fn build(dimensions: &[Dimension], windows: &TmuxStateCache) -> Self {
+        let dimensions = dimensions
+            .iter()
+            .enumerate()
+            .map(|(index, dimension)| IndexedDimension {
+                index,
+                name: dimension.name.clone(),
+                name_lower: dimension.name.to_lowercase(),
+                tags_lower: dimension
+                    .group
+                    .iter()
+                    .chain(dimension.tags.iter())
+                    .chain(dimension.aliases.iter())
+                    .map(|t| t.to_lowercase())
+                    .collect(),
+                tabs: index_tabs(dimension, windows),
+            })
+            .collect();
+        Self { dimensions }
+    }
+}
+
+fn index_tabs(dimension: &Dimension, windows: &TmuxStateCache) -> Vec<IndexedTab> {
+    let command_for = |name: &str| {
+        dimension
+            .configured_tabs
+            .iter()
+            .find(|t| t.name == name)
+            .and_then(|t| t.command.as_ref())
+            .map(|c| c.to_lowercase())
+    };
+
+    // Live sessions list their real windows; commands come from the matching configured tab
+    let tabs: Vec<(usize, String)> = match windows.windows(&dimension.name) {
+        Some(live) => live,
+        None => dimension
+            .configured_tabs
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (i, t.name.clone()))
+            .collect(),
+    };
+
+    tabs.into_iter()
+        .enumerate()
+        .map(|(list_index, (window_index, name))| IndexedTab {
+            list_index,
+            window_index,
+            name_lower: name.to_lowercase(),
+            command_lower: command_for(&name),
+            name,
+        })
+        .collect()
+}
@@ -0,0 +1,379 @@
+//! `dimensions serve`: a small read-only dashboard (an HTML page and a JSON API) of dimensions,
+//! their sessions and health checks, for keeping an eye on a dev box from another machine.
+//! Switching is only accepted from the machine itself, with the token of this run that the
+//! page's buttons send, so other web pages open in a local browser can't trigger it. Requests
+//! naming a host other than this machine (DNS rebinding) are turned away.
+
+use crate::cli;
+use crate::dimension::DimensionConfig;
+use crate::engine::DimensionsEngine;
+use crate::machine;
+use crate::paths;
+use crate::tmux::Tmux;
+use crate::tmux_options;
+use crate::ui::view_model::CONCEALED;
+use crate::uri;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+/// How often the dashboard page reloads itself
+const REFRESH_SECS: u32 = 10;
+
+/// Header carrying the run's token on switch requests
+const TOKEN_HEADER: &str = "x-dimensions-token";
+
+/// Most of a request that is read: its request line and headers
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// How long a client gets to send all of that, however it trickles in
+const REQUEST_DEADLINE: Duration = Duration::from_secs(5);
+
+/// What the handler needs from a request
+struct Request<'a> {
+    method: &'a str,
+    path: &'a str,
+    // Host header, port included
+    host: Option<String>,
+    token: Option<String>,
+    // Whether the peer is this machine
+    local: bool,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(value: &Value) -> Self {
+        Self { status: "200 OK", content_type: "application/json", body: value.to_string() }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Self { status, content_type: "application/json", body: json!({ "error": message }).to_string() }
+    }
+}
+
+/// `dimensions serve [--port N] [--bind ADDR]`: answer requests one at a time until interrupted
+pub fn serve_cli(port: u16, bind: &str) -> Result<()> {
+    let listener =
+        TcpListener::bind((bind, port)).with_context(|| format!("Failed to listen on {}:{}", bind, port))?;
+    // A secret for this run, which switching must present
+    let token = paths::random_suffix()?;
+    println!("Serving dimensions on http://{}:{} (Ctrl+C to stop)", bind, port);
+    for stream in listener.incoming().flatten() {
+        if let Err(e) = handle(stream, &token) {
+            eprintln!("serve: {:#}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Reads from a client until a deadline, however slowly it sends, so one connection can't
+/// hold up the others for long
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request took too long"));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle(mut stream: TcpStream, token: &str) -> Result<()> {
+    let local = stream.peer_addr().is_ok_and(|addr| addr.ip().is_loopback());
+
+    let deadline = Instant::now() + REQUEST_DEADLINE;
+    let mut reader = BufReader::new(DeadlineReader { stream: &stream, deadline }.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Only Host and the token matter; a body is never read
+    let (mut host, mut sent_token) = (None, None);
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        if let Some((name, value)) = header.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "host" => host = Some(value.trim().to_string()),
+                TOKEN_HEADER => sent_token = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+        header.clear();
+    }
+
+    // Headers cut off at the limit can't be trusted to be complete
+    let too_large = reader.get_ref().limit() == 0;
+    let response = match parse_request_line(&request_line) {
+        _ if too_large => Response::error("431 Request Header Fields Too Large", "Request too large"),
+        Some((method, path)) => {
+            route(&Request { method, path, host, token: sent_token, local }, token, machine::hostname().as_deref())
+        }
+        None => Response::error("400 Bad Request", "Malformed request"),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    Ok(())
+}
+
+/// Method and path (without a query string) of an HTTP request line
+fn parse_request_line(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+    path.starts_with('/').then_some((method, path))
+}
+
+/// Whether a Host header names this machine: `localhost`, an IP address or its hostname.
+/// Anything else is a name pointed at us by someone else's DNS.
+fn host_allowed(host: &str, hostname: Option<&str>) -> bool {
+    let name = match host.strip_prefix('[') {
+        // [::1]:7777
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    let name = name.to_ascii_lowercase();
+    name == "localhost"
+        || name.parse::<IpAddr>().is_ok()
+        || hostname.is_some_and(|hostname| {
+            let hostname = hostname.to_ascii_lowercase();
+            name == hostname || name == format!("{}.local", hostname)
+        })
+}
+
+fn route(request: &Request, token: &str, hostname: Option<&str>) -> Response {
+    if !request.host.as_deref().is_some_and(|host| host_allowed(host, hostname)) {
+        return Response::error("403 Forbidden", "Unexpected Host header");
+    }
+    let result = match (request.method, request.path) {
+        ("GET", "/") => status().map(|status| Response {
+            status: "200 OK",
+            content_type: "text/html; charset=utf-8",
+            body: page(&status, request.local.then_some(token)),
+        }),
+        ("GET", "/api/dimensions") => status().map(|status| Response::json(&status)),
+        ("POST", path) if path.starts_with("/api/switch/") => {
+            if !request.local {
+                return Response::error("403 Forbidden", "Switching is only allowed from this machine");
+            }
+            if request.token.as_deref() != Some(token) {
+                return Response::error("403 Forbidden", "Missing or wrong token; use the dashboard's Switch buttons");
+            }
+            switch(&path["/api/switch/".len()..]).map(|()| Response::json(&json!({ "ok": true })))
+        }
+        _ => return Response::error("404 Not Found", "Not found"),
+    };
+    result.unwrap_or_else(|e| Response::error("500 Internal Server Error", &format!("{:#}", e)))
+}
+
+/// Every dimension with its session and missing paths, plus tmux options that get in the way.
+/// Read fresh for each request, so edits made elsewhere show up on the next refresh.
+fn status() -> Result<Value> {
+    let engine = DimensionsEngine::new(DimensionConfig::load()?);
+    let dimensions: Vec<Value> = engine
+        .list()
+        .into_iter()
+        .zip(&engine.config.dimensions)
+        .map(|(summary, dimension)| {
+            // Same as in the TUI: private dimensions keep their tabs and paths to themselves
+            let (tabs, base_dir) = if dimension.private {
+                (vec![CONCEALED.to_string(); summary.tabs.len()], summary.base_dir.map(|_| CONCEALED.to_string()))
+            } else {
+                (summary.tabs, summary.base_dir.map(|dir| dir.display().to_string()))
+            };
+            let missing: Vec<String> = dimension.missing_paths().iter().map(|p| p.display().to_string()).collect();
+            json!({
+                "name": summary.name,
+                "running": summary.running,
+                "tabs": tabs,
+                "base_dir": base_dir,
+                "healthy": missing.is_empty(),
+                "missing_paths": if dimension.private { vec![] } else { missing },
+            })
+        })
+        .collect();
+    let conflicts: Vec<Value> = tmux_options::conflicts(Tmux::global_option)
+        .into_iter()
+        .map(|c| json!({ "option": c.option, "value": c.value, "problem": c.problem, "fix": c.fix }))
+        .collect();
+    Ok(json!({ "dimensions": dimensions, "tmux_conflicts": conflicts }))
+}
+
+/// Start (if needed) and switch the most recently used tmux client to a dimension
+fn switch(encoded_name: &str) -> Result<()> {
+    let name = uri::percent_decode(encoded_name)?;
+    let target = DimensionsEngine::load()?.switch(&name, None)?;
+    cli::switch_recent_client(target)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// The dashboard: one table row per dimension, drawn from the same data as the JSON API.
+/// Viewed from this machine (`token` given), rows get Switch buttons.
+fn page(status: &Value, token: Option<&str>) -> String {
+    let text = |value: &Value| escape_html(value.as_str().unwrap_or(""));
+    let mut rows = String::new();
+    for dimension in status["dimensions"].as_array().into_iter().flatten() {
+        let tabs: Vec<String> = dimension["tabs"].as_array().into_iter().flatten().map(text).collect();
+        let health = if dimension["healthy"].as_bool() == Some(false) {
+            let missing: Vec<String> = dimension["missing_paths"].as_array().into_iter().flatten().map(text).collect();
+            format!("⚠ missing {}", missing.join(", "))
+        } else {
+            "ok".to_string()
+        };
+        let switch = match token {
+            Some(token) => format!(
+                r#"<button data-name="{}" onclick="fetch('/api/switch/'+encodeURIComponent(this.dataset.name),{{method:'POST',headers:{{'{}':'{}'}}}}).then(()=>location.reload())">Switch</button>"#,
+                text(&dimension["name"]),
+                TOKEN_HEADER,
+                token
+            ),
+            None => String::new(),
+        };
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            text(&dimension["name"]),
+            if dimension["running"].as_bool() == Some(true) { "running" } else { "stopped" },
+            tabs.join(", "),
+            text(&dimension["base_dir"]),
+            health,
+            switch
+        ));
+    }
+
+    let mut conflicts = String::new();
+    for conflict in status["tmux_conflicts"].as_array().into_iter().flatten() {
+        conflicts.push_str(&format!(
+            "<li>{} is {}: {} (fix: <code>{}</code>)</li>\n",
+            text(&conflict["option"]),
+            text(&conflict["value"]),
+            text(&conflict["problem"]),
+            text(&conflict["fix"])
+        ));
+    }
+    if !conflicts.is_empty() {
+        conflicts = format!("<h2>tmux options</h2>\n<ul>\n{}</ul>\n", conflicts);
+    }
+
+    format!(
+        r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{}">
+<title>Dimensions</title>
+<style>body {{ font-family: monospace; margin: 2em; }} td, th {{ padding: 0.2em 1em; text-align: left; }}</style>
+</head>
+<body>
+<h1>Dimensions</h1>
+<table>
+<tr><th>Name</th><th>Session</th><th>Tabs</th><th>Directory</th><th>Health</th><th></th></tr>
+{}</table>
+{}<p><a href="/api/dimensions">JSON</a></p>
+</body>
+</html>
+"#,
+        REFRESH_SECS, rows, conflicts
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request<'a>(method: &'a str, path: &'a str, host: &str, token: Option<&str>, local: bool) -> Request<'a> {
+        Request { method, path, host: Some(host.to_string()), token: token.map(str::to_string), local }
+    }
+
+    fn status_of(request: &Request) -> &'static str {
+        route(request, "secret", Some("devbox")).status
+    }
+
+    #[test]
+    fn request_lines_give_method_and_path() {
+        assert_eq!(parse_request_line("GET /api/dimensions?x=1 HTTP/1.1\r\n"), Some(("GET", "/api/dimensions")));
+        assert_eq!(parse_request_line("POST /api/switch/my%20app HTTP/1.1"), Some(("POST", "/api/switch/my%20app")));
+        assert_eq!(parse_request_line("GET http://example.com/ HTTP/1.1"), None);
+        assert_eq!(parse_request_line("garbage"), None);
+    }
+
+    #[test]
+    fn only_names_for_this_machine_are_allowed_hosts() {
+        assert!(host_allowed("localhost:7777", None));
+        assert!(host_allowed("127.0.0.1:7777", None));
+        assert!(host_allowed("[::1]:7777", None));
+        assert!(host_allowed("DevBox.local:7777", Some("devbox")));
+        assert!(!host_allowed("devbox.evil.example", Some("devbox")));
+        assert!(!host_allowed("devbox:7777", None));
+
+        // DNS rebinding: an attacker's name resolving to us
+        assert_eq!(status_of(&request("GET", "/nope", "evil.example:7777", None, true)), "403 Forbidden");
+        let mut missing = request("GET", "/nope", "localhost", None, true);
+        missing.host = None;
+        assert_eq!(status_of(&missing), "403 Forbidden");
+        assert_eq!(status_of(&request("GET", "/nope", "localhost:7777", None, true)), "404 Not Found");
+    }
+
+    #[test]
+    fn switching_takes_this_machine_and_the_token() {
+        assert_eq!(status_of(&request("POST", "/api/switch/web", "10.0.0.2:7777", Some("secret"), false)), "403 Forbidden");
+        // Another page in a local browser knows neither the token nor a way to send it
+        assert_eq!(status_of(&request("POST", "/api/switch/web", "127.0.0.1:7777", None, true)), "403 Forbidden");
+        assert_eq!(status_of(&request("POST", "/api/switch/web", "127.0.0.1:7777", Some("guess"), true)), "403 Forbidden");
+        // Only a POST switches
+        assert_eq!(status_of(&request("GET", "/api/switch/web", "127.0.0.1:7777", Some("secret"), true)), "404 Not Found");
+    }
+
+    #[test]
+    fn the_page_escapes_names_and_offers_switching_with_the_token() {
+        let status = json!({
+            "dimensions": [{ "name": "<web>", "running": true, "tabs": ["editor"], "base_dir": null, "healthy": true }],
+            "tmux_conflicts": [],
+        });
+        let html = page(&status, None);
+        assert!(html.contains("<td>&lt;web&gt;</td><td>running</td><td>editor</td>"));
+        assert!(!html.contains("<button"));
+        let html = page(&status, Some("secret"));
+        assert!(html.contains(r#"data-name="&lt;web&gt;""#));
+        assert!(html.contains("'x-dimensions-token':'secret'"));
+    }
+
+    #[test]
+    fn oversized_requests_are_turned_away() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        // Headers that never end; exactly the limit, so nothing is left unread to reset the connection
+        let mut request = "GET / HTTP/1.1\r\nHost: localhost\r\nX-Padding: ".to_string();
+        request.push_str(&"x".repeat(MAX_REQUEST_BYTES as usize - request.len()));
+        client.write_all(request.as_bytes()).unwrap();
+
+        let (stream, _) = listener.accept().unwrap();
+        handle(stream, "secret").unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 431 "));
+    }
+}
@@ -0,0 +1,314 @@
+//! Snapshots of live sessions (windows, panes, layouts, directories, running programs),
+//! saved to the state directory so a dimension can come back as it was after a reboot
+//! or a killed tmux server, rather than from its configured tabs. A hibernated dimension
+//! is one whose session was killed right after a snapshot; switching to it thaws it.
+
+use crate::backend::{MultiplexerBackend, TmuxBackend};
+use crate::dimension::{DimensionConfig, SnapshotSettings};
+use crate::paths;
+use crate::tmux::PaneInfo;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Programs restarted on restore unless `snapshots.restore_processes` says otherwise
+const DEFAULT_RESTORE_PROCESSES: &[&str] =
+    &["vi", "vim", "nvim", "emacs", "nano", "hx", "man", "less", "more", "tail", "top", "htop", "btop", "watch"];
+
+/// Foreground programs that are just the pane's shell
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "fish", "dash", "ksh", "tcsh", "csh", "nu", "elvish", "xonsh"];
+
+/// Whether a pane's `#{pane_current_command}` is a shell waiting at its prompt
+/// (login shells show up as `-zsh`)
+pub fn is_shell(command: &str) -> bool {
+    SHELLS.contains(&command.trim_start_matches('-'))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub dimension: String,
+    // Seconds since the epoch
+    pub taken_at: u64,
+    // Taken just before the session was killed to free resources; cleared once thawed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hibernated: bool,
+    pub windows: Vec<WindowSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowSnapshot {
+    pub name: String,
+    pub layout: String,
+    #[serde(default)]
+    pub active: bool,
+    pub panes: Vec<PaneSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneSnapshot {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    // Full command line of the foreground program; None for a plain shell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+fn snapshot_dir() -> PathBuf {
+    let dir = paths::state_dir().join("snapshots");
+    fs::create_dir_all(&dir).ok();
+    dir
+}
+
+fn snapshot_path(dimension: &str) -> PathBuf {
+    let file: String = dimension
+        .chars()
+        .map(|c| if c == '/' || c == '\\' { '_' } else { c })
+        .collect();
+    snapshot_dir().join(format!("{}.json", file))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Group `list-panes` output into windows, in window then pane order
+pub fn from_panes(dimension: &str, mut panes: Vec<PaneInfo>, command_of: impl Fn(&PaneInfo) -> Option<String>) -> Snapshot {
+    panes.sort_by_key(|p| (p.window_index, p.pane_index));
+    let mut windows: Vec<(usize, WindowSnapshot)> = vec![];
+    for pane in &panes {
+        if windows.last().is_none_or(|(index, _)| *index != pane.window_index) {
+            windows.push((
+                pane.window_index,
+                WindowSnapshot {
+                    name: pane.window_name.clone(),
+                    layout: pane.layout.clone(),
+                    active: pane.window_active,
+                    panes: vec![],
+                },
+            ));
+        }
+        if let Some((_, window)) = windows.last_mut() {
+            window.panes.push(PaneSnapshot { path: pane.path.clone(), command: command_of(pane) });
+        }
+    }
+    Snapshot {
+        dimension: dimension.to_string(),
+        taken_at: now(),
+        hibernated: false,
+        windows: windows.into_iter().map(|(_, w)| w).collect(),
+    }
+}
+
+/// Capture the live session of `dimension`
+pub fn capture(backend: &dyn MultiplexerBackend, dimension: &str) -> Result<Snapshot> {
+    if !backend.session_exists(dimension) {
+        anyhow::bail!("'{}' has no running session to snapshot", dimension);
+    }
+    Ok(from_panes(dimension, backend.list_panes(dimension)?, |pane| backend.pane_command_line(pane)))
+}
+
+/// Full command line of the pane's foreground program, or None when the pane sits at a shell
+pub fn foreground_command(pane: &PaneInfo) -> Option<String> {
+    if is_shell(&pane.command) {
+        return None;
+    }
+    // The pane's own process is its shell (or the wrapper a tab command runs under);
+    // the program it's running is its newest child
+    let child = Command::new("pgrep").args(["-n", "-P", &pane.pid.to_string()]).output().ok()?;
+    let child = String::from_utf8_lossy(&child.stdout).trim().to_string();
+    let pid = if child.is_empty() { pane.pid.to_string() } else { child };
+    let args = Command::new("ps").args(["-o", "args=", "-p", &pid]).output().ok()?;
+    let args = String::from_utf8_lossy(&args.stdout).trim().to_string();
+    Some(if args.is_empty() { pane.command.clone() } else { args })
+}
+
+pub fn save(snapshot: &Snapshot) -> Result<PathBuf> {
+    let path = snapshot_path(&snapshot.dimension);
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(snapshot)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+pub fn load(dimension: &str) -> Result<Option<Snapshot>> {
+    let path = snapshot_path(dimension);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let snapshot = serde_json::from_str(&contents).with_context(|| format!("Invalid snapshot {}", path.display()))?;
+    Ok(Some(snapshot))
+}
+
+/// The snapshot to thaw `dimension` from, if it was hibernated
+pub fn hibernated(dimension: &str) -> Option<Snapshot> {
+    load(dimension).ok().flatten().filter(|s| s.hibernated)
+}
+
+/// Save a snapshot taken with `capture` as hibernated, once its session has been killed
+pub fn hibernate(mut snapshot: Snapshot) -> Result<Snapshot> {
+    snapshot.hibernated = true;
+    save(&snapshot)?;
+    Ok(snapshot)
+}
+
+/// Restore a hibernated session and keep its snapshot as a plain one
+pub fn thaw(backend: &dyn MultiplexerBackend, mut snapshot: Snapshot, settings: &SnapshotSettings) -> Result<()> {
+    restore(backend, &snapshot, settings)?;
+    snapshot.hibernated = false;
+    save(&snapshot)?;
+    Ok(())
+}
+
+/// Whether a pane's program should be started again on restore
+pub fn should_restore(settings: &SnapshotSettings, command: &str) -> bool {
+    let program = command
+        .split_whitespace()
+        .next()
+        .map(|p| p.rsplit('/').next().unwrap_or(p))
+        .unwrap_or_default();
+    match &settings.restore_processes {
+        Some(list) => list.iter().any(|p| p == "*" || p == program),
+        None => DEFAULT_RESTORE_PROCESSES.contains(&program),
+    }
+}
+
+/// Recreate the session from `snapshot`. Refuses when the session is already running.
+pub fn restore(backend: &dyn MultiplexerBackend, snapshot: &Snapshot, settings: &SnapshotSettings) -> Result<()> {
+    let session = &snapshot.dimension;
+    if backend.session_exists(session) {
+        anyhow::bail!("'{}' is already running; kill it first to restore its snapshot", session);
+    }
+    let Some(first) = snapshot.windows.first() else {
+        anyhow::bail!("The snapshot of '{}' has no windows", session);
+    };
+
+    let first_dir = first.panes.first().and_then(|p| p.path.clone());
+    match &first_dir {
+        Some(dir) => backend.create_session_with_dir(session, true, &dir.to_string_lossy())?,
+        None => backend.create_session(session, true)?,
+    }
+    let first_index = backend.get_first_window_index(session)?;
+    backend.rename_window(session, first_index, &first.name)?;
+    for window in &snapshot.windows[1..] {
+        let dir = window.panes.first().and_then(|p| p.path.as_deref());
+        backend.new_window(session, &window.name, None, dir)?;
+    }
+
+    // New windows are appended, so live windows line up with the snapshot's in order
+    let indexes: Vec<usize> = backend.list_windows(session)?.into_iter().map(|(index, _)| index).collect();
+    for (window, &index) in snapshot.windows.iter().zip(&indexes) {
+        for pane in window.panes.iter().skip(1) {
+            backend.split_window(session, index, pane.path.as_deref())?;
+        }
+        if window.panes.len() > 1 {
+            // A layout can fail to apply when the client size differs a lot; keep the panes anyway
+            let _ = backend.select_layout(session, index, &window.layout);
+        }
+        let base = backend
+            .list_panes(session)?
+            .into_iter()
+            .filter(|p| p.window_index == index)
+            .map(|p| p.pane_index)
+            .min()
+            .unwrap_or(0);
+        for (offset, pane) in window.panes.iter().enumerate() {
+            if let Some(command) = pane.command.as_deref().filter(|c| should_restore(settings, c)) {
+                backend.send_keys_to_pane(session, index, base + offset, command)?;
+            }
+        }
+        if window.active {
+            backend.select_window(session, index)?;
+        }
+    }
+    Ok(())
+}
+
+/// Names to act on: the given dimension, or every configured one with `--all`
+fn targets(config: &DimensionConfig, name: Option<&str>, all: bool) -> Result<Vec<String>> {
+    match (name, all) {
+        (Some(name), false) => match config.find(name) {
+            Some(dimension) => Ok(vec![dimension.name.clone()]),
+            None => anyhow::bail!("Dimension '{}' not found", name),
+        },
+        (None, true) => Ok(config.dimensions.iter().map(|d| d.name.clone()).collect()),
+        _ => anyhow::bail!("Give a dimension name or --all"),
+    }
+}
+
+/// `dimensions snapshot [<name> | --all]`
+pub fn snapshot_cli(config: &DimensionConfig, name: Option<&str>, all: bool) -> Result<()> {
+    for name in targets(config, name, all)? {
+        if all && !TmuxBackend.session_exists(&name) {
+            continue;
+        }
+        let snapshot = capture(&TmuxBackend, &name)?;
+        let panes: usize = snapshot.windows.iter().map(|w| w.panes.len()).sum();
+        save(&snapshot)?;
+        println!("Saved '{}': {} windows, {} panes", name, snapshot.windows.len(), panes);
+    }
+    Ok(())
+}
+
+/// `dimensions restore [<name> | --all]`
+pub fn restore_cli(config: &DimensionConfig, name: Option<&str>, all: bool) -> Result<()> {
+    for name in targets(config, name, all)? {
+        let Some(snapshot) = load(&name)? else {
+            if all {
+                continue;
+            }
+            anyhow::bail!("No snapshot of '{}'; take one with `dimensions snapshot {}`", name, name);
+        };
+        if all && TmuxBackend.session_exists(&name) {
+            continue;
+        }
+        if snapshot.hibernated {
+            thaw(&TmuxBackend, snapshot, &config.snapshots)?;
+        } else {
+            restore(&TmuxBackend, &snapshot, &config.snapshots)?;
+        }
+        println!("Restored '{}'", name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(window_index: usize, pane_index: usize, command: &str) -> PaneInfo {
+        PaneInfo {
+            window_index,
+            window_name: format!("w{}", window_index),
+            window_active: window_index == 2,
+            layout: format!("layout{}", window_index),
+            pane_index,
+            pid: 1,
+            command: command.to_string(),
+            path: Some(PathBuf::from(format!("/srv/{}", pane_index))),
+        }
+    }
+
+    #[test]
+    fn groups_panes_by_window_and_picks_programs_to_restart() {
+        let panes = vec![pane(2, 1, "zsh"), pane(1, 0, "nvim"), pane(2, 0, "tail")];
+        let snapshot = from_panes("api", panes, |p| (p.command != "zsh").then(|| p.command.clone()));
+        let windows: Vec<_> = snapshot.windows.iter().map(|w| (w.name.as_str(), w.panes.len(), w.active)).collect();
+        assert_eq!(windows, [("w1", 1, false), ("w2", 2, true)]);
+        assert_eq!(snapshot.windows[1].panes[0].command.as_deref(), Some("tail"));
+        assert_eq!(snapshot.windows[1].panes[1].command, None);
+
+        let defaults = SnapshotSettings::default();
+        assert!(should_restore(&defaults, "/usr/bin/nvim src/main.rs"));
+        assert!(!should_restore(&defaults, "npm run dev"));
+        let all = SnapshotSettings { restore_processes: Some(vec!["*".to_string()]) };
+        assert!(should_restore(&all, "npm run dev"));
+    }
+}
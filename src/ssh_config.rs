@@ -0,0 +1,153 @@
+use crate::dimension::{Dimension, DimensionConfig, Tab};
+use crate::discover;
+use crate::hooks::shell_quote;
+use crate::path_completion::PathCompleter;
+use anyhow::Result;
+use std::fs;
+use std::io::{IsTerminal, Write};
+
+/// Host aliases from `~/.ssh/config` (plus files it `Include`s by plain path)
+pub fn hosts() -> Vec<String> {
+    let Some(home) = dirs::home_dir() else {
+        return vec![];
+    };
+    let ssh_dir = home.join(".ssh");
+    let Ok(contents) = fs::read_to_string(ssh_dir.join("config")) else {
+        return vec![];
+    };
+
+    let mut hosts = parse_hosts(&contents);
+    for include in parse_includes(&contents) {
+        // Relative includes are resolved against ~/.ssh, like ssh does for the user config
+        let path = match include.strip_prefix("~/") {
+            Some(rest) => home.join(rest),
+            None => ssh_dir.join(&include),
+        };
+        if let Ok(included) = fs::read_to_string(path) {
+            for host in parse_hosts(&included) {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
+                }
+            }
+        }
+    }
+    hosts
+}
+
+/// Concrete host names from `Host` lines, in file order. Patterns (`*`, `?`) and
+/// negations (`!host`) can't be connected to by name, so they are skipped.
+pub fn parse_hosts(contents: &str) -> Vec<String> {
+    let mut hosts: Vec<String> = vec![];
+    for value in keyword_values(contents, "host") {
+        for name in value.split_whitespace() {
+            let name = name.trim_matches('"');
+            if name.is_empty() || name.contains(['*', '?']) || name.starts_with('!') {
+                continue;
+            }
+            if !hosts.iter().any(|h| h == name) {
+                hosts.push(name.to_string());
+            }
+        }
+    }
+    hosts
+}
+
+/// Plain-path `Include` targets (globbed includes are left out)
+fn parse_includes(contents: &str) -> Vec<String> {
+    keyword_values(contents, "include")
+        .flat_map(|value| value.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .filter(|path| !path.contains(['*', '?']))
+        .collect()
+}
+
+/// Values of every `Keyword value` / `Keyword=value` line for `keyword` (case-insensitive)
+fn keyword_values<'a>(contents: &'a str, keyword: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    contents.lines().filter_map(move |line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let split = line.find(|c: char| c.is_whitespace() || c == '=')?;
+        let (key, rest) = line.split_at(split);
+        if !key.eq_ignore_ascii_case(keyword) {
+            return None;
+        }
+        Some(rest.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim())
+    })
+}
+
+pub struct HostCompleter;
+
+impl HostCompleter {
+    /// Host completions for a partial host name, like `PathCompleter::complete_directory`
+    /// Returns (candidates, common_prefix)
+    pub fn complete_host(input: &str) -> (Vec<String>, String) {
+        let mut candidates: Vec<String> = hosts()
+            .into_iter()
+            .filter(|host| host.starts_with(input))
+            .collect();
+        candidates.sort();
+        let common_prefix = PathCompleter::common_prefix(&candidates);
+        (candidates, common_prefix)
+    }
+}
+
+/// What a tab of an ssh host dimension runs: a shell on `host`, or `remote` run there
+/// (with a terminal, so programs like `htop` work)
+pub fn ssh_command(host: &str, remote: Option<&str>) -> String {
+    match remote.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(remote) => format!("ssh -t {} {}", shell_quote(host), shell_quote(remote)),
+        None => format!("ssh {}", shell_quote(host)),
+    }
+}
+
+/// `dimensions ssh [<host>...] [--yes]`: a dimension per host, starting with one shell tab
+/// there. Without hosts, offers those in `~/.ssh/config` that have no dimension yet.
+pub fn ssh_cli(config: &mut DimensionConfig, hosts: &[String], yes: bool) -> Result<()> {
+    let has_dimension = |host: &str| config.dimensions.iter().any(|d| d.ssh_host.as_deref() == Some(host));
+    let picked: Vec<String> = if !hosts.is_empty() {
+        hosts.to_vec()
+    } else {
+        let offered: Vec<String> = self::hosts().into_iter().filter(|h| !has_dimension(h)).collect();
+        if offered.is_empty() {
+            println!("No hosts in ~/.ssh/config without a dimension");
+            return Ok(());
+        }
+        for (i, host) in offered.iter().enumerate() {
+            println!("{:>3}  {}", i + 1, host);
+        }
+        let selection = if yes {
+            (0..offered.len()).collect()
+        } else {
+            if !std::io::stdin().is_terminal() {
+                anyhow::bail!("Refusing to create dimensions without confirmation; pass --yes or name the hosts");
+            }
+            eprint!("Create dimensions for which? [all, none, or e.g. 1,3-5] ");
+            std::io::stderr().flush().ok();
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            if input.trim().eq_ignore_ascii_case("none") {
+                eprintln!("Cancelled.");
+                return Ok(());
+            }
+            discover::parse_selection(&input, offered.len())?
+        };
+        selection.into_iter().map(|i| offered[i].clone()).collect()
+    };
+
+    for host in picked {
+        let mut name = host.clone();
+        let mut n = 2;
+        while config.name_in_use(&name) {
+            name = format!("{}-{}", host, n);
+            n += 1;
+        }
+        let mut dimension = Dimension::new_with_base_dir(name.clone(), None);
+        dimension.ssh_host = Some(host);
+        dimension.add_tab(Tab::new("shell".to_string(), None, None));
+        config.add_dimension(dimension);
+        println!("Created dimension: {}", name);
+    }
+    config.save()?;
+    Ok(())
+}
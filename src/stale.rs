@@ -0,0 +1,224 @@
+//! Stale dimensions: base directories with no file changes or commits for months. `dimensions gc`
+//! lists them and archives the ones picked; the TUI mentions them now and then.
+
+use crate::dimension::{Dimension, DimensionConfig, TidySettings};
+use crate::discover;
+use crate::paths;
+use crate::tmux::Tmux;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTH_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// The TUI mentions stale dimensions at most this often
+const PROMPT_INTERVAL_SECS: u64 = MONTH_SECS;
+
+/// Directories that change on their own (or never matter) when looking for recent edits
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target", "vendor", "dist", "build", ".venv", "__pycache__"];
+
+/// How deep and how many entries to look at for the newest file, so huge trees stay cheap
+const MAX_DEPTH: usize = 4;
+const MAX_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleDimension {
+    pub name: String,
+    // Seconds since the epoch of the newest commit or file change found
+    pub last_activity: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn archive_path() -> PathBuf {
+    paths::state_dir().join("archive.json")
+}
+
+fn prompt_stamp_path() -> PathBuf {
+    paths::state_dir().join("tidy-prompted")
+}
+
+/// Newest commit or file modification under `dir`, whichever is later
+pub fn last_activity(dir: &Path) -> Option<u64> {
+    let commit = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", "-1", "--format=%ct"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse::<u64>().ok());
+    let mut budget = MAX_ENTRIES;
+    let modified = newest_mtime(dir, MAX_DEPTH, &mut budget);
+    commit.max(modified)
+}
+
+fn mtime(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    modified.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+fn newest_mtime(dir: &Path, depth: usize, budget: &mut usize) -> Option<u64> {
+    let mut newest = mtime(dir);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return newest;
+    };
+    for entry in entries.flatten() {
+        if *budget == 0 {
+            break;
+        }
+        *budget -= 1;
+        let path = entry.path();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        let found = if !is_dir {
+            mtime(&path)
+        } else if depth > 1 && !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()) {
+            newest_mtime(&path, depth - 1, budget)
+        } else {
+            None
+        };
+        newest = newest.max(found);
+    }
+    newest
+}
+
+/// Dimensions whose base directory has been quiet for `months`, oldest first. Dimensions without
+/// a base directory (or with a missing one, which `dimensions check` reports) and running
+/// sessions are left out.
+pub fn find_stale(dimensions: &[Dimension], months: u32, now: u64, is_running: impl Fn(&str) -> bool) -> Vec<StaleDimension> {
+    let cutoff = now.saturating_sub(months as u64 * MONTH_SECS);
+    let mut stale: Vec<StaleDimension> = dimensions
+        .iter()
+        .filter(|d| !is_running(&d.name))
+        .filter_map(|d| {
+            let dir = d.base_dir.as_ref().filter(|dir| dir.is_dir())?;
+            let last_activity = last_activity(dir)?;
+            (last_activity < cutoff).then(|| StaleDimension { name: d.name.clone(), last_activity })
+        })
+        .collect();
+    stale.sort_by_key(|s| s.last_activity);
+    stale
+}
+
+/// "8 months ago" style age of a timestamp
+pub fn age(then: u64, now: u64) -> String {
+    let months = now.saturating_sub(then) / MONTH_SECS;
+    match months {
+        0 => "under a month ago".to_string(),
+        1 => "1 month ago".to_string(),
+        m if m < 24 => format!("{} months ago", m),
+        m => format!("{} years ago", m / 12),
+    }
+}
+
+/// Message for the TUI when some dimensions look stale and the last mention is a month old.
+/// Runs in the background: it walks directories and asks git.
+pub fn tidy_prompt(dimensions: &[Dimension], settings: &TidySettings) -> Option<String> {
+    if settings.prompt == Some(false) {
+        return None;
+    }
+    let now = now();
+    let last = mtime(&prompt_stamp_path()).unwrap_or(0);
+    if now.saturating_sub(last) < PROMPT_INTERVAL_SECS {
+        return None;
+    }
+    let stale = find_stale(dimensions, settings.months(), now, Tmux::session_exists);
+    let _ = fs::write(prompt_stamp_path(), "");
+    match stale.len() {
+        0 => None,
+        1 => Some(format!("'{}' hasn't changed in {}+ months — run `dimensions gc` to tidy up", stale[0].name, settings.months())),
+        n => Some(format!("{} dimensions haven't changed in {}+ months — run `dimensions gc` to tidy up", n, settings.months())),
+    }
+}
+
+/// Move dimensions out of the config into the archive file in the state directory
+fn archive(config: &mut DimensionConfig, names: &[String]) -> Result<()> {
+    let path = archive_path();
+    let mut archived: Vec<Dimension> = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).with_context(|| format!("Invalid {}", path.display()))?,
+        Err(_) => vec![],
+    };
+    for name in names {
+        if let Some(dimension) = config.remove_dimension(name) {
+            archived.retain(|d| d.name != dimension.name);
+            archived.push(dimension);
+        }
+    }
+    fs::write(&path, serde_json::to_string_pretty(&archived)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    config.save()?;
+    Ok(())
+}
+
+/// `dimensions gc [--suggest] [--months N] [--yes]`
+pub fn gc_cli(config: &mut DimensionConfig, suggest: bool, months: Option<u32>, yes: bool) -> Result<()> {
+    let months = months.unwrap_or_else(|| config.tidy.months());
+    let now = now();
+    let stale = find_stale(&config.dimensions, months, now, Tmux::session_exists);
+    if stale.is_empty() {
+        println!("Nothing has been idle for {}+ months", months);
+        return Ok(());
+    }
+
+    for (i, s) in stale.iter().enumerate() {
+        println!("{:>3}  {}\tlast change {}", i + 1, s.name, age(s.last_activity, now));
+    }
+    if suggest {
+        return Ok(());
+    }
+    let picked = if yes {
+        (0..stale.len()).collect()
+    } else {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to archive without confirmation; pass --yes (or --suggest to only list)");
+        }
+        eprint!("Archive which? [all, none, or e.g. 1,3-5] ");
+        std::io::stderr().flush().ok();
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if input.trim().eq_ignore_ascii_case("none") {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+        discover::parse_selection(&input, stale.len())?
+    };
+
+    let names: Vec<String> = picked.iter().map(|&i| stale[i].name.clone()).collect();
+    archive(config, &names)?;
+    println!("Archived {} to {}", names.join(", "), archive_path().display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_directories_are_stale_unless_running() {
+        let root = std::env::temp_dir().join(format!("dimensions-stale-{}", std::process::id()));
+        fs::create_dir_all(root.join("old")).unwrap();
+        fs::write(root.join("old/notes.txt"), "").unwrap();
+
+        let dimensions = vec![
+            Dimension::new_with_base_dir("old".to_string(), Some(root.join("old"))),
+            Dimension::new_with_base_dir("running".to_string(), Some(root.join("old"))),
+            Dimension::new_with_base_dir("scratch".to_string(), None),
+        ];
+        // Everything was just written, so nothing is stale today, and all of it is a year from now
+        assert!(find_stale(&dimensions, 6, now(), |_| false).is_empty());
+        let later = find_stale(&dimensions, 6, now() + 12 * MONTH_SECS, |name| name == "running");
+        assert_eq!(later.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["old"]);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(age(0, 8 * MONTH_SECS + 5), "8 months ago");
+        assert_eq!(age(0, 30 * MONTH_SECS), "2 years ago");
+    }
+}
@@ -0,0 +1,159 @@
+//! Reconciling a dimension's configured tabs with the windows of its live session.
+
+use crate::dimension::Tab;
+use std::path::PathBuf;
+
+/// A window of the live session, in tmux order
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveWindow {
+    pub name: String,
+    pub path: Option<PathBuf>,
+}
+
+/// One difference between config and tmux
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncChange {
+    /// A window created by hand: add it as a tab
+    Adopt(String),
+    /// Same position, different name: rename the configured tab to the window's name
+    Rename { from: String, to: String },
+    /// A configured tab with no window (closed by hand): remove it from the config
+    Drop(String),
+}
+
+impl SyncChange {
+    pub fn describe(&self) -> String {
+        match self {
+            SyncChange::Adopt(name) => format!("+ add tab '{}'", name),
+            SyncChange::Rename { from, to } => format!("~ rename '{}' → '{}'", from, to),
+            SyncChange::Drop(name) => format!("- remove tab '{}'", name),
+        }
+    }
+
+    /// Whether the change is applied unless deselected; dropping config is opt-in
+    pub fn default_accepted(&self) -> bool {
+        !matches!(self, SyncChange::Drop(_))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncItem {
+    pub change: SyncChange,
+    pub accepted: bool,
+}
+
+/// Differences between `tabs` and `live`. A window and a tab at the same position whose
+/// names appear nowhere on the other side are taken as a rename rather than add + drop.
+pub fn plan(tabs: &[Tab], live: &[LiveWindow]) -> Vec<SyncItem> {
+    let configured = |name: &str| tabs.iter().any(|t| t.name == name);
+    let is_live = |name: &str| live.iter().any(|w| w.name == name);
+
+    let mut changes = vec![];
+    let mut renamed_from = vec![];
+    for (i, window) in live.iter().enumerate() {
+        if configured(&window.name) {
+            continue;
+        }
+        match tabs.get(i) {
+            Some(tab) if !is_live(&tab.name) && !renamed_from.contains(&tab.name) => {
+                renamed_from.push(tab.name.clone());
+                changes.push(SyncChange::Rename { from: tab.name.clone(), to: window.name.clone() });
+            }
+            _ => changes.push(SyncChange::Adopt(window.name.clone())),
+        }
+    }
+    for tab in tabs {
+        if !is_live(&tab.name) && !renamed_from.contains(&tab.name) {
+            changes.push(SyncChange::Drop(tab.name.clone()));
+        }
+    }
+
+    changes
+        .into_iter()
+        .map(|change| SyncItem { accepted: change.default_accepted(), change })
+        .collect()
+}
+
+/// Configured tabs after applying the accepted items: live windows first in tmux order,
+/// then tabs that have no window and weren't dropped
+pub fn apply(tabs: &[Tab], live: &[LiveWindow], items: &[SyncItem]) -> Vec<Tab> {
+    let accepted = |change: &SyncChange| items.iter().any(|i| i.accepted && i.change == *change);
+    let mut remaining: Vec<Tab> = tabs.to_vec();
+    let mut result = vec![];
+
+    for window in live {
+        if let Some(pos) = remaining.iter().position(|t| t.name == window.name) {
+            result.push(remaining.remove(pos));
+            continue;
+        }
+        let renamed = remaining.iter().position(|t| {
+            accepted(&SyncChange::Rename { from: t.name.clone(), to: window.name.clone() })
+        });
+        if let Some(pos) = renamed {
+            let mut tab = remaining.remove(pos);
+            tab.name = window.name.clone();
+            result.push(tab);
+        } else if accepted(&SyncChange::Adopt(window.name.clone())) {
+            result.push(Tab::new(window.name.clone(), None, window.path.clone()));
+        }
+    }
+
+    result.extend(
+        remaining
+            .into_iter()
+            .filter(|t| !accepted(&SyncChange::Drop(t.name.clone()))),
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(name: &str) -> Tab {
+        Tab::new(name.to_string(), None, None)
+    }
+
+    fn window(name: &str) -> LiveWindow {
+        LiveWindow { name: name.to_string(), path: None }
+    }
+
+    fn names(tabs: &[Tab]) -> Vec<&str> {
+        tabs.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn adopts_new_windows_in_tmux_order() {
+        let tabs = [tab("editor"), tab("server")];
+        let live = [window("editor"), window("logs"), window("server")];
+        let items = plan(&tabs, &live);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].change, SyncChange::Adopt("logs".to_string()));
+        assert_eq!(names(&apply(&tabs, &live, &items)), ["editor", "logs", "server"]);
+    }
+
+    #[test]
+    fn diverging_names_at_the_same_position_are_a_rename() {
+        let tabs = [tab("editor"), tab("srv")];
+        let live = [window("editor"), window("server")];
+        let items = plan(&tabs, &live);
+        assert_eq!(
+            items[0].change,
+            SyncChange::Rename { from: "srv".to_string(), to: "server".to_string() }
+        );
+        assert_eq!(names(&apply(&tabs, &live, &items)), ["editor", "server"]);
+    }
+
+    #[test]
+    fn closed_tabs_are_only_dropped_when_accepted() {
+        let tabs = [tab("editor"), tab("server"), tab("db")];
+        let live = [window("editor"), window("server")];
+        let mut items = plan(&tabs, &live);
+        assert_eq!(items[0].change, SyncChange::Drop("db".to_string()));
+        assert!(!items[0].accepted);
+        assert_eq!(names(&apply(&tabs, &live, &items)), ["editor", "server", "db"]);
+
+        items[0].accepted = true;
+        assert_eq!(names(&apply(&tabs, &live, &items)), ["editor", "server"]);
+    }
+}
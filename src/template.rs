@@ -0,0 +1,162 @@
+//! Dimension templates: dimensions in the config's `templates` list whose strings hold
+//! `{{variable}}` placeholders, filled in when `T` creates a dimension from one.
+
+use crate::dimension::Dimension;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Base directory of templates that don't set one
+const DEFAULT_BASE_DIR: &str = "{{path}}";
+
+/// Progress through a template's variables while `T` prompts for them
+#[derive(Debug, Clone)]
+pub struct Fill {
+    // Index into `config.templates`
+    pub template: usize,
+    pub variables: Vec<String>,
+    pub values: HashMap<String, String>,
+}
+
+impl Fill {
+    pub fn new(template: usize, dimension: &Dimension) -> Result<Self> {
+        Ok(Self { template, variables: variables(dimension)?, values: HashMap::new() })
+    }
+
+    /// The variable being asked for, or None once every one has a value
+    pub fn current(&self) -> Option<&str> {
+        self.variables.get(self.values.len()).map(String::as_str)
+    }
+}
+
+/// Variables to ask for: `name` (the new dimension's name) first, then `path` if the
+/// template uses it, then the rest by the field they first appear in. Fields are visited
+/// in alphabetical order (how `serde_json` keeps object keys), list items in order.
+pub fn variables(template: &Dimension) -> Result<Vec<String>> {
+    let body = body(template)?;
+    let mut strings = vec![];
+    collect_strings(&body, &mut strings);
+
+    let mut found: Vec<String> = vec![];
+    for text in strings {
+        for name in placeholders(text) {
+            if name != "name" && !found.iter().any(|f| f == name) {
+                found.push(name.to_string());
+            }
+        }
+    }
+    let mut variables = vec!["name".to_string()];
+    if let Some(pos) = found.iter().position(|v| v == "path") {
+        variables.push(found.remove(pos));
+    }
+    variables.extend(found);
+    Ok(variables)
+}
+
+/// A new dimension from `template`, with every placeholder replaced by its value
+pub fn instantiate(template: &Dimension, values: &HashMap<String, String>) -> Result<Dimension> {
+    let mut value = body(template)?;
+    substitute(&mut value, values);
+    if let Some(map) = value.as_object_mut() {
+        map.insert("name".to_string(), Value::String(values.get("name").cloned().unwrap_or_default()));
+    }
+    let mut dimension: Dimension = serde_json::from_value(value)
+        .with_context(|| format!("Template '{}' doesn't make a valid dimension", template.name))?;
+    // An empty path means no base directory
+    if dimension.base_dir.as_ref().is_some_and(|dir| dir.as_os_str().is_empty()) {
+        dimension.base_dir = None;
+    }
+    Ok(dimension)
+}
+
+/// The template as a value, with the default base directory filled in and its own name left out
+fn body(template: &Dimension) -> Result<Value> {
+    let mut template = template.clone();
+    if template.base_dir.is_none() {
+        template.base_dir = Some(PathBuf::from(DEFAULT_BASE_DIR));
+    }
+    let mut value = serde_json::to_value(&template)?;
+    if let Some(map) = value.as_object_mut() {
+        map.remove("name");
+    }
+    Ok(value)
+}
+
+fn collect_strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::String(s) => out.push(s),
+        Value::Array(items) => items.iter().for_each(|v| collect_strings(v, out)),
+        Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+fn substitute(value: &mut Value, values: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => *s = fill(s, values),
+        Value::Array(items) => items.iter_mut().for_each(|v| substitute(v, values)),
+        Value::Object(map) => map.values_mut().for_each(|v| substitute(v, values)),
+        _ => {}
+    }
+}
+
+/// Next `{{name}}` in `text`: (start, end, name)
+fn next_placeholder(text: &str) -> Option<(usize, usize, &str)> {
+    let mut from = 0;
+    while let Some(open) = text[from..].find("{{").map(|i| i + from) {
+        let close = text[open..].find("}}").map(|i| i + open)?;
+        let name = text[open + 2..close].trim();
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Some((open, close + 2, name));
+        }
+        from = open + 2;
+    }
+    None
+}
+
+fn placeholders(mut text: &str) -> Vec<&str> {
+    let mut names = vec![];
+    while let Some((_, end, name)) = next_placeholder(text) {
+        names.push(name);
+        text = &text[end..];
+    }
+    names
+}
+
+pub fn fill(mut text: &str, values: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    while let Some((start, end, name)) = next_placeholder(text) {
+        out.push_str(&text[..start]);
+        out.push_str(values.get(name).map(String::as_str).unwrap_or_default());
+        text = &text[end..];
+    }
+    out.push_str(text);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimension::Tab;
+
+    #[test]
+    fn fills_placeholders_across_the_template() {
+        let mut template = Dimension::new_with_base_dir("web project".to_string(), None);
+        template.add_tab(Tab::new("editor".to_string(), Some("nvim {{entry}}".to_string()), None));
+        template.add_tab(Tab::new("server".to_string(), Some("PORT={{port}} npm run dev # {{name}}".to_string()), None));
+        template.add_tab(Tab::new("logs".to_string(), Some("tail -f {{ port }}.log {{not a var}}".to_string()), None));
+
+        assert_eq!(variables(&template).unwrap(), ["name", "path", "entry", "port"]);
+
+        let values: HashMap<String, String> = [("name", "shop"), ("path", "/srv/shop"), ("entry", "src/main.ts"), ("port", "3000")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let dimension = instantiate(&template, &values).unwrap();
+        assert_eq!(dimension.name, "shop");
+        assert_eq!(dimension.base_dir, Some(PathBuf::from("/srv/shop")));
+        let commands: Vec<_> = dimension.configured_tabs.iter().map(|t| t.command.clone().unwrap()).collect();
+        assert_eq!(commands, ["nvim src/main.ts", "PORT=3000 npm run dev # shop", "tail -f 3000.log {{not a var}}"]);
+    }
+}
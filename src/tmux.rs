@@ -1,29 +1,227 @@
+use crate::events;
 use anyhow::{Context, Result};
-use std::process::Command;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Wrapper for tmux operations
 pub struct Tmux;
 
+/// One pane from `list-panes`, with its window's details
+#[derive(Debug, Clone)]
+pub struct PaneInfo {
+    pub window_index: usize,
+    pub window_name: String,
+    pub window_active: bool,
+    pub layout: String,
+    pub pane_index: usize,
+    // The pane's shell (or the command it was started with)
+    pub pid: u32,
+    // Name of the foreground program, e.g. `nvim` or `zsh`
+    pub command: String,
+    pub path: Option<std::path::PathBuf>,
+}
+
+/// Which tmux server to control, when not the default one
+#[derive(Debug, Clone)]
+pub enum TmuxServer {
+    // `tmux -L <name>`
+    SocketName(String),
+    // `tmux -S <path>`
+    SocketPath(std::path::PathBuf),
+}
+
+/// Set from config/flags, or by sandboxed runs like the tutorial; first setter wins
+static SERVER: OnceLock<TmuxServer> = OnceLock::new();
+
+/// Whether our $TMUX client belongs to the server we control (computed once)
+static INSIDE_TARGET: OnceLock<bool> = OnceLock::new();
+
+/// tmux calls that take longer than this are killed (a busy or wedged server
+/// would otherwise freeze the UI)
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Window option holding the exit status of the tab's last command
+const EXIT_STATUS_OPTION: &str = "@dimensions-exit";
+
+/// Set when the last tmux call timed out; cleared by the next one that finishes
+static NOT_RESPONDING: AtomicBool = AtomicBool::new(false);
+
+/// `Command::output` with `COMMAND_TIMEOUT`
+trait OutputWithTimeout {
+    fn output_with_timeout(&mut self) -> io::Result<Output>;
+}
+
+impl OutputWithTimeout for Command {
+    fn output_with_timeout(&mut self) -> io::Result<Output> {
+        match output_within(self, COMMAND_TIMEOUT) {
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                NOT_RESPONDING.store(true, Ordering::Relaxed);
+                Err(io::Error::new(io::ErrorKind::TimedOut, "tmux not responding"))
+            }
+            Err(e) => Err(e),
+            Ok(output) => {
+                NOT_RESPONDING.store(false, Ordering::Relaxed);
+                Ok(output)
+            }
+        }
+    }
+}
+
+/// `Command::output`, killing the command and failing with `TimedOut` once `timeout` passes
+pub fn output_within(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain pipes on threads so a large listing can't block on a full pipe
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = read(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = read(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "not responding"));
+        }
+        thread::sleep(Duration::from_millis(2));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// What a window is doing, for the matrix view
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindowStatus {
+    /// Exit status of the window's command, once it has finished
+    pub exit_status: Option<i32>,
+    /// When the window last printed anything, in seconds since the epoch
+    pub activity: u64,
+}
+
+/// A terminal attached to a tmux session
+#[derive(Debug, Clone)]
+pub struct TmuxClient {
+    pub tty: String,
+    pub width: u16,
+    pub height: u16,
+}
+
 impl Tmux {
+    /// Base `tmux` command, pointed at the configured server when there is one
+    fn command() -> Command {
+        let mut cmd = Command::new("tmux");
+        match SERVER.get() {
+            Some(TmuxServer::SocketName(name)) => {
+                cmd.args(["-L", name]);
+            }
+            Some(TmuxServer::SocketPath(path)) => {
+                cmd.arg("-S").arg(path);
+            }
+            None => {}
+        }
+        cmd
+    }
+
+    /// Run every tmux command against `server` instead of the default one
+    pub fn use_server(server: TmuxServer) {
+        let _ = SERVER.set(server);
+    }
+
+    /// Run every tmux command against a separate server on socket `name`
+    pub fn use_private_socket(name: &str) {
+        Self::use_server(TmuxServer::SocketName(name.to_string()));
+    }
+
+    /// Stop the server selected with `use_private_socket` and all of its sessions
+    /// (no-op on the default server)
+    pub fn kill_private_server() {
+        if SERVER.get().is_some() {
+            let _ = Self::command().arg("kill-server").output_with_timeout();
+        }
+    }
+
+    /// Short description of the server being controlled, when it isn't simply the
+    /// local default: a custom socket, a nested client, or a shell reached over SSH
+    pub fn target_label() -> Option<String> {
+        let mut parts = vec![];
+        match SERVER.get() {
+            Some(TmuxServer::SocketName(name)) => parts.push(format!("socket {}", name)),
+            Some(TmuxServer::SocketPath(path)) => parts.push(format!("socket {}", path.display())),
+            None => {}
+        }
+        if std::env::var_os("TMUX").is_some() && !Self::is_inside_session() {
+            parts.push("nested".to_string());
+        }
+        if std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some() {
+            parts.push("over ssh".to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// False after a tmux call timed out, until one succeeds again
+    pub fn is_responding() -> bool {
+        !NOT_RESPONDING.load(Ordering::Relaxed)
+    }
+
     /// Check if tmux is installed
     pub fn is_installed() -> bool {
-        Command::new("tmux")
+        Self::command()
             .arg("-V")
-            .output()
+            .output_with_timeout()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 
     /// Check if we're currently inside a tmux session
     pub fn is_inside_session() -> bool {
-        std::env::var("TMUX").is_ok()
+        // $TMUX is "<socket path>,<pid>,<session>"; with a configured server it only counts
+        // when it is that server, otherwise switch-client would drive the wrong one
+        *INSIDE_TARGET.get_or_init(|| {
+            let Ok(tmux) = std::env::var("TMUX") else {
+                return false;
+            };
+            if SERVER.get().is_none() {
+                return true;
+            }
+            let our_socket = tmux.split(',').next().unwrap_or_default().to_string();
+            Self::command()
+                .args(["display-message", "-p", "#{socket_path}"])
+                .output_with_timeout()
+                .ok()
+                .filter(|o| o.status.success())
+                .is_some_and(|o| String::from_utf8_lossy(&o.stdout).trim() == our_socket)
+        })
     }
 
     /// Get the current tmux session name
     pub fn get_current_session() -> Result<String> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args(["display-message", "-p", "#S"])
-            .output()
+            .output_with_timeout()
             .context("Failed to get current tmux session")?;
 
         if !output.status.success() {
@@ -39,9 +237,9 @@ impl Tmux {
 
     /// Get the current tmux window index
     pub fn get_current_window_index() -> Result<usize> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args(["display-message", "-p", "#I"])
-            .output()
+            .output_with_timeout()
             .context("Failed to get current tmux window index")?;
 
         if !output.status.success() {
@@ -58,14 +256,14 @@ impl Tmux {
     /// List all tmux sessions
     /// Create a new tmux session
     pub fn create_session(name: &str, detached: bool) -> Result<()> {
-        let mut cmd = Command::new("tmux");
+        let mut cmd = Self::command();
         cmd.args(["new-session", "-s", name]);
 
         if detached {
             cmd.arg("-d");
         }
 
-        let output = cmd.output().context("Failed to create tmux session")?;
+        let output = cmd.output_with_timeout().context("Failed to create tmux session")?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -80,14 +278,14 @@ impl Tmux {
 
     /// Create a new tmux session in a specific directory
     pub fn create_session_with_dir(name: &str, detached: bool, start_dir: &str) -> Result<()> {
-        let mut cmd = Command::new("tmux");
+        let mut cmd = Self::command();
         cmd.args(["new-session", "-s", name, "-c", start_dir]);
 
         if detached {
             cmd.arg("-d");
         }
 
-        let output = cmd.output().context("Failed to create tmux session")?;
+        let output = cmd.output_with_timeout().context("Failed to create tmux session")?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -103,9 +301,9 @@ impl Tmux {
 
     /// Kill a tmux session
     pub fn kill_session(name: &str) -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args(["kill-session", "-t", name])
-            .output()
+            .output_with_timeout()
             .context("Failed to kill tmux session")?;
 
         if !output.status.success() {
@@ -121,7 +319,7 @@ impl Tmux {
 
     /// Attach to a tmux session
     pub fn attach_session(name: &str) -> Result<()> {
-        let status = Command::new("tmux")
+        let status = Self::attach_command()
             .args(["attach-session", "-t", name])
             .status()
             .context("Failed to attach to tmux session")?;
@@ -133,11 +331,87 @@ impl Tmux {
         Ok(())
     }
 
+    /// Land on `session` (optionally at `window`): switch-client from inside tmux, attach otherwise
+    pub fn switch_or_attach(session: &str, window: Option<usize>, detach_others: bool) -> Result<()> {
+        let target = match window {
+            Some(window_index) => format!("{}:{}", session, window_index),
+            None => session.to_string(),
+        };
+
+        if Self::is_inside_session() {
+            // switch-client has no -d, so detach the others first
+            if detach_others {
+                let own_tty = Self::current_client().map(|c| c.tty);
+                Self::detach_other_clients(session, own_tty.as_deref())?;
+            }
+            Self::switch_session(&target)
+        } else if detach_others {
+            Self::attach_session_detaching_others(&target)
+        } else {
+            Self::attach_session(&target)
+        }
+    }
+
+    /// Like `switch_or_attach`, but the client only watches: `attach-session -r` from
+    /// outside tmux, `switch-client -r` (which toggles the client's read-only flag) inside
+    pub fn switch_or_attach_read_only(session: &str, window: Option<usize>) -> Result<()> {
+        let target = match window {
+            Some(window_index) => format!("{}:{}", session, window_index),
+            None => session.to_string(),
+        };
+
+        if !Self::is_inside_session() {
+            let status = Self::attach_command()
+                .args(["attach-session", "-r", "-t", &target])
+                .status()
+                .context("Failed to attach to tmux session")?;
+            if !status.success() {
+                anyhow::bail!("Failed to attach to session '{}'", target);
+            }
+            return Ok(());
+        }
+
+        let output = Self::command()
+            .args(["switch-client", "-r", "-t", &target])
+            .output_with_timeout()
+            .context("Failed to switch tmux session")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to switch to session '{}': {}",
+                target,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// Put `text` in a tmux paste buffer and, via `-w`, the system clipboard (OSC 52)
+    pub fn copy_to_clipboard(text: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["set-buffer", "-w", "--", text])
+            .output_with_timeout()
+            .context("Failed to set tmux buffer")?;
+        if !output.status.success() {
+            anyhow::bail!("Failed to copy '{}': {}", text, String::from_utf8_lossy(&output.stderr));
+        }
+        Ok(())
+    }
+
+    /// Command for attaching. Inside a tmux that isn't the target server this is a
+    /// deliberate nested client, which tmux refuses while $TMUX is set.
+    fn attach_command() -> Command {
+        let mut cmd = Self::command();
+        if !Self::is_inside_session() {
+            cmd.env_remove("TMUX");
+        }
+        cmd
+    }
+
     /// Switch to a tmux session (when inside tmux)
     pub fn switch_session(name: &str) -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args(["switch-client", "-t", name])
-            .output()
+            .output_with_timeout()
             .context("Failed to switch tmux session")?;
 
         if !output.status.success() {
@@ -153,7 +427,7 @@ impl Tmux {
 
     /// Create a new window in a session
     pub fn new_window(session: &str, name: &str, command: Option<&str>, working_dir: Option<&std::path::Path>) -> Result<()> {
-        let mut cmd = Command::new("tmux");
+        let mut cmd = Self::command();
         // Use `session:` to unambiguously target the session (tmux `-t` expects a target-window).
         // `-d` avoids switching the current client to the newly-created window.
         cmd.args(["new-window", "-d", "-t", &format!("{}:", session), "-n", name]);
@@ -169,11 +443,11 @@ impl Tmux {
             // This handles aliases, one-shot commands (ls), and long-running commands (npm run dev).
             // After the command exits, a shell is started so the user can see output and continue working.
             let user_shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
-            let wrapped_command = format!("{}; exec $SHELL", user_command);
+            let wrapped_command = format!("{}; exec $SHELL", Self::record_exit_status(user_command));
             cmd.arg(&user_shell).arg("-i").arg("-c").arg(wrapped_command);
         }
 
-        let output = cmd.output().context("Failed to create tmux window")?;
+        let output = cmd.output_with_timeout().context("Failed to create tmux window")?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -188,7 +462,7 @@ impl Tmux {
 
     /// List windows in a session, returns (window_index, window_name) tuples
     pub fn list_windows(session: &str) -> Result<Vec<(usize, String)>> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args([
                 "list-windows",
                 "-t",
@@ -196,7 +470,7 @@ impl Tmux {
                 "-F",
                 "#{window_index}:#{window_name}",
             ])
-            .output()
+            .output_with_timeout()
             .context("Failed to list tmux windows")?;
 
         if !output.status.success() {
@@ -222,16 +496,47 @@ impl Tmux {
         Ok(windows)
     }
 
+    /// Windows of every session on the server in one call (`list-windows -a`), keyed by
+    /// session name. Fields are tab-separated since session names may contain colons.
+    pub fn snapshot() -> Result<HashMap<String, Vec<(usize, String)>>> {
+        let output = Self::command()
+            .args([
+                "list-windows",
+                "-a",
+                "-F",
+                "#{session_name}\t#{window_index}\t#{window_name}",
+            ])
+            .output_with_timeout()
+            .context("Failed to list tmux windows")?;
+
+        // No server running means no sessions
+        if !output.status.success() {
+            return Ok(HashMap::new());
+        }
+
+        let mut sessions: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut parts = line.splitn(3, '\t');
+            let (Some(session), Some(index), Some(name)) = (parts.next(), parts.next(), parts.next()) else {
+                continue;
+            };
+            if let Ok(index) = index.parse::<usize>() {
+                sessions.entry(session.to_string()).or_default().push((index, name.to_string()));
+            }
+        }
+        Ok(sessions)
+    }
+
     /// Rename a window in a session
     pub fn rename_window(session: &str, window_index: usize, new_name: &str) -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args([
                 "rename-window",
                 "-t",
                 &format!("{}:{}", session, window_index),
                 new_name,
             ])
-            .output()
+            .output_with_timeout()
             .context("Failed to rename tmux window")?;
 
         if !output.status.success() {
@@ -247,8 +552,106 @@ impl Tmux {
     }
 
     /// Send keys (command) to a window in a session
+    /// `command` followed by storing its exit status on the window it runs in.
+    /// Tab windows fall back to a shell instead of dying, so `remain-on-exit`/`pane-died`
+    /// never see the status; the command reports it into a window option instead.
+    pub fn record_exit_status(command: &str) -> String {
+        format!(
+            "{}; tmux set-option -wq -t \"$TMUX_PANE\" {} $?{}",
+            command,
+            EXIT_STATUS_OPTION,
+            events::exit_hook().unwrap_or_default()
+        )
+    }
+
+    /// Last recorded exit status per window index of `session`
+    pub fn window_exit_statuses(session: &str) -> Result<HashMap<usize, i32>> {
+        let output = Self::command()
+            .args([
+                "list-windows",
+                "-t",
+                session,
+                "-F",
+                &format!("#{{window_index}}\t#{{{}}}", EXIT_STATUS_OPTION),
+            ])
+            .output_with_timeout()
+            .context("Failed to list tmux windows")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list windows for session '{}': {}",
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (index, status) = line.split_once('\t')?;
+                Some((index.parse().ok()?, status.trim().parse().ok()?))
+            })
+            .collect())
+    }
+
+    /// Status of every window on the server, keyed by session and window index, from one
+    /// `list-windows -a`
+    pub fn window_statuses() -> Result<HashMap<String, HashMap<usize, WindowStatus>>> {
+        let output = Self::command()
+            .args([
+                "list-windows",
+                "-a",
+                "-F",
+                &format!("#{{window_index}}\t#{{window_activity}}\t#{{{}}}\t#{{session_name}}", EXIT_STATUS_OPTION),
+            ])
+            .output_with_timeout()
+            .context("Failed to list tmux windows")?;
+
+        // No server running means no windows
+        if !output.status.success() {
+            return Ok(HashMap::new());
+        }
+
+        let mut statuses: HashMap<String, HashMap<usize, WindowStatus>> = HashMap::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut parts = line.splitn(4, '\t');
+            let (Some(index), Some(activity), Some(exit_status), Some(session)) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let Ok(index) = index.parse::<usize>() else {
+                continue;
+            };
+            let status = WindowStatus {
+                exit_status: exit_status.trim().parse().ok(),
+                activity: activity.parse().unwrap_or(0),
+            };
+            statuses.entry(session.to_string()).or_default().insert(index, status);
+        }
+        Ok(statuses)
+    }
+
+    /// The window of `session` with the most recent activity (`#{window_activity}`)
+    pub fn last_active_window(session: &str) -> Option<usize> {
+        let output = Self::command()
+            .args(["list-windows", "-t", session, "-F", "#{window_activity}\t#{window_index}"])
+            .output_with_timeout()
+            .ok()
+            .filter(|output| output.status.success())?;
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (activity, index) = line.split_once('\t')?;
+                Some((activity.parse::<u64>().ok()?, index.trim().parse::<usize>().ok()?))
+            })
+            .max_by_key(|(activity, _)| *activity)
+            .map(|(_, index)| index)
+    }
+
     pub fn send_keys(session: &str, window_index: usize, keys: &str) -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args([
                 "send-keys",
                 "-t",
@@ -256,7 +659,7 @@ impl Tmux {
                 keys,
                 "C-m", // Enter key
             ])
-            .output()
+            .output_with_timeout()
             .context("Failed to send keys to tmux window")?;
 
         if !output.status.success() {
@@ -271,17 +674,11 @@ impl Tmux {
         Ok(())
     }
 
-    /// Get the number of windows in a session
-    pub fn get_window_count(session: &str) -> Result<usize> {
-        let windows = Self::list_windows(session)?;
-        Ok(windows.len())
-    }
-
     /// Detach from the current tmux session
     pub fn detach() -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .arg("detach")
-            .output()
+            .output_with_timeout()
             .context("Failed to detach from tmux")?;
 
         if !output.status.success() {
@@ -293,9 +690,9 @@ impl Tmux {
 
     /// Rename a tmux session
     pub fn rename_session(old_name: &str, new_name: &str) -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args(["rename-session", "-t", old_name, new_name])
-            .output()
+            .output_with_timeout()
             .context("Failed to rename tmux session")?;
 
         if !output.status.success() {
@@ -310,18 +707,439 @@ impl Tmux {
         Ok(())
     }
 
+    /// Global value of a session option, None when it can't be read (e.g. no server running)
+    pub fn global_option(option: &str) -> Option<String> {
+        let output = Self::command()
+            .args(["show-options", "-gv", option])
+            .output_with_timeout()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Names of every session on the server
+    pub fn list_sessions() -> Result<Vec<String>> {
+        let output = Self::command()
+            .args(["list-sessions", "-F", "#{session_name}"])
+            .output_with_timeout()
+            .context("Failed to list tmux sessions")?;
+
+        // No server running means no sessions
+        if !output.status.success() {
+            return Ok(vec![]);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.to_string())
+            .collect())
+    }
+
+    /// Number of attached clients per live session, not counting control-mode clients
+    /// (like our own event connection); sessions without clients are left out
+    pub fn attached_client_counts() -> HashMap<String, usize> {
+        let output = Self::command()
+            .args(["list-clients", "-F", "#{client_control_mode}\t#{client_session}"])
+            .output_with_timeout();
+
+        let Ok(output) = output else {
+            return HashMap::new();
+        };
+        let mut counts = HashMap::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            match line.split_once('\t') {
+                Some(("1", _)) | None => {}
+                Some((_, session)) => *counts.entry(session.to_string()).or_insert(0) += 1,
+            }
+        }
+        counts
+    }
+
+    /// Terminals attached to a session, with their sizes (control-mode clients have neither)
+    pub fn list_clients(session: &str) -> Result<Vec<TmuxClient>> {
+        let output = Self::command()
+            .args([
+                "list-clients",
+                "-t",
+                session,
+                "-F",
+                "#{client_control_mode}\t#{client_tty}\t#{client_width}\t#{client_height}",
+            ])
+            .output_with_timeout()
+            .context("Failed to list tmux clients")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list clients of '{}': {}",
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let clients = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split('\t');
+                if parts.next()? == "1" {
+                    return None;
+                }
+                let tty = parts.next()?.to_string();
+                let width = parts.next()?.parse().ok()?;
+                let height = parts.next()?.parse().ok()?;
+                Some(TmuxClient { tty, width, height })
+            })
+            .collect();
+        Ok(clients)
+    }
+
+    /// The client running us (only meaningful inside tmux)
+    pub fn current_client() -> Option<TmuxClient> {
+        if !Self::is_inside_session() {
+            return None;
+        }
+        let output = Self::command()
+            .args(["display-message", "-p", "#{client_tty}\t#{client_width}\t#{client_height}"])
+            .output_with_timeout()
+            .ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut parts = text.trim().split('\t');
+        let tty = parts.next()?.to_string();
+        let width = parts.next()?.parse().ok()?;
+        let height = parts.next()?.parse().ok()?;
+        Some(TmuxClient { tty, width, height })
+    }
+
+    /// tty of the client (on any session) that was used most recently
+    pub fn most_recent_client() -> Option<String> {
+        let output = Self::command()
+            .args(["list-clients", "-F", "#{client_control_mode}\t#{client_activity}\t#{client_tty}"])
+            .output_with_timeout()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (control, rest) = line.split_once('\t')?;
+                let (activity, tty) = rest.split_once('\t').filter(|_| control != "1")?;
+                Some((activity.parse::<u64>().ok()?, tty.to_string()))
+            })
+            .max_by_key(|(activity, _)| *activity)
+            .map(|(_, tty)| tty)
+    }
+
+    /// `tmux -C` attached to `session` as a read-only control client that gets no pane output
+    /// and doesn't take part in window sizing (tmux 3.2+); see `tmux_control`
+    pub fn control_mode_command(session: &str) -> Command {
+        let mut cmd = Self::command();
+        cmd.args(["-C", "attach-session", "-f", "ignore-size,no-output,read-only", "-t"])
+            .arg(format!("={}", session));
+        cmd
+    }
+
+    /// Point another client (by tty) at `target`
+    pub fn switch_client(tty: &str, target: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["switch-client", "-c", tty, "-t", target])
+            .output_with_timeout()
+            .context("Failed to switch tmux client")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to switch client {} to '{}': {}",
+                tty,
+                target,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Detach every client of `session` except the one on `keep_tty`
+    pub fn detach_other_clients(session: &str, keep_tty: Option<&str>) -> Result<()> {
+        for client in Self::list_clients(session)? {
+            if Some(client.tty.as_str()) == keep_tty {
+                continue;
+            }
+            let output = Self::command()
+                .args(["detach-client", "-t", &client.tty])
+                .output_with_timeout()
+                .context("Failed to detach tmux client")?;
+
+            if !output.status.success() {
+                anyhow::bail!(
+                    "Failed to detach client '{}': {}",
+                    client.tty,
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Attach to a session, detaching any other clients (`attach-session -d`)
+    pub fn attach_session_detaching_others(name: &str) -> Result<()> {
+        let status = Self::attach_command()
+            .args(["attach-session", "-d", "-t", name])
+            .status()
+            .context("Failed to attach to tmux session")?;
+
+        if !status.success() {
+            anyhow::bail!("Failed to attach to session '{}'", name);
+        }
+
+        Ok(())
+    }
+
+    /// A session's working directory (`#{session_path}`), where new windows start by default
+    pub fn session_path(session: &str) -> Option<std::path::PathBuf> {
+        let output = Self::command()
+            .args(["display-message", "-p", "-t", session, "#{session_path}"])
+            .output_with_timeout()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!path.is_empty()).then(|| std::path::PathBuf::from(path))
+    }
+
+    /// Windows of `session` with the current directory of their active pane
+    pub fn list_window_paths(session: &str) -> Result<Vec<(usize, String, Option<std::path::PathBuf>)>> {
+        let output = Self::command()
+            .args([
+                "list-windows",
+                "-t",
+                session,
+                "-F",
+                "#{window_index}\t#{pane_current_path}\t#{window_name}",
+            ])
+            .output_with_timeout()
+            .context("Failed to list tmux windows")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list windows for session '{}': {}",
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let index = parts.next()?.parse().ok()?;
+                let path = parts.next().filter(|p| !p.is_empty()).map(std::path::PathBuf::from);
+                let name = parts.next()?.to_string();
+                Some((index, name, path))
+            })
+            .collect())
+    }
+
+    /// Move a window to the end of another session (`move-window`)
+    pub fn move_window(session: &str, window_index: usize, target_session: &str) -> Result<()> {
+        let output = Self::command()
+            .args([
+                "move-window",
+                "-d",
+                "-s",
+                &format!("{}:{}", session, window_index),
+                "-t",
+                &format!("{}:", target_session),
+            ])
+            .output_with_timeout()
+            .context("Failed to move tmux window")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to move window {}:{} to '{}': {}",
+                session,
+                window_index,
+                target_session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Swap two windows of a session in place (`swap-window -d`), keeping the selection where it was
+    pub fn swap_windows(session: &str, first: usize, second: usize) -> Result<()> {
+        let output = Self::command()
+            .args([
+                "swap-window",
+                "-d",
+                "-s",
+                &format!("{}:{}", session, first),
+                "-t",
+                &format!("{}:{}", session, second),
+            ])
+            .output_with_timeout()
+            .context("Failed to swap tmux windows")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to swap windows {} and {} in '{}': {}",
+                first,
+                second,
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Working directory of a window's active pane
+    pub fn pane_current_path(session: &str, window_index: usize) -> Option<std::path::PathBuf> {
+        let output = Self::command()
+            .args([
+                "display-message",
+                "-p",
+                "-t",
+                &format!("{}:{}", session, window_index),
+                "#{pane_current_path}",
+            ])
+            .output_with_timeout()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!path.is_empty()).then(|| std::path::PathBuf::from(path))
+    }
+
+    /// Every pane of `session`, window by window, for snapshots
+    pub fn list_panes(session: &str) -> Result<Vec<PaneInfo>> {
+        let output = Self::command()
+            .args([
+                "list-panes",
+                "-s",
+                "-t",
+                session,
+                "-F",
+                "#{window_index}\t#{window_active}\t#{window_layout}\t#{pane_index}\t#{pane_pid}\t#{pane_current_command}\t#{pane_current_path}\t#{window_name}",
+            ])
+            .output_with_timeout()
+            .context("Failed to list tmux panes")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list panes for session '{}': {}",
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(8, '\t');
+                Some(PaneInfo {
+                    window_index: parts.next()?.parse().ok()?,
+                    window_active: parts.next()? == "1",
+                    layout: parts.next()?.to_string(),
+                    pane_index: parts.next()?.parse().ok()?,
+                    pid: parts.next()?.parse().ok()?,
+                    command: parts.next()?.to_string(),
+                    path: parts.next().filter(|p| !p.is_empty()).map(std::path::PathBuf::from),
+                    window_name: parts.next()?.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Add a pane to a window (`split-window -d`), starting in `working_dir`
+    pub fn split_window(session: &str, window_index: usize, working_dir: Option<&std::path::Path>) -> Result<()> {
+        let mut cmd = Self::command();
+        cmd.args(["split-window", "-d", "-t", &format!("{}:{}", session, window_index)]);
+        if let Some(dir) = working_dir {
+            cmd.args(["-c", dir.to_str().unwrap_or(".")]);
+        }
+        let output = cmd.output_with_timeout().context("Failed to split tmux window")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to split window {} in session '{}': {}",
+                window_index,
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// Arrange a window's panes from a `#{window_layout}` string
+    pub fn select_layout(session: &str, window_index: usize, layout: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["select-layout", "-t", &format!("{}:{}", session, window_index), layout])
+            .output_with_timeout()
+            .context("Failed to set tmux layout")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to set the layout of window {} in session '{}': {}",
+                window_index,
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// Type a command into one pane and press Enter
+    pub fn send_keys_to_pane(session: &str, window_index: usize, pane_index: usize, keys: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["send-keys", "-t", &format!("{}:{}.{}", session, window_index, pane_index), keys, "C-m"])
+            .output_with_timeout()
+            .context("Failed to send keys to tmux pane")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to send keys to pane {}.{} in session '{}': {}",
+                window_index,
+                pane_index,
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// Make a window the session's current one without switching any client to the session
+    pub fn select_window(session: &str, window_index: usize) -> Result<()> {
+        let output = Self::command()
+            .args(["select-window", "-t", &format!("{}:{}", session, window_index)])
+            .output_with_timeout()
+            .context("Failed to select tmux window")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to select window {} in session '{}': {}",
+                window_index,
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
     /// Check if a session exists
     pub fn session_exists(name: &str) -> bool {
-        Command::new("tmux")
+        Self::command()
             .args(["has-session", "-t", name])
-            .output()
+            .output_with_timeout()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 
     /// Get the base-index option for a session (defaults to 0 if not set)
     pub fn get_base_index(session: &str) -> Result<usize> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args([
                 "show-options",
                 "-t",
@@ -329,7 +1147,7 @@ impl Tmux {
                 "-gv",  // get global value
                 "base-index"
             ])
-            .output()
+            .output_with_timeout()
             .context("Failed to get base-index from tmux")?;
 
         if !output.status.success() {
@@ -360,13 +1178,13 @@ impl Tmux {
 
     /// Kill a window in a session by index
     pub fn kill_window(session: &str, window_index: usize) -> Result<()> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args([
                 "kill-window",
                 "-t",
                 &format!("{}:{}", session, window_index),
             ])
-            .output()
+            .output_with_timeout()
             .context("Failed to kill tmux window")?;
 
         if !output.status.success() {
@@ -381,9 +1199,175 @@ impl Tmux {
         Ok(())
     }
 
+    /// Set a session-scoped option (e.g. `status-style`)
+    pub fn set_session_option(session: &str, option: &str, value: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["set-option", "-t", session, option, value])
+            .output_with_timeout()
+            .context("Failed to set tmux option")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to set '{}' for session '{}': {}",
+                option,
+                session,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Set a server-wide hook, e.g. `session-closed[77]` (an index leaves the user's own hooks alone)
+    pub fn set_global_hook(hook: &str, command: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["set-hook", "-g", hook, command])
+            .output_with_timeout()
+            .context("Failed to set tmux hook")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to set hook '{}': {}",
+                hook,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Remove a hook set with `set_global_hook`
+    pub fn unset_global_hook(hook: &str) -> Result<()> {
+        let output = Self::command()
+            .args(["set-hook", "-gu", hook])
+            .output_with_timeout()
+            .context("Failed to unset tmux hook")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to unset hook '{}': {}",
+                hook,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Session, window index and window name of a pane (e.g. `$TMUX_PANE`)
+    pub fn pane_window(pane: &str) -> Option<(String, usize, String)> {
+        let output = Self::command()
+            .args(["display-message", "-p", "-t", pane, "#{session_name}\t#{window_index}\t#{window_name}"])
+            .output_with_timeout()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
+        let mut parts = line.splitn(3, '\t');
+        let session = parts.next()?.to_string();
+        let index = parts.next()?.parse().ok()?;
+        let name = parts.next().unwrap_or_default().to_string();
+        Some((session, index, name))
+    }
+
+    /// `list-keys` output for one key table, one `bind-key` command per line
+    pub fn list_keys(table: &str) -> Result<String> {
+        let output = Self::command()
+            .args(["list-keys", "-T", table])
+            .output_with_timeout()
+            .context("Failed to list tmux keys")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list keys in table '{}': {}",
+                table,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Bind `key` in `table` to a tmux command given as separate arguments
+    pub fn bind_key(table: &str, key: &str, command: &[String]) -> Result<()> {
+        let output = Self::command()
+            .args(["bind-key", "-T", table, key])
+            .args(command)
+            .output_with_timeout()
+            .context("Failed to bind tmux key")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to bind '{}': {}",
+                key,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Show `message` in the status line of the current client for `duration`
+    pub fn display_message(message: &str, duration: std::time::Duration) -> Result<()> {
+        // `#` starts a format in display-message
+        let output = Self::command()
+            .args(["display-message", "-d", &duration.as_millis().to_string(), &message.replace('#', "##")])
+            .output_with_timeout()
+            .context("Failed to display tmux message")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to display message: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Run `display-popup` with the given arguments on the current client, returning once it closes
+    pub fn display_popup(args: &[String]) -> Result<()> {
+        // No timeout: the popup stays open for as long as it's used
+        let status = Self::command()
+            .args(args)
+            .status()
+            .context("Failed to open tmux popup")?;
+        if !status.success() {
+            anyhow::bail!("tmux display-popup failed ({})", status);
+        }
+        Ok(())
+    }
+
+    /// Remove every binding in `table` (a no-op when the table doesn't exist)
+    pub fn unbind_table(table: &str) {
+        let _ = Self::command()
+            .args(["unbind-key", "-a", "-T", table])
+            .output_with_timeout();
+    }
+
+    /// Run the tmux commands in a file
+    pub fn source_file(path: &std::path::Path) -> Result<()> {
+        let output = Self::command()
+            .arg("source-file")
+            .arg(path)
+            .output_with_timeout()
+            .context("Failed to source tmux commands")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to source {}: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
     /// Capture pane contents for a window
     pub fn capture_pane(session: &str, window_index: usize) -> Result<String> {
-        let output = Command::new("tmux")
+        let output = Self::command()
             .args([
                 "capture-pane",
                 "-t",
@@ -392,7 +1376,7 @@ impl Tmux {
                 "-e",  // Preserve ANSI escape sequences
                 "-J",
             ])
-            .output()
+            .output_with_timeout()
             .context("Failed to capture pane contents")?;
 
         if !output.status.success() {
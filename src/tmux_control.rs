@@ -0,0 +1,164 @@
+//! A persistent `tmux -C` control-mode connection. tmux reports session and window changes
+//! (including ones made outside Dimensions) as `%` notifications on it, so the cached tmux
+//! state can be dropped exactly when it goes stale instead of re-read on a short timer.
+
+use crate::backend::MultiplexerBackend;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// What a notification line means for us
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notification {
+    // Sessions, windows or attached clients changed
+    Changed,
+    // The control client is gone (its session was killed, or tmux refused the connection)
+    Exited,
+}
+
+/// The notifications that invalidate session/window/client state; output, layout and
+/// pane focus changes don't matter to the lists
+const CHANGES: &[&str] = &[
+    "%sessions-changed",
+    "%session-changed",
+    "%session-renamed",
+    "%session-window-changed",
+    "%window-add",
+    "%window-close",
+    "%window-renamed",
+    "%unlinked-window-add",
+    "%unlinked-window-close",
+    "%unlinked-window-renamed",
+    "%client-session-changed",
+    "%client-detached",
+];
+
+/// Meaning of one line of control-mode output, if it's a notification we care about
+pub fn parse(line: &str) -> Option<Notification> {
+    let name = line.split_whitespace().next()?;
+    if name == "%exit" {
+        return Some(Notification::Exited);
+    }
+    CHANGES.contains(&name).then_some(Notification::Changed)
+}
+
+/// What arrived since the last `ControlClient::drain`
+#[derive(Debug, Default)]
+pub struct Drained {
+    pub changed: bool,
+    pub exited: bool,
+}
+
+pub struct ControlClient {
+    child: Child,
+    // Control mode exits when its input closes, so this is held open for as long as we are
+    _stdin: ChildStdin,
+    notifications: Receiver<Notification>,
+}
+
+impl ControlClient {
+    /// Attach a control client to one of the backend's sessions. None when there is no
+    /// session to attach to or tmux can't be started; a refused attach (tmux older than 3.2)
+    /// shows up as `exited` on the first drain.
+    pub fn connect(backend: &dyn MultiplexerBackend) -> Option<Self> {
+        Self::spawn(backend.control_mode_command()?)
+    }
+
+    /// Run `command` as the control client, reading notifications from its output
+    fn spawn(mut command: Command) -> Option<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let stdin = child.stdin.take()?;
+        let stdout = child.stdout.take()?;
+
+        let (tx, notifications) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let Some(notification) = parse(&line) else {
+                    continue;
+                };
+                if tx.send(notification).is_err() {
+                    return;
+                }
+            }
+            // End of output without %exit: tmux went away
+            let _ = tx.send(Notification::Exited);
+        });
+
+        Some(Self { child, _stdin: stdin, notifications })
+    }
+
+    /// Take every notification received so far without blocking
+    pub fn drain(&self) -> Drained {
+        let mut drained = Drained::default();
+        loop {
+            match self.notifications.try_recv() {
+                Ok(Notification::Changed) => drained.changed = true,
+                Ok(Notification::Exited) | Err(TryRecvError::Disconnected) => {
+                    drained.exited = true;
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+        drained
+    }
+}
+
+impl Drop for ControlClient {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_and_session_notifications_are_changes() {
+        assert_eq!(parse("%window-add @4"), Some(Notification::Changed));
+        assert_eq!(parse("%unlinked-window-renamed @2 logs"), Some(Notification::Changed));
+        assert_eq!(parse("%sessions-changed"), Some(Notification::Changed));
+        assert_eq!(parse("%exit"), Some(Notification::Exited));
+        assert_eq!(parse("%exit server exited"), Some(Notification::Exited));
+        assert_eq!(parse("%layout-change @1 b25d,80x24,0,0,2"), None);
+        assert_eq!(parse("%begin 1700000000 12 0"), None);
+        assert_eq!(parse("%window-added"), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn drains_what_the_client_reported() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "printf '%%begin 1 2 0\\n%%end 1 2 0\\n%%window-add @4\\n'; cat"]);
+        let client = ControlClient::spawn(command).unwrap();
+        let started = std::time::Instant::now();
+        let mut drained = client.drain();
+        while !drained.changed && started.elapsed() < std::time::Duration::from_secs(5) {
+            thread::sleep(std::time::Duration::from_millis(10));
+            drained = client.drain();
+        }
+        assert!(drained.changed);
+        assert!(!drained.exited);
+        drop(client);
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "printf '%%exit\\n'"]);
+        let client = ControlClient::spawn(command).unwrap();
+        let started = std::time::Instant::now();
+        while !client.drain().exited {
+            assert!(started.elapsed() < std::time::Duration::from_secs(5));
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+}
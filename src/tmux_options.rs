@@ -0,0 +1,78 @@
+//! tmux options that quietly break assumptions Dimensions makes about sessions and windows.
+
+/// A global tmux option set to a value Dimensions doesn't cope with
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionConflict {
+    pub option: &'static str,
+    pub value: String,
+    pub problem: &'static str,
+    /// Line to add to ~/.tmux.conf
+    pub fix: &'static str,
+}
+
+struct Rule {
+    option: &'static str,
+    /// Values that are fine; anything else conflicts
+    safe: &'static [&'static str],
+    problem: &'static str,
+    fix: &'static str,
+}
+
+const RULES: &[Rule] = &[
+    Rule {
+        option: "destroy-unattached",
+        safe: &["off"],
+        problem: "dimension sessions are destroyed as soon as you switch away from them",
+        fix: "set -g destroy-unattached off",
+    },
+    Rule {
+        option: "renumber-windows",
+        safe: &["off"],
+        problem: "closing a window shifts the indexes of the others, so tab selection can land on the wrong window",
+        fix: "set -g renumber-windows off",
+    },
+];
+
+/// Conflicting options, reading values with `get` (None means unset or unknown)
+pub fn conflicts(get: impl Fn(&str) -> Option<String>) -> Vec<OptionConflict> {
+    RULES
+        .iter()
+        .filter_map(|rule| {
+            let value = get(rule.option)?;
+            if rule.safe.contains(&value.as_str()) {
+                return None;
+            }
+            Some(OptionConflict { option: rule.option, value, problem: rule.problem, fix: rule.fix })
+        })
+        .collect()
+}
+
+/// One-line warning for the status bar
+pub fn summary(conflicts: &[OptionConflict]) -> Option<String> {
+    let first = conflicts.first()?;
+    let more = match conflicts.len() {
+        1 => String::new(),
+        n => format!(" (+{} more, see `dimensions check`)", n - 1),
+    };
+    Some(format!(
+        "⚠ tmux {} is {}: {}; add `{}` to ~/.tmux.conf{}",
+        first.option, first.value, first.problem, first.fix, more
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_unsafe_values_only() {
+        let found = conflicts(|option| match option {
+            "destroy-unattached" => Some("keep-last".to_string()),
+            "renumber-windows" => Some("off".to_string()),
+            _ => None,
+        });
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].option, "destroy-unattached");
+        assert!(conflicts(|_| None).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::backend::MultiplexerBackend;
+use crate::tmux::WindowStatus;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// How long a session's window list is reused before asking tmux again
+pub const DEFAULT_TTL: Duration = Duration::from_secs(1);
+
+/// TTL while a control-mode connection reports changes as they happen; only what tmux
+/// sends no notification for (exit statuses, session paths) relies on it
+pub const EVENT_DRIVEN_TTL: Duration = Duration::from_secs(10);
+
+type Windows = Vec<(usize, String)>;
+type ExitStatuses = HashMap<usize, i32>;
+type WindowStatuses = HashMap<usize, WindowStatus>;
+
+/// Per-session window lists (and other per-frame tmux lookups) for rendering.
+/// The UI draws ~10x/sec, and asking tmux for every dimension on every frame spawns
+/// hundreds of subprocesses with large configs; entries expire after a short TTL and
+/// are dropped whenever the app itself changes tmux state (or on Ctrl+R/F5).
+pub struct TmuxStateCache {
+    backend: Rc<dyn MultiplexerBackend>,
+    ttl: Cell<Duration>,
+    // Windows of every live session, from one `list-windows -a`
+    snapshot: RefCell<Option<(Instant, HashMap<String, Windows>)>>,
+    session_paths: RefCell<HashMap<String, (Instant, Option<PathBuf>)>>,
+    exit_statuses: RefCell<HashMap<String, (Instant, ExitStatuses)>>,
+    // Status of every window, from one `list-windows -a`
+    window_statuses: RefCell<Option<(Instant, HashMap<String, WindowStatuses>)>>,
+    attached: RefCell<Option<(Instant, HashMap<String, usize>)>>,
+    sessions: RefCell<Option<(Instant, Vec<String>)>>,
+}
+
+impl TmuxStateCache {
+    pub fn new(backend: Rc<dyn MultiplexerBackend>, ttl: Duration) -> Self {
+        Self {
+            backend,
+            ttl: Cell::new(ttl),
+            snapshot: RefCell::new(None),
+            session_paths: RefCell::new(HashMap::new()),
+            exit_statuses: RefCell::new(HashMap::new()),
+            window_statuses: RefCell::new(None),
+            attached: RefCell::new(None),
+            sessions: RefCell::new(None),
+        }
+    }
+
+    /// Change how long entries are reused, e.g. once tmux reports changes on its own
+    pub fn set_ttl(&self, ttl: Duration) {
+        self.ttl.set(ttl);
+    }
+
+    /// Windows of a live session, or None if there is no such session
+    pub fn windows(&self, session: &str) -> Option<Windows> {
+        self.refresh_snapshot();
+        self.snapshot.borrow().as_ref()?.1.get(session).cloned()
+    }
+
+    /// Re-read every session's windows at once if the last read has expired, so a frame
+    /// costs one tmux call however many dimensions are shown
+    fn refresh_snapshot(&self) {
+        if self.snapshot.borrow().as_ref().is_some_and(|(fetched, _)| self.is_fresh(fetched)) {
+            return;
+        }
+        let sessions = self.backend.snapshot().unwrap_or_default();
+        *self.snapshot.borrow_mut() = Some((Instant::now(), sessions));
+    }
+
+    /// Whether something read at `fetched` can still be used
+    fn is_fresh(&self, fetched: &Instant) -> bool {
+        fetched.elapsed() < self.ttl.get()
+    }
+
+    pub fn session_exists(&self, session: &str) -> bool {
+        self.windows(session).is_some()
+    }
+
+    pub fn window_count(&self, session: &str) -> Option<usize> {
+        self.windows(session).map(|w| w.len())
+    }
+
+    /// `#{session_path}` of a live session
+    pub fn session_path(&self, session: &str) -> Option<PathBuf> {
+        if let Some((_, path)) = self.session_paths.borrow().get(session).filter(|(fetched, _)| self.is_fresh(fetched)) {
+            return path.clone();
+        }
+
+        let path = self.backend.session_path(session);
+        self.session_paths
+            .borrow_mut()
+            .insert(session.to_string(), (Instant::now(), path.clone()));
+        path
+    }
+
+    /// Last recorded exit status of each window's command in a live session
+    pub fn exit_statuses(&self, session: &str) -> ExitStatuses {
+        if let Some((_, statuses)) = self.exit_statuses.borrow().get(session).filter(|(fetched, _)| self.is_fresh(fetched)) {
+            return statuses.clone();
+        }
+
+        let statuses = self.backend.window_exit_statuses(session).unwrap_or_default();
+        self.exit_statuses
+            .borrow_mut()
+            .insert(session.to_string(), (Instant::now(), statuses.clone()));
+        statuses
+    }
+
+    /// Exit status and last activity of each window in a live session
+    pub fn window_statuses(&self, session: &str) -> WindowStatuses {
+        let fresh = self.window_statuses.borrow().as_ref().is_some_and(|(fetched, _)| self.is_fresh(fetched));
+        if !fresh {
+            let statuses = self.backend.window_statuses().unwrap_or_default();
+            *self.window_statuses.borrow_mut() = Some((Instant::now(), statuses));
+        }
+        self.window_statuses
+            .borrow()
+            .as_ref()
+            .and_then(|(_, statuses)| statuses.get(session).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Attached client count per live session
+    pub fn attached_client_counts(&self) -> HashMap<String, usize> {
+        if let Some((_, counts)) = self.attached.borrow().as_ref().filter(|(fetched, _)| self.is_fresh(fetched)) {
+            return counts.clone();
+        }
+
+        let counts = self.backend.attached_client_counts();
+        *self.attached.borrow_mut() = Some((Instant::now(), counts.clone()));
+        counts
+    }
+
+    /// Every session on the server, dimension or not
+    pub fn sessions(&self) -> Vec<String> {
+        if let Some((_, sessions)) = self.sessions.borrow().as_ref().filter(|(fetched, _)| self.is_fresh(fetched)) {
+            return sessions.clone();
+        }
+
+        let sessions = self.backend.list_sessions().unwrap_or_default();
+        *self.sessions.borrow_mut() = Some((Instant::now(), sessions.clone()));
+        sessions
+    }
+
+    /// When the window lists currently cached were fetched
+    pub fn oldest_fetch(&self) -> Option<Instant> {
+        self.snapshot.borrow().as_ref().map(|(fetched, _)| *fetched)
+    }
+
+    /// Forget everything; call after creating, renaming or killing sessions/windows
+    pub fn invalidate_all(&self) {
+        self.snapshot.borrow_mut().take();
+        self.session_paths.borrow_mut().clear();
+        self.exit_statuses.borrow_mut().clear();
+        self.window_statuses.borrow_mut().take();
+        self.attached.borrow_mut().take();
+        self.sessions.borrow_mut().take();
+    }
+}
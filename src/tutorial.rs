@@ -0,0 +1,78 @@
+use crate::paths;
+use crate::tmux::Tmux;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+/// One lesson of `dimensions tutorial`
+pub struct TutorialStep {
+    pub title: &'static str,
+    pub lines: &'static [&'static str],
+}
+
+pub const STEPS: [TutorialStep; 4] = [
+    TutorialStep {
+        title: "Create a dimension",
+        lines: &[
+            "Press n, type a name (e.g. playground) and press Enter.",
+            "Enter again accepts the suggested base directory.",
+        ],
+    },
+    TutorialStep {
+        title: "Add a tab",
+        lines: &[
+            "With the dimension selected, press t and type `shell`",
+            "or `logs:top` (name:command), then press Enter.",
+        ],
+    },
+    TutorialStep {
+        title: "Search",
+        lines: &[
+            "Press / and start typing part of a name; results update live.",
+            "Esc leaves search, ↑/↓ move through the results.",
+        ],
+    },
+    TutorialStep {
+        title: "Switch",
+        lines: &[
+            "Select your dimension (Tab moves between panes) and press Enter.",
+            "Dimensions creates the tmux session and switches to it.",
+        ],
+    },
+];
+
+/// Sandbox for `dimensions tutorial`: a throwaway config directory and a private
+/// tmux server, so nothing touches the user's real setup
+pub struct Tutorial {
+    pub step: usize,
+    sandbox: PathBuf,
+}
+
+impl Tutorial {
+    /// Point config and tmux at the sandbox. Must run before the config is loaded.
+    pub fn setup() -> Result<Self> {
+        let id = std::process::id();
+        let sandbox = std::env::temp_dir().join(format!("dimensions-tutorial-{}", id));
+        fs::create_dir_all(&sandbox)
+            .with_context(|| format!("Failed to create {}", sandbox.display()))?;
+
+        paths::set_config_dir_override(sandbox.clone());
+        Tmux::use_private_socket(&format!("dimensions-tutorial-{}", id));
+
+        Ok(Self { step: 0, sandbox })
+    }
+
+    pub fn current(&self) -> Option<&'static TutorialStep> {
+        STEPS.get(self.step)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step >= STEPS.len()
+    }
+
+    /// Stop the private tmux server and delete the sandbox config
+    pub fn cleanup(&self) {
+        Tmux::kill_private_server();
+        let _ = fs::remove_dir_all(&self.sandbox);
+    }
+}
@@ -0,0 +1,123 @@
+//! Left pane: every configured dimension.
+
+use super::pane_block;
+use crate::app::{DimensionItem, ListArea};
+use super::view_model::DimensionListView;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState},
+    Frame,
+};
+
+/// Returns where the rows landed, for mouse clicks
+pub fn render(f: &mut Frame, view: &DimensionListView, area: Rect) -> ListArea {
+    let mut items: Vec<ListItem> = vec![];
+    for row in &view.rows {
+        let current_marker = if row.is_current { " *" } else { "" };
+
+        let style = if row.is_current {
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+
+        // Group/tag color carries through to the name (current stays bold)
+        let name_style = match row.color {
+            Some(color) => style.fg(color),
+            None => style,
+        };
+
+        // Create styled line with name, tab count, marker, and path (faded)
+        let mut spans = Vec::new();
+        if row.nested_tabs.is_some() {
+            spans.push(Span::styled("▾ ", Style::default().fg(Color::DarkGray)));
+        }
+        if let Some(icon) = row.icon {
+            spans.push(Span::raw(format!("{} ", icon)));
+        }
+        spans.extend([
+            Span::styled(row.name.clone(), name_style),
+            Span::styled(format!(" [{} tabs]", row.tab_count), style),
+            Span::styled(current_marker, style),
+        ]);
+
+        // Windows added or closed outside the config; `S` reconciles them
+        if let Some(drift) = row.drift {
+            spans.push(Span::styled(format!(" {:+}", drift), Style::default().fg(Color::Yellow)));
+        }
+
+        // Attached clients; more than one means the session is shared across terminals
+        match row.attached_clients {
+            0 => {}
+            n => spans.push(Span::styled(
+                format!(" 👥{}", n),
+                Style::default().fg(if n > 1 { Color::Magenta } else { Color::DarkGray }),
+            )),
+        }
+
+        if row.missing_dir {
+            spans.push(Span::styled(" ⚠ missing dir", Style::default().fg(Color::Yellow)));
+        }
+
+        if let Some(path) = &row.path {
+            spans.push(Span::styled(
+                format!(" ({})", path),
+                Style::default().fg(Color::Gray)
+            ));
+        }
+
+        items.push(ListItem::new(Line::from(spans)));
+
+        // Expanded: the tabs hang off the dimension, tree style
+        let nested = row.nested_tabs.as_deref().unwrap_or_default();
+        for (i, (_, name)) in nested.iter().enumerate() {
+            let branch = if i + 1 == nested.len() { "└ " } else { "├ " };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!("  {}", branch), Style::default().fg(Color::DarkGray)),
+                Span::styled(name.clone(), Style::default().fg(Color::Gray)),
+            ])));
+        }
+    }
+
+    // Sessions started outside Dimensions: Enter attaches, `a` adopts into the config
+    if !view.orphans.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "── other tmux sessions ──",
+            Style::default().fg(Color::DarkGray),
+        ))));
+        for orphan in &view.orphans {
+            let mut spans = vec![Span::styled(orphan.name.clone(), Style::default().fg(Color::Gray))];
+            if orphan.attached_clients > 0 {
+                spans.push(Span::styled(
+                    format!(" 👥{}", orphan.attached_clients),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            items.push(ListItem::new(Line::from(spans)));
+        }
+    }
+
+    let block = pane_block(view.title.clone(), view.focused);
+    let inner = block.inner(area);
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut state = ListState::default();
+    // Nested tabs and the orphan section header shift rows down, so look the selection up
+    let selected = match view.selected_orphan {
+        Some(i) => Some(DimensionItem::Orphan(i)),
+        None => view.selected.map(DimensionItem::Dimension),
+    };
+    state.select(selected.and_then(|selected| view.items().iter().position(|item| *item == selected)));
+    f.render_stateful_widget(list, area, &mut state);
+    ListArea { area: inner, offset: state.offset() }
+}
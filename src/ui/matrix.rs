@@ -0,0 +1,121 @@
+//! Matrix view (`m`): one row per dimension, its tabs across with what each is doing.
+
+use super::pane_block;
+use super::text::truncate_ellipsis;
+use super::view_model::{CellState, MatrixCell, MatrixView};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Widest the name column gets; longer names are cut
+const MAX_NAME_WIDTH: usize = 20;
+
+const CELL_GAP: &str = "  ";
+
+fn glyph(state: CellState) -> (&'static str, Color) {
+    match state {
+        CellState::Running => ("●", Color::Green),
+        CellState::Active => ("◆", Color::Yellow),
+        CellState::Exited => ("○", Color::Gray),
+        CellState::Failed(_) => ("✗", Color::Red),
+        CellState::Stopped => ("·", Color::DarkGray),
+    }
+}
+
+fn cell_label(cell: &MatrixCell) -> String {
+    match cell.state {
+        CellState::Failed(code) => format!("{} {}", cell.name, code),
+        _ => cell.name.clone(),
+    }
+}
+
+/// Spans of one cell, glyph first
+fn cell_spans(cell: &MatrixCell, selected: bool) -> Vec<Span<'static>> {
+    let (glyph, color) = glyph(cell.state);
+    let mut style = if cell.is_current {
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    if selected {
+        style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+    }
+    vec![
+        Span::styled(format!("{} ", glyph), Style::default().fg(color)),
+        Span::styled(cell_label(cell), style),
+    ]
+}
+
+pub fn render(f: &mut Frame, view: &MatrixView, area: Rect) {
+    let block = pane_block("Matrix (● running ◆ output ✗ failed ○ exited · stopped)".to_string(), true);
+    let inner = block.inner(area);
+    let name_width = view
+        .rows
+        .iter()
+        .map(|row| row.name.width())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_NAME_WIDTH)
+        .min(inner.width as usize / 3);
+    let cells_width = (inner.width as usize).saturating_sub(name_width + 1);
+
+    let items: Vec<ListItem> = view
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let selected_cell = view.selected.filter(|(selected, _)| *selected == i).map(|(_, cell)| cell);
+            let mut name_style = match (row.is_current, row.color) {
+                (true, _) => Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                (false, Some(color)) => Style::default().fg(color),
+                (false, None) => Style::default(),
+            };
+            if selected_cell.is_some() {
+                name_style = name_style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                if selected_cell == Some(None) {
+                    name_style = name_style.add_modifier(Modifier::REVERSED);
+                }
+            }
+            let name = truncate_ellipsis(&row.name, name_width);
+            let padding = " ".repeat(name_width.saturating_sub(name.width()) + 1);
+            let mut spans = vec![Span::styled(name, name_style), Span::raw(padding)];
+
+            if row.cells.is_empty() {
+                spans.push(Span::styled("(no tabs)", Style::default().fg(Color::DarkGray)));
+                return ListItem::new(Line::from(spans));
+            }
+
+            // Scroll the selected row sideways until its selected cell is in view
+            let widths: Vec<usize> = row.cells.iter().map(|cell| 2 + cell_label(cell).width()).collect();
+            let mut first = 0;
+            if let Some(Some(selected)) = selected_cell {
+                let end = |first: usize| -> usize {
+                    let skipped = if first > 0 { 2 } else { 0 };
+                    skipped + widths[first..=selected].iter().sum::<usize>() + CELL_GAP.len() * (selected - first)
+                };
+                while first < selected && end(first) > cells_width {
+                    first += 1;
+                }
+            }
+            if first > 0 {
+                spans.push(Span::styled("… ", Style::default().fg(Color::DarkGray)));
+            }
+            for (j, cell) in row.cells.iter().enumerate().skip(first) {
+                if j > first {
+                    spans.push(Span::raw(CELL_GAP));
+                }
+                spans.extend(cell_spans(cell, selected_cell == Some(Some(j))));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(view.selected.map(|(row, _)| row));
+    f.render_stateful_widget(List::new(items).block(block), area, &mut state);
+}
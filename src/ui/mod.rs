@@ -0,0 +1,193 @@
+mod dimension_list;
+mod matrix;
+mod popups;
+mod preview;
+mod search;
+mod statusbar;
+mod tab_list;
+mod text;
+mod title;
+pub mod view_model;
+
+#[cfg(test)]
+mod tests;
+
+use crate::app::{App, InputMode, MouseTargets};
+use crate::profile;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders},
+    Frame,
+};
+
+fn inner_list_width(area: Rect) -> usize {
+    // Account for left/right borders.
+    area.width.saturating_sub(2) as usize
+}
+
+/// Border style for a list pane: highlighted when it has keyboard focus
+fn pane_block(title: String, focused: bool) -> Block<'static> {
+    let (border, title_style) = if focused {
+        (
+            Style::default().fg(Color::Cyan),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        (Style::default().fg(Color::DarkGray), Style::default())
+    };
+    Block::default()
+        .title(Span::styled(title, title_style))
+        .borders(Borders::ALL)
+        .border_style(border)
+}
+
+fn format_path_with_tilde(path: &str) -> String {
+    if let Ok(home) = std::env::var("HOME") {
+        if path.starts_with(&home) {
+            return path.replacen(&home, "~", 1);
+        }
+    }
+    path.to_string()
+}
+
+pub fn render(f: &mut Frame, app: &mut App) {
+    if app.here {
+        render_here(f, app);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Title
+            Constraint::Min(0),     // Main content
+            Constraint::Length(3),  // Status bar
+            Constraint::Length(5),  // Help
+        ])
+        .split(f.area());
+
+    // Main content first: it refreshes expired tmux state, which the title reports the age of
+    {
+        let _span = profile::span("  render lists/preview");
+        render_main_content(f, app, chunks[1]);
+    }
+    {
+        let _span = profile::span("  render title");
+        title::render(f, &view_model::title(app), chunks[0]);
+    }
+    statusbar::render_status(f, app, chunks[2]);
+    statusbar::render_help(f, app, chunks[3]);
+
+    // Completion popup floats above the status bar (where the input is)
+    if app.is_completion_popup_open() {
+        popups::render_completion_popup(f, app, chunks[2]);
+    }
+
+    if app.input_mode == InputMode::PickingTask {
+        popups::render_task_picker(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::PickingLink {
+        popups::render_link_picker(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::PickingSuggestion {
+        popups::render_suggestions(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::SearchActions {
+        popups::render_search_actions(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::PickingTemplate {
+        popups::render_template_picker(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::Syncing {
+        popups::render_sync(f, app, chunks[1]);
+    }
+
+    if app.tutorial.is_some() {
+        popups::render_tutorial(f, app, chunks[1]);
+    }
+
+    if app.startup.is_some() {
+        popups::render_startup_progress(f, app, chunks[1]);
+    } else if app.waiting.is_some() {
+        popups::render_waiting_for_ready(f, app, chunks[1]);
+    }
+}
+
+/// `--here`: just the prompt and its keys, for a small popup over the window being labeled
+fn render_here(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(f.area());
+    statusbar::render_status(f, app, chunks[0]);
+    statusbar::render_help(f, app, chunks[1]);
+}
+
+/// Centered rect of the given size, clamped to `area`
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+fn render_main_content(f: &mut Frame, app: &mut App, area: Rect) {
+    // Check if we're in active search mode with a query
+    let searching = matches!(app.input_mode, InputMode::Searching | InputMode::SearchActions);
+    if searching && (!app.search_query.is_empty() || app.pick) {
+        // Compute search results if needed
+        app.compute_search_results();
+        app.mouse_targets = MouseTargets::default();
+
+        // Render single-column search results
+        search::render(f, &view_model::search(app), area);
+    } else if app.matrix {
+        app.mouse_targets = MouseTargets::default();
+        matrix::render(f, &view_model::matrix(app), area);
+    } else {
+        // Render normal two-column layout, or three with the preview panel open
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(if app.preview_panel {
+                vec![
+                    Constraint::Percentage(30), // Dimensions list
+                    Constraint::Percentage(30), // Tabs list
+                    Constraint::Percentage(40), // Preview
+                ]
+            } else {
+                vec![
+                    Constraint::Percentage(40),  // Dimensions list
+                    Constraint::Percentage(60),  // Tabs list
+                ]
+            })
+            .split(area);
+
+        let dimension_view = view_model::dimension_list(app);
+        let dimensions = dimension_list::render(f, &dimension_view, chunks[0]);
+        let tab_view = view_model::tab_list(app);
+        let preview = if app.preview_panel {
+            preview::render_panel(f, &view_model::preview_panel(app), chunks[2]);
+            None
+        } else {
+            view_model::preview(app)
+        };
+        let tabs = tab_list::render(f, tab_view.as_ref(), preview.as_ref(), chunks[1]);
+        app.mouse_targets = MouseTargets {
+            dimensions: Some(dimensions),
+            dimension_items: dimension_view.items(),
+            tabs,
+            tab_indexes: tab_view.map(|v| v.rows.iter().map(|r| r.index).collect()).unwrap_or_default(),
+        };
+    }
+}
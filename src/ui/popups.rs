@@ -0,0 +1,423 @@
+//! Floating popups drawn over the main area: startup progress, readiness wait,
+//! the tutorial panel and path completion.
+
+use super::text::{truncate_ellipsis, truncate_with_suffix};
+use super::{centered_rect, format_path_with_tilde, inner_list_width};
+use crate::app::{App, SearchAction};
+use crate::tutorial;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+pub fn render_waiting_for_ready(f: &mut Frame, app: &App, area: Rect) {
+    let Some(waiting) = app.waiting.as_ref() else {
+        return;
+    };
+
+    let elapsed = waiting.started.elapsed();
+    let frame = SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()];
+    let popup = centered_rect(60, 6, area);
+    let width = popup.width.saturating_sub(2) as usize;
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{} ", frame), Style::default().fg(Color::Cyan)),
+            // Keep the elapsed time visible when the description is long
+            Span::raw(truncate_with_suffix(
+                &format!("Waiting for {}", waiting.description),
+                &format!(" ({}s)", elapsed.as_secs()),
+                width.saturating_sub(2),
+            )),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Switch now  "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!("Starting '{}'", waiting.session))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    f.render_widget(Clear, popup);
+    f.render_widget(widget, popup);
+}
+
+/// Tutorial instructions, pinned to the bottom of the main area
+pub fn render_tutorial(f: &mut Frame, app: &App, area: Rect) {
+    let Some(tutorial) = app.tutorial.as_ref() else {
+        return;
+    };
+
+    let height = 5.min(area.height);
+    let popup = Rect {
+        x: area.x + 1,
+        y: area.y + area.height - height,
+        width: area.width.saturating_sub(2),
+        height,
+    };
+
+    let (title, lines) = match tutorial.current() {
+        Some(step) => (
+            format!("Tutorial {}/{}: {}", tutorial.step + 1, tutorial::STEPS.len(), step.title),
+            step.lines.iter().map(|l| Line::from(*l)).collect::<Vec<_>>(),
+        ),
+        None => (
+            "Tutorial complete".to_string(),
+            vec![
+                Line::from("That's the core loop: create, add tabs, search, switch."),
+                Line::from("Press q to leave. Your real config and tmux sessions were never touched."),
+            ],
+        ),
+    };
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(title, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+
+    f.render_widget(Clear, popup);
+    f.render_widget(widget, popup);
+}
+
+pub fn render_startup_progress(f: &mut Frame, app: &App, area: Rect) {
+    let Some(progress) = app.startup.as_ref() else {
+        return;
+    };
+
+    let total = progress.tabs.len().max(1);
+    let popup = centered_rect(60, 7, area);
+    let block = Block::default()
+        .title(format!("Starting '{}'", progress.session))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup);
+
+    f.render_widget(Clear, popup);
+    f.render_widget(block, popup);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Counts
+            Constraint::Length(1), // Current window
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Gauge
+        ])
+        .split(inner);
+
+    let counts = Line::from(vec![
+        Span::raw("Windows "),
+        Span::styled(
+            format!("{}/{}", progress.windows_created, progress.tabs.len()),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw("  Commands launched "),
+        Span::styled(
+            format!("{}/{}", progress.commands_launched, progress.total_commands()),
+            Style::default().fg(Color::Yellow),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(counts), rows[0]);
+
+    if let Some(tab) = progress.tabs.get(progress.windows_created) {
+        let next = truncate_ellipsis(&format!("Creating {}", tab.name), rows[1].width as usize);
+        f.render_widget(
+            Paragraph::new(next).style(Style::default().fg(Color::DarkGray)),
+            rows[1],
+        );
+    }
+
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Cyan))
+        .ratio((progress.windows_created as f64 / total as f64).min(1.0));
+    f.render_widget(gauge, rows[3]);
+}
+
+pub fn render_completion_popup(f: &mut Frame, app: &App, anchor: Rect) {
+    let max_visible = 8;
+    let visible = app.completion_candidates.len().min(max_visible) as u16;
+    // Candidates plus borders, clamped to the space available above the input
+    let height = (visible + 2).min(anchor.y);
+    if height < 3 {
+        return;
+    }
+
+    let area = Rect {
+        x: anchor.x,
+        y: anchor.y - height,
+        width: anchor.width,
+        height,
+    };
+
+    // Leave room for the highlight symbol
+    let max_width = inner_list_width(area).saturating_sub(2);
+    let items: Vec<ListItem> = app
+        .completion_candidates
+        .iter()
+        .map(|candidate| ListItem::new(truncate_ellipsis(&format_path_with_tilde(candidate), max_width)))
+        .collect();
+
+    let title = format!(
+        "Matches ({}/{})",
+        app.completion_index + 1,
+        app.completion_candidates.len()
+    );
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.completion_index));
+
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+/// Task menu of the selected task tab, centered over the main area
+pub fn render_task_picker(f: &mut Frame, app: &App, area: Rect) {
+    let Some(tab) = app.selected_configured_tab() else {
+        return;
+    };
+
+    let popup = centered_rect(60, tab.tasks.len() as u16 + 2, area);
+    // Leave room for the highlight symbol
+    let max_width = inner_list_width(popup).saturating_sub(2);
+    let name_width = tab.tasks.iter().map(|t| t.name.width()).max().unwrap_or(0);
+    let items: Vec<ListItem> = tab
+        .tasks
+        .iter()
+        .map(|task| {
+            let name = format!("{:<width$}", task.name, width = name_width);
+            let command = truncate_ellipsis(&task.command, max_width.saturating_sub(name_width + 2));
+            ListItem::new(Line::from(vec![
+                Span::raw(name),
+                Span::raw("  "),
+                Span::styled(command, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Tasks: {}", tab.name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.task_selected));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// Links of the selected dimension, centered over the main area
+pub fn render_link_picker(f: &mut Frame, app: &App, area: Rect) {
+    let Some(dimension) = app.get_current_dimension() else {
+        return;
+    };
+
+    let popup = centered_rect(60, dimension.links.len() as u16 + 2, area);
+    // Leave room for the highlight symbol
+    let max_width = inner_list_width(popup).saturating_sub(2);
+    let name_width = dimension.links.iter().map(|l| l.name.width()).max().unwrap_or(0);
+    let items: Vec<ListItem> = dimension
+        .links
+        .iter()
+        .map(|link| {
+            let name = format!("{:<width$}", link.name, width = name_width);
+            let url = truncate_ellipsis(&link.url, max_width.saturating_sub(name_width + 2));
+            ListItem::new(Line::from(vec![
+                Span::raw(name),
+                Span::raw("  "),
+                Span::styled(url, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Links: {}", dimension.name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.link_selected));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// Projects under `discover.roots` without a dimension, named as they would be created
+pub fn render_suggestions(f: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(70, app.suggestions.len() as u16 + 2, area);
+    let max_width = inner_list_width(popup).saturating_sub(2);
+    let names: Vec<String> = app
+        .suggestions
+        .iter()
+        .map(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default())
+        .collect();
+    let name_width = names.iter().map(|n| n.width()).max().unwrap_or(0);
+    let items: Vec<ListItem> = app
+        .suggestions
+        .iter()
+        .zip(&names)
+        .map(|(dir, name)| {
+            let name = format!("{:<width$}", name, width = name_width);
+            let path = format_path_with_tilde(&dir.to_string_lossy());
+            let path = truncate_ellipsis(&path, max_width.saturating_sub(name_width + 2));
+            ListItem::new(Line::from(vec![
+                Span::raw(name),
+                Span::raw("  "),
+                Span::styled(path, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Suggested dimensions ({})", app.suggestions.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.suggestion_selected));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// What to do with the highlighted search result (`Tab` in search)
+pub fn render_search_actions(f: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(30, SearchAction::ALL.len() as u16 + 2, area);
+    let items: Vec<ListItem> = SearchAction::ALL
+        .iter()
+        .map(|action| ListItem::new(action.label()))
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("Actions")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.search_action));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// Templates for `T`, each with the tabs it creates
+pub fn render_template_picker(f: &mut Frame, app: &App, area: Rect) {
+    let templates = &app.engine.config.templates;
+    let popup = centered_rect(60, templates.len() as u16 + 2, area);
+    let max_width = inner_list_width(popup).saturating_sub(2);
+    let name_width = templates.iter().map(|t| t.name.width()).max().unwrap_or(0);
+    let items: Vec<ListItem> = templates
+        .iter()
+        .map(|template| {
+            let name = format!("{:<width$}", template.name, width = name_width);
+            let tabs: Vec<&str> = template.configured_tabs.iter().map(|t| t.name.as_str()).collect();
+            let tabs = truncate_ellipsis(&tabs.join(", "), max_width.saturating_sub(name_width + 2));
+            ListItem::new(Line::from(vec![
+                Span::raw(name),
+                Span::raw("  "),
+                Span::styled(tabs, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("New dimension from template")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.template_selected));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// Review list for `S`: every difference between config and tmux, each toggleable
+pub fn render_sync(f: &mut Frame, app: &App, area: Rect) {
+    let Some(sync) = &app.sync else {
+        return;
+    };
+
+    let popup = centered_rect(60, sync.items.len() as u16 + 2, area);
+    let max_width = inner_list_width(popup).saturating_sub(2);
+    let items: Vec<ListItem> = sync
+        .items
+        .iter()
+        .map(|item| {
+            let (mark, style) = if item.accepted {
+                ("[x] ", Style::default())
+            } else {
+                ("[ ] ", Style::default().fg(Color::DarkGray))
+            };
+            let text = truncate_ellipsis(&item.change.describe(), max_width.saturating_sub(mark.len()));
+            ListItem::new(Line::from(vec![Span::styled(mark, style), Span::styled(text, style)]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Sync: {}", sync.session))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    let mut state = ListState::default();
+    state.select(Some(sync.selected));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
@@ -0,0 +1,239 @@
+//! Capture of the selected tab, with its ANSI colors translated for ratatui: below the tabs,
+//! or as a panel of its own (`p`) showing the end of the pane.
+
+use super::view_model::PreviewView;
+use ansi_to_tui::IntoText;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Most lines the preview panel shows: the end of the pane, where the action usually is
+pub const PANEL_LINES: usize = 30;
+
+pub fn render(f: &mut Frame, preview: &PreviewView, area: Rect) {
+    let text = styled_text(&preview.content);
+    let title = preview.title.clone();
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let total_lines = text.lines.len();
+
+    // If content fits, show it all. Otherwise, show first 8, "...", last 8
+    let display_text = if total_lines <= inner_height {
+        text
+    } else {
+        let preview_lines = 8;
+        let mut lines = Vec::new();
+
+        // First 8 lines
+        lines.extend(text.lines.iter().take(preview_lines).cloned());
+
+        // Separator
+        lines.push(Line::from(Span::styled(
+            "...",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        // Last 8 lines
+        if total_lines > preview_lines {
+            lines.extend(
+                text.lines
+                    .iter()
+                    .skip(total_lines.saturating_sub(preview_lines))
+                    .cloned(),
+            );
+        }
+
+        Text::from(lines)
+    };
+
+    let paragraph = Paragraph::new(display_text)
+        .block(Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)));
+
+    f.render_widget(paragraph, area);
+}
+
+/// The preview panel: the last lines of the capture that fit (at most `PANEL_LINES`)
+pub fn render_panel(f: &mut Frame, preview: &PreviewView, area: Rect) {
+    let mut text = styled_text(&preview.content);
+    // capture-pane pads to the pane's height; the tail should end at the last output
+    while text.lines.last().is_some_and(|line| line.width() == 0) {
+        text.lines.pop();
+    }
+    let shown = PANEL_LINES.min(area.height.saturating_sub(2) as usize);
+    let skip = text.lines.len().saturating_sub(shown);
+    text.lines.drain(..skip);
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(preview.title.clone())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+/// Parse ANSI escape codes into styled text and convert to ratatui types
+fn styled_text(content: &str) -> Text<'static> {
+    let content = normalize_preview_content(content);
+    let parsed = content.as_bytes().into_text().unwrap_or_default();
+    Text {
+        alignment: convert_alignment(parsed.alignment),
+        style: Style::default(), // Don't inherit global style
+        lines: parsed
+            .lines
+            .into_iter()
+            .map(|line| {
+                let mut spans: Vec<Span> = line
+                    .spans
+                    .into_iter()
+                    .map(|span| Span::styled(span.content.into_owned(), convert_style(span.style)))
+                    .collect();
+
+                // Ensure line ends with a style reset to prevent color bleeding to next line
+                if !spans.is_empty() {
+                    spans.push(Span::raw(""));
+                }
+
+                Line {
+                    style: Style::default(), // Don't inherit line-level style
+                    alignment: convert_alignment(line.alignment),
+                    spans,
+                }
+            })
+            .collect(),
+    }
+}
+
+fn normalize_preview_content(content: &str) -> String {
+    // Normalize CRLF and stray carriage returns that can skew TUI layout.
+    let normalized = content.replace("\r\n", "\n").replace('\r', "");
+
+    // Strip OSC 8 hyperlink sequences which break ansi-to-tui parsing
+    // Format: ESC ] 8 ; ; URL (BEL or ESC \) for opening
+    //         ESC ] 8 ; ; (BEL or ESC \) for closing
+    let bytes = normalized.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        // Check for ESC ] 8 ; sequence
+        if i + 4 <= bytes.len()
+            && bytes[i] == 0x1b      // ESC
+            && bytes[i + 1] == b']'   // ]
+            && bytes[i + 2] == b'8'   // 8
+            && bytes[i + 3] == b';'   // ;
+        {
+            // Skip the OSC 8 sequence until we find the terminator
+            i += 4;
+            while i < bytes.len() {
+                if bytes[i] == 0x07 {
+                    // BEL terminator
+                    i += 1;
+                    break;
+                } else if i + 1 < bytes.len() && bytes[i] == 0x1b && bytes[i + 1] == b'\\' {
+                    // ESC \ terminator
+                    i += 2;
+                    break;
+                }
+                i += 1;
+            }
+        } else {
+            // Keep this byte
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+// Helper functions to convert ratatui_core types into ratatui types.
+fn convert_color(color: ratatui_core::style::Color) -> Color {
+    use ratatui_core::style::Color as CoreColor;
+    match color {
+        CoreColor::Reset => Color::Reset,
+        CoreColor::Black => Color::Black,
+        CoreColor::Red => Color::Red,
+        CoreColor::Green => Color::Green,
+        CoreColor::Yellow => Color::Yellow,
+        CoreColor::Blue => Color::Blue,
+        CoreColor::Magenta => Color::Magenta,
+        CoreColor::Cyan => Color::Cyan,
+        CoreColor::Gray => Color::Gray,
+        CoreColor::DarkGray => Color::DarkGray,
+        CoreColor::LightRed => Color::LightRed,
+        CoreColor::LightGreen => Color::LightGreen,
+        CoreColor::LightYellow => Color::LightYellow,
+        CoreColor::LightBlue => Color::LightBlue,
+        CoreColor::LightMagenta => Color::LightMagenta,
+        CoreColor::LightCyan => Color::LightCyan,
+        CoreColor::White => Color::White,
+        CoreColor::Rgb(r, g, b) => Color::Rgb(r, g, b),
+        CoreColor::Indexed(i) => Color::Indexed(i),
+    }
+}
+
+fn convert_modifier(modifier: ratatui_core::style::Modifier) -> Modifier {
+    let mut result = Modifier::empty();
+
+    if modifier.contains(ratatui_core::style::Modifier::BOLD) {
+        result |= Modifier::BOLD;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::DIM) {
+        result |= Modifier::DIM;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::ITALIC) {
+        result |= Modifier::ITALIC;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::UNDERLINED) {
+        result |= Modifier::UNDERLINED;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::SLOW_BLINK) {
+        result |= Modifier::SLOW_BLINK;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::RAPID_BLINK) {
+        result |= Modifier::RAPID_BLINK;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::REVERSED) {
+        result |= Modifier::REVERSED;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::HIDDEN) {
+        result |= Modifier::HIDDEN;
+    }
+    if modifier.contains(ratatui_core::style::Modifier::CROSSED_OUT) {
+        result |= Modifier::CROSSED_OUT;
+    }
+
+    result
+}
+
+fn convert_style(style: ratatui_core::style::Style) -> Style {
+    let mut converted = Style::default();
+    if let Some(fg) = style.fg {
+        converted = converted.fg(convert_color(fg));
+    }
+    if let Some(bg) = style.bg {
+        converted = converted.bg(convert_color(bg));
+    }
+    converted = converted.add_modifier(convert_modifier(style.add_modifier));
+    converted = converted.remove_modifier(convert_modifier(style.sub_modifier));
+    converted
+}
+
+fn convert_alignment(
+    alignment: Option<ratatui_core::layout::Alignment>,
+) -> Option<ratatui::layout::Alignment> {
+    match alignment {
+        Some(ratatui_core::layout::Alignment::Left) => Some(ratatui::layout::Alignment::Left),
+        Some(ratatui_core::layout::Alignment::Center) => Some(ratatui::layout::Alignment::Center),
+        Some(ratatui_core::layout::Alignment::Right) => Some(ratatui::layout::Alignment::Right),
+        None => None,
+    }
+}
@@ -0,0 +1,112 @@
+//! Single-column search results, shown while a query is typed.
+
+use super::view_model::SearchView;
+use super::inner_list_width;
+use super::text::truncate_ellipsis;
+use crate::app::MatchType;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, view: &SearchView, area: Rect) {
+    let max_width = inner_list_width(area);
+    let items: Vec<ListItem> = view
+        .rows
+        .iter()
+        .map(|row| {
+            let base_style = match row.match_type {
+                MatchType::Both => Style::default().fg(Color::White),
+                MatchType::DimensionOnly => Style::default().fg(Color::Gray),
+                MatchType::TabOnly => Style::default().fg(Color::White),
+            };
+
+            let dim_style = if row.is_current_session {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                base_style
+            };
+
+            let tab_style = if row.is_current_tab {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                base_style
+            };
+
+            let mut spans = Vec::new();
+            let separator_style = base_style;
+
+            let marker = if row.is_current_tab { " *" } else { "" };
+            let marker_width = marker.width();
+            let available = max_width.saturating_sub(marker_width);
+
+            let dim = row.dimension.as_str();
+            let (sep, tab) = match &row.tab {
+                Some(tab) => (": ", tab.as_str()),
+                None => (" ", "(no tabs)"),
+            };
+
+            let sep_width = sep.width();
+            let mut dim_out = dim.to_string();
+            let mut tab_out = tab.to_string();
+
+            if dim.width() + sep_width + tab.width() > available {
+                // Truncate tab first, then dimension if needed.
+                let tab_max = available.saturating_sub(dim.width() + sep_width);
+                if tab_max > 0 {
+                    tab_out = truncate_ellipsis(tab, tab_max);
+                } else {
+                    dim_out = truncate_ellipsis(dim, available.saturating_sub(sep_width));
+                    let tab_max2 = available
+                        .saturating_sub(dim_out.width() + sep_width);
+                    if tab_max2 > 0 {
+                        tab_out = truncate_ellipsis(tab, tab_max2);
+                    } else {
+                        tab_out.clear();
+                    }
+                }
+            }
+
+            // Command in whatever room the names leave, dimmed
+            let remaining = available.saturating_sub(dim_out.width() + sep_width + tab_out.width());
+            let command = row.command.as_deref().filter(|_| remaining > 2);
+
+            spans.push(Span::styled(dim_out, dim_style));
+            spans.push(Span::styled(sep, separator_style));
+            spans.push(Span::styled(tab_out, tab_style));
+            if let Some(cmd) = command {
+                spans.push(Span::styled(
+                    format!(" {}", truncate_ellipsis(cmd, remaining - 1)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            if !marker.is_empty() {
+                spans.push(Span::styled(marker, tab_style));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = if view.rows.is_empty() {
+        format!("Search Results: '{}' (no matches)", view.query)
+    } else {
+        format!("Search Results: '{}' ({} matches)", view.query, view.rows.len())
+    };
+
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut state = ListState::default();
+    state.select(view.selected);
+    f.render_stateful_widget(list, area, &mut state);
+}
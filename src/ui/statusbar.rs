@@ -0,0 +1,578 @@
+//! Status line and key help at the bottom of the screen.
+
+use crate::app::{App, InputMode, Pane};
+use crate::keymap::Action;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render_status(f: &mut Frame, app: &App, area: Rect) {
+    let mut spans = vec![];
+
+    match app.input_mode {
+        InputMode::Normal => {
+            if let Some(msg) = &app.message {
+                spans.push(Span::styled(
+                    msg.clone(),
+                    Style::default().fg(Color::Green),
+                ));
+            } else if let Some(msg) = &app.update_message {
+                spans.push(Span::styled(
+                    msg.clone(),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+        InputMode::CreatingDimension | InputMode::AddingTab => {
+            spans.push(Span::raw("Input: "));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+        }
+        InputMode::RenamingDimension => {
+            if let Some(msg) = &app.message {
+                spans.push(Span::styled(msg.clone(), Style::default().fg(Color::Red)));
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::raw("Rename dimension: "));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+        }
+        InputMode::CurrentWindowMenu => {
+            let here = match (&app.current_session, app.current_window) {
+                (Some(session), Some(window)) => format!("{}:{}", session, window),
+                _ => "?".to_string(),
+            };
+            let target = app.get_current_dimension().map(|d| d.name.clone()).unwrap_or_default();
+            spans.push(Span::raw("Current window "));
+            spans.push(Span::styled(here, Style::default().fg(Color::Green)));
+            spans.push(Span::raw(" → "));
+            spans.push(Span::styled(target, Style::default().fg(Color::Cyan)));
+        }
+        InputMode::PickingTask => {
+            if let Some(tab) = app.selected_configured_tab() {
+                spans.push(Span::raw("Run a task in "));
+                spans.push(Span::styled(tab.name.clone(), Style::default().fg(Color::Cyan)));
+            }
+        }
+        InputMode::PickingLink => {
+            if let Some(dimension) = app.get_current_dimension() {
+                spans.push(Span::raw("Open a link of "));
+                spans.push(Span::styled(dimension.name.clone(), Style::default().fg(Color::Cyan)));
+            }
+        }
+        InputMode::PickingSuggestion => {
+            spans.push(Span::raw("Create dimensions for projects under "));
+            spans.push(Span::styled(app.engine.config.discover.roots.join(", "), Style::default().fg(Color::Cyan)));
+        }
+        InputMode::PickingTemplate => {
+            spans.push(Span::raw("Create a dimension from a template"));
+        }
+        InputMode::FillingTemplate => {
+            if let Some(msg) = &app.message {
+                spans.push(Span::styled(msg.clone(), Style::default().fg(Color::Red)));
+                spans.push(Span::raw("  "));
+            }
+            if let Some(fill) = &app.template_fill {
+                let template = app.engine.config.templates.get(fill.template).map(|t| t.name.as_str()).unwrap_or_default();
+                spans.push(Span::styled(template.to_string(), Style::default().fg(Color::Cyan)));
+                spans.push(Span::raw(format!(" · {}: ", fill.current().unwrap_or_default())));
+            }
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+        }
+        InputMode::Syncing => {
+            if let Some(sync) = &app.sync {
+                spans.push(Span::raw("Sync tabs of "));
+                spans.push(Span::styled(sync.session.clone(), Style::default().fg(Color::Cyan)));
+                spans.push(Span::raw(" with its live windows"));
+            }
+        }
+        InputMode::QuickActions => {
+            if let Some(dim) = app.get_current_dimension() {
+                spans.push(Span::raw("Actions for "));
+                spans.push(Span::styled(dim.name.clone(), Style::default().fg(Color::Cyan)));
+            }
+        }
+        InputMode::RenamingCurrentWindow => {
+            spans.push(Span::raw("Rename current window: "));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+        }
+        InputMode::RenamingTab => {
+            if let Some(msg) = &app.message {
+                spans.push(Span::styled(msg.clone(), Style::default().fg(Color::Red)));
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::raw("Rename tab: "));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+        }
+        InputMode::CreatingDimensionDirectory | InputMode::RepointingDirectory | InputMode::AddingTabDirectory => {
+            if app.input_mode == InputMode::RepointingDirectory {
+                if let Some(msg) = &app.message {
+                    spans.push(Span::styled(msg.clone(), Style::default().fg(Color::Red)));
+                    spans.push(Span::raw("  "));
+                }
+                spans.push(Span::raw("New directory: "));
+            } else if app.input_mode == InputMode::AddingTabDirectory {
+                if let Some(msg) = &app.message {
+                    spans.push(Span::styled(msg.clone(), Style::default().fg(Color::Red)));
+                    spans.push(Span::raw("  "));
+                }
+                spans.push(Span::raw("Tab directory: "));
+            } else {
+                spans.push(Span::raw("Directory: "));
+            }
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+
+            // Show completion position if the popup is open, or hint to press Tab
+            if !app.completion_candidates.is_empty() {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!("[{}/{}]", app.completion_index + 1, app.completion_candidates.len()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            } else {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    "Press Tab ⇥ for completion",
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+                ));
+            }
+        }
+        InputMode::Searching => {
+            spans.push(Span::raw(if app.search_regex { "Regex: /" } else { "Search: /" }));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+            if let Some(error) = &app.search_error {
+                spans.push(Span::styled(format!("  invalid pattern: {}", error), Style::default().fg(Color::Red)));
+            }
+        }
+        InputMode::SearchActions => {
+            if let Some(result) = app.search_results.get(app.search_selected_index) {
+                spans.push(Span::raw("Act on "));
+                spans.push(Span::styled(result.dimension_name.clone(), Style::default().fg(Color::Cyan)));
+                if result.is_tab() && !app.is_concealed(&result.dimension_name) {
+                    spans.push(Span::raw(": "));
+                    spans.push(Span::styled(result.tab_name.clone(), Style::default().fg(Color::Cyan)));
+                }
+            }
+        }
+        InputMode::Command => {
+            spans.push(Span::raw(":"));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+            if !app.completion_candidates.is_empty() {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!("[{}/{}]", app.completion_index + 1, app.completion_candidates.len()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+        }
+        InputMode::JumpingToTab => {
+            spans.push(Span::raw("Jump to tab #"));
+            spans.push(Span::styled(
+                app.input_buffer.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+        }
+        InputMode::ConfirmingSharedAttach => {
+            if let Some(shared) = &app.shared_attach {
+                let sizes: Vec<String> = shared
+                    .others
+                    .iter()
+                    .map(|c| format!("{}x{}", c.width, c.height))
+                    .collect();
+                let own = shared
+                    .own_size
+                    .map(|(w, h)| format!(", yours {}x{}", w, h))
+                    .unwrap_or_default();
+                spans.push(Span::styled(
+                    format!(
+                        "'{}' is attached elsewhere ({}{}); sharing shrinks it to the smallest client",
+                        shared.session,
+                        sizes.join(", "),
+                        own
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+        InputMode::DeletingDimension => {
+            if let Some(dim) = app.get_current_dimension() {
+                let is_current = app.current_session.as_deref() == Some(dim.name.as_str());
+                if !app.delete_running.is_empty() {
+                    spans.push(Span::styled(
+                        format!("Kills {}. ", app.delete_running.join(", ")),
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                if app.delete_requires_name {
+                    let color = if app.delete_name_matches() { Color::Green } else { Color::Yellow };
+                    spans.push(Span::styled(
+                        format!("'{}' is running. Type its name to delete it: ", dim.name),
+                        Style::default().fg(Color::Red),
+                    ));
+                    spans.push(Span::styled(app.input_buffer.clone(), Style::default().fg(color)));
+                    spans.push(Span::styled(" █", Style::default().fg(Color::White)));
+                } else {
+                    let msg = if is_current && app.tmux_state.session_exists(&dim.name) {
+                        format!("Delete dimension '{}'? Will switch to first available tab (y/n)", dim.name)
+                    } else {
+                        format!("Delete dimension '{}'? (y/n)", dim.name)
+                    };
+                    spans.push(Span::styled(msg, Style::default().fg(Color::Red)));
+                }
+            }
+        }
+        InputMode::DeletingTab => {
+            if let Some(dimension) = app.get_current_dimension() {
+                if let Some(tab_index) = app.selected_tab {
+                    let is_current_session =
+                        app.current_session.as_deref() == Some(dimension.name.as_str());
+
+                    let (tab_name, is_last) = if let Some(windows) = app.tmux_state.windows(&dimension.name) {
+                        let name = windows
+                            .iter()
+                            .find(|(idx, _)| *idx == tab_index)
+                            .map(|(_, name)| name.clone())
+                            .unwrap_or_else(|| "unknown".to_string());
+                        let is_last = windows.len() == 1;
+                        (name, is_last)
+                    } else {
+                        let name = dimension
+                            .configured_tabs
+                            .get(tab_index)
+                            .map(|t| t.name.clone())
+                            .unwrap_or_else(|| "unknown".to_string());
+                        let is_last = dimension.configured_tabs.len() == 1;
+                        (name, is_last)
+                    };
+
+                    let msg = if is_last && is_current_session {
+                        format!("Delete last tab '{}'? Will switch to first available tab (y/n)", tab_name)
+                    } else {
+                        format!("Delete tab '{}'? (y/n)", tab_name)
+                    };
+
+                    spans.push(Span::styled(msg, Style::default().fg(Color::Red)));
+                }
+            }
+        }
+    }
+
+    let status = Paragraph::new(Line::from(spans))
+        .block(Block::default().title("Status").borders(Borders::ALL));
+
+    f.render_widget(status, area);
+}
+
+/// Action keys of the main view, labelled with whatever the keymap binds them to
+fn normal_key_help(app: &App) -> Vec<Span<'static>> {
+    const ACTIONS: &[(Action, &str)] = &[
+        (Action::Switch, "Switch"),
+        (Action::NewDimension, "New dim"),
+        (Action::NewTab, "New tab"),
+        (Action::Delete, "Delete"),
+        (Action::Undo, "Undo"),
+        (Action::Rename, "Rename"),
+        (Action::Search, "Search"),
+        (Action::Command, "Command"),
+        (Action::LastTab, "Last tab"),
+        (Action::WindowMenu, "This window"),
+        (Action::QuickActions, "Actions"),
+        (Action::OpenDir, "Open dir"),
+        (Action::Sync, "Sync"),
+        (Action::Matrix, "Matrix"),
+        (Action::Close, "Close"),
+        (Action::Quit, "Quit"),
+    ];
+    let mut spans = Vec::new();
+    for (i, (action, label)) in ACTIONS.iter().enumerate() {
+        let sep = if i + 1 < ACTIONS.len() { "  " } else { "" };
+        spans.push(Span::styled(app.keymap.label(*action), Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(format!(" {label}{sep}")));
+    }
+    spans
+}
+
+pub fn render_help(f: &mut Frame, app: &App, area: Rect) {
+    let help_text = match app.input_mode {
+        InputMode::Normal => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(if app.focus == Pane::Tabs { " Navigate tabs  " } else { " Navigate dimensions  " }),
+                Span::styled("←/→", Style::default().fg(Color::Yellow)),
+                Span::raw(" Move between panes  "),
+                Span::styled("Tab", Style::default().fg(Color::Yellow)),
+                Span::raw(" Switch pane"),
+            ]),
+            Line::from(normal_key_help(app)),
+        ],
+        InputMode::CreatingDimensionDirectory | InputMode::RepointingDirectory | InputMode::AddingTab | InputMode::AddingTabDirectory | InputMode::FillingTemplate if app.is_completion_popup_open() => vec![
+            Line::from(vec![
+                Span::styled("Tab/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Next  "),
+                Span::styled("Shift+Tab/↑", Style::default().fg(Color::Yellow)),
+                Span::raw(" Prev  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Accept  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Close matches"),
+            ]),
+        ],
+        InputMode::CreatingDimension | InputMode::AddingTab => vec![
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Submit  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::CreatingDimensionDirectory | InputMode::RepointingDirectory | InputMode::AddingTabDirectory => vec![
+            Line::from(vec![
+                Span::styled("Tab", Style::default().fg(Color::Yellow)),
+                Span::raw(" Complete  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Submit (empty for none)  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+            Line::from(vec![
+                Span::raw("Supports: "),
+                Span::styled("~/path", Style::default().fg(Color::Cyan)),
+                Span::raw(", "),
+                Span::styled("../relative", Style::default().fg(Color::Cyan)),
+                Span::raw(", "),
+                Span::styled("$VAR/path", Style::default().fg(Color::Cyan)),
+            ]),
+        ],
+        InputMode::Searching => {
+            if app.search_query.is_empty() {
+                // Before query is entered
+                let mut hints = vec![Span::raw("Type to search dimensions and tabs (live)  ")];
+                if !app.search_history.is_empty() {
+                    hints.push(Span::styled("↑", Style::default().fg(Color::Yellow)));
+                    hints.push(Span::raw(" Recent  "));
+                }
+                hints.extend([
+                    Span::styled("Ctrl+R", Style::default().fg(Color::Yellow)),
+                    Span::raw(" Regex on/off  "),
+                    Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                    Span::raw(" Cancel"),
+                ]);
+                vec![Line::from(hints)]
+            } else {
+                // After query is entered, showing results
+                vec![
+                    Line::from(vec![
+                        Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                        Span::raw(" Navigate results  "),
+                        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                        Span::raw(" Select  "),
+                        Span::styled("Tab", Style::default().fg(Color::Yellow)),
+                        Span::raw(" Actions  "),
+                        Span::styled("Ctrl+R", Style::default().fg(Color::Yellow)),
+                        Span::raw(" Regex on/off  "),
+                        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                        Span::raw(" Cancel"),
+                    ]),
+                ]
+            }
+        }
+        InputMode::SearchActions => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose action  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Run  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Back to results"),
+            ]),
+        ],
+        InputMode::Command => vec![
+            Line::from(vec![
+                Span::raw("switch <dim> [tab]  new <name> [dir]  rename [old] <new>  delete [dim]  <n>  quit  "),
+                Span::styled("Tab", Style::default().fg(Color::Yellow)),
+                Span::raw(" Complete  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Run  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::JumpingToTab => vec![
+            Line::from(vec![
+                Span::raw("Type window number to jump  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Switch  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::CurrentWindowMenu => vec![
+            Line::from(vec![
+                Span::styled("a", Style::default().fg(Color::Yellow)),
+                Span::raw(" Adopt as tab of selected dimension  "),
+                Span::styled("m", Style::default().fg(Color::Yellow)),
+                Span::raw(" Move to selected dimension  "),
+                Span::styled("r", Style::default().fg(Color::Yellow)),
+                Span::raw(" Rename  "),
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Other dimension  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::PickingTask => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose task  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Run & switch  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::PickingSuggestion => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose project  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Create dimension  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Done"),
+            ]),
+        ],
+        InputMode::PickingLink => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose link  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Open  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::PickingTemplate => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose template  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Use  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::FillingTemplate => vec![
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Next  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::Syncing => vec![
+            Line::from(vec![
+                Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose  "),
+                Span::styled("Space", Style::default().fg(Color::Yellow)),
+                Span::raw(" Toggle  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Apply  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::QuickActions => vec![
+            Line::from(vec![
+                Span::styled("←/→", Style::default().fg(Color::Yellow)),
+                Span::raw(" Choose action  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Run  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::RenamingCurrentWindow if app.here => vec![
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Rename  "),
+                Span::styled("Tab", Style::default().fg(Color::Yellow)),
+                Span::raw(" Adopt or move  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Close"),
+            ]),
+        ],
+        InputMode::RenamingDimension | InputMode::RenamingTab | InputMode::RenamingCurrentWindow => vec![
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Confirm  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::ConfirmingSharedAttach => vec![
+            Line::from(vec![
+                Span::styled("d", Style::default().fg(Color::Yellow)),
+                Span::raw(" Detach other clients & switch  "),
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Switch anyway  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::DeletingDimension if app.delete_requires_name => vec![
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(" Delete once the name matches  "),
+                Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+        InputMode::DeletingDimension | InputMode::DeletingTab => vec![
+            Line::from(vec![
+                Span::styled("y", Style::default().fg(Color::Yellow)),
+                Span::raw(" Confirm  "),
+                Span::styled("n/Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" Cancel"),
+            ]),
+        ],
+    };
+
+    let help = Paragraph::new(help_text)
+        .block(Block::default().title("Help").borders(Borders::ALL));
+
+    f.render_widget(help, area);
+}
@@ -0,0 +1,139 @@
+//! Right pane: tabs of the selected dimension, with the preview below when open.
+
+use super::pane_block;
+use super::preview;
+use crate::app::ListArea;
+use super::view_model::{PreviewView, QuickActionsView, TabListView};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Returns where the tab rows landed, for mouse clicks
+pub fn render(f: &mut Frame, view: Option<&TabListView>, preview: Option<&PreviewView>, area: Rect) -> Option<ListArea> {
+    // Split area vertically: tabs list (top) and preview (bottom)
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if preview.is_some() {
+            [Constraint::Percentage(40), Constraint::Percentage(60)]
+        } else {
+            [Constraint::Percentage(100), Constraint::Length(0)]
+        })
+        .split(area);
+
+    let mut list_area = None;
+    if let Some(view) = view {
+        let items: Vec<ListItem> = view
+            .rows
+            .iter()
+            .map(|row| {
+                let style = if row.is_current {
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                let current_marker = if row.is_current { " *" } else { "" };
+
+                // Build spans with name, command, and marker
+                let mut spans = vec![
+                    Span::styled(format!("{}. {}", row.index, row.name), style)
+                ];
+
+                if let Some(cmd) = &row.command {
+                    spans.push(Span::styled(format!(" ({})", cmd), style));
+                }
+
+                // Failed commands get a red badge so a crashed server stands out
+                if let Some(code) = row.exit_status.filter(|code| *code != 0) {
+                    spans.push(Span::styled(
+                        format!(" ✗ {}", code),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    ));
+                }
+
+                spans.push(Span::styled(current_marker, style));
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let block = pane_block(view.title.clone(), view.focused);
+        let inner = block.inner(chunks[0]);
+        f.render_widget(block, chunks[0]);
+
+        // Quick actions take the last line of the pane, when there's room for it, and links the one above
+        let actions = view.actions.as_ref().filter(|_| inner.height >= 3);
+        let links = view.links.as_ref().filter(|_| inner.height >= 4);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(if links.is_some() { 1 } else { 0 }),
+                Constraint::Length(if actions.is_some() { 1 } else { 0 }),
+            ])
+            .split(inner);
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let mut state = ListState::default();
+        state.select(view.selected);
+        f.render_stateful_widget(list, rows[0], &mut state);
+        list_area = Some(ListArea { area: rows[0], offset: state.offset() });
+
+        if let Some((key, names)) = links {
+            render_links(f, key, names, rows[1]);
+        }
+        if let Some(actions) = actions {
+            render_actions(f, actions, rows[2]);
+        }
+    } else {
+        let text = Paragraph::new("No dimension selected")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().title("Tabs").borders(Borders::ALL));
+        f.render_widget(text, chunks[0]);
+    }
+
+    if let Some(content) = preview {
+        preview::render(f, content, chunks[1]);
+    }
+    list_area
+}
+
+fn render_links(f: &mut Frame, key: &str, names: &[String], area: Rect) {
+    let line = Line::from(vec![
+        Span::styled(format!("🔗 {}", names.join(" · ")), Style::default().fg(Color::Cyan)),
+        Span::styled(format!("  {} to open", key), Style::default().fg(Color::DarkGray)),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
+}
+
+fn render_actions(f: &mut Frame, view: &QuickActionsView, area: Rect) {
+    let active = view.selected.is_some();
+    let mut spans = vec![];
+    for (i, button) in view.buttons.iter().enumerate() {
+        let style = if view.selected == Some(i) {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else if active && button.enabled {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(format!("[{}]", button.label), style));
+        spans.push(Span::raw(" "));
+    }
+    if !active {
+        spans.push(Span::styled("a", Style::default().fg(Color::Yellow)));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
@@ -0,0 +1,242 @@
+//! Golden-file render tests: widgets are drawn from hand-built view models on a
+//! `TestBackend` and compared with `src/ui/snapshots/*.snap`.
+//!
+//! A missing snapshot is written on first run; set `UPDATE_SNAPSHOTS=1` to
+//! rewrite existing ones after an intentional layout change.
+
+use super::view_model::{
+    CellState, DimensionListView, DimensionRow, MatrixCell, MatrixRow, MatrixView, PreviewView, SearchRow, SearchView,
+    TabListView, TabRow,
+};
+use super::{dimension_list, matrix, preview, search, tab_list};
+use crate::app::MatchType;
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout},
+    Frame, Terminal,
+};
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthStr;
+
+fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+    buffer_to_string(terminal.backend().buffer())
+}
+
+/// One line per row; cells hidden behind wide characters are skipped
+fn buffer_to_string(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for y in 0..buffer.area.height {
+        let mut skip = 0;
+        for x in 0..buffer.area.width {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            skip = symbol.width().saturating_sub(1);
+            out.push_str(symbol);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{}.snap", name));
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    match std::fs::read_to_string(&path) {
+        Ok(expected) if !update => assert_eq!(
+            actual, expected,
+            "\nsnapshot '{}' changed; rerun with UPDATE_SNAPSHOTS=1 if intended\n\nexpected:\n{}\nactual:\n{}",
+            name, expected, actual
+        ),
+        _ => std::fs::write(&path, actual).unwrap(),
+    }
+}
+
+/// The normal two-column layout, as `render_main_content` draws it
+fn render_lists(width: u16, height: u16, dimensions: &DimensionListView, tabs: Option<&TabListView>) -> String {
+    render(width, height, |f| {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(f.area());
+        dimension_list::render(f, dimensions, chunks[0]);
+        tab_list::render(f, tabs, None, chunks[1]);
+    })
+}
+
+fn dimension(name: &str, tab_count: usize) -> DimensionRow {
+    DimensionRow {
+        index: 0,
+        name: name.to_string(),
+        tab_count,
+        drift: None,
+        is_current: false,
+        color: None,
+        icon: None,
+        attached_clients: 0,
+        missing_dir: false,
+        path: None,
+        nested_tabs: None,
+    }
+}
+
+fn search_row(dimension: &str, tab: Option<&str>) -> SearchRow {
+    SearchRow {
+        dimension: dimension.to_string(),
+        tab: tab.map(str::to_string),
+        command: None,
+        match_type: MatchType::Both,
+        is_current_session: false,
+        is_current_tab: false,
+    }
+}
+
+#[test]
+fn empty_config() {
+    let dimensions = DimensionListView {
+        title: "Dimensions".to_string(),
+        rows: vec![],
+        selected: None,
+        focused: true,
+        orphans: vec![],
+        selected_orphan: None,
+    };
+    assert_snapshot("empty_config", &render_lists(40, 5, &dimensions, None));
+}
+
+#[test]
+fn long_names() {
+    let dimensions = DimensionListView {
+        title: "Dimensions".to_string(),
+        rows: vec![
+            DimensionRow {
+                path: Some("~/code/x".to_string()),
+                ..dimension("a-very-long-dimension-name-that-overflows", 3)
+            },
+            DimensionRow {
+                index: 1,
+                is_current: true,
+                ..dimension("web", 2)
+            },
+        ],
+        selected: Some(0),
+        focused: true,
+        orphans: vec![],
+        selected_orphan: None,
+    };
+    let rendered = render(30, 4, |f| {
+        dimension_list::render(f, &dimensions, f.area());
+    });
+    assert_snapshot("long_names", &rendered);
+}
+
+#[test]
+fn search_results() {
+    let view = SearchView {
+        query: "x".to_string(),
+        rows: vec![
+            search_row("frontend", Some("a-really-long-tab-name-for-testing")),
+            search_row("api", None),
+            SearchRow {
+                is_current_session: true,
+                is_current_tab: true,
+                command: Some("npm run dev".to_string()),
+                ..search_row("web", Some("server"))
+            },
+        ],
+        selected: Some(0),
+    };
+    let rendered = render(40, 5, |f| search::render(f, &view, f.area()));
+    assert_snapshot("search_results", &rendered);
+}
+
+#[test]
+fn nested_tabs() {
+    let dimensions = DimensionListView {
+        title: "Dimensions".to_string(),
+        rows: vec![
+            DimensionRow {
+                nested_tabs: Some(vec![(0, "editor".to_string()), (1, "server".to_string())]),
+                ..dimension("web", 2)
+            },
+            DimensionRow { index: 1, ..dimension("api", 0) },
+        ],
+        selected: Some(1),
+        focused: true,
+        orphans: vec![],
+        selected_orphan: None,
+    };
+    let rendered = render(30, 6, |f| {
+        dimension_list::render(f, &dimensions, f.area());
+    });
+    assert_snapshot("nested_tabs", &rendered);
+}
+
+#[test]
+fn small_terminal() {
+    let dimensions = DimensionListView {
+        title: "Dimensions".to_string(),
+        rows: vec![dimension("api", 2)],
+        selected: Some(0),
+        focused: false,
+        orphans: vec![],
+        selected_orphan: None,
+    };
+    let tabs = TabListView {
+        title: "Tabs".to_string(),
+        rows: vec![TabRow {
+            index: 0,
+            name: "shell".to_string(),
+            command: None,
+            is_current: false,
+            exit_status: None,
+        }],
+        selected: Some(0),
+        focused: true,
+        actions: None,
+        links: None,
+    };
+    assert_snapshot("small_terminal", &render_lists(20, 3, &dimensions, Some(&tabs)));
+}
+
+#[test]
+fn preview_panel_shows_the_end_of_the_pane() {
+    let mut content: Vec<String> = (1..=40).map(|n| format!("line {}", n)).collect();
+    content.extend(["".to_string(), "".to_string()]);
+    let view = PreviewView {
+        title: "Preview: web:1".to_string(),
+        content: content.join("\n"),
+    };
+    let rendered = render(20, 6, |f| preview::render_panel(f, &view, f.area()));
+    assert_snapshot("preview_panel", &rendered);
+}
+
+#[test]
+fn matrix_rows_and_cells() {
+    let cell = |name: &str, state| MatrixCell { name: name.to_string(), state, is_current: false };
+    let row = |name: &str, cells| MatrixRow { name: name.to_string(), color: None, is_current: false, cells };
+    let view = MatrixView {
+        rows: vec![
+            row(
+                "api",
+                vec![
+                    cell("editor", CellState::Running),
+                    cell("server", CellState::Failed(1)),
+                    cell("logs", CellState::Active),
+                ],
+            ),
+            row("web", vec![cell("shell", CellState::Stopped)]),
+            row("db", vec![]),
+        ],
+        selected: Some((0, Some(1))),
+    };
+    let rendered = render(60, 5, |f| matrix::render(f, &view, f.area()));
+    assert_snapshot("matrix", &rendered);
+}
@@ -0,0 +1,95 @@
+//! Width-aware string helpers for widgets. Widths are terminal columns as
+//! measured by `unicode-width`, so wide (CJK, emoji) characters count as two.
+
+use unicode_width::UnicodeWidthStr;
+
+/// Cut `input` to at most `max_width` columns, ending in `…` when shortened
+pub fn truncate_ellipsis(input: &str, max_width: usize) -> String {
+    // Zero-width input (a lone combining mark) fits even in no columns
+    if input.width() <= max_width {
+        return input.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    if max_width == 1 {
+        return "…".to_string();
+    }
+
+    let budget = max_width - 1; // leave room for ellipsis
+    let mut out = String::new();
+    for ch in input.chars() {
+        out.push(ch);
+        // Measure the whole prefix: some sequences (e.g. emoji + variation selector)
+        // are wider than their characters summed
+        if out.width() > budget {
+            out.pop();
+            break;
+        }
+    }
+    out.push('…');
+    out
+}
+
+/// Like `truncate_ellipsis`, but keeps `suffix` intact and shortens `input` in
+/// front of it. Falls back to truncating both when the suffix alone doesn't fit.
+pub fn truncate_with_suffix(input: &str, suffix: &str, max_width: usize) -> String {
+    let suffix_width = suffix.width();
+    if input.width() + suffix_width <= max_width {
+        return format!("{}{}", input, suffix);
+    }
+    if suffix_width < max_width {
+        return format!("{}{}", truncate_ellipsis(input, max_width - suffix_width), suffix);
+    }
+    truncate_ellipsis(&format!("{}{}", input, suffix), max_width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Mix of ASCII, wide CJK, emoji and a combining accent
+    const MIXED: &str = "[a-z 日本語😀🚀\u{301}]{0,40}";
+
+    #[test]
+    fn shortens_with_ellipsis() {
+        assert_eq!(truncate_ellipsis("dimensions", 6), "dimen…");
+        assert_eq!(truncate_ellipsis("日本語", 4), "日…");
+        assert_eq!(truncate_ellipsis("abc", 1), "…");
+        assert_eq!(truncate_ellipsis("abc", 0), "");
+        assert_eq!(truncate_with_suffix("dimensions", " (3s)", 10), "dime… (3s)");
+    }
+
+    proptest! {
+        #[test]
+        fn never_exceeds_max_width(input in MIXED, max_width in 0usize..50) {
+            prop_assert!(truncate_ellipsis(&input, max_width).width() <= max_width);
+        }
+
+        #[test]
+        fn fitting_input_round_trips(input in MIXED, extra in 0usize..5) {
+            let max_width = input.width() + extra;
+            prop_assert_eq!(truncate_ellipsis(&input, max_width), input);
+        }
+
+        #[test]
+        fn truncated_output_is_a_prefix(input in MIXED, max_width in 2usize..50) {
+            let out = truncate_ellipsis(&input, max_width);
+            if out != input {
+                let kept = out.strip_suffix('…').unwrap();
+                prop_assert!(input.starts_with(kept));
+            }
+        }
+
+        #[test]
+        fn suffix_never_exceeds_max_width(input in MIXED, suffix in MIXED, max_width in 0usize..50) {
+            prop_assert!(truncate_with_suffix(&input, &suffix, max_width).width() <= max_width);
+        }
+
+        #[test]
+        fn suffix_kept_when_it_fits(input in MIXED, suffix in "[a-z ()0-9]{0,8}", max_width in 10usize..50) {
+            prop_assert!(truncate_with_suffix(&input, &suffix, max_width).ends_with(&suffix));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Breadcrumb bar at the top of the screen.
+
+use super::view_model::TitleView;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, view: &TitleView, area: Rect) {
+    let sep = Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let arrow = Span::styled(" ▸ ", Style::default().fg(Color::DarkGray));
+
+    let mut spans = vec![Span::styled(
+        "🌌 Dimensions",
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    )];
+
+    if !view.tmux_responding {
+        spans.push(sep.clone());
+        spans.push(Span::styled(
+            "⚠ tmux not responding",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(server) = &view.server {
+        spans.push(sep.clone());
+        spans.push(Span::styled("tmux ", label));
+        spans.push(Span::styled(server.clone(), Style::default().fg(Color::Yellow)));
+    }
+
+    if let Some(here) = &view.here {
+        let style = Style::default().fg(Color::Green);
+        spans.push(sep.clone());
+        spans.push(Span::styled("here ", label));
+        spans.push(Span::styled(here.session.clone(), style));
+        if let Some(tab) = &here.tab {
+            spans.push(arrow.clone());
+            spans.push(Span::styled(tab.clone(), style));
+        }
+    }
+
+    if let Some(selected) = &view.selected {
+        spans.push(sep.clone());
+        spans.push(Span::styled("selected ", label));
+        spans.push(Span::styled(selected.session.clone(), value));
+        if let Some(tab) = &selected.tab {
+            spans.push(arrow.clone());
+            spans.push(Span::styled(tab.clone(), value));
+        }
+    }
+
+    if let Some(filter) = &view.filter {
+        spans.push(sep.clone());
+        spans.push(Span::styled("filter ", label));
+        spans.push(Span::styled(format!("/{}", filter), Style::default().fg(Color::Cyan)));
+    }
+
+    if let Some(scope) = &view.scope {
+        spans.push(sep.clone());
+        spans.push(Span::styled("scope ", label));
+        spans.push(Span::styled(scope.clone(), Style::default().fg(Color::Magenta)));
+    }
+
+    if let Some(sort) = &view.sort {
+        spans.push(sep.clone());
+        spans.push(Span::styled("sort ", label));
+        spans.push(Span::styled(sort.clone(), value));
+    }
+
+    if view.presentation {
+        spans.push(sep.clone());
+        spans.push(Span::styled("presentation", Style::default().fg(Color::Magenta)));
+    }
+
+    // Age of the tmux state on screen (F5 / Ctrl+R refreshes it)
+    if let Some(age) = view.state_age {
+        let text = if age == 0 { "↻ just now".to_string() } else { format!("↻ {}s ago", age) };
+        spans.push(sep.clone());
+        spans.push(Span::styled(text, Style::default().fg(Color::DarkGray)));
+    }
+
+    let title = Paragraph::new(Line::from(spans))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, area);
+}
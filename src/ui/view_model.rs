@@ -0,0 +1,598 @@
+//! Plain data for each widget, computed from `App` and the tmux window cache.
+//! Widgets only draw these structs, so they can be rendered without tmux.
+
+use super::format_path_with_tilde;
+use crate::app::{App, DimensionItem, InputMode, MatchType, Pane, QuickAction};
+use crate::colors;
+use crate::dimension::{Dimension, DimensionSort, Tab};
+use crate::keymap::Action;
+use crate::tmux::{Tmux, WindowStatus};
+use ratatui::style::Color;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Stand-in for the details of a private dimension
+pub const CONCEALED: &str = "•••";
+
+/// A session, optionally narrowed to one of its tabs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub session: String,
+    pub tab: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleView {
+    pub tmux_responding: bool,
+    /// Which tmux server we're driving, when that could be surprising
+    pub server: Option<String>,
+    /// Where the client currently is
+    pub here: Option<Location>,
+    /// What Enter would switch to
+    pub selected: Option<Location>,
+    pub filter: Option<String>,
+    pub scope: Option<String>,
+    /// Only set for non-default sort orders
+    pub sort: Option<String>,
+    /// Seconds since the oldest tmux state on screen was fetched
+    pub state_age: Option<u64>,
+    pub presentation: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionRow {
+    /// Position in the config, which `startup.sort` may list it away from
+    pub index: usize,
+    pub name: String,
+    pub tab_count: usize,
+    /// Live windows minus configured tabs, when the running session has drifted
+    pub drift: Option<isize>,
+    pub is_current: bool,
+    /// Group/tag color for the name
+    pub color: Option<Color>,
+    /// Project type icon shown before the name
+    pub icon: Option<&'static str>,
+    pub attached_clients: usize,
+    pub missing_dir: bool,
+    /// Base directory, with `~` for $HOME
+    pub path: Option<String>,
+    /// Tabs listed under an expanded dimension (window index or configured position, name);
+    /// `None` when collapsed
+    pub nested_tabs: Option<Vec<(usize, String)>>,
+}
+
+/// A live tmux session that no dimension owns
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanRow {
+    pub name: String,
+    pub attached_clients: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionListView {
+    pub title: String,
+    pub rows: Vec<DimensionRow>,
+    /// Config position of the selected dimension
+    pub selected: Option<usize>,
+    pub focused: bool,
+    /// Listed in their own section below the dimensions
+    pub orphans: Vec<OrphanRow>,
+    pub selected_orphan: Option<usize>,
+}
+
+impl DimensionListView {
+    /// What each list row stands for, in drawing order: dimensions with their nested tabs,
+    /// then the orphan section
+    pub fn items(&self) -> Vec<DimensionItem> {
+        let mut items = vec![];
+        for row in &self.rows {
+            items.push(DimensionItem::Dimension(row.index));
+            for (tab, _) in row.nested_tabs.iter().flatten() {
+                items.push(DimensionItem::Tab(row.index, *tab));
+            }
+        }
+        if !self.orphans.is_empty() {
+            items.push(DimensionItem::Header);
+            items.extend((0..self.orphans.len()).map(DimensionItem::Orphan));
+        }
+        items
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabRow {
+    /// tmux window index for live sessions, configured position otherwise
+    pub index: usize,
+    pub name: String,
+    pub command: Option<String>,
+    pub is_current: bool,
+    /// Exit status of the tab's last command, once it has finished
+    pub exit_status: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabListView {
+    pub title: String,
+    pub rows: Vec<TabRow>,
+    /// Position in `rows`, not a window index
+    pub selected: Option<usize>,
+    pub focused: bool,
+    pub actions: Option<QuickActionsView>,
+    /// Names of the dimension's links, with the key that opens them
+    pub links: Option<(String, Vec<String>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionButton {
+    pub label: &'static str,
+    pub enabled: bool,
+}
+
+/// Buttons under the tab list; `selected` is only set while the row has focus
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickActionsView {
+    pub buttons: Vec<ActionButton>,
+    pub selected: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewView {
+    pub title: String,
+    pub content: String,
+}
+
+/// How a matrix cell is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellState {
+    Running,
+    // Printed something in the last `RECENT_ACTIVITY_SECS`
+    Active,
+    Exited,
+    Failed(i32),
+    // A configured tab of a dimension whose session isn't running
+    Stopped,
+}
+
+/// Output this recent counts as activity in the matrix
+pub const RECENT_ACTIVITY_SECS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixCell {
+    pub name: String,
+    pub state: CellState,
+    pub is_current: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixRow {
+    pub name: String,
+    pub color: Option<Color>,
+    pub is_current: bool,
+    pub cells: Vec<MatrixCell>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixView {
+    pub rows: Vec<MatrixRow>,
+    /// Row, and cell within it when the dimension has tabs
+    pub selected: Option<(usize, Option<usize>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchRow {
+    pub dimension: String,
+    /// `None` for dimensions without tabs
+    pub tab: Option<String>,
+    /// Configured command of the tab, to tell same-named tabs apart
+    pub command: Option<String>,
+    pub match_type: MatchType,
+    pub is_current_session: bool,
+    pub is_current_tab: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchView {
+    pub query: String,
+    pub rows: Vec<SearchRow>,
+    pub selected: Option<usize>,
+}
+
+/// Name of a tab in `dimension`: `tab` is a tmux window index for live sessions,
+/// a configured tab index otherwise
+fn tab_name(app: &App, dimension: &str, configured: &[Tab], tab: usize) -> Option<String> {
+    if let Some(windows) = app.tmux_state.windows(dimension) {
+        windows
+            .into_iter()
+            .find(|(idx, _)| *idx == tab)
+            .map(|(idx, name)| format!("{}:{}", idx, name))
+    } else {
+        configured.get(tab).map(|t| t.name.clone())
+    }
+}
+
+/// What a tab runs, as shown next to its name; task tabs list their tasks
+fn tab_command(tab: &Tab) -> Option<String> {
+    if tab.is_task_tab() {
+        let names: Vec<&str> = tab.tasks.iter().map(|t| t.name.as_str()).collect();
+        return Some(format!("tasks: {}", names.join(", ")));
+    }
+    tab.command.clone()
+}
+
+/// Tabs shown under an expanded dimension: live windows when running, configured tabs otherwise
+fn nested_tabs(app: &App, dimension: &Dimension) -> Vec<(usize, String)> {
+    if app.is_concealed(&dimension.name) {
+        return vec![];
+    }
+    match app.tmux_state.windows(&dimension.name) {
+        Some(windows) => windows,
+        None => dimension
+            .configured_tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| (i, tab.name.clone()))
+            .collect(),
+    }
+}
+
+pub fn title(app: &App) -> TitleView {
+    let here = app.current_session.as_ref().map(|session| {
+        let tab = app.current_window.map(|window| {
+            let configured = app
+                .engine.config
+                .get_dimension(session)
+                .map(|d| d.configured_tabs.as_slice())
+                .unwrap_or(&[]);
+            tab_name(app, session, configured, window).unwrap_or_else(|| window.to_string())
+        });
+        Location { session: session.clone(), tab }
+    });
+
+    let selected = app.get_current_dimension().map(|dimension| Location {
+        session: dimension.name.clone(),
+        tab: app
+            .selected_tab
+            .and_then(|tab| tab_name(app, &dimension.name, &dimension.configured_tabs, tab)),
+    });
+
+    TitleView {
+        tmux_responding: Tmux::is_responding(),
+        server: app.server_label.clone(),
+        here,
+        selected,
+        filter: (!app.search_query.is_empty()).then(|| app.search_query.clone()),
+        scope: app.engine.config.scope_label.clone(),
+        sort: (app.engine.config.startup.sort != DimensionSort::Config)
+            .then(|| format!("{:?}", app.engine.config.startup.sort).to_lowercase()),
+        state_age: app.tmux_state.oldest_fetch().map(|fetched| fetched.elapsed().as_secs()),
+        presentation: app.presentation,
+    }
+}
+
+/// Live windows minus configured tabs, unless they match (a remote dimension's
+/// windows are on its host, so it has none to compare)
+fn drift(dimension: &Dimension, windows: usize) -> Option<isize> {
+    if dimension.remote.is_some() {
+        return None;
+    }
+    let drift = windows as isize - dimension.configured_tabs.len() as isize;
+    (drift != 0).then_some(drift)
+}
+
+pub fn dimension_list(app: &App) -> DimensionListView {
+    let attached = app.tmux_state.attached_client_counts();
+    let rows = app
+        .dimension_order()
+        .into_iter()
+        .map(|index| {
+            let dim = &app.engine.config.dimensions[index];
+            // Actual window count from tmux if the session exists
+            let windows = app.tmux_state.window_count(&dim.name);
+            DimensionRow {
+                index,
+                name: dim.name.clone(),
+                tab_count: windows.unwrap_or(dim.configured_tabs.len()),
+                drift: windows.and_then(|windows| drift(dim, windows)),
+                is_current: app.current_session.as_ref() == Some(&dim.name),
+                color: colors::dimension_color(&app.engine.config.colors, dim).and_then(|c| colors::to_ratatui(&c)),
+                icon: app.project_kinds.get(&dim.name).and_then(|kind| kind.icon(app.engine.config.ui.icons)),
+                attached_clients: attached.get(&dim.name).copied().unwrap_or(0),
+                missing_dir: app.broken_dimensions.contains(&dim.name),
+                path: if app.presentation {
+                    None
+                } else if app.is_concealed(&dim.name) {
+                    dim.base_dir.as_ref().map(|_| CONCEALED.to_string())
+                } else {
+                    dim.base_dir
+                        .as_ref()
+                        .and_then(|p| p.to_str())
+                        .map(format_path_with_tilde)
+                },
+                nested_tabs: app.expanded.contains(&dim.name).then(|| nested_tabs(app, dim)),
+            }
+        })
+        .collect();
+
+    let title = match app.input_mode {
+        InputMode::CreatingDimension => "Dimensions (Enter name)".to_string(),
+        InputMode::CreatingDimensionDirectory => {
+            if let Some(name) = &app.pending_dimension_name {
+                format!("Creating '{}' - Enter base directory", name)
+            } else {
+                "Dimensions (Enter base directory)".to_string()
+            }
+        }
+        InputMode::RepointingDirectory => {
+            if let Some(dim) = app.get_current_dimension() {
+                format!("Re-point '{}' - Enter new base directory", dim.name)
+            } else {
+                "Dimensions (Enter new base directory)".to_string()
+            }
+        }
+        InputMode::DeletingDimension if app.delete_requires_name => "Dimensions (Type the name to delete)".to_string(),
+        InputMode::DeletingDimension => "Dimensions (Confirm delete? y/n)".to_string(),
+        InputMode::RenamingDimension => "Dimensions (Rename)".to_string(),
+        _ => "Dimensions".to_string(),
+    };
+
+    let orphans = app
+        .orphan_sessions()
+        .into_iter()
+        .map(|name| OrphanRow {
+            attached_clients: attached.get(&name).copied().unwrap_or(0),
+            name,
+        })
+        .collect();
+
+    DimensionListView {
+        title,
+        rows,
+        selected: (!app.engine.config.dimensions.is_empty() && app.selected_orphan.is_none())
+            .then_some(app.selected_dimension),
+        focused: app.focus == Pane::Dimensions,
+        orphans,
+        selected_orphan: app.selected_orphan,
+    }
+}
+
+/// Tabs of the selected dimension, or `None` when there is none
+pub fn tab_list(app: &App) -> Option<TabListView> {
+    let dimension = app.get_current_dimension()?;
+    let query = app.search_query.to_lowercase();
+    let matches = |name: &str| query.is_empty() || name.to_lowercase().contains(&query);
+
+    // Actual windows from tmux if the session exists, configured tabs otherwise
+    let (mut rows, selected): (Vec<TabRow>, Option<usize>) = if let Some(windows) = app.tmux_state.windows(&dimension.name) {
+        let exit_statuses = app.tmux_state.exit_statuses(&dimension.name);
+        let rows: Vec<TabRow> = windows
+            .into_iter()
+            .filter(|(_, window_name)| matches(window_name))
+            .map(|(window_idx, window_name)| TabRow {
+                index: window_idx,
+                command: dimension
+                    .configured_tabs
+                    .iter()
+                    .find(|t| t.name == window_name)
+                    .and_then(tab_command),
+                is_current: app.current_session.as_ref() == Some(&dimension.name)
+                    && app.current_window == Some(window_idx),
+                exit_status: exit_statuses.get(&window_idx).copied(),
+                name: window_name,
+            })
+            .collect();
+        let selected = rows.iter().position(|row| app.selected_tab == Some(row.index));
+        (rows, selected)
+    } else {
+        let rows = dimension
+            .configured_tabs
+            .iter()
+            .enumerate()
+            .filter(|(_, tab)| matches(&tab.name))
+            .map(|(i, tab)| TabRow {
+                index: i,
+                name: tab.name.clone(),
+                command: tab_command(tab),
+                is_current: false,
+                exit_status: None,
+            })
+            .collect();
+        (rows, app.selected_tab)
+    };
+
+    let concealed = app.is_concealed(&dimension.name);
+    for row in &mut rows {
+        if concealed {
+            row.name = CONCEALED.to_string();
+        }
+        if concealed || app.presentation {
+            row.command = None;
+        }
+    }
+
+    let title = match app.input_mode {
+        InputMode::AddingTab => "Tabs (Format: name or name:command)".to_string(),
+        InputMode::AddingTabDirectory => match &app.pending_tab {
+            Some((name, _)) => format!("Adding '{}' - Enter working directory", name),
+            None => "Tabs (Enter working directory)".to_string(),
+        },
+        InputMode::DeletingTab => "Tabs (Confirm delete? y/n)".to_string(),
+        InputMode::RenamingTab => "Tabs (Rename)".to_string(),
+        _ if concealed => format!("Tabs ({}, v to reveal)", CONCEALED),
+        _ if app.presentation => "Tabs".to_string(),
+        _ => {
+            // Show dimension's base_dir in the title, plus the live session's path when it differs
+            let base = dimension.base_dir.as_ref().and_then(|p| p.to_str());
+            let session_path = app.tmux_state.session_path(&dimension.name)
+                .filter(|p| dimension.base_dir.as_ref() != Some(p))
+                .and_then(|p| p.to_str().map(format_path_with_tilde));
+            match (base, session_path) {
+                (Some(base), Some(session)) => {
+                    format!("Tabs ({} · session {})", format_path_with_tilde(base), session)
+                }
+                (Some(base), None) => format!("Tabs ({})", format_path_with_tilde(base)),
+                (None, Some(session)) => format!("Tabs (session {})", session),
+                (None, None) => "Tabs".to_string(),
+            }
+        }
+    };
+
+    // Point at `S` when the live session no longer matches the configured tabs
+    let drift = app.tmux_state.window_count(&dimension.name).and_then(|windows| drift(dimension, windows));
+    let title = match drift {
+        Some(drift) if app.input_mode == InputMode::Normal && !concealed => {
+            format!("{} · {:+} vs config, S to sync", title, drift)
+        }
+        _ => title,
+    };
+
+    let running = app.tmux_state.session_exists(&dimension.name);
+    let actions = QuickActionsView {
+        buttons: QuickAction::ALL
+            .iter()
+            .map(|action| ActionButton {
+                label: action.label(running),
+                enabled: action.is_enabled(dimension, running),
+            })
+            .collect(),
+        selected: (app.input_mode == InputMode::QuickActions).then_some(app.quick_action),
+    };
+
+    let links = (!dimension.links.is_empty() && !concealed && !app.presentation).then(|| {
+        let names = dimension.links.iter().map(|link| link.name.clone()).collect();
+        (app.keymap.label(Action::OpenLink), names)
+    });
+
+    Some(TabListView {
+        title,
+        rows,
+        selected,
+        focused: app.focus == Pane::Tabs,
+        actions: Some(actions),
+        links,
+    })
+}
+
+/// Capture of the selected tab, when one has been taken
+pub fn preview(app: &App) -> Option<PreviewView> {
+    if app.presentation {
+        return None;
+    }
+    app.selected_tab?;
+    let content = match &app.preview_session {
+        Some(session) if app.is_concealed(session) => CONCEALED.to_string(),
+        _ => app.preview_content.clone()?,
+    };
+    let title = if let (Some(session), Some(window)) = (&app.preview_session, &app.preview_window) {
+        format!("Preview: {}:{}", session, window)
+    } else {
+        "Preview".to_string()
+    };
+    Some(PreviewView { title, content })
+}
+
+/// The preview panel's contents, with a hint while there's nothing to capture
+pub fn preview_panel(app: &App) -> PreviewView {
+    preview(app).unwrap_or_else(|| PreviewView {
+        title: "Preview".to_string(),
+        content: if app.presentation {
+            String::new()
+        } else {
+            "Highlight a tab of a running dimension to see it here".to_string()
+        },
+    })
+}
+
+fn cell_state(status: WindowStatus, now: u64) -> CellState {
+    match status.exit_status {
+        Some(0) => CellState::Exited,
+        Some(code) => CellState::Failed(code),
+        None if now.saturating_sub(status.activity) < RECENT_ACTIVITY_SECS => CellState::Active,
+        None => CellState::Running,
+    }
+}
+
+/// Every dimension with its tabs side by side: live windows of running sessions (with
+/// what they're doing), configured tabs otherwise
+pub fn matrix(app: &App) -> MatrixView {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut selected = None;
+    let rows = app
+        .dimension_order()
+        .into_iter()
+        .enumerate()
+        .map(|(row, index)| {
+            let dim = &app.engine.config.dimensions[index];
+            let is_current = app.current_session.as_ref() == Some(&dim.name);
+            let (indexes, mut cells): (Vec<usize>, Vec<MatrixCell>) = match app.tmux_state.windows(&dim.name) {
+                Some(windows) => {
+                    let statuses = app.tmux_state.window_statuses(&dim.name);
+                    windows
+                        .into_iter()
+                        .map(|(index, name)| {
+                            let state = cell_state(statuses.get(&index).copied().unwrap_or_default(), now);
+                            let is_current = is_current && app.current_window == Some(index);
+                            (index, MatrixCell { name, state, is_current })
+                        })
+                        .unzip()
+                }
+                None => dim
+                    .configured_tabs
+                    .iter()
+                    .enumerate()
+                    .map(|(index, tab)| {
+                        (index, MatrixCell { name: tab.name.clone(), state: CellState::Stopped, is_current: false })
+                    })
+                    .unzip(),
+            };
+            if app.is_concealed(&dim.name) {
+                for cell in &mut cells {
+                    cell.name = CONCEALED.to_string();
+                }
+            }
+            if index == app.selected_dimension {
+                selected = Some((row, app.selected_tab.and_then(|tab| indexes.iter().position(|&i| i == tab))));
+            }
+            MatrixRow {
+                name: dim.name.clone(),
+                color: colors::dimension_color(&app.engine.config.colors, dim).and_then(|c| colors::to_ratatui(&c)),
+                is_current,
+                cells,
+            }
+        })
+        .collect();
+    MatrixView { rows, selected }
+}
+
+/// Search results as last computed by `App::compute_search_results`
+pub fn search(app: &App) -> SearchView {
+    let rows = app
+        .search_results
+        .iter()
+        .map(|result| {
+            let has_tab = result.tab_name != "(no tabs)";
+            let is_current_session = app.current_session.as_ref() == Some(&result.dimension_name);
+            let concealed = app.is_concealed(&result.dimension_name);
+            SearchRow {
+                dimension: result.dimension_name.clone(),
+                tab: has_tab.then(|| if concealed { CONCEALED.to_string() } else { result.tab_name.clone() }),
+                command: app
+                    .engine.config
+                    .dimensions
+                    .get(result.dimension_index)
+                    .filter(|_| !concealed && !app.presentation)
+                    .and_then(|d| d.configured_tabs.iter().find(|t| t.name == result.tab_name))
+                    .and_then(|t| t.command.clone()),
+                match_type: result.match_type.clone(),
+                is_current_session,
+                is_current_tab: is_current_session
+                    && app.current_window == Some(result.tmux_window_index)
+                    && has_tab,
+            }
+        })
+        .collect();
+
+    SearchView {
+        query: app.search_query.clone(),
+        rows,
+        selected: (app.search_selected_index < app.search_results.len()).then_some(app.search_selected_index),
+    }
+}
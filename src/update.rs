@@ -25,8 +25,8 @@ fn now_unix() -> i64 {
         .as_secs() as i64
 }
 
-fn cache_path(config_dir: PathBuf) -> PathBuf {
-    config_dir.join(UPDATE_CACHE_FILE)
+fn cache_path(cache_dir: PathBuf) -> PathBuf {
+    cache_dir.join(UPDATE_CACHE_FILE)
 }
 
 fn load_cache(path: &PathBuf) -> UpdateCache {
@@ -103,13 +103,13 @@ Or download the binary from:\n\
     )
 }
 
-pub fn check_for_update_message(config_dir: PathBuf, current_version: &str) -> Option<String> {
+pub fn check_for_update_message(cache_dir: PathBuf, current_version: &str) -> Option<String> {
     if std::env::var("DIMENSIONS_NO_UPDATE_CHECK").is_ok() {
         return None;
     }
 
     let current = Version::parse(current_version).ok()?;
-    let path = cache_path(config_dir);
+    let path = cache_path(cache_dir);
     let mut cache = load_cache(&path);
     let now = now_unix();
 
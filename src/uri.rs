@@ -0,0 +1,200 @@
+//! `dimensions://<name>[/<tab>]` deep links, and registering Dimensions as their handler.
+
+use crate::hooks::shell_quote;
+use anyhow::{Context, Result};
+use std::fs;
+use std::process::Command;
+
+pub const SCHEME: &str = "dimensions";
+
+/// Where a deep link points
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeepLink {
+    pub dimension: String,
+    pub tab: Option<String>,
+}
+
+/// Parse `dimensions://<name>[/<tab>]`; both parts may be percent-encoded
+pub fn parse(uri: &str) -> Result<DeepLink> {
+    let prefix = format!("{}://", SCHEME);
+    let Some(rest) = uri.strip_prefix(&prefix) else {
+        anyhow::bail!("Not a {} link: {}", prefix, uri);
+    };
+    let rest = rest.trim_end_matches('/');
+    let (dimension, tab) = match rest.split_once('/') {
+        Some((dimension, tab)) => (dimension, Some(tab)),
+        None => (rest, None),
+    };
+
+    let dimension = percent_decode(dimension)?;
+    if dimension.is_empty() {
+        anyhow::bail!("Link has no dimension name: {}", uri);
+    }
+    let tab = tab.map(percent_decode).transpose()?.filter(|t| !t.is_empty());
+    Ok(DeepLink { dimension, tab })
+}
+
+pub fn percent_decode(s: &str) -> Result<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3).context("Truncated %-escape in link")?;
+            out.push(u8::from_str_radix(hex, 16).context("Invalid %-escape in link")?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).context("Link is not valid UTF-8")
+}
+
+/// `dimensions register-handler`: make the OS open `dimensions://` links with this binary
+pub fn register_handler() -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the dimensions binary")?;
+    let exe = exe.to_str().context("Binary path is not valid UTF-8")?;
+    if cfg!(target_os = "macos") {
+        register_macos(exe)
+    } else {
+        register_desktop_entry(exe)
+    }
+}
+
+/// freedesktop: a `.desktop` entry claiming the scheme, run in a terminal so attaching works
+fn register_desktop_entry(exe: &str) -> Result<()> {
+    let dir = dirs::data_dir()
+        .context("Could not determine the data directory")?
+        .join("applications");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let file_name = "dimensions-url-handler.desktop";
+    let path = dir.join(file_name);
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Dimensions\n\
+         Comment=Open dimensions:// links\n\
+         Exec={} open %u\n\
+         Terminal=true\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/{};\n",
+        desktop_quote(exe),
+        SCHEME
+    );
+    fs::write(&path, entry).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mime = format!("x-scheme-handler/{}", SCHEME);
+    let status = Command::new("xdg-mime").args(["default", file_name, &mime]).status();
+    if !status.is_ok_and(|s| s.success()) {
+        anyhow::bail!(
+            "Wrote {} but `xdg-mime default {} {}` failed; run it by hand",
+            path.display(),
+            file_name,
+            mime
+        );
+    }
+    println!("Registered {} for {}:// links", path.display(), SCHEME);
+    Ok(())
+}
+
+/// Quote an `Exec=` argument per the desktop entry spec (double quotes, backslash escapes)
+fn desktop_quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// macOS: URLs arrive as Apple Events rather than arguments, so build a small AppleScript
+/// applet that forwards them, and declare the scheme in its Info.plist
+fn register_macos(exe: &str) -> Result<()> {
+    let app = dirs::home_dir()
+        .context("Could not determine home directory")?
+        .join("Applications/Dimensions URL Handler.app");
+    let app_path = app.to_str().context("Path is not valid UTF-8")?;
+
+    let forward = format!(
+        "do shell script \"{} open \" & quoted form of this_url",
+        shell_quote(exe).replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    run(Command::new("osacompile").args([
+        "-o",
+        app_path,
+        "-e",
+        "on open location this_url",
+        "-e",
+        &forward,
+        "-e",
+        "end open location",
+    ]))?;
+
+    let plist = format!("{}/Contents/Info.plist", app_path);
+    let url_types = format!(
+        "<array><dict><key>CFBundleURLName</key><string>Dimensions</string>\
+         <key>CFBundleURLSchemes</key><array><string>{}</string></array></dict></array>",
+        SCHEME
+    );
+    run(Command::new("plutil").args(["-replace", "CFBundleURLTypes", "-xml", &url_types, &plist]))?;
+    run(Command::new("plutil").args([
+        "-replace",
+        "CFBundleIdentifier",
+        "-string",
+        "com.dimensions.url-handler",
+        &plist,
+    ]))?;
+
+    // Launch Services picks up the scheme once the app has been registered
+    let lsregister = "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister";
+    let _ = Command::new(lsregister).args(["-f", app_path]).status();
+
+    println!("Registered {} for {}:// links", app.display(), SCHEME);
+    println!("Links switch the most recently used tmux client; with none attached, start tmux first.");
+    Ok(())
+}
+
+fn run(cmd: &mut Command) -> Result<()> {
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let output = cmd.output().with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        anyhow::bail!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dimension_and_tab() {
+        assert_eq!(
+            parse("dimensions://api/server").unwrap(),
+            DeepLink { dimension: "api".to_string(), tab: Some("server".to_string()) }
+        );
+        assert_eq!(
+            parse("dimensions://api/").unwrap(),
+            DeepLink { dimension: "api".to_string(), tab: None }
+        );
+    }
+
+    #[test]
+    fn decodes_percent_escapes() {
+        let link = parse("dimensions://my%20work/dev%2Fserver").unwrap();
+        assert_eq!(link.dimension, "my work");
+        assert_eq!(link.tab.as_deref(), Some("dev/server"));
+    }
+
+    #[test]
+    fn rejects_other_schemes_and_empty_names() {
+        assert!(parse("https://api").is_err());
+        assert!(parse("dimensions://").is_err());
+        assert!(parse("dimensions://a%2").is_err());
+    }
+}
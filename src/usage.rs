@@ -0,0 +1,246 @@
+//! Local usage log (switches, sessions created/killed, commands launched) and the
+//! `dimensions report` summary built from it. Nothing leaves the machine.
+
+use crate::paths;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A stretch between two switches longer than this is assumed to include time away
+const MAX_ATTACHED_SPAN_SECS: u64 = 2 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Switch,
+    SessionCreated,
+    SessionKilled,
+    Command,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub ts: u64,
+    pub kind: EventKind,
+    pub dimension: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+fn log_path() -> PathBuf {
+    paths::state_dir().join("usage.jsonl")
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Append an event; failures are ignored since usage tracking must never get in the way
+pub fn record(kind: EventKind, dimension: &str, command: Option<&str>) {
+    let event = Event {
+        ts: now(),
+        kind,
+        dimension: dimension.to_string(),
+        command: command.map(|c| c.to_string()),
+    };
+    let Ok(line) = serde_json::to_string(&event) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(log_path()) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// Events at or after `since` (seconds since the epoch), oldest first
+fn load_since(since: u64) -> Result<Vec<Event>> {
+    let path = log_path();
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let contents = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Event>(line).ok())
+        .filter(|event| event.ts >= since)
+        .collect())
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DimensionUsage {
+    pub dimension: String,
+    pub switches: usize,
+    pub attached_secs: u64,
+    pub sessions_created: usize,
+    pub sessions_killed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommandCount {
+    pub command: String,
+    pub runs: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub days: u64,
+    pub dimensions: Vec<DimensionUsage>,
+    pub commands: Vec<CommandCount>,
+}
+
+/// Summarize `events` (oldest first). Time attached is approximated by the gaps between
+/// switches, each capped at `MAX_ATTACHED_SPAN_SECS`; the last one runs until `until`.
+pub fn summarize(events: &[Event], days: u64, until: u64) -> Report {
+    let mut by_dimension: HashMap<String, DimensionUsage> = HashMap::new();
+    let mut commands: HashMap<String, usize> = HashMap::new();
+
+    let switches: Vec<&Event> = events.iter().filter(|e| e.kind == EventKind::Switch).collect();
+    for (i, event) in switches.iter().enumerate() {
+        let end = switches.get(i + 1).map(|next| next.ts).unwrap_or(until);
+        let usage = by_dimension.entry(event.dimension.clone()).or_default();
+        usage.switches += 1;
+        usage.attached_secs += end.saturating_sub(event.ts).min(MAX_ATTACHED_SPAN_SECS);
+    }
+
+    for event in events {
+        let usage = by_dimension.entry(event.dimension.clone()).or_default();
+        match event.kind {
+            EventKind::SessionCreated => usage.sessions_created += 1,
+            EventKind::SessionKilled => usage.sessions_killed += 1,
+            EventKind::Command => {
+                if let Some(command) = &event.command {
+                    *commands.entry(command.clone()).or_default() += 1;
+                }
+            }
+            EventKind::Switch => {}
+        }
+    }
+
+    let mut dimensions: Vec<DimensionUsage> = by_dimension
+        .into_iter()
+        .map(|(dimension, usage)| DimensionUsage { dimension, ..usage })
+        .collect();
+    dimensions.sort_by(|a, b| b.attached_secs.cmp(&a.attached_secs).then(b.switches.cmp(&a.switches)));
+
+    let mut commands: Vec<CommandCount> = commands
+        .into_iter()
+        .map(|(command, runs)| CommandCount { command, runs })
+        .collect();
+    commands.sort_by(|a, b| b.runs.cmp(&a.runs).then(a.command.cmp(&b.command)));
+    commands.truncate(10);
+
+    Report { days, dimensions, commands }
+}
+
+fn format_duration(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3600, secs % 3600 / 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+pub fn to_markdown(report: &Report) -> String {
+    let mut out = format!("# Dimensions: past {} days\n\n", report.days);
+    if report.dimensions.is_empty() {
+        out.push_str("No activity recorded.\n");
+        return out;
+    }
+
+    out.push_str("| Dimension | Time attached | Switches | Sessions created | Sessions killed |\n");
+    out.push_str("|---|---|---|---|---|\n");
+    for usage in &report.dimensions {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            usage.dimension,
+            format_duration(usage.attached_secs),
+            usage.switches,
+            usage.sessions_created,
+            usage.sessions_killed
+        ));
+    }
+
+    if !report.commands.is_empty() {
+        out.push_str("\n## Most run commands\n\n");
+        for count in &report.commands {
+            out.push_str(&format!("- `{}` ({}×)\n", count.command, count.runs));
+        }
+    }
+    out
+}
+
+/// `dimensions report [--days N] [--json]`
+pub fn report_cli(days: u64, json: bool) -> Result<()> {
+    let until = now();
+    let events = load_since(until.saturating_sub(days * 24 * 60 * 60))?;
+    let report = summarize(&events, days, until);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", to_markdown(&report));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(ts: u64, kind: EventKind, dimension: &str, command: Option<&str>) -> Event {
+        Event { ts, kind, dimension: dimension.to_string(), command: command.map(str::to_string) }
+    }
+
+    #[test]
+    fn attached_time_is_the_capped_gap_between_switches() {
+        let events = [
+            event(0, EventKind::Switch, "api", None),
+            event(600, EventKind::Switch, "web", None),
+            event(600 + 3 * 3600, EventKind::Switch, "api", None),
+        ];
+        let report = summarize(&events, 7, 600 + 3 * 3600 + 60);
+        let api = report.dimensions.iter().find(|d| d.dimension == "api").unwrap();
+        let web = report.dimensions.iter().find(|d| d.dimension == "web").unwrap();
+        assert_eq!(api.attached_secs, 660);
+        assert_eq!(api.switches, 2);
+        assert_eq!(web.attached_secs, MAX_ATTACHED_SPAN_SECS);
+    }
+
+    #[test]
+    fn counts_sessions_and_commands() {
+        let events = [
+            event(0, EventKind::SessionCreated, "api", None),
+            event(1, EventKind::Command, "api", Some("cargo test")),
+            event(2, EventKind::Command, "api", Some("cargo test")),
+            event(3, EventKind::Command, "api", Some("npm run dev")),
+            event(4, EventKind::SessionKilled, "api", None),
+        ];
+        let report = summarize(&events, 7, 10);
+        assert_eq!(report.dimensions[0].sessions_created, 1);
+        assert_eq!(report.dimensions[0].sessions_killed, 1);
+        assert_eq!(report.commands[0].command, "cargo test");
+        assert_eq!(report.commands[0].runs, 2);
+    }
+
+    #[test]
+    fn recorded_events_are_read_back() {
+        paths::use_temp_dir();
+        let since = now();
+        record(EventKind::Command, "usage-log", Some("make"));
+        record(EventKind::SessionKilled, "usage-log", None);
+
+        let events: Vec<_> = load_since(since)
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.dimension == "usage-log")
+            .map(|e| (e.kind, e.command))
+            .collect();
+        assert_eq!(events, [(EventKind::Command, Some("make".to_string())), (EventKind::SessionKilled, None)]);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::hooks::shell_quote;
+use crate::scope::glob_match;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Directories that are never worth watching and can be huge
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// Tab command that reruns `command` whenever files matching `glob` change,
+/// by running it under `dimensions watch`
+pub fn wrap_command(glob: &str, command: &str) -> String {
+    let exe = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.to_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| "dimensions".to_string());
+    format!(
+        "{} watch {} {}",
+        shell_quote(&exe),
+        shell_quote(glob),
+        shell_quote(command)
+    )
+}
+
+/// `dimensions watch <glob> <command>`: run `command`, restarting it on every change
+/// to a file under the current directory that matches `glob`. Runs until interrupted.
+pub fn run(glob: &str, command: &str) -> Result<()> {
+    let root = std::env::current_dir().context("Failed to read current directory")?;
+    let mut seen = snapshot(&root, glob);
+
+    loop {
+        println!("[watch] {}", command);
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .process_group(0)
+            .spawn()
+            .context("Failed to run watched command")?;
+
+        let mut exited = false;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if !exited && let Some(status) = child.try_wait()? {
+                println!("[watch] {} — waiting for changes to {}", status, glob);
+                exited = true;
+            }
+            let current = snapshot(&root, glob);
+            if current != seen {
+                seen = current;
+                break;
+            }
+        }
+
+        if !exited {
+            stop(&mut child);
+        }
+        println!("[watch] change detected, rerunning");
+    }
+}
+
+/// Stop the command and anything it started (it runs in its own process group)
+fn stop(child: &mut Child) {
+    let _ = Command::new("kill")
+        .args(["-TERM", "--", &format!("-{}", child.id())])
+        .output();
+    let _ = child.wait();
+}
+
+/// Modification times of the files under `root` that match `glob`
+fn snapshot(root: &Path, glob: &str) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                    pending.push(path);
+                }
+                continue;
+            }
+
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string();
+            // Patterns without a slash match the file name at any depth, like `*.rs`
+            let matched = glob_match(glob, &relative) || (!glob.contains('/') && glob_match(glob, &name));
+            if matched && let Ok(modified) = metadata.modified() {
+                files.insert(path, modified);
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_takes_matching_files_outside_skipped_dirs() {
+        let root = std::env::temp_dir().join(format!("dimensions-watch-{}", std::process::id()));
+        for dir in ["src/bin", "target/debug", "node_modules/pkg", ".git"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let files = ["main.rs", "notes.md", "src/lib.rs", "src/bin/tool.rs", "target/debug/build.rs"];
+        for file in files.iter().chain(&["node_modules/pkg/x.rs", ".git/hook.rs"]) {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let names = |glob: &str| -> Vec<String> {
+            snapshot(&root, glob)
+                .into_keys()
+                .map(|path| path.strip_prefix(&root).unwrap().display().to_string())
+                .collect()
+        };
+
+        assert_eq!(names("*.rs"), ["main.rs", "src/bin/tool.rs", "src/lib.rs"]);
+        assert_eq!(names("src/*.rs"), ["src/bin/tool.rs", "src/lib.rs"]);
+        assert_eq!(names("*.md"), ["notes.md"]);
+        assert!(names("*.toml").is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+//! Directories from zoxide's frecency database, offered ahead of plain path completion
+//! when typing a dimension's or tab's directory.
+
+use crate::path_completion::PathCompleter;
+use std::path::Path;
+use std::process::Command;
+
+/// Most of zoxide's directories offered for one completion
+const LIMIT: usize = 20;
+
+/// zoxide's directories, most frecent first; empty when zoxide isn't installed
+pub fn directories() -> Vec<String> {
+    let Ok(output) = Command::new("zoxide").args(["query", "--list"]).output() else {
+        return vec![];
+    };
+    if !output.status.success() {
+        return vec![];
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The entries of `frecent` for `input`, in their order: those under the directory it
+/// spells out, or for a bare word (`api`), those whose last component contains it, like `z api`
+pub fn matching(frecent: &[String], input: &str) -> Vec<String> {
+    let input = input.trim();
+    let is_path = input.contains('/') || input.starts_with(['~', '.', '$']);
+    let expanded = if is_path { PathCompleter::expand_path(input) } else { String::new() };
+    let word = input.to_lowercase();
+
+    frecent
+        .iter()
+        .filter(|dir| {
+            if is_path {
+                return dir.starts_with(&expanded);
+            }
+            let last = Path::new(dir).file_name().map(|n| n.to_string_lossy().to_lowercase());
+            last.is_some_and(|last| last.contains(&word))
+        })
+        .take(LIMIT)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_by_prefix_or_last_component() {
+        let frecent: Vec<String> =
+            ["/home/me/code/api", "/home/me/work/api-gateway", "/home/me/code/web", "/srv/API"]
+                .iter()
+                .map(|d| d.to_string())
+                .collect();
+
+        assert_eq!(matching(&frecent, "api"), ["/home/me/code/api", "/home/me/work/api-gateway", "/srv/API"]);
+        assert_eq!(matching(&frecent, "/home/me/code/"), ["/home/me/code/api", "/home/me/code/web"]);
+        assert_eq!(matching(&frecent, "").len(), 4);
+        assert!(matching(&frecent, "docs").is_empty());
+    }
+}